@@ -0,0 +1,271 @@
+use anyhow::{Context, Result};
+use colored::*;
+use std::fs;
+use crate::state::ProjectState;
+use crate::utils;
+use crate::utils::history::{self, DailyPoint};
+
+const CHART_HEIGHT: usize = 10;
+/// Most recent days shown in the terminal; the SVG shows the full history
+const MAX_ASCII_COLUMNS: usize = 60;
+
+const SVG_WIDTH: f64 = 640.0;
+const SVG_HEIGHT: f64 = 320.0;
+const SVG_MARGIN: f64 = 48.0;
+
+/// Execute the burndown command
+///
+/// Reads `.doplan/history.jsonl` and renders burndown (remaining tasks) and
+/// burnup (completed tasks against total scope) charts. `--phase P-01` limits
+/// the charts to one phase.
+pub async fn execute(args: Vec<String>) -> Result<()> {
+    let phase_id = args.iter()
+        .position(|a| a == "--phase")
+        .and_then(|i| args.get(i + 1))
+        .cloned();
+
+    let history_path = history::history_path(&utils::dot_doplan_dir()?);
+    let snapshots = history::load(&history_path)?;
+    if snapshots.is_empty() {
+        println!("{}", "No progress history found. Run '/progress' to record a snapshot.".bright_yellow());
+        println!();
+        return Ok(());
+    }
+
+    let points = history::daily_points(&snapshots, phase_id.as_deref());
+    if points.is_empty() {
+        anyhow::bail!("No progress history recorded for phase {}", phase_id.unwrap_or_default());
+    }
+
+    let title = match &phase_id {
+        Some(id) => {
+            let state = ProjectState::load()
+                .context("Failed to load project state")?;
+            let name = state.phase_by_id(id).map(|p| p.name.clone()).unwrap_or_else(|| id.clone());
+            format!("{} Burndown", name)
+        }
+        None => "Project Burndown".to_string(),
+    };
+
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
+    println!("{}", format!("  DoPlan: {}", title).bright_cyan().bold());
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
+    println!();
+
+    let recent = &points[points.len().saturating_sub(MAX_ASCII_COLUMNS)..];
+    println!("{}", "Burndown (remaining tasks):".bright_cyan());
+    println!("{}", render_burndown_ascii(recent));
+    println!("{}", "Burnup (█ completed, ░ remaining scope):".bright_cyan());
+    println!("{}", render_burnup_ascii(recent));
+
+    let last = points[points.len() - 1];
+    println!(
+        "{}",
+        format!(
+            "{} of {} tasks completed, {} remaining ({} day(s) of history)",
+            last.tasks.completed,
+            last.tasks.total,
+            last.tasks.remaining(),
+            points.len()
+        )
+        .bright_white()
+    );
+    println!();
+
+    let file_name = match &phase_id {
+        Some(id) => format!("burndown-{}.svg", id.to_lowercase()),
+        None => "burndown.svg".to_string(),
+    };
+    let svg_path = utils::doplan_dir()?.join(&file_name);
+    utils::ensure_dir(svg_path.parent().unwrap_or(&svg_path))?;
+    fs::write(&svg_path, render_svg(&title, &points))
+        .context("Failed to write burndown chart")?;
+
+    println!("{}", "Files updated:".bright_cyan());
+    println!("  • doplan/{}", file_name);
+    println!();
+
+    Ok(())
+}
+
+/// Rows (out of `CHART_HEIGHT`) a value fills on a chart topping out at `max`
+fn level(value: usize, max: usize) -> usize {
+    (value as f64 / max as f64 * CHART_HEIGHT as f64).round() as usize
+}
+
+/// Lay out one character per day under a y axis labelled with `max` and 0
+fn render_ascii(points: &[DailyPoint], cell: impl Fn(&DailyPoint, usize, usize) -> char) -> String {
+    let max = points.iter().map(|p| p.tasks.total).max().unwrap_or(0).max(1);
+    let label_width = max.to_string().len();
+    let mut chart = String::new();
+
+    for row in (1..=CHART_HEIGHT).rev() {
+        let label = if row == CHART_HEIGHT { max.to_string() } else { String::new() };
+        chart.push_str(&format!("  {:>width$} │", label, width = label_width));
+        let line: String = points.iter().map(|p| cell(p, row, max)).collect();
+        chart.push_str(line.trim_end());
+        chart.push('\n');
+    }
+    chart.push_str(&format!("  {:>width$} └{}\n", 0, "─".repeat(points.len()), width = label_width));
+
+    let first = points.first().map(|p| p.date.format("%m-%d").to_string()).unwrap_or_default();
+    let last = points.last().map(|p| p.date.format("%m-%d").to_string()).unwrap_or_default();
+    chart.push_str(&format!("  {:>width$}  {}", "", first, width = label_width));
+    if points.len() > 1 {
+        let gap = points.len().saturating_sub(first.len() + last.len()).max(1);
+        chart.push_str(&format!("{}{}", " ".repeat(gap), last));
+    }
+    chart.push('\n');
+    chart
+}
+
+pub fn render_burndown_ascii(points: &[DailyPoint]) -> String {
+    render_ascii(points, |p, row, max| {
+        if level(p.tasks.remaining(), max) >= row { '█' } else { ' ' }
+    })
+}
+
+pub fn render_burnup_ascii(points: &[DailyPoint]) -> String {
+    render_ascii(points, |p, row, max| {
+        if level(p.tasks.completed, max) >= row {
+            '█'
+        } else if level(p.tasks.total, max) >= row {
+            '░'
+        } else {
+            ' '
+        }
+    })
+}
+
+/// Burndown and burnup lines on one chart: remaining, completed and total scope
+pub fn render_svg(title: &str, points: &[DailyPoint]) -> String {
+    let max = points.iter().map(|p| p.tasks.total).max().unwrap_or(0).max(1) as f64;
+    let plot_width = SVG_WIDTH - 2.0 * SVG_MARGIN;
+    let plot_height = SVG_HEIGHT - 2.0 * SVG_MARGIN;
+    let x = |i: usize| {
+        if points.len() > 1 {
+            SVG_MARGIN + plot_width * i as f64 / (points.len() - 1) as f64
+        } else {
+            SVG_MARGIN + plot_width / 2.0
+        }
+    };
+    let y = |value: usize| SVG_HEIGHT - SVG_MARGIN - plot_height * value as f64 / max;
+
+    let series = |value: fn(&DailyPoint) -> usize, color: &str, dash: &str| {
+        let coords: Vec<String> = points.iter().enumerate()
+            .map(|(i, p)| format!("{:.1},{:.1}", x(i), y(value(p))))
+            .collect();
+        let mut svg = format!(
+            "  <polyline fill=\"none\" stroke=\"{}\" stroke-width=\"2\"{} points=\"{}\"/>\n",
+            color,
+            dash,
+            coords.join(" ")
+        );
+        for (i, p) in points.iter().enumerate() {
+            svg.push_str(&format!(
+                "  <circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"3\" fill=\"{}\"/>\n",
+                x(i),
+                y(value(p)),
+                color
+            ));
+        }
+        svg
+    };
+
+    let mut svg = String::new();
+    svg.push_str(&format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" font-family=\"sans-serif\" font-size=\"12\">\n",
+        w = SVG_WIDTH,
+        h = SVG_HEIGHT
+    ));
+    svg.push_str(&format!("  <rect width=\"{}\" height=\"{}\" fill=\"#ffffff\"/>\n", SVG_WIDTH, SVG_HEIGHT));
+    svg.push_str(&format!(
+        "  <text x=\"{}\" y=\"24\" font-size=\"16\" font-weight=\"bold\">{}</text>\n",
+        SVG_MARGIN,
+        escape_xml(title)
+    ));
+
+    // Axes and labels
+    let bottom = SVG_HEIGHT - SVG_MARGIN;
+    svg.push_str(&format!(
+        "  <line x1=\"{m}\" y1=\"{m}\" x2=\"{m}\" y2=\"{b}\" stroke=\"#333333\"/>\n  <line x1=\"{m}\" y1=\"{b}\" x2=\"{r}\" y2=\"{b}\" stroke=\"#333333\"/>\n",
+        m = SVG_MARGIN,
+        b = bottom,
+        r = SVG_WIDTH - SVG_MARGIN
+    ));
+    svg.push_str(&format!("  <text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>\n", SVG_MARGIN - 6.0, SVG_MARGIN + 4.0, max));
+    svg.push_str(&format!("  <text x=\"{}\" y=\"{}\" text-anchor=\"end\">0</text>\n", SVG_MARGIN - 6.0, bottom + 4.0));
+    if let Some(first) = points.first() {
+        svg.push_str(&format!("  <text x=\"{:.1}\" y=\"{}\" text-anchor=\"middle\">{}</text>\n", x(0), bottom + 18.0, first.date));
+    }
+    if points.len() > 1 {
+        let last = points[points.len() - 1];
+        svg.push_str(&format!("  <text x=\"{:.1}\" y=\"{}\" text-anchor=\"middle\">{}</text>\n", x(points.len() - 1), bottom + 18.0, last.date));
+    }
+
+    svg.push_str(&series(|p| p.tasks.total, "#999999", " stroke-dasharray=\"4 4\""));
+    svg.push_str(&series(|p| p.tasks.completed, "#2e9e5b", ""));
+    svg.push_str(&series(|p| p.tasks.remaining(), "#d9534f", ""));
+
+    // Legend
+    let legend_x = SVG_WIDTH - SVG_MARGIN - 220.0;
+    for (i, (label, color)) in [("Remaining", "#d9534f"), ("Completed", "#2e9e5b"), ("Scope", "#999999")].iter().enumerate() {
+        let lx = legend_x + i as f64 * 75.0;
+        svg.push_str(&format!(
+            "  <rect x=\"{:.1}\" y=\"14\" width=\"10\" height=\"10\" fill=\"{}\"/>\n  <text x=\"{:.1}\" y=\"23\">{}</text>\n",
+            lx,
+            color,
+            lx + 14.0,
+            label
+        ));
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::history::TaskCounts;
+    use chrono::NaiveDate;
+
+    fn point(day: u32, total: usize, completed: usize) -> DailyPoint {
+        DailyPoint {
+            date: NaiveDate::from_ymd_opt(2026, 10, day).unwrap(),
+            tasks: TaskCounts { total, completed, ..Default::default() },
+        }
+    }
+
+    #[test]
+    fn test_burndown_ascii_bars_shrink_as_work_completes() {
+        let chart = render_burndown_ascii(&[point(19, 10, 0), point(20, 10, 5), point(21, 10, 10)]);
+        let lines: Vec<&str> = chart.lines().collect();
+        assert_eq!(lines.len(), CHART_HEIGHT + 2);
+        assert_eq!(lines[0], "  10 │█");
+        assert_eq!(lines[5], "     │██");
+        assert_eq!(lines[CHART_HEIGHT], "   0 └───");
+        assert!(lines[CHART_HEIGHT + 1].contains("10-19"));
+    }
+
+    #[test]
+    fn test_burnup_ascii_shows_scope_above_completed() {
+        let chart = render_burnup_ascii(&[point(19, 10, 0), point(20, 10, 5)]);
+        let lines: Vec<&str> = chart.lines().collect();
+        assert_eq!(lines[0], "  10 │░░");
+        assert_eq!(lines[9], "     │░█");
+    }
+
+    #[test]
+    fn test_svg_contains_all_series() {
+        let svg = render_svg("Project <Burndown>", &[point(19, 6, 1), point(20, 7, 4)]);
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(svg.matches("<polyline").count(), 3);
+        assert!(svg.contains("Project &lt;Burndown&gt;"));
+        assert!(svg.contains("2026-10-20"));
+    }
+}
@@ -0,0 +1,182 @@
+use anyhow::{Context, Result};
+use colored::*;
+use git2::{BranchType, Repository};
+use std::fs;
+use std::path::Path;
+use crate::config::Config;
+use crate::integration::commits::{self, Source, StatusChange};
+use crate::state::ProjectState;
+use crate::utils;
+use crate::utils::plan_dirs::PlanDirs;
+use super::implement;
+
+/// First line after the shebang of the hook DoPlan installs, so it can tell
+/// its own hook from someone else's
+const HOOK_MARKER: &str = "# Installed by doplan: task status from commit messages";
+
+/// Update task statuses from commit messages.
+///
+/// Usage:
+/// - `doplan commits [--quiet]` applies the task references on every feature
+///   branch (see [`commits::parse_message`] for the syntax)
+/// - `doplan commits install-hook [--force]` adds a `post-commit` hook that
+///   does the same after every commit; `--force` replaces an existing hook
+/// - `doplan commits uninstall-hook` removes it again
+pub async fn execute(args: Vec<String>) -> Result<()> {
+    let quiet = args.iter().any(|a| a == "--quiet");
+    let force = args.iter().any(|a| a == "--force");
+    let root = utils::project_root()?;
+
+    match args.iter().find(|a| !a.starts_with("--")).map(String::as_str) {
+        None | Some("scan") => {
+            if !quiet {
+                println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
+                println!("{}", "  DoPlan: Task Status from Commits".bright_cyan().bold());
+                println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
+                println!();
+            }
+            let mut state = ProjectState::load().context("Failed to load project state")?;
+            let Some(changes) = sync(&mut state)? else {
+                if !quiet {
+                    println!("{}", "Not a Git repository. Nothing to scan.".bright_yellow());
+                }
+                return Ok(());
+            };
+            if !changes.is_empty() {
+                state.save().context("Failed to save project state")?;
+            }
+            if changes.is_empty() && !quiet {
+                println!("{}", "No task status changes.".bright_white());
+            }
+            print_changes(&changes);
+            Ok(())
+        }
+        Some("install-hook") => install_hook(&root, force),
+        Some("uninstall-hook") => uninstall_hook(&root),
+        _ => anyhow::bail!("Usage: doplan commits [--quiet] | install-hook [--force] | uninstall-hook"),
+    }
+}
+
+/// Apply the task references in the project's commits to `tasks.md` and
+/// `state` (which is not saved). `None` when the project is not a Git
+/// repository or has no commits yet.
+///
+/// Bare task ids belong to the feature whose branch the commit is on; only
+/// commits that are not on another, non-feature branch count for them. Ids
+/// that name their feature (`F-0003/T2`) count on any local branch.
+pub fn sync(state: &mut ProjectState) -> Result<Option<Vec<StatusChange>>> {
+    let root = utils::project_root()?;
+    let Ok(repo) = Repository::open(&root) else {
+        return Ok(None);
+    };
+    if repo.head().is_err() {
+        return Ok(None);
+    }
+    let config = Config::load(&root)?;
+    let plan_dir = root.join(&config.paths.docs_dir).join("plan");
+
+    let layout = PlanDirs::scan(&plan_dir, state)?;
+    let mut feature_branches: Vec<(String, String)> = Vec::new();
+    for phase in &layout.phases {
+        for feature in &phase.features {
+            let Some(id) = feature.id.clone() else {
+                continue;
+            };
+            let branch = match state.worktrees.iter().find(|w| w.feature_id == id) {
+                Some(worktree) => worktree.branch.clone(),
+                None => implement::branch_name(&config, &layout, &phase.dir_name, &feature.dir_name)?,
+            };
+            if repo.find_branch(&branch, BranchType::Local).is_ok() {
+                feature_branches.push((id, branch));
+            }
+        }
+    }
+
+    let mut other_branches = Vec::new();
+    for branch in repo.branches(Some(BranchType::Local))? {
+        let (branch, _) = branch?;
+        if let Some(name) = branch.name()? {
+            if !feature_branches.iter().any(|(_, b)| b == name) {
+                other_branches.push(name.to_string());
+            }
+        }
+    }
+
+    let mut sources: Vec<Source> = feature_branches
+        .into_iter()
+        .map(|(feature_id, branch)| Source {
+            branch,
+            feature_id: Some(feature_id),
+            hide: other_branches.clone(),
+        })
+        .collect();
+    sources.extend(other_branches.iter().map(|branch| Source {
+        branch: branch.clone(),
+        feature_id: None,
+        hide: Vec::new(),
+    }));
+
+    let log_path = commits::log_path(&root.join(&config.paths.state_dir));
+    commits::apply(&repo, &plan_dir, state, &sources, &log_path).map(Some)
+}
+
+/// One line per change: `F-0001/T2: Not Started → Completed (1a2b3c4d Add login)`
+pub fn print_changes(changes: &[StatusChange]) {
+    for change in changes {
+        println!(
+            "  {} {}/{}: {} → {} ({} {})",
+            "→".bright_green(),
+            change.feature_id,
+            change.task_id,
+            change.from.label(),
+            change.to.label().bright_green(),
+            &change.commit[..8],
+            change.summary
+        );
+    }
+}
+
+fn install_hook(root: &Path, force: bool) -> Result<()> {
+    let repo = Repository::open(root).context("Not a Git repository. Run git init first.")?;
+    let hooks_dir = repo.path().join("hooks");
+    let hook = hooks_dir.join("post-commit");
+    if hook.exists() && !force {
+        let existing = fs::read_to_string(&hook).unwrap_or_default();
+        if !existing.contains(HOOK_MARKER) {
+            anyhow::bail!("{} already exists. Pass --force to replace it", hook.display());
+        }
+    }
+
+    // Commits in a worktree run the same hook, so go back to the project root
+    // where the state and the editable tasks.md files live
+    let root = root.to_string_lossy().replace('\'', r"'\''");
+    let script = format!(
+        "#!/bin/sh\n{}\ncd '{}' && doplan execute commits --quiet || true\n",
+        HOOK_MARKER, root
+    );
+    utils::ensure_dir(&hooks_dir)?;
+    fs::write(&hook, script).with_context(|| format!("Failed to write {}", hook.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&hook, fs::Permissions::from_mode(0o755))
+            .with_context(|| format!("Failed to make {} executable", hook.display()))?;
+    }
+    println!("{} Installed {}", "✓".bright_green(), hook.display());
+    println!("{}", "Task references in new commits now update tasks.md, e.g. \"Refs T2\" or \"Task: F-0003/T2 done\"".bright_white());
+    Ok(())
+}
+
+fn uninstall_hook(root: &Path) -> Result<()> {
+    let repo = Repository::open(root).context("Not a Git repository")?;
+    let hook = repo.path().join("hooks").join("post-commit");
+    match fs::read_to_string(&hook) {
+        Ok(existing) if existing.contains(HOOK_MARKER) => {
+            fs::remove_file(&hook).with_context(|| format!("Failed to remove {}", hook.display()))?;
+            println!("{} Removed {}", "✓".bright_green(), hook.display());
+        }
+        Ok(_) => anyhow::bail!("{} was not installed by DoPlan; leaving it alone", hook.display()),
+        Err(_) => println!("{}", "No post-commit hook installed.".bright_white()),
+    }
+    Ok(())
+}
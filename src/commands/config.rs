@@ -0,0 +1,78 @@
+use anyhow::{Context, Result};
+use colored::*;
+use std::path::Path;
+use crate::config::{self, Config, Source, KEYS};
+use crate::utils;
+
+/// Read and change settings.
+///
+/// Usage:
+/// - `doplan config list` shows every key, its resolved value and where it came from
+/// - `doplan config get <key>` prints one value
+/// - `doplan config set <key> <value> [--global]` writes the project's
+///   `doplan.toml`, or the user file with `--global`. An empty value removes
+///   the key. Secret keys such as `llm.api_key` only go in the user file,
+///   since `doplan.toml` is meant to be committed.
+pub async fn execute(args: Vec<String>) -> Result<()> {
+    let global = args.iter().any(|a| a == "--global");
+    let positional: Vec<&str> = args.iter().filter(|a| *a != "--global").map(String::as_str).collect();
+    let root = utils::project_root()?;
+
+    match positional.as_slice() {
+        [] | ["list"] => list(&root),
+        ["get", key] => {
+            let config = Config::load(&root)?;
+            if let Some(value) = config.get(key)? {
+                println!("{}", value);
+            }
+            Ok(())
+        }
+        ["set", key, value] => {
+            if !global && config::find_key(key)?.secret && !value.trim().is_empty() {
+                anyhow::bail!(
+                    "{} is a secret and doplan.toml is meant to be committed. Use `doplan config set {} <value> --global` or set ${}.",
+                    key,
+                    key,
+                    config::env_var(key)
+                );
+            }
+            let path = if global {
+                config::user_config_path().context("Could not find the user config directory. Set HOME or XDG_CONFIG_HOME.")?
+            } else {
+                root.join(config::PROJECT_FILE)
+            };
+            config::set_in_file(&path, key, value)?;
+            if value.trim().is_empty() {
+                println!("{} Removed {} from {}", "✓".bright_green(), key.bright_cyan(), path.display());
+            } else {
+                println!("{} Set {} = {} in {}", "✓".bright_green(), key.bright_cyan(), value, path.display());
+            }
+            // A higher layer still wins; say so rather than look like a no-op
+            let written = if global { Source::User(path) } else { Source::Project(path) };
+            match Config::resolve(&root)?.sources.get(key) {
+                Some(source) if !value.trim().is_empty() && *source != written => {
+                    println!("{}", format!("⚠ {} is overridden by {}", key, source).bright_yellow());
+                }
+                _ => {}
+            }
+            Ok(())
+        }
+        _ => anyhow::bail!("Usage: doplan config list | get <key> | set <key> <value> [--global]"),
+    }
+}
+
+fn list(root: &Path) -> Result<()> {
+    let resolved = Config::resolve(root)?;
+
+    println!("{}", "━".repeat(40).bright_blue());
+    println!("{}", "Configuration".bright_cyan().bold());
+    println!("{}", "━".repeat(40).bright_blue());
+    println!();
+    for key in KEYS {
+        let value = resolved.config.get(key.name)?.unwrap_or_default();
+        let value = if key.secret && !value.is_empty() { "********".to_string() } else { value };
+        let source = resolved.sources.get(key.name).map(ToString::to_string).unwrap_or_default();
+        println!("{} = {}  {}", key.name.bright_cyan(), value, format!("({})", source).dimmed());
+    }
+    Ok(())
+}
@@ -1,7 +1,10 @@
 use anyhow::{Context, Result};
 use colored::*;
 use serde_json::Value;
+use crate::config::Config;
 use crate::utils;
+use crate::utils::history;
+use crate::utils::velocity::Forecast;
 use std::fs;
 
 /// Execute the dashboard command
@@ -21,16 +24,22 @@ pub async fn execute(_args: Vec<String>) -> Result<()> {
     let dashboard_content = fs::read_to_string(&dashboard_json_path)
         .context("Failed to read dashboard.json")?;
     
-    let dashboard: Value = serde_json::from_str(&dashboard_content)
+    let mut dashboard: Value = serde_json::from_str(&dashboard_content)
         .context("Failed to parse dashboard.json")?;
 
+    // Projections are relative to today, so refresh them from the history
+    let snapshots = history::load(&history::history_path(&dot_doplan))?;
+    if let Some(forecast) = Forecast::from_history(&snapshots, chrono::Utc::now().date_naive()) {
+        dashboard["forecast"] = forecast.to_json();
+    }
+
     // Display dashboard
-    display_dashboard(&dashboard)?;
+    display_dashboard(&dashboard, Config::current()?.display.progress_bar_width)?;
 
     Ok(())
 }
 
-fn display_dashboard(dashboard: &Value) -> Result<()> {
+fn display_dashboard(dashboard: &Value, bar_width: usize) -> Result<()> {
     println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
     println!("{}", "  DoPlan: Project Dashboard".bright_cyan().bold());
     println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
@@ -54,7 +63,10 @@ fn display_dashboard(dashboard: &Value) -> Result<()> {
         println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
         println!();
         println!("{}", format!("{:.1}%", overall_progress).bright_green().bold());
-        println!("{}", generate_progress_bar(overall_progress));
+        println!("{}", generate_progress_bar(overall_progress, bar_width));
+        if let Some(mode) = dashboard.get("weighting").and_then(|w| w.get("mode")).and_then(|v| v.as_str()) {
+            println!("Weighting: {}", mode.bright_white());
+        }
         println!();
     }
 
@@ -73,7 +85,7 @@ fn display_dashboard(dashboard: &Value) -> Result<()> {
                 println!("{}", format!("Phase: {}", phase_name).bright_white().bold());
                 println!("  Progress: {:.1}%", progress);
                 println!("  Status: {}", format_status(status));
-                println!("  {}", generate_progress_bar(progress));
+                println!("  {}", generate_progress_bar(progress, bar_width));
                 println!();
 
                 // Features
@@ -145,12 +157,51 @@ fn display_dashboard(dashboard: &Value) -> Result<()> {
         println!();
     }
 
+    // Forecast
+    if let Some(forecast) = dashboard.get("forecast").filter(|f| !f.is_null()) {
+        println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
+        println!("{}", "  Forecast".bright_cyan().bold());
+        println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
+        println!();
+
+        match forecast.get("velocity").filter(|v| !v.is_null()) {
+            Some(velocity) => {
+                let per_day = velocity.get("tasks_per_day").and_then(|v| v.as_f64()).unwrap_or(0.0);
+                let per_week = velocity.get("tasks_per_week").and_then(|v| v.as_f64()).unwrap_or(0.0);
+                let window = velocity.get("window_days").and_then(|v| v.as_i64()).unwrap_or(0);
+                println!("  Velocity: {} tasks/day ({:.1}/week, last {} days)", format!("{:.1}", per_day).bright_white(), per_week, window);
+            }
+            None => println!("  Velocity: {}", "not enough history yet".bright_yellow()),
+        }
+        if let Some(project) = forecast.get("project") {
+            println!("  Projected completion: {}", describe_projection(project).bright_green());
+        }
+        if let Some(phases) = forecast.get("phases").and_then(|v| v.as_array()) {
+            for phase in phases {
+                let name = phase.get("name").and_then(|v| v.as_str()).unwrap_or("unknown");
+                println!("    {} {}: {}", "→".bright_cyan(), name.bright_white(), describe_projection(phase));
+            }
+        }
+        println!();
+    }
+
     Ok(())
 }
 
-fn generate_progress_bar(progress: f64) -> String {
-    let width = 30;
-    let filled = (progress / 100.0 * width as f64) as usize;
+fn describe_projection(projection: &Value) -> String {
+    let date = |key: &str| projection.get(key).and_then(|v| v.as_str());
+    if projection.get("remaining_tasks").and_then(|v| v.as_u64()) == Some(0) {
+        return "done".to_string();
+    }
+    match (date("projected_finish"), date("earliest_finish"), date("latest_finish")) {
+        (Some(finish), Some(earliest), Some(latest)) => format!("{} ({} – {})", finish, earliest, latest),
+        (Some(finish), Some(earliest), None) => format!("{} ({} – open-ended)", finish, earliest),
+        _ => "no velocity yet".to_string(),
+    }
+}
+
+fn generate_progress_bar(progress: f64, width: usize) -> String {
+    let filled = ((progress / 100.0 * width as f64) as usize).min(width);
     let empty = width - filled;
     format!("[{}{}]", "█".repeat(filled), "░".repeat(empty))
 }
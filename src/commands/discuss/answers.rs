@@ -0,0 +1,228 @@
+//! Answers file for `doplan execute discuss --answers <file>`.
+//!
+//! YAML, or JSON when the file ends in `.json`. Every field is optional;
+//! whatever is missing is asked for on a terminal and left empty otherwise.
+//!
+//! ```yaml
+//! project_name: Recipe Box
+//! idea: Share and plan family recipes
+//! questions:
+//!   target_audience: Home cooks
+//!   platforms: web, mobile
+//! features:
+//!   - name: User Auth
+//!     priority: high
+//!   - Recipe Search          # priority from its position, like the prompt
+//! tech_stack: ["Backend: Rust/Axum", "Database: PostgreSQL"]
+//! phases:
+//!   - name: Foundation
+//!     description: Accounts and storage
+//!     features: [User Auth]
+//! ```
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::Path;
+use super::QUESTIONS;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DiscussAnswers {
+    pub project_name: Option<String>,
+    pub idea: Option<String>,
+    /// Answers keyed by [`QUESTIONS`] key
+    pub questions: Option<BTreeMap<String, String>>,
+    pub features: Option<Vec<FeatureAnswer>>,
+    pub tech_stack: Option<Vec<String>>,
+    pub phases: Option<Vec<PhaseAnswer>>,
+}
+
+/// A feature given as a bare name or with its details
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FeatureAnswer {
+    Name(String),
+    #[serde(rename_all = "snake_case")]
+    Detailed {
+        name: String,
+        priority: Option<String>,
+        description: Option<String>,
+    },
+}
+
+impl FeatureAnswer {
+    pub fn name(&self) -> &str {
+        match self {
+            FeatureAnswer::Name(name) | FeatureAnswer::Detailed { name, .. } => name.trim(),
+        }
+    }
+
+    /// Lower-cased priority, if one was given
+    pub fn priority(&self) -> Option<String> {
+        match self {
+            FeatureAnswer::Detailed { priority: Some(priority), .. } => Some(priority.trim().to_lowercase()),
+            _ => None,
+        }
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        match self {
+            FeatureAnswer::Detailed { description: Some(description), .. } => Some(description.trim()),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PhaseAnswer {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Names of features listed under `features`
+    #[serde(default)]
+    pub features: Vec<String>,
+}
+
+impl DiscussAnswers {
+    /// Read and validate an answers file against the configured `priorities`
+    pub fn load(path: &Path, priorities: &[String]) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read answers file {}", path.display()))?;
+        let answers: Self = if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json")) {
+            serde_json::from_str(&content)
+                .with_context(|| format!("Invalid answers file {}", path.display()))?
+        } else {
+            serde_yaml::from_str(&content)
+                .with_context(|| format!("Invalid answers file {}", path.display()))?
+        };
+        answers.validate(priorities)
+            .with_context(|| format!("Invalid answers file {}", path.display()))?;
+        Ok(answers)
+    }
+
+    /// Fill the fields missing here from `fallback`, question by question
+    pub fn or(mut self, fallback: DiscussAnswers) -> Self {
+        if let Some(fallback_questions) = fallback.questions {
+            let questions = self.questions.get_or_insert_with(BTreeMap::new);
+            for (key, answer) in fallback_questions {
+                questions.entry(key).or_insert(answer);
+            }
+        }
+        DiscussAnswers {
+            project_name: self.project_name.or(fallback.project_name),
+            idea: self.idea.or(fallback.idea),
+            questions: self.questions,
+            features: self.features.or(fallback.features),
+            tech_stack: self.tech_stack.or(fallback.tech_stack),
+            phases: self.phases.or(fallback.phases),
+        }
+    }
+
+    /// Check question keys, feature names and priorities, and that every
+    /// phase lists known features at most once across all phases
+    pub fn validate(&self, priorities: &[String]) -> Result<()> {
+        for key in self.questions.iter().flatten().map(|(key, _)| key) {
+            if !QUESTIONS.iter().any(|(known, _)| known == key) {
+                let known: Vec<&str> = QUESTIONS.iter().map(|(key, _)| *key).collect();
+                anyhow::bail!("questions.{}: unknown question (expected one of: {})", key, known.join(", "));
+            }
+        }
+
+        let mut names = HashSet::new();
+        for (i, feature) in self.features.iter().flatten().enumerate() {
+            let name = feature.name();
+            if name.is_empty() {
+                anyhow::bail!("features[{}]: name is empty", i);
+            }
+            if !names.insert(name.to_lowercase()) {
+                anyhow::bail!("features[{}]: duplicate feature '{}'", i, name);
+            }
+            if let Some(priority) = feature.priority() {
+                if !priorities.contains(&priority) {
+                    anyhow::bail!(
+                        "features[{}] ({}): unknown priority '{}' (expected {})",
+                        i,
+                        name,
+                        priority,
+                        priorities.join(", ")
+                    );
+                }
+            }
+        }
+
+        let mut assigned: BTreeMap<String, &str> = BTreeMap::new();
+        for (i, phase) in self.phases.iter().flatten().enumerate() {
+            if phase.name.trim().is_empty() {
+                anyhow::bail!("phases[{}]: name is empty", i);
+            }
+            for (j, feature) in phase.features.iter().enumerate() {
+                let key = feature.trim().to_lowercase();
+                if self.features.is_some() && !names.contains(&key) {
+                    anyhow::bail!(
+                        "phases[{}].features[{}] ({}): unknown feature '{}'",
+                        i,
+                        j,
+                        phase.name,
+                        feature
+                    );
+                }
+                if let Some(other) = assigned.insert(key, &phase.name) {
+                    anyhow::bail!(
+                        "phases[{}].features[{}] ({}): feature '{}' is already in phase '{}'",
+                        i,
+                        j,
+                        phase.name,
+                        feature,
+                        other
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn parse(yaml: &str) -> Result<DiscussAnswers> {
+        let answers: DiscussAnswers = serde_yaml::from_str(yaml)?;
+        answers.validate(&Config::default().plan.priorities)?;
+        Ok(answers)
+    }
+
+    #[test]
+    fn test_feature_shorthand_and_details() {
+        let answers = parse("features:\n  - Search\n  - name: Auth\n    priority: HIGH\n").unwrap();
+        let features = answers.features.unwrap();
+        assert_eq!(features[0].name(), "Search");
+        assert_eq!(features[0].priority(), None);
+        assert_eq!(features[1].priority().as_deref(), Some("high"));
+    }
+
+    #[test]
+    fn test_validation_errors() {
+        let error = |yaml: &str| format!("{:#}", parse(yaml).unwrap_err());
+
+        assert!(error("questions:\n  budget: none\n").starts_with("questions.budget: unknown question"));
+        assert_eq!(
+            error("features:\n  - name: Auth\n    priority: urgent\n"),
+            "features[0] (Auth): unknown priority 'urgent' (expected high, medium, low)"
+        );
+        assert_eq!(error("features: [Auth, auth]\n"), "features[1]: duplicate feature 'auth'");
+        assert_eq!(
+            error("features: [Auth]\nphases:\n  - name: One\n    features: [Auth, Billing]\n"),
+            "phases[0].features[1] (One): unknown feature 'Billing'"
+        );
+        assert_eq!(
+            error("features: [Auth]\nphases:\n  - name: One\n    features: [Auth]\n  - name: Two\n    features: [Auth]\n"),
+            "phases[1].features[0] (Two): feature 'Auth' is already in phase 'One'"
+        );
+        assert!(error("project: x\n").contains("unknown field `project`"));
+    }
+}
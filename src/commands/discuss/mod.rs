@@ -0,0 +1,575 @@
+pub mod answers;
+pub mod session;
+
+use anyhow::{Context, Result};
+use dialoguer::{Confirm, Input, MultiSelect};
+use colored::*;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use crate::config::Config;
+use crate::integration::llm::{self, LlmProvider};
+use crate::state::{DiscussionAnswer, ProjectState, Feature, Phase};
+use crate::utils;
+
+pub use answers::DiscussAnswers;
+pub use session::DiscussSession;
+use answers::{FeatureAnswer, PhaseAnswer};
+
+/// The discussion questions: answers-file key and prompt
+pub const QUESTIONS: [(&str, &str); 8] = [
+    ("target_audience", "Who is your target audience?"),
+    ("problem", "What problem does this solve?"),
+    ("unique_value", "What makes your solution unique?"),
+    ("core_features", "What are the core features you envision?"),
+    ("constraints", "Are there any technical constraints or requirements?"),
+    ("timeline", "What's your timeline or deadline?"),
+    ("design_preferences", "Do you have any design preferences?"),
+    ("platforms", "What platforms should this support? (web, mobile, desktop)"),
+];
+
+/// Asks for whatever the answers file leaves out. Without a terminal,
+/// optional answers are left empty and required ones fail.
+struct Prompter {
+    interactive: bool,
+}
+
+impl Prompter {
+    /// A required answer, defaulting to `default` (the value already in state)
+    fn text(&self, field: &str, prompt: &str, default: Option<String>) -> Result<String> {
+        if !self.interactive {
+            return default
+                .filter(|value| !value.trim().is_empty())
+                .ok_or_else(|| anyhow::anyhow!("`{}` is missing from the answers file and there is no terminal to ask for it", field));
+        }
+        Input::new()
+            .with_prompt(prompt)
+            .default(default.unwrap_or_default())
+            .interact_text()
+            .with_context(|| format!("Failed to read {}", field))
+    }
+
+    fn optional_text(&self, field: &str, prompt: &str) -> Result<String> {
+        if !self.interactive {
+            return Ok(String::new());
+        }
+        Input::new()
+            .with_prompt(prompt)
+            .allow_empty(true)
+            .interact_text()
+            .with_context(|| format!("Failed to read {}", field))
+    }
+
+    fn select(&self, field: &str, prompt: &str, items: &[&str]) -> Result<Vec<usize>> {
+        if !self.interactive {
+            return Ok(Vec::new());
+        }
+        MultiSelect::new()
+            .with_prompt(prompt)
+            .items(items)
+            .interact()
+            .with_context(|| format!("Failed to select {}", field))
+    }
+}
+
+/// The session being filled in. Saved after every prompted step so an
+/// aborted discussion can be resumed; nothing is saved without a terminal.
+struct Draft {
+    session: DiscussSession,
+    path: PathBuf,
+    enabled: bool,
+}
+
+impl Draft {
+    fn save(&mut self) -> Result<()> {
+        if self.enabled {
+            self.session.save(&self.path)?;
+        }
+        Ok(())
+    }
+}
+
+/// Execute the /discuss command.
+///
+/// Usage: `doplan execute discuss [--answers answers.yaml] [--resume]`. See
+/// [`answers`] for the file format; anything it leaves out is asked for on a
+/// terminal. Answers are kept in [`DiscussSession`] until the discussion
+/// completes, and a re-run offers to resume from the first unanswered step.
+/// Without a terminal the draft is only merged under the answers file with
+/// `--resume`; otherwise it is ignored and discarded.
+pub async fn execute(args: Vec<String>) -> Result<()> {
+    let answers_path = args
+        .iter()
+        .position(|a| a == "--answers")
+        .map(|i| args.get(i + 1).context("--answers needs a file path"))
+        .transpose()?;
+    let config = Config::current()?;
+    let file_answers = match answers_path {
+        Some(path) => DiscussAnswers::load(Path::new(path), &config.plan.priorities)?,
+        None => DiscussAnswers::default(),
+    };
+    let resume_flag = args.iter().any(|a| a == "--resume");
+    let prompter = Prompter { interactive: std::io::stdin().is_terminal() };
+    if answers_path.is_none() && !prompter.interactive {
+        anyhow::bail!("/discuss needs a terminal. Pass --answers <file> to run it non-interactively.");
+    }
+
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
+    println!("{}", "  DoPlan: Idea Discussion & Refinement".bright_cyan().bold());
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
+    println!();
+
+    // Load existing state or create new
+    let mut state = ProjectState::load()
+        .context("Failed to load project state")?;
+
+    // Resume an unfinished discussion
+    let session_path = DiscussSession::path(&utils::dot_doplan_dir()?);
+    let mut session = DiscussSession::default();
+    if let Some(saved) = DiscussSession::load(&session_path)? {
+        let resume = if resume_flag || !prompter.interactive {
+            resume_flag
+        } else {
+            Confirm::new()
+                .with_prompt(format!("Resume the unfinished discussion at {}?", saved.next_step()))
+                .default(true)
+                .interact()
+                .context("Failed to read resume choice")?
+        };
+        if resume {
+            println!("{}", format!("Resuming the discussion at {}...", saved.next_step()).bright_yellow());
+            session = saved;
+        } else {
+            if !prompter.interactive {
+                println!(
+                    "{}",
+                    format!("Ignoring the unfinished discussion at {}; pass --resume to continue it.", saved.next_step())
+                        .bright_yellow()
+                );
+            }
+            DiscussSession::remove(&session_path)?;
+        }
+    }
+    session.answers = file_answers.or(session.answers);
+    let mut draft = Draft { session, path: session_path, enabled: prompter.interactive };
+
+    // Step 1: Project Name
+    let project_name = match draft.session.answers.project_name.clone() {
+        Some(name) => name,
+        None => {
+            let name = prompter.text("project_name", "What's your project name?", state.project_name.clone())?;
+            draft.session.answers.project_name = Some(name.clone());
+            draft.save()?;
+            name
+        }
+    };
+
+    // Step 2: Core Idea
+    let idea = match draft.session.answers.idea.clone() {
+        Some(idea) => idea,
+        None => {
+            println!();
+            println!("{}", "Let's start with your core idea...".bright_yellow());
+            let idea = prompter.text("idea", "Describe your app idea in detail", state.idea.clone())?;
+            draft.session.answers.idea = Some(idea.clone());
+            draft.save()?;
+            idea
+        }
+    };
+
+    // Step 3: Ask comprehensive questions
+    let answered = draft.session.answers.questions.as_ref().map_or(0, |q| q.len());
+    if prompter.interactive && answered < QUESTIONS.len() {
+        println!();
+        println!("{}", "Now let's dive deeper with some questions...".bright_yellow());
+    }
+    let mut discussion = Vec::new();
+    for (key, question) in QUESTIONS {
+        let answer = match draft.session.answers.questions.as_ref().and_then(|q| q.get(key)) {
+            Some(answer) => answer.clone(),
+            None => {
+                let answer = prompter.optional_text(key, question)?;
+                draft.session.answers.questions.get_or_insert_with(Default::default).insert(key.to_string(), answer.clone());
+                draft.save()?;
+                answer
+            }
+        };
+        discussion.push(DiscussionAnswer { key: key.to_string(), question: question.to_string(), answer });
+    }
+
+    // Step 4: Feature organization
+    let listed = match draft.session.answers.features.clone() {
+        Some(listed) => listed,
+        None => {
+            println!();
+            println!("{}", "Let's organize your features...".bright_yellow());
+            let feature_names = prompter.optional_text("features", "List your main features (comma-separated)")?;
+            let listed: Vec<FeatureAnswer> = feature_names
+                .split(',')
+                .map(str::trim)
+                .filter(|f| !f.is_empty())
+                .map(|f| FeatureAnswer::Name(f.to_string()))
+                .collect();
+            draft.session.answers.features = Some(listed.clone());
+            draft.save()?;
+            listed
+        }
+    };
+    let mut features: Vec<Feature> = listed
+        .iter()
+        .enumerate()
+        .map(|(i, feature)| {
+            let priority = feature.priority().unwrap_or_else(|| config.default_priority(i).to_string());
+            build_feature(&mut state, feature.name(), &priority, feature.description())
+        })
+        .collect();
+
+    // Step 5: Tech stack recommendations
+    let tech_stack: Vec<String> = match draft.session.answers.tech_stack.clone() {
+        Some(tech_stack) => tech_stack,
+        None => {
+            println!();
+            println!("{}", "Let's recommend a tech stack...".bright_yellow());
+            let options: Vec<&str> = config.discuss.tech_options.iter().map(String::as_str).collect();
+            let tech_stack: Vec<String> = prompter
+                .select("technologies", "Select recommended technologies", &options)?
+                .iter()
+                .map(|&i| options[i].to_string())
+                .collect();
+            draft.session.answers.tech_stack = Some(tech_stack.clone());
+            draft.save()?;
+            tech_stack
+        }
+    };
+
+    // Step 6: Suggest improvements
+    println!();
+    println!("{}", "Based on your idea, here are some suggestions...".bright_green());
+    
+    let refined = match llm::configured()? {
+        Some(provider) => {
+            let mut draft_state = state.clone();
+            draft_state.project_name = Some(project_name.clone());
+            draft_state.idea = Some(idea.clone());
+            draft_state.discussion = discussion.clone();
+            draft_state.tech_stack = Some(tech_stack.clone());
+            refine_with_llm(&provider, draft_state, &mut features).await
+        }
+        None => None,
+    };
+    let improvements = refined.unwrap_or_else(|| generate_improvements(&idea, &features));
+    for improvement in &improvements {
+        println!("  • {}", improvement.bright_white());
+    }
+
+    // Step 7: Phase organization
+    if draft.session.answers.phases.is_none() && prompter.interactive {
+        println!();
+        println!("{}", "Let's organize features into phases...".bright_yellow());
+
+        let phase_count = match draft.session.phase_count {
+            Some(count) => count,
+            None => {
+                let count: usize = Input::new()
+                    .with_prompt("How many development phases do you want?")
+                    .default(3)
+                    .interact_text()
+                    .context("Failed to read phase count")?;
+                draft.session.phase_count = Some(count);
+                draft.save()?;
+                count
+            }
+        };
+
+        for i in draft.session.partial_phases.len() + 1..=phase_count {
+            let phase_name: String = Input::new()
+                .with_prompt(format!("Phase {} name", i))
+                .default(format!("Phase {}", i))
+                .interact_text()
+                .context("Failed to read phase name")?;
+
+            let phase_description: String = Input::new()
+                .with_prompt(format!("Phase {} description", i))
+                .allow_empty(true)
+                .interact_text()
+                .context("Failed to read phase description")?;
+
+            // Assign features to phases
+            let feature_list: Vec<&str> = features.iter().map(|f| f.name.as_str()).collect();
+            let selected = if feature_list.is_empty() {
+                Vec::new()
+            } else {
+                prompter.select("features", &format!("Select features for {}", phase_name), &feature_list)?
+            };
+
+            draft.session.partial_phases.push(PhaseAnswer {
+                name: phase_name,
+                description: phase_description,
+                features: selected.iter().map(|&i| features[i].name.clone()).collect(),
+            });
+            draft.save()?;
+        }
+        draft.session.answers.phases = Some(std::mem::take(&mut draft.session.partial_phases));
+    }
+
+    let mut phases = Vec::new();
+    for (i, phase) in draft.session.answers.phases.iter().flatten().enumerate() {
+        let mut feature_ids = Vec::new();
+        for (j, name) in phase.features.iter().enumerate() {
+            let feature = features
+                .iter()
+                .find(|f| f.name.eq_ignore_ascii_case(name.trim()))
+                .ok_or_else(|| anyhow::anyhow!("phases[{}].features[{}] ({}): unknown feature '{}'", i, j, phase.name, name))?;
+            feature_ids.push(feature.id.clone());
+        }
+        phases.push(build_phase(&mut state, phase.name.trim(), &phase.description, feature_ids));
+    }
+
+    // Update state
+    state.project_name = Some(project_name.clone());
+    state.idea = Some(idea.clone());
+    state.discussion = discussion;
+    state.tech_stack = Some(tech_stack.clone());
+    state.features = Some(features.clone());
+    state.phases = Some(phases.clone());
+    state.improvements = Some(improvements.clone());
+    state.notes = Some(format!("Discussion completed on {}", chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC")));
+
+    // Save state
+    state.save()
+        .context("Failed to save project state")?;
+    DiscussSession::remove(&draft.path)?;
+
+    // Generate idea notes
+    generate_idea_notes(&state)
+        .context("Failed to generate idea notes")?;
+
+    // Summary
+    println!();
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_green());
+    println!("{}", "  Discussion Complete!".bright_green().bold());
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_green());
+    println!();
+    println!("{}", format!("Project: {}", project_name).bright_white());
+    println!("{}", format!("Features: {}", features.len()).bright_white());
+    println!("{}", format!("Phases: {}", phases.len()).bright_white());
+    println!("{}", format!("Tech Stack: {} technologies", tech_stack.len()).bright_white());
+    println!();
+    println!("{}", "Files created:".bright_cyan());
+    println!("  • .doplan/state.json");
+    println!("  • doplan/idea-notes.md");
+    println!();
+    println!("{}", "Next steps:".bright_yellow());
+    println!("  1. Review doplan/idea-notes.md");
+    println!("  2. Run /generate to create PRD and project structure");
+    println!();
+
+    Ok(())
+}
+
+/// A phase named `name`, keeping the id of an existing phase with that name
+fn build_phase(state: &mut ProjectState, name: &str, description: &str, features: Vec<String>) -> Phase {
+    let id = match state.find_phase(name) {
+        Some(phase) => phase.id.clone(),
+        None => state.new_phase_id(),
+    };
+    Phase {
+        id,
+        name: name.to_string(),
+        description: description.to_string(),
+        features,
+    }
+}
+
+/// Features from a comma-separated list of names. Earlier names get higher
+/// priority (see [`Config::default_priority`]); features that already exist
+/// keep their id, tasks and dependencies.
+pub(crate) fn build_features(state: &mut ProjectState, config: &Config, feature_names: &str) -> Vec<Feature> {
+    feature_names
+        .split(',')
+        .map(str::trim)
+        .filter(|f| !f.is_empty())
+        .enumerate()
+        .map(|(i, name)| build_feature(state, name, config.default_priority(i), None))
+        .collect()
+}
+
+/// A feature named `name`. Re-discussing keeps the id, tasks and
+/// dependencies of a feature that already exists.
+pub(crate) fn build_feature(state: &mut ProjectState, name: &str, priority: &str, description: Option<&str>) -> Feature {
+    let existing = state.find_feature(name).cloned();
+    let id = match &existing {
+        Some(feature) => feature.id.clone(),
+        None => state.new_feature_id(),
+    };
+    Feature {
+        id,
+        name: name.to_string(),
+        description: description
+            .filter(|d| !d.is_empty())
+            .map(str::to_string)
+            .unwrap_or_else(|| placeholder_description(name)),
+        priority: priority.to_string(),
+        tasks: existing.as_ref().map(|f| f.tasks.clone()).unwrap_or_default(),
+        depends_on: existing.as_ref().map(|f| f.depends_on.clone()).unwrap_or_default(),
+        user_stories: existing.as_ref().map(|f| f.user_stories.clone()).unwrap_or_default(),
+        pull_request: existing.and_then(|f| f.pull_request),
+    }
+}
+
+/// Description given to a feature listed without one
+fn placeholder_description(name: &str) -> String {
+    format!("Feature: {}", name)
+}
+
+/// Ask the LLM provider for improvements, and for descriptions of the
+/// features that only have a placeholder. Failures are reported and leave
+/// the rule-based content in place; `None` means no improvements came back.
+async fn refine_with_llm(provider: &impl LlmProvider, mut state: ProjectState, features: &mut [Feature]) -> Option<Vec<String>> {
+    let undescribed: Vec<&Feature> = features
+        .iter()
+        .filter(|f| f.description == placeholder_description(&f.name))
+        .collect();
+    if !undescribed.is_empty() {
+        state.features = Some(features.to_vec());
+        match llm::describe_features(provider, &state, &undescribed).await {
+            Ok(descriptions) => {
+                for feature in features.iter_mut() {
+                    if let Some(description) = descriptions.get(&feature.id) {
+                        feature.description = description.clone();
+                    }
+                }
+            }
+            Err(e) => println!("{}", format!("⚠ Keeping the feature descriptions: {:#}", e).bright_yellow()),
+        }
+    }
+
+    state.features = Some(features.to_vec());
+    match llm::suggest_improvements(provider, &state).await {
+        Ok(improvements) => Some(improvements),
+        Err(e) => {
+            println!("{}", format!("⚠ Using the built-in suggestions: {:#}", e).bright_yellow());
+            None
+        }
+    }
+}
+
+pub(crate) fn generate_improvements(idea: &str, features: &[Feature]) -> Vec<String> {
+    let mut improvements = Vec::new();
+
+    // Basic suggestions based on common patterns
+    if idea.to_lowercase().contains("user") || idea.to_lowercase().contains("auth") {
+        improvements.push("Consider implementing user authentication and authorization".to_string());
+    }
+
+    if features.len() > 5 {
+        improvements.push("Consider prioritizing features - start with MVP and iterate".to_string());
+    }
+
+    if idea.to_lowercase().contains("data") || idea.to_lowercase().contains("storage") {
+        improvements.push("Plan your data model and storage strategy early".to_string());
+    }
+
+    improvements.push("Set up proper error handling and logging from the start".to_string());
+    improvements.push("Consider implementing automated testing (unit, integration, e2e)".to_string());
+    improvements.push("Plan for scalability - design with growth in mind".to_string());
+    improvements.push("Document your API contracts early for better team collaboration".to_string());
+
+    improvements
+}
+
+pub(crate) fn generate_idea_notes(state: &ProjectState) -> Result<()> {
+    let doplan_dir = utils::doplan_dir()?;
+    utils::ensure_dir(&doplan_dir)?;
+
+    let notes_path = doplan_dir.join("idea-notes.md");
+    
+    let mut content = String::new();
+    
+    // Header
+    content.push_str("# Idea Discussion Notes\n\n");
+    content.push_str(&format!("**Project:** {}\n\n", 
+        state.project_name.as_ref().unwrap_or(&"Untitled".to_string())));
+    content.push_str(&format!("**Date:** {}\n\n", 
+        state.notes.as_ref().unwrap_or(&"Unknown".to_string())));
+    content.push_str("---\n\n");
+
+    // Core Idea
+    content.push_str("## Core Idea\n\n");
+    content.push_str(&format!("{}\n\n", 
+        state.idea.as_ref().unwrap_or(&"No idea provided".to_string())));
+
+    // Q&A Section
+    content.push_str("## Discussion Questions & Answers\n\n");
+    for qa in &state.discussion {
+        if !qa.answer.is_empty() {
+            content.push_str(&format!("### {}\n\n", qa.question));
+            content.push_str(&format!("{}\n\n", qa.answer));
+        }
+    }
+
+    // Features
+    if let Some(features) = &state.features {
+        if !features.is_empty() {
+            content.push_str("## Features\n\n");
+            for feature in features {
+                content.push_str(&format!("### {}\n", feature.name));
+                content.push_str(&format!("- **Priority:** {}\n", feature.priority));
+                content.push_str(&format!("- **Description:** {}\n\n", feature.description));
+            }
+        }
+    }
+
+    // Phases
+    if let Some(phases) = &state.phases {
+        if !phases.is_empty() {
+            content.push_str("## Development Phases\n\n");
+            for (i, phase) in phases.iter().enumerate() {
+                content.push_str(&format!("### Phase {}: {}\n\n", i + 1, phase.name));
+                content.push_str(&format!("{}\n\n", phase.description));
+                let phase_features = state.phase_features(phase);
+                if !phase_features.is_empty() {
+                    content.push_str("**Features:**\n");
+                    for feature in phase_features {
+                        content.push_str(&format!("- {}\n", feature.name));
+                    }
+                    content.push('\n');
+                }
+            }
+        }
+    }
+
+    // Tech Stack
+    if let Some(tech_stack) = &state.tech_stack {
+        if !tech_stack.is_empty() {
+            content.push_str("## Recommended Tech Stack\n\n");
+            for tech in tech_stack {
+                content.push_str(&format!("- {}\n", tech));
+            }
+            content.push('\n');
+        }
+    }
+
+    // Improvements
+    if let Some(improvements) = &state.improvements {
+        if !improvements.is_empty() {
+            content.push_str("## Suggested Improvements\n\n");
+            for improvement in improvements {
+                content.push_str(&format!("- {}\n", improvement));
+            }
+            content.push('\n');
+        }
+    }
+
+    // Next Steps
+    content.push_str("## Next Steps\n\n");
+    content.push_str("1. Review and refine the idea based on the discussion\n");
+    content.push_str("2. Run `/generate` to create PRD and project structure\n");
+    content.push_str("3. Run `/plan` to create detailed phase and feature plans\n");
+    content.push_str("4. Run `/generate` again to create DPR, SOPS, and other documents\n");
+
+    std::fs::write(&notes_path, content)
+        .context("Failed to write idea notes")?;
+
+    Ok(())
+}
+
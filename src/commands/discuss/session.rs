@@ -0,0 +1,111 @@
+//! Draft of an unfinished `/discuss`, saved after every answered step.
+//!
+//! The draft lives in `.doplan/discuss-session.json` and holds the same
+//! fields as an answers file, so resuming is running `/discuss` with the
+//! draft as its answers. It is removed once the discussion is saved to state.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use super::answers::{DiscussAnswers, PhaseAnswer};
+use super::QUESTIONS;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DiscussSession {
+    /// RFC 3339 time of the last saved step
+    pub updated_at: String,
+    /// Steps answered so far
+    pub answers: DiscussAnswers,
+    /// Number of phases chosen in the phase step
+    #[serde(default)]
+    pub phase_count: Option<usize>,
+    /// Phases entered before `answers.phases` is complete
+    #[serde(default)]
+    pub partial_phases: Vec<PhaseAnswer>,
+}
+
+impl DiscussSession {
+    pub fn path(dot_doplan_dir: &Path) -> PathBuf {
+        dot_doplan_dir.join("discuss-session.json")
+    }
+
+    /// The saved draft, if there is one
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let session = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}. Delete it to start the discussion over.", path.display()))?;
+        Ok(Some(session))
+    }
+
+    pub fn save(&mut self, path: &Path) -> Result<()> {
+        self.updated_at = chrono::Utc::now().to_rfc3339();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .context("Failed to serialize discuss session")?;
+        fs::write(path, content)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    pub fn remove(path: &Path) -> Result<()> {
+        if path.exists() {
+            fs::remove_file(path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+        Ok(())
+    }
+
+    /// The first step still to be answered, for the resume prompt
+    pub fn next_step(&self) -> String {
+        let answers = &self.answers;
+        if answers.project_name.is_none() {
+            return "the project name".to_string();
+        }
+        if answers.idea.is_none() {
+            return "the idea".to_string();
+        }
+        let answered = answers.questions.as_ref().map_or(0, |q| q.len());
+        if answered < QUESTIONS.len() {
+            return format!("question {} of {}", answered + 1, QUESTIONS.len());
+        }
+        if answers.features.is_none() {
+            return "the feature list".to_string();
+        }
+        if answers.tech_stack.is_none() {
+            return "the tech stack".to_string();
+        }
+        match self.phase_count {
+            Some(count) if answers.phases.is_none() => format!("phase {} of {}", self.partial_phases.len() + 1, count),
+            _ => "the phases".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_step() {
+        let mut session = DiscussSession::default();
+        assert_eq!(session.next_step(), "the project name");
+        session.answers.project_name = Some("App".to_string());
+        session.answers.idea = Some("Idea".to_string());
+        session.answers.questions = Some([("target_audience".to_string(), String::new())].into());
+        assert_eq!(session.next_step(), "question 2 of 8");
+        session.answers.questions = Some(QUESTIONS.iter().map(|(key, _)| (key.to_string(), String::new())).collect());
+        session.answers.features = Some(Vec::new());
+        session.answers.tech_stack = Some(Vec::new());
+        assert_eq!(session.next_step(), "the phases");
+        session.phase_count = Some(3);
+        session.partial_phases.push(PhaseAnswer { name: "One".to_string(), description: String::new(), features: Vec::new() });
+        assert_eq!(session.next_step(), "phase 2 of 3");
+    }
+}
@@ -1,7 +1,10 @@
 use anyhow::{Context, Result};
 use colored::*;
 use dialoguer::{Input, Select};
+use crate::config::Config;
 use crate::state::{ProjectState, Feature};
+use crate::utils;
+use crate::utils::plan_dirs;
 
 /// Execute feature management commands
 pub async fn execute(args: Vec<String>) -> Result<()> {
@@ -65,15 +68,45 @@ async fn add_feature() -> Result<()> {
         .context("Failed to read feature description")?;
 
     // Get priority
-    let priority_options = vec!["high", "medium", "low"];
+    let config = Config::current()?;
+    let priority_options = &config.plan.priorities;
     let priority_idx = Select::new()
         .with_prompt("Priority")
-        .items(&priority_options)
-        .default(1)
+        .items(priority_options)
+        .default(priority_options.len() / 2)
         .interact()
         .context("Failed to select priority")?;
     let priority = priority_options[priority_idx].to_string();
 
+    // Get dependencies (optional)
+    let depends_input: String = Input::new()
+        .with_prompt("Depends on features (comma-separated, or leave empty)")
+        .allow_empty(true)
+        .interact_text()
+        .context("Failed to read feature dependencies")?;
+    let depends_on = resolve_feature_dependencies(&state, &depends_input)?;
+
+    let new_feature_id = create_feature(&mut state, &name, description, priority, depends_on)?;
+
+    println!();
+    println!("{}", format!("✓ Feature '{}' added successfully ({})", name, new_feature_id).bright_green());
+    println!();
+    println!("{}", "Next steps:".bright_yellow());
+    println!("  1. Run /plan to regenerate the plan structure");
+    println!("  2. Review the updated state in .doplan/state.json");
+    println!();
+
+    Ok(())
+}
+
+/// Add a feature to `state` and save it. Returns the new feature's id.
+pub fn create_feature(
+    state: &mut ProjectState,
+    name: &str,
+    description: String,
+    priority: String,
+    depends_on: Vec<String>,
+) -> Result<String> {
     // Validate feature name
     if name.trim().is_empty() {
         anyhow::bail!("Feature name cannot be empty");
@@ -87,30 +120,29 @@ async fn add_feature() -> Result<()> {
     }
 
     let new_feature = Feature {
-        name: name.clone(),
+        id: state.new_feature_id(),
+        name: name.to_string(),
         description,
         priority,
+        tasks: Vec::new(),
+        depends_on,
+        user_stories: Vec::new(),
+        pull_request: None,
     };
 
+    let new_feature_id = new_feature.id.clone();
+
     // Add feature to state
-    if state.features.is_none() {
-        state.features = Some(Vec::new());
-    }
-    state.features.as_mut().unwrap().push(new_feature);
+    state.features.get_or_insert_with(Vec::new).push(new_feature);
+
+    state.validate_dependencies()
+        .context("Feature dependencies are invalid")?;
 
     // Save state
     state.save()
         .context("Failed to save project state")?;
 
-    println!();
-    println!("{}", format!("✓ Feature '{}' added successfully", name).bright_green());
-    println!();
-    println!("{}", "Next steps:".bright_yellow());
-    println!("  1. Run /plan to regenerate the plan structure");
-    println!("  2. Review the updated state in .doplan/state.json");
-    println!();
-
-    Ok(())
+    Ok(new_feature_id)
 }
 
 async fn list_features() -> Result<()> {
@@ -137,7 +169,7 @@ async fn list_features() -> Result<()> {
             _ => feature.priority.as_str().bright_white(),
         };
         
-        println!("  {} Feature {}: {}", "→".bright_cyan(), idx + 1, feature.name.bright_white().bold());
+        println!("  {} Feature {}: {} ({})", "→".bright_cyan(), idx + 1, feature.name.bright_white().bold(), feature.id);
         println!("     Description: {}", feature.description);
         println!("     Priority: {}", priority_color);
         println!();
@@ -164,7 +196,7 @@ async fn show_feature(feature_name: Option<String>) -> Result<()> {
     
     // Select feature if not provided
     let feature = if let Some(name) = feature_name {
-        features.iter().find(|f| f.name == name)
+        state.find_feature(&name)
             .ok_or_else(|| anyhow::anyhow!("Feature '{}' not found", name))?
     } else {
         let feature_names: Vec<String> = features.iter().map(|f| f.name.clone()).collect();
@@ -185,15 +217,26 @@ async fn show_feature(feature_name: Option<String>) -> Result<()> {
 
     println!("{}", format!("Feature: {}", feature.name).bright_white().bold());
     println!();
+    println!("  ID: {}", feature.id);
     println!("  Description: {}", feature.description);
     println!("  Priority: {}", priority_color);
+    if !feature.depends_on.is_empty() {
+        let names: Vec<String> = feature.depends_on
+            .iter()
+            .map(|id| match state.feature_by_id(id) {
+                Some(dep) => format!("{} ({})", id, dep.name),
+                None => id.clone(),
+            })
+            .collect();
+        println!("  Depends on: {}", names.join(", "));
+    }
     println!();
 
     // Check which phases include this feature
     if let Some(phases) = &state.phases {
         let phases_with_feature: Vec<&str> = phases
             .iter()
-            .filter(|phase| phase.features.contains(&feature.name))
+            .filter(|phase| phase.features.contains(&feature.id))
             .map(|phase| phase.name.as_str())
             .collect();
         
@@ -215,7 +258,7 @@ async fn update_feature(feature_name: Option<String>) -> Result<()> {
     println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
     println!();
 
-    let mut state = ProjectState::load()
+    let state = ProjectState::load()
         .context("Failed to load project state. Ensure .doplan/state.json exists")?;
 
     if state.features.is_none() || state.features.as_ref().unwrap().is_empty() {
@@ -225,7 +268,7 @@ async fn update_feature(feature_name: Option<String>) -> Result<()> {
 
     // Select feature if not provided
     let feature_idx = if let Some(name) = feature_name {
-        state.features.as_ref().unwrap().iter().position(|f| f.name == name)
+        state.features.as_ref().unwrap().iter().position(|f| f.id == name || f.name == name)
             .ok_or_else(|| anyhow::anyhow!("Feature '{}' not found", name))?
     } else {
         let feature_names: Vec<String> = state.features.as_ref().unwrap().iter().map(|f| f.name.clone()).collect();
@@ -258,18 +301,29 @@ async fn update_feature(feature_name: Option<String>) -> Result<()> {
 
     // Update priority
     let old_priority = state.features.as_ref().unwrap()[feature_idx].priority.clone();
-    let priority_options = vec!["high", "medium", "low"];
+    let config = Config::current()?;
+    let priority_options = &config.plan.priorities;
     let current_priority_idx = priority_options.iter()
-        .position(|p| *p == old_priority.as_str())
-        .unwrap_or(1);
+        .position(|p| *p == old_priority)
+        .unwrap_or(priority_options.len() / 2);
     let priority_idx = Select::new()
         .with_prompt("Priority")
-        .items(&priority_options)
+        .items(priority_options)
         .default(current_priority_idx)
         .interact()
         .context("Failed to select priority")?;
     let new_priority = priority_options[priority_idx].to_string();
 
+    // Update dependencies
+    let old_depends_on = state.features.as_ref().unwrap()[feature_idx].depends_on.join(", ");
+    let depends_input: String = Input::new()
+        .with_prompt("Depends on features (comma-separated, or leave empty)")
+        .with_initial_text(&old_depends_on)
+        .allow_empty(true)
+        .interact_text()
+        .context("Failed to read feature dependencies")?;
+    let new_depends_on = resolve_feature_dependencies(&state, &depends_input)?;
+
     // Validate new name
     if new_name.trim().is_empty() {
         anyhow::bail!("Feature name cannot be empty");
@@ -283,14 +337,29 @@ async fn update_feature(feature_name: Option<String>) -> Result<()> {
         }
     }
 
-    // Update feature
-    let features = state.features.as_mut().unwrap();
-    features[feature_idx].name = new_name;
+    // Update feature, checking the result before anything on disk changes
+    let mut updated = state.clone();
+    let features = updated.features.as_mut().unwrap();
+    features[feature_idx].name = new_name.clone();
     features[feature_idx].description = new_description;
     features[feature_idx].priority = new_priority;
+    features[feature_idx].depends_on = new_depends_on;
+
+    updated.validate_dependencies()
+        .context("Feature dependencies are invalid")?;
+
+    // Move the plan directory along with the rename so progress is kept;
+    // directories from before ids existed are found by the old name
+    if new_name != old_name {
+        let feature_id = state.features.as_ref().unwrap()[feature_idx].id.clone();
+        let plan_dir = utils::doplan_dir()?.join("plan");
+        if let Some((from, to)) = plan_dirs::rename_feature_dir(&plan_dir, &state, &feature_id, &new_name)? {
+            println!("{}", format!("  ↻ Moved {} → {}", from.display(), to.display()).bright_cyan());
+        }
+    }
 
     // Save state
-    state.save()
+    updated.save()
         .context("Failed to save project state")?;
 
     println!();
@@ -300,6 +369,19 @@ async fn update_feature(feature_name: Option<String>) -> Result<()> {
     Ok(())
 }
 
+/// Turn a comma-separated list of feature names or ids into feature ids
+pub fn resolve_feature_dependencies(state: &ProjectState, input: &str) -> Result<Vec<String>> {
+    let mut depends_on = Vec::new();
+    for key in input.split(',').map(|s| s.trim()).filter(|s| !s.is_empty()) {
+        let feature = state.find_feature(key)
+            .ok_or_else(|| anyhow::anyhow!("Feature '{}' not found. Dependencies must refer to existing features.", key))?;
+        if !depends_on.contains(&feature.id) {
+            depends_on.push(feature.id.clone());
+        }
+    }
+    Ok(depends_on)
+}
+
 async fn delete_feature(feature_name: Option<String>) -> Result<()> {
     println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
     println!("{}", "  Delete Feature".bright_cyan().bold());
@@ -318,7 +400,7 @@ async fn delete_feature(feature_name: Option<String>) -> Result<()> {
     
     // Select feature if not provided
     let feature_idx = if let Some(name) = feature_name {
-        features.iter().position(|f| f.name == name)
+        features.iter().position(|f| f.id == name || f.name == name)
             .ok_or_else(|| anyhow::anyhow!("Feature '{}' not found", name))?
     } else {
         let feature_names: Vec<String> = features.iter().map(|f| f.name.clone()).collect();
@@ -334,12 +416,23 @@ async fn delete_feature(feature_name: Option<String>) -> Result<()> {
     // Also remove from phases if present
     if let Some(phases) = &mut state.phases {
         for phase in phases.iter_mut() {
-            phase.features.retain(|f| f != &feature.name);
+            phase.features.retain(|f| f != &feature.id);
         }
     }
+
+    // Drop it from other features' dependencies as well, down to the tasks
+    // that name it in their tasks.md
+    for other in state.features.iter_mut().flatten() {
+        other.depends_on.retain(|d| d != &feature.id);
+    }
+    let plan_dir = utils::doplan_dir()?.join("plan");
+    let unlinked = plan_dirs::remove_task_references(&plan_dir, &mut state, &feature.id)?;
     
     println!();
     println!("{}", format!("⚠ Deleted feature: {}", feature.name).bright_yellow());
+    for task in &unlinked {
+        println!("{}", format!("  ↻ {} no longer depends on {}", task, feature.id).bright_cyan());
+    }
     println!();
 
     // Save state
@@ -74,6 +74,8 @@ pub async fn execute(_args: Vec<String>) -> Result<()> {
         println!("  {} Generating templates...", "→".bright_cyan());
         generators::templates::generate_all()
             .context("Failed to generate templates")?;
+        generators::templates::generate_task_rules()
+            .context("Failed to generate task rules")?;
         println!("  {} Templates generated", "✓".bright_green());
 
         println!();
@@ -89,6 +91,7 @@ pub async fn execute(_args: Vec<String>) -> Result<()> {
         println!("  • doplan/templates/plan-template.md");
         println!("  • doplan/templates/design-template.md");
         println!("  • doplan/templates/tasks-template.md");
+        println!("  • doplan/templates/task-rules.yaml");
         println!();
         println!("{}", "Next steps:".bright_yellow());
         println!("  1. Review the generated documents");
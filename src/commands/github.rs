@@ -0,0 +1,76 @@
+use anyhow::{Context, Result};
+use colored::*;
+use crate::config::Config;
+use crate::integration::github::{self, GitHubClient, SyncMap};
+use crate::state::ProjectState;
+use crate::utils;
+
+/// Sync the plan with GitHub issues and milestones.
+///
+/// Usage: `doplan github [--repo owner/name] [--api-url URL]`. The repository
+/// defaults to the one recorded by the last sync, then `github.repo`, then the
+/// `origin` remote; the API URL defaults to `github.api_url` (see [`Config`]). The token is read from `$GITHUB_TOKEN` (or
+/// `$GH_TOKEN`). See [`github`] for how the plan maps onto GitHub.
+pub async fn execute(args: Vec<String>) -> Result<()> {
+    let mut state = ProjectState::load()
+        .context("Failed to load project state")?;
+    let plan_dir = utils::doplan_dir()?.join("plan");
+    if !plan_dir.exists() {
+        println!("{}", "No plan structure found. Run /plan first.".bright_yellow());
+        return Ok(());
+    }
+
+    let map_path = SyncMap::path(&utils::dot_doplan_dir()?);
+    let mut map = SyncMap::load(&map_path)?;
+    let client = client(&args, &map)?;
+    let repo = client.repo().to_string();
+    let result = github::sync(&client, &plan_dir, &mut state, &mut map).await;
+    // Keep what was created before an error, or the next sync duplicates it
+    state.save()?;
+    map.save(&map_path)?;
+    let report = result.with_context(|| format!("Failed to sync with {}", repo))?;
+
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
+    println!("{}", format!("  GitHub Sync: {}", repo).bright_cyan().bold());
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
+    println!();
+    println!("  Milestones created: {}", report.milestones_created);
+    println!("  Issues created:     {}", report.issues_created);
+    println!("  Issues updated:     {}", report.issues_updated);
+    if report.tasks_completed.is_empty() {
+        println!("  {}", "No task changes from GitHub.".bright_black());
+    } else {
+        println!();
+        println!("{}", "Completed on GitHub:".bright_white().bold());
+        for task in &report.tasks_completed {
+            println!("  {} {}", "✓".bright_green(), task);
+        }
+    }
+    println!();
+    println!("{}", format!("Issue mapping saved to {}", map_path.display()).bright_black());
+
+    Ok(())
+}
+
+/// Client for the repository and API URL chosen by `--repo` and `--api-url`,
+/// falling back as described on [`execute`]
+pub(crate) fn client(args: &[String], map: &SyncMap) -> Result<GitHubClient> {
+    let option = |name: &str| {
+        args.iter()
+            .position(|a| a == name)
+            .and_then(|i| args.get(i + 1))
+            .cloned()
+    };
+
+    let config = Config::current()?;
+    let repo = option("--repo")
+        .or_else(|| map.repo.clone())
+        .or_else(|| config.github.repo.clone())
+        .or_else(|| github::detect_repo(&utils::project_root().ok()?))
+        .context("Could not determine the GitHub repository. Pass --repo owner/name or add an 'origin' remote.")?;
+    let api_url = option("--api-url").unwrap_or_else(|| config.github.api_url.clone());
+    let token = std::env::var("GITHUB_TOKEN")
+        .or_else(|_| std::env::var("GH_TOKEN"))
+        .context("Set GITHUB_TOKEN to a token with access to the repository")?;
+    Ok(GitHubClient::new(&api_url, &repo, &token))
+}
@@ -0,0 +1,467 @@
+pub mod worktree;
+
+use anyhow::{Context, Result};
+use colored::*;
+use std::path::{Path, PathBuf};
+use std::fs;
+use crate::config::Config;
+use crate::integration::git::{self, BranchVars};
+use crate::state::{tasks_md, ProjectState, TaskStatus};
+use crate::utils;
+use crate::utils::plan_dirs::PlanDirs;
+use dialoguer::Select;
+use git2::{BranchType, Oid, Repository, Signature};
+use std::io::IsTerminal;
+
+/// Execute the /implement command.
+///
+/// `/implement <target> [--worktree] [--dirty stash|abort|continue]` prepares
+/// a feature branch; `/implement list` and `/implement finish` manage the
+/// worktrees `--worktree` creates (see [`worktree`]).
+pub async fn execute(args: Vec<String>) -> Result<()> {
+    match args.first().map(String::as_str) {
+        Some("list") => return worktree::list(),
+        Some("finish") => return worktree::finish(&args[1..]),
+        _ => {}
+    }
+
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
+    println!("{}", "  DoPlan: Feature Implementation".bright_cyan().bold());
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
+    println!();
+
+    // Parse arguments: the phase or feature path, and what to do with uncommitted changes
+    let dirty_option = args.iter()
+        .position(|a| a == "--dirty")
+        .and_then(|i| args.get(i + 1))
+        .cloned();
+    let use_worktree = args.iter().any(|a| a == "--worktree");
+    let target = args.iter()
+        .enumerate()
+        .find(|(i, a)| !a.starts_with("--") && (*i == 0 || args[i - 1] != "--dirty"))
+        .map(|(_, a)| a.as_str())
+        .unwrap_or("");
+    
+    if target.is_empty() {
+        println!("{}", "Usage: /implement <phase-id> or /implement <phase-id>/<feature-id> [--worktree] [--dirty stash|abort|continue]".bright_yellow());
+        println!("{}", "Example: /implement 01-foundation or /implement 01-foundation/01-project-planning".bright_yellow());
+        println!("{}", "Stable ids work too: /implement P-01 or /implement F-0003".bright_yellow());
+        println!("{}", "Worktrees: /implement list, /implement finish <feature-id> [--merge] [--force]".bright_yellow());
+        return Ok(());
+    }
+
+    let mut state = ProjectState::load()
+        .context("Failed to load project state")?;
+
+    let doplan_dir = utils::doplan_dir()?;
+    let plan_dir = doplan_dir.join("plan");
+
+    // Stable ids (F-0003, P-01) resolve to wherever the directory lives now
+    let resolved = resolve_id_target(&plan_dir, &state, target)?;
+    let target = resolved.as_deref().unwrap_or(target);
+
+    // Find phase and feature
+    let (phase_path, feature_path, phase_name, feature_name) = if target.contains('/') {
+        // Specific feature: 01-foundation/01-project-planning
+        let parts: Vec<&str> = target.split('/').collect();
+        if parts.len() != 2 {
+            println!("{}", "Invalid format. Use: phase-id/feature-id".bright_red());
+            return Ok(());
+        }
+        let phase_id = parts[0];
+        let feature_id = parts[1];
+        
+        let phase_path = plan_dir.join(phase_id);
+        let feature_path = phase_path.join(feature_id);
+        
+        if !feature_path.exists() {
+            println!("{}", format!("Feature path not found: {}", feature_path.display()).bright_red());
+            return Ok(());
+        }
+
+        let phase_name = extract_name_from_path(phase_id);
+        let feature_name = extract_name_from_path(feature_id);
+        
+        (phase_path, feature_path, phase_name, feature_name)
+    } else {
+        // Phase only: 01-foundation (implement first feature)
+        let phase_path = plan_dir.join(target);
+        
+        if !phase_path.exists() {
+            println!("{}", format!("Phase path not found: {}", phase_path.display()).bright_red());
+            return Ok(());
+        }
+
+        // Find first feature in phase
+        let mut first_feature: Option<PathBuf> = None;
+        if let Ok(entries) = fs::read_dir(&phase_path) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    let name = path.file_name().unwrap().to_string_lossy();
+                    if name.starts_with("01-") {
+                        first_feature = Some(path);
+                        break;
+                    }
+                }
+            }
+        }
+
+        let feature_path = match first_feature {
+            Some(path) => path,
+            None => {
+                println!("{}", "No features found in this phase".bright_red());
+                return Ok(());
+            }
+        };
+
+        let phase_name = extract_name_from_path(target);
+        let feature_name = extract_name_from_path(
+            feature_path.file_name().unwrap().to_string_lossy().as_ref()
+        );
+        
+        (phase_path, feature_path, phase_name, feature_name)
+    };
+
+    // Generate branch name from git.branch_template and git.branch_suffix
+    let config = Config::current()?;
+    let phase_dir = phase_path.file_name().unwrap().to_string_lossy().to_string();
+    let feature_dir = feature_path.file_name().unwrap().to_string_lossy().to_string();
+    let layout = PlanDirs::scan(&plan_dir, &state)?;
+    let stable = layout.feature_by_dir_names(&phase_dir, &feature_dir);
+    let branch_name = branch_name(&config, &layout, &phase_dir, &feature_dir)?;
+
+    println!("{}", format!("Phase: {}", phase_name).bright_white());
+    println!("{}", format!("Feature: {}", feature_name).bright_white());
+    println!("{}", format!("Branch: {}", branch_name).bright_cyan());
+    println!();
+
+    // Open Git repository
+    let project_root = utils::project_root()?;
+    let mut repo = match Repository::open(&project_root) {
+        Ok(repo) => repo,
+        Err(_) => {
+            println!("{}", "Not a Git repository. Initializing...".bright_yellow());
+            Repository::init(&project_root)
+                .context("Failed to initialize Git repository")?
+        }
+    };
+    let signature = git::identity(&repo)?;
+
+    let mut planning_files: Vec<PathBuf> = Vec::new();
+    for file in ["plan.md", "design.md", "tasks.md"] {
+        let file_path = feature_path.join(file);
+        if file_path.exists() {
+            let relative_path = file_path.strip_prefix(&project_root)
+                .context("Failed to get relative path")?;
+            planning_files.push(relative_path.to_path_buf());
+        }
+    }
+    let branch = FeatureBranch {
+        name: branch_name.clone(),
+        feature_id: stable.and_then(|(_, feature)| feature.id.clone()).unwrap_or_else(|| feature_dir.clone()),
+        planning_files,
+        commit_message: format!("docs: add planning docs for {}", feature_name),
+    };
+
+    let workdir = if use_worktree {
+        worktree::create(&repo, &mut state, &config, &project_root, &branch, &signature)?
+    } else {
+        if !check_out(&mut repo, &config, &project_root, &branch, &signature, dirty_option.as_deref())? {
+            return Ok(());
+        }
+        let planning_files: Vec<&Path> = branch.planning_files.iter().map(PathBuf::as_path).collect();
+        if commit_planning_docs(&repo, &branch, &signature)?.is_some() {
+            git::stage_paths(&repo, &planning_files)?;
+        }
+        project_root.clone()
+    };
+    let branch_ref = format!("refs/heads/{}", branch_name);
+
+    // Try to push (may fail if no remote, that's okay)
+    println!();
+    println!("{}", "Attempting to push branch...".bright_cyan());
+    
+    if let Ok(mut remote) = repo.find_remote("origin") {
+        match remote.push(&[&branch_ref], None) {
+            Ok(_) => {
+                println!("{}", format!("✓ Branch pushed to origin/{}", branch_name).bright_green());
+            }
+            Err(e) => {
+                println!("{}", format!("⚠ Could not push branch: {}. You can push manually later.", e).bright_yellow());
+            }
+        }
+    } else {
+        println!("{}", "⚠ No remote 'origin' found. Push manually when ready.".bright_yellow());
+    }
+
+    // Update state (add branch info if we extend state structure)
+    println!();
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_green());
+    println!("{}", "  Implementation Ready!".bright_green().bold());
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_green());
+    println!();
+    println!("{}", "Branch created and ready for implementation:".bright_cyan());
+    println!("  {}", branch_name.bright_white());
+    if use_worktree {
+        println!("{}", "Checked out in its own worktree:".bright_cyan());
+        println!("  cd {}", workdir.display().to_string().bright_white());
+    }
+    println!();
+    let feature_path = workdir.join(feature_path.strip_prefix(&project_root).unwrap_or(&feature_path));
+    println!("{}", "Next steps:".bright_yellow());
+    println!("  1. Review planning documents:");
+    println!("     - {}", feature_path.join("plan.md").display());
+    println!("     - {}", feature_path.join("design.md").display());
+    println!("     - {}", feature_path.join("tasks.md").display());
+    println!("  2. Start implementing the feature");
+    println!("  3. Check off tasks in tasks.md as you complete them");
+    println!("  4. Commit regularly with clear messages");
+    println!("  5. Open a pull request: doplan execute pr {}", branch.feature_id);
+    println!();
+
+    // Show the open tasks from the feature's task list
+    let tasks_path = feature_path.join("tasks.md");
+    if tasks_path.exists() {
+        let open_tasks: Vec<_> = tasks_md::read(&tasks_path)?
+            .into_iter()
+            .filter(|t| t.status != TaskStatus::Completed)
+            .collect();
+        if !open_tasks.is_empty() {
+            println!("{}", "Open tasks:".bright_cyan());
+            for task in &open_tasks {
+                println!("  {} {} {} [{}]", "→".bright_cyan(), task.id, task.title.bright_white(), task.status.label());
+            }
+            println!();
+        }
+    }
+
+    Ok(())
+}
+
+/// The branch `/implement` prepares and what goes in its first commit
+pub(crate) struct FeatureBranch {
+    pub name: String,
+    /// Stable feature id, or the feature directory when the state has none
+    pub feature_id: String,
+    /// Relative to the project root
+    pub planning_files: Vec<PathBuf>,
+    pub commit_message: String,
+}
+
+/// The commit a new feature branch starts from: the base branch, or HEAD.
+/// An empty repository gets an initial commit on its default branch first.
+pub(crate) fn start_commit(repo: &Repository, config: &Config, project_root: &Path, signature: &Signature) -> Result<Oid> {
+    match repo.head() {
+        Ok(head) => match git::base_branch(repo, config.git.base_branch.as_deref()) {
+            Some(base) => {
+                println!("{}", format!("Base: {}", base).bright_white());
+                Ok(git::branch_commit(repo, &base)?.id())
+            }
+            None => Ok(head.peel_to_commit()
+                .context("Failed to get HEAD commit")?
+                .id()),
+        },
+        Err(_) => {
+            println!("{}", "Empty repository detected. Creating initial commit...".bright_yellow());
+
+            // The configured base branch, or the one HEAD already names
+            // (init.defaultBranch for a fresh repository)
+            let base_ref = match &config.git.base_branch {
+                Some(base) => format!("refs/heads/{}", base),
+                None => git::unborn_head(repo)?,
+            };
+            let readme = Path::new("README.md");
+            let files: Vec<&Path> = if project_root.join(readme).exists() { vec![readme] } else { vec![] };
+            let commit_id = git::commit_paths(repo, &base_ref, None, &files, "chore: initial commit", signature)?
+                .context("Failed to create initial commit")?;
+            git::stage_paths(repo, &files)?;
+            Ok(commit_id)
+        }
+    }
+}
+
+/// Commit the planning documents on their own on top of the branch; anything
+/// else already staged stays staged. `None` when they are already committed.
+pub(crate) fn commit_planning_docs(repo: &Repository, branch: &FeatureBranch, signature: &Signature) -> Result<Option<Oid>> {
+    println!();
+    println!("{}", "Committing planning documents...".bright_cyan());
+    for file in &branch.planning_files {
+        println!("  {} Added {}", "→".bright_cyan(), file.file_name().unwrap_or_default().to_string_lossy());
+    }
+
+    let branch_ref = format!("refs/heads/{}", branch.name);
+    let tip = repo.find_reference(&branch_ref)
+        .and_then(|r| r.peel_to_commit())
+        .with_context(|| format!("Failed to find branch '{}'", branch.name))?;
+    let files: Vec<&Path> = branch.planning_files.iter().map(PathBuf::as_path).collect();
+    let commit_id = git::commit_paths(repo, &branch_ref, Some(&tip), &files, &branch.commit_message, signature)?;
+    match commit_id {
+        Some(id) => println!("{}", format!("✓ Commit created: {}", &id.to_string()[..8]).bright_green()),
+        None => println!("{}", "Planning documents are already committed".bright_white()),
+    }
+    Ok(commit_id)
+}
+
+/// Switch the working tree to the feature branch, creating it from the base
+/// branch when needed. `false` when the user chose to abort.
+fn check_out(
+    repo: &mut Repository,
+    config: &Config,
+    project_root: &Path,
+    branch: &FeatureBranch,
+    signature: &Signature,
+    dirty_option: Option<&str>,
+) -> Result<bool> {
+    // Never switch branches over uncommitted work without asking
+    let status = git::worktree_status(repo)?;
+    if !status.is_clean() {
+        match dirty_action(&status, dirty_option)? {
+            DirtyAction::Abort => {
+                println!("{}", "Aborted. Commit or stash your changes, then run /implement again.".bright_yellow());
+                return Ok(false);
+            }
+            DirtyAction::Stash => {
+                let message = format!("doplan: before implementing {}", branch.name);
+                repo.stash_save(signature, &message, None)
+                    .context("Failed to stash changes")?;
+                println!("{}", "✓ Changes stashed. Restore them with `git stash pop`.".bright_green());
+            }
+            DirtyAction::Continue => {
+                println!("{}", "Carrying your changes over to the feature branch.".bright_yellow());
+            }
+        }
+        println!();
+    }
+
+    // Check if branch already exists
+    let existing = repo.find_branch(&branch.name, BranchType::Local).ok()
+        .map(|b| b.get().peel_to_commit().map(|c| c.id()))
+        .transpose()
+        .context("Failed to get branch commit")?;
+    let created = existing.is_none();
+    let target = match existing {
+        Some(id) => {
+            println!("{}", format!("Branch '{}' already exists. Switching to it...", branch.name).bright_yellow());
+            id
+        }
+        None => {
+            println!("{}", format!("Creating branch: {}", branch.name).bright_cyan());
+            start_commit(repo, config, project_root, signature)?
+        }
+    };
+    let target = repo.find_commit(target)?;
+
+    // Safe checkout: refuses to overwrite changes that conflict with the
+    // branch. The branch is only created once the checkout went through.
+    git::safe_checkout(repo, &target)
+        .with_context(|| format!("Failed to check out '{}': uncommitted changes conflict with it", branch.name))?;
+    if created {
+        repo.branch(&branch.name, &target, false)
+            .context("Failed to create branch")?;
+    }
+    repo.set_head(&format!("refs/heads/{}", branch.name))
+        .context("Failed to set HEAD")?;
+    if created {
+        println!("{}", format!("✓ Branch '{}' created and checked out", branch.name).bright_green());
+    }
+    Ok(true)
+}
+
+/// What to do with uncommitted changes before switching branches
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DirtyAction {
+    Stash,
+    Abort,
+    Continue,
+}
+
+impl DirtyAction {
+    const ALL: [DirtyAction; 3] = [DirtyAction::Stash, DirtyAction::Abort, DirtyAction::Continue];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            DirtyAction::Stash => "stash",
+            DirtyAction::Abort => "abort",
+            DirtyAction::Continue => "continue",
+        }
+    }
+
+    fn parse(value: &str) -> Result<Self> {
+        Self::ALL.into_iter()
+            .find(|action| action.as_str() == value)
+            .ok_or_else(|| anyhow::anyhow!("Unknown --dirty value '{}' (expected stash, abort or continue)", value))
+    }
+}
+
+/// The action from `--dirty`, else asked for on a terminal. Without either
+/// the command stops rather than guess.
+fn dirty_action(status: &git::WorktreeStatus, option: Option<&str>) -> Result<DirtyAction> {
+    if let Some(value) = option {
+        return DirtyAction::parse(value);
+    }
+
+    println!("{}", "⚠ The working tree has uncommitted changes:".bright_yellow());
+    for path in &status.staged {
+        println!("  {} {}", "staged:  ".bright_green(), path);
+    }
+    for path in &status.modified {
+        println!("  {} {}", "modified:".bright_red(), path);
+    }
+    println!();
+
+    if !std::io::stdin().is_terminal() {
+        anyhow::bail!("Uncommitted changes in the working tree. Commit them, or pass --dirty stash|abort|continue");
+    }
+    let choices = [
+        "Stash them and continue",
+        "Abort",
+        "Continue and carry them over to the feature branch",
+    ];
+    let choice = Select::new()
+        .with_prompt("What should DoPlan do with them?")
+        .items(&choices)
+        .default(0)
+        .interact()
+        .context("Failed to read choice")?;
+    Ok(DirtyAction::ALL[choice])
+}
+
+pub(crate) fn resolve_id_target(plan_dir: &Path, state: &ProjectState, target: &str) -> Result<Option<String>> {
+    let layout = PlanDirs::scan(plan_dir, state)?;
+    if let Some((phase, feature)) = layout.feature(target) {
+        return Ok(Some(format!("{}/{}", phase.dir_name, feature.dir_name)));
+    }
+    Ok(layout.phase(target).map(|phase| phase.dir_name.clone()))
+}
+
+/// Feature branch for `phase_dir/feature_dir`, from `git.branch_template`
+/// and `git.branch_suffix`
+pub(crate) fn branch_name(config: &Config, layout: &PlanDirs, phase_dir: &str, feature_dir: &str) -> Result<String> {
+    let stable = layout.feature_by_dir_names(phase_dir, feature_dir);
+    let vars = BranchVars {
+        kind: config.git.branch_type.clone(),
+        phase_id: extract_id_from_path(phase_dir),
+        phase_slug: git::slug(&extract_name_from_path(phase_dir)),
+        phase_key: stable.and_then(|(phase, _)| phase.id.clone()).unwrap_or_default(),
+        feature_id: extract_id_from_path(feature_dir),
+        feature_slug: git::slug(&extract_name_from_path(feature_dir)),
+        feature_key: stable.and_then(|(_, feature)| feature.id.clone()).unwrap_or_default(),
+    };
+    git::render_branch(&format!("{}{}", config.git.branch_template, config.git.branch_suffix), &vars)
+        .context("Invalid git.branch_template")
+}
+
+fn extract_name_from_path(path: &str) -> String {
+    path.split('-')
+        .skip(1)
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+fn extract_id_from_path(path: &str) -> String {
+    path.split('-')
+        .next()
+        .unwrap_or("01")
+        .to_string()
+}
@@ -0,0 +1,174 @@
+//! `/implement --worktree`: one git worktree per feature, so several features
+//! can be worked on at once without switching the main working tree.
+//!
+//! Worktrees live under [`Config::worktree_dir`] and are recorded in
+//! `ProjectState::worktrees`. `/implement list` shows them and
+//! `/implement finish` merges and removes them.
+
+use anyhow::{Context, Result};
+use colored::*;
+use git2::{BranchType, Repository, Signature, WorktreeAddOptions, WorktreePruneOptions};
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::config::Config;
+use crate::integration::git::{self, MergeOutcome};
+use crate::state::{ProjectState, WorktreeInfo};
+use crate::utils;
+use super::{commit_planning_docs, start_commit, FeatureBranch};
+
+/// Create the feature branch when needed, commit the planning documents on
+/// it and check it out in a new worktree. Returns the worktree's path.
+pub(crate) fn create(
+    repo: &Repository,
+    state: &mut ProjectState,
+    config: &Config,
+    project_root: &Path,
+    branch: &FeatureBranch,
+    signature: &Signature,
+) -> Result<PathBuf> {
+    if let Some(existing) = state.worktrees.iter().find(|w| w.feature_id == branch.feature_id).cloned() {
+        let path = PathBuf::from(&existing.path);
+        if path.exists() {
+            println!("{}", format!("{} already has a worktree at {}", branch.feature_id, path.display()).bright_yellow());
+            return Ok(path);
+        }
+        // Deleted by hand: forget it and let git forget it too
+        prune(repo, &existing.branch)?;
+        state.worktrees.retain(|w| w.feature_id != branch.feature_id);
+    }
+
+    if repo.find_branch(&branch.name, BranchType::Local).is_err() {
+        println!("{}", format!("Creating branch: {}", branch.name).bright_cyan());
+        let base = repo.find_commit(start_commit(repo, config, project_root, signature)?)?;
+        repo.branch(&branch.name, &base, false)
+            .context("Failed to create branch")?;
+    }
+    commit_planning_docs(repo, branch, signature)?;
+
+    let name = worktree_name(&branch.name);
+    let path = config.worktree_dir(project_root).join(&name);
+    if path.exists() {
+        anyhow::bail!("{} already exists. Remove it, or point git.worktree_dir somewhere else", path.display());
+    }
+    if let Some(parent) = path.parent() {
+        utils::ensure_dir(parent)?;
+    }
+    let reference = repo.find_reference(&format!("refs/heads/{}", branch.name))?;
+    repo.worktree(&name, &path, Some(WorktreeAddOptions::new().reference(Some(&reference))))
+        .with_context(|| format!("Failed to create a worktree for '{}'. Is it checked out elsewhere?", branch.name))?;
+    println!();
+    println!("{}", format!("✓ Worktree created at {}", path.display()).bright_green());
+
+    state.worktrees.push(WorktreeInfo {
+        feature_id: branch.feature_id.clone(),
+        branch: branch.name.clone(),
+        path: path.to_string_lossy().to_string(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+    });
+    state.save().context("Failed to save project state")?;
+    Ok(path)
+}
+
+/// `/implement list`: every recorded worktree and whether it has uncommitted work
+pub fn list() -> Result<()> {
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
+    println!("{}", "  DoPlan: Worktrees".bright_cyan().bold());
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
+    println!();
+
+    let state = ProjectState::load().context("Failed to load project state")?;
+    if state.worktrees.is_empty() {
+        println!("{}", "No worktrees. Create one with /implement <feature-id> --worktree".bright_yellow());
+        return Ok(());
+    }
+    for info in &state.worktrees {
+        let path = Path::new(&info.path);
+        let status = if !path.exists() {
+            "missing".bright_red()
+        } else {
+            match Repository::open(path).map_err(anyhow::Error::from).and_then(|repo| git::worktree_status(&repo)) {
+                Ok(status) if status.is_clean() => "clean".bright_green(),
+                Ok(status) => format!("{} changed", status.staged.len() + status.modified.len()).bright_yellow(),
+                Err(_) => "unreadable".bright_red(),
+            }
+        };
+        println!("{}  {}  [{}]", info.feature_id.bright_white(), info.branch.bright_cyan(), status);
+        println!("    {}", info.path);
+    }
+    Ok(())
+}
+
+/// `/implement finish <feature-id|branch> [--merge] [--force]`: merge the
+/// branch into the base branch when asked, then remove the worktree. The
+/// branch itself is kept. Uncommitted changes in the worktree stop it unless
+/// `--force` is given, and are then lost.
+pub fn finish(args: &[String]) -> Result<()> {
+    let merge = args.iter().any(|a| a == "--merge");
+    let force = args.iter().any(|a| a == "--force");
+    let key = args.iter()
+        .find(|a| !a.starts_with("--"))
+        .context("Usage: /implement finish <feature-id> [--merge] [--force]")?;
+
+    let mut state = ProjectState::load().context("Failed to load project state")?;
+    let index = state.worktrees.iter()
+        .position(|w| &w.feature_id == key || &w.branch == key)
+        .with_context(|| format!("No worktree for '{}'. See /implement list", key))?;
+    let info = state.worktrees[index].clone();
+    let path = PathBuf::from(&info.path);
+    let repo = Repository::open(utils::project_root()?).context("Failed to open the Git repository")?;
+
+    if path.exists() && !force {
+        let worktree_repo = Repository::open(&path)
+            .with_context(|| format!("Failed to open the worktree at {}", path.display()))?;
+        if !git::worktree_status(&worktree_repo)?.is_clean() {
+            anyhow::bail!(
+                "The worktree at {} has uncommitted changes. Commit them, or pass --force to discard them",
+                path.display()
+            );
+        }
+    }
+
+    if merge {
+        let config = Config::current()?;
+        let base = git::base_branch(&repo, config.git.base_branch.as_deref())
+            .filter(|base| *base != info.branch)
+            .context("Could not tell which branch to merge into. Set git.base_branch")?;
+        let signature = git::identity(&repo)?;
+        match git::merge_branch(&repo, &info.branch, &base, &signature)? {
+            MergeOutcome::UpToDate => {
+                println!("{}", format!("{} already contains {}", base, info.branch).bright_white());
+            }
+            MergeOutcome::FastForward(id) => {
+                println!("{}", format!("✓ Fast-forwarded {} to {}", base, &id.to_string()[..8]).bright_green());
+            }
+            MergeOutcome::MergeCommit(id) => {
+                println!("{}", format!("✓ Merged {} into {}: {}", info.branch, base, &id.to_string()[..8]).bright_green());
+            }
+        }
+    }
+
+    prune(&repo, &info.branch)?;
+    if path.exists() {
+        fs::remove_dir_all(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    }
+    state.worktrees.remove(index);
+    state.save().context("Failed to save project state")?;
+    println!("{}", format!("✓ Removed the worktree at {} (branch {} is kept)", path.display(), info.branch).bright_green());
+    Ok(())
+}
+
+/// Name git knows the worktree of `branch` by
+fn worktree_name(branch: &str) -> String {
+    branch.replace('/', "-")
+}
+
+/// Remove the worktree of `branch` and its directory; nothing to do when
+/// git has no such worktree
+fn prune(repo: &Repository, branch: &str) -> Result<()> {
+    let Ok(worktree) = repo.find_worktree(&worktree_name(branch)) else {
+        return Ok(());
+    };
+    worktree
+        .prune(Some(WorktreePruneOptions::new().valid(true).locked(true).working_tree(true)))
+        .with_context(|| format!("Failed to remove the worktree of '{}'", branch))
+}
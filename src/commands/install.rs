@@ -0,0 +1,244 @@
+use anyhow::{Context, Result};
+use colored::*;
+use dialoguer::MultiSelect;
+use std::fs;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use crate::config::Config;
+use crate::integration::ide::{self, Ide};
+use crate::state::{InstallInfo, ProjectState};
+use crate::utils;
+
+/// Heading of the lines `doplan install` keeps in the project's `.gitignore`,
+/// which ignore the state directory (`paths.state_dir`)
+const GITIGNORE_HEADER: &str = "# DoPlan";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Created,
+    Updated,
+    Unchanged,
+}
+
+/// One file or directory touched by an install run
+#[derive(Debug, Clone)]
+pub struct InstallChange {
+    pub path: PathBuf,
+    pub change: Change,
+}
+
+/// Bootstrap DoPlan in the project containing the current directory.
+///
+/// Usage: `doplan install [--ide cursor,claude,...]`. Without `--ide` the
+/// IDEs recorded by a previous install are reused; otherwise the user is
+/// asked, or, when not on a terminal, the IDEs with a config directory in
+/// the project are picked (Cursor if there are none). See [`ide`] for the
+/// files each IDE gets.
+pub async fn execute(args: Vec<String>) -> Result<()> {
+    let mut requested = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let value = match arg.strip_prefix("--ide=") {
+            Some(value) => value,
+            None if arg == "--ide" => iter.next().map(String::as_str).context("--ide needs a value")?,
+            None => anyhow::bail!("Unknown install option: {}. Use: --ide <{}>", arg, supported_ides()),
+        };
+        for name in value.split(',').map(str::trim).filter(|v| !v.is_empty()) {
+            let ide = Ide::parse(name)
+                .ok_or_else(|| anyhow::anyhow!("Unsupported IDE '{}' (expected one of: {})", name, supported_ides()))?;
+            if !requested.contains(&ide) {
+                requested.push(ide);
+            }
+        }
+    }
+
+    let cwd = utils::project_root()?;
+    let root = utils::find_project_root(&cwd);
+    if root != cwd {
+        std::env::set_current_dir(&root)
+            .with_context(|| format!("Failed to change to project root: {}", root.display()))?;
+    }
+
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
+    println!("{}", "  DoPlan: Install".bright_cyan().bold());
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
+    println!();
+    println!("{} {}", "Project root:".bright_white(), root.display());
+    println!();
+
+    let mut state = ProjectState::load()
+        .context("Failed to load project state")?;
+    let ides = if !requested.is_empty() {
+        requested
+    } else if let Some(install) = &state.install {
+        install.ides.iter().filter_map(|name| Ide::parse(name)).collect()
+    } else {
+        choose_ides(&root)?
+    };
+
+    let changes = install(&root, &mut state, &ides)?;
+
+    for change in &changes {
+        let path = change.path.strip_prefix(&root).unwrap_or(&change.path).display();
+        match change.change {
+            Change::Created => println!("  {} Created   {}", "+".bright_green(), path),
+            Change::Updated => println!("  {} Updated   {}", "~".bright_yellow(), path),
+            Change::Unchanged => println!("  {} Unchanged {}", "·".bright_black(), path.to_string().bright_black()),
+        }
+    }
+    println!();
+
+    let count = |kind: Change| changes.iter().filter(|c| c.change == kind).count();
+    let (created, updated) = (count(Change::Created), count(Change::Updated));
+    if created + updated == 0 {
+        println!("{}", "DoPlan is already installed and up to date.".bright_green());
+    } else {
+        println!(
+            "{} {} created, {} updated, {} unchanged",
+            "✅ DoPlan installed:".bright_green().bold(),
+            created,
+            updated,
+            count(Change::Unchanged)
+        );
+    }
+    println!(
+        "{} {}",
+        "IDE integrations:".bright_white(),
+        ides.iter().map(|ide| ide.as_str()).collect::<Vec<_>>().join(", ")
+    );
+    println!();
+    println!("{}", "Next: run /discuss in your IDE (or `doplan`) to describe your idea.".bright_cyan());
+
+    Ok(())
+}
+
+/// Create the DoPlan directories, IDE command files and ignore rules under
+/// `root` (the current directory) and record the install in `state`.
+/// Running it again with the same IDEs changes nothing.
+pub fn install(root: &Path, state: &mut ProjectState, ides: &[Ide]) -> Result<Vec<InstallChange>> {
+    let mut changes = Vec::new();
+
+    for dir in [utils::doplan_dir()?, utils::dot_doplan_dir()?] {
+        let change = if dir.is_dir() { Change::Unchanged } else { Change::Created };
+        utils::ensure_dir(&dir)?;
+        changes.push(InstallChange { path: dir, change });
+    }
+
+    for ide in ides {
+        for file in ide.render(root).with_context(|| format!("Failed to render {} commands", ide.as_str()))? {
+            changes.push(write_file(&root.join(&file.path), &file.content)?);
+        }
+    }
+
+    let config = Config::load(root)?;
+    let rules = [format!("{}/", config.paths.state_dir.trim_end_matches('/'))];
+    changes.push(update_gitignore(&root.join(".gitignore"), &rules)?);
+
+    let state_path = ProjectState::state_path()?;
+    let existed = state_path.exists();
+    let install = InstallInfo {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        ides: ides.iter().map(|ide| ide.as_str().to_string()).collect(),
+        installed_at: state
+            .install
+            .as_ref()
+            .map(|i| i.installed_at.clone())
+            .unwrap_or_else(|| chrono::Utc::now().to_rfc3339()),
+    };
+    let change = if !existed {
+        Change::Created
+    } else if state.install.as_ref() != Some(&install) {
+        Change::Updated
+    } else {
+        Change::Unchanged
+    };
+    if change != Change::Unchanged {
+        state.install = Some(install);
+        state.save().context("Failed to save project state")?;
+    }
+    changes.push(InstallChange { path: state_path, change });
+
+    Ok(changes)
+}
+
+fn supported_ides() -> String {
+    Ide::ALL.iter().map(|ide| ide.as_str()).collect::<Vec<_>>().join(", ")
+}
+
+fn choose_ides(root: &Path) -> Result<Vec<Ide>> {
+    let detected = ide::detect_ides(root);
+    if !std::io::stdin().is_terminal() {
+        return Ok(if detected.is_empty() { vec![Ide::Cursor] } else { detected });
+    }
+
+    let names: Vec<&str> = Ide::ALL.iter().map(|ide| ide.as_str()).collect();
+    let defaults: Vec<bool> = Ide::ALL
+        .iter()
+        .map(|ide| detected.contains(ide) || (detected.is_empty() && *ide == Ide::Cursor))
+        .collect();
+    let selected = MultiSelect::new()
+        .with_prompt("Which IDEs should DoPlan set up? (space to toggle, enter to confirm)")
+        .items(&names)
+        .defaults(&defaults)
+        .interact()
+        .context("Failed to select IDEs")?;
+    if selected.is_empty() {
+        anyhow::bail!("Select at least one IDE to install");
+    }
+    Ok(selected.into_iter().map(|i| Ide::ALL[i]).collect())
+}
+
+/// Write `content` unless the file already holds exactly that
+fn write_file(path: &Path, content: &str) -> Result<InstallChange> {
+    let change = match fs::read_to_string(path) {
+        Ok(existing) if existing == content => Change::Unchanged,
+        Ok(_) => Change::Updated,
+        Err(_) => Change::Created,
+    };
+    if change != Change::Unchanged {
+        if let Some(parent) = path.parent() {
+            utils::ensure_dir(parent)?;
+        }
+        fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    Ok(InstallChange { path: path.to_path_buf(), change })
+}
+
+/// Append the DoPlan `rules` that are not already in `.gitignore`
+fn update_gitignore(path: &Path, rules: &[String]) -> Result<InstallChange> {
+    let existed = path.exists();
+    let existing = if existed {
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?
+    } else {
+        String::new()
+    };
+    let present = |rule: &str| {
+        existing
+            .lines()
+            .any(|line| line.trim() == rule || line.trim() == rule.trim_end_matches('/') || line.trim() == format!("/{}", rule))
+    };
+    let missing: Vec<&str> = rules.iter().map(String::as_str).filter(|rule| !present(rule)).collect();
+    if missing.is_empty() {
+        return Ok(InstallChange { path: path.to_path_buf(), change: Change::Unchanged });
+    }
+
+    let mut content = existing.clone();
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    if !content.is_empty() {
+        content.push('\n');
+    }
+    content.push_str(GITIGNORE_HEADER);
+    content.push('\n');
+    for rule in missing {
+        content.push_str(rule);
+        content.push('\n');
+    }
+    fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+
+    Ok(InstallChange {
+        path: path.to_path_buf(),
+        change: if existed { Change::Updated } else { Change::Created },
+    })
+}
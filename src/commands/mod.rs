@@ -2,9 +2,17 @@ pub mod discuss;
 pub mod generate;
 pub mod plan;
 pub mod implement;
+pub mod commits;
 pub mod next;
 pub mod progress;
 pub mod phase;
 pub mod feature;
+pub mod install;
+pub mod github;
+pub mod pr;
+pub mod burndown;
 pub mod dashboard;
+pub mod schedule;
+pub mod server;
 
+pub mod config;
@@ -1,11 +1,12 @@
 use anyhow::{Context, Result};
 use colored::*;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::fs;
-use walkdir::WalkDir;
 use serde_json::Value;
-use crate::state::ProjectState;
+use crate::state::deps::{Blocker, DependencyGraph, NodeRef};
+use crate::state::{tasks_md, ProjectState, TaskStatus};
 use crate::utils;
+use crate::utils::plan_dirs::PlanDirs;
 
 #[derive(Debug, Clone)]
 struct TaskInfo {
@@ -14,32 +15,55 @@ struct TaskInfo {
     feature_name: String,
     priority: String,
     task_name: String,
-    status: String,
+    status: TaskStatus,
     estimated_time: String,
     progress: f64,
+    /// Graph node of the task
+    node: NodeRef,
+    /// Incomplete prerequisites; a task is only recommended when this is empty
+    blocked_by: Vec<Blocker>,
 }
 
 #[derive(Debug, Clone)]
-struct Recommendation {
-    action: String,
-    priority: String,
-    feature_path: String,
-    phase_name: String,
-    feature_name: String,
-    task_name: String,
-    estimated_effort: String,
-    reason: String,
+pub struct Recommendation {
+    pub action: String,
+    pub priority: String,
+    pub feature_path: String,
+    pub phase_name: String,
+    pub feature_name: String,
+    pub task_name: String,
+    pub estimated_effort: String,
+    pub reason: String,
+    /// `F-0001/T2`
+    pub task_ref: NodeRef,
+}
+
+/// What `/next` would recommend, for callers that display it themselves
+#[derive(Debug, Clone)]
+pub enum NextAction {
+    NoPlan,
+    AllComplete,
+    /// Every incomplete task is waiting on prerequisites
+    AllBlocked { waiting: usize },
+    Task(Box<Recommendation>),
 }
 
 /// Execute the /next command
-pub async fn execute(_args: Vec<String>) -> Result<()> {
+///
+/// `next explain <F-0001/T2>` lists what is blocking a task instead of
+/// recommending one.
+pub async fn execute(args: Vec<String>) -> Result<()> {
+    if matches!(args.first().map(|s| s.as_str()), Some("explain") | Some("why")) {
+        return explain(args.get(1).map(|s| s.as_str()).unwrap_or(""));
+    }
+
     println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
     println!("{}", "  DoPlan: Next Action Recommendation".bright_cyan().bold());
     println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
     println!();
 
     // Load state
-    let state = ProjectState::load()
+    let mut state = ProjectState::load()
         .context("Failed to load project state")?;
 
     let doplan_dir = utils::doplan_dir()?;
@@ -51,8 +75,7 @@ pub async fn execute(_args: Vec<String>) -> Result<()> {
     }
 
     // Scan all features for incomplete tasks
-    let mut all_tasks = Vec::new();
-    scan_features(&plan_dir, &mut all_tasks)?;
+    let all_tasks = incomplete_tasks(&plan_dir, &mut state)?;
 
     if all_tasks.is_empty() {
         println!("{}", "🎉 All tasks are complete!".bright_green());
@@ -61,8 +84,14 @@ pub async fn execute(_args: Vec<String>) -> Result<()> {
         return Ok(());
     }
 
+    let ready: Vec<TaskInfo> = all_tasks.iter().filter(|t| t.blocked_by.is_empty()).cloned().collect();
+    if ready.is_empty() {
+        display_all_blocked(&all_tasks);
+        return Ok(());
+    }
+
     // Analyze and generate recommendations
-    let recommendation = analyze_and_recommend(&all_tasks, &state)?;
+    let recommendation = analyze_and_recommend(&ready, &state)?;
 
     // Display recommendation
     display_recommendation(&recommendation, &all_tasks)?;
@@ -70,150 +99,182 @@ pub async fn execute(_args: Vec<String>) -> Result<()> {
     Ok(())
 }
 
-fn scan_features(plan_dir: &PathBuf, tasks: &mut Vec<TaskInfo>) -> Result<()> {
-    for entry in WalkDir::new(plan_dir)
-        .min_depth(2)
-        .max_depth(4)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        if entry.file_name() == "tasks.md" {
-            let tasks_path = entry.path();
-            let feature_dir = tasks_path.parent().unwrap();
-            
-            // Extract phase and feature names from path
-            let relative_path = feature_dir
-                .strip_prefix(plan_dir)
-                .unwrap()
-                .to_string_lossy()
-                .to_string();
-            let path_parts: Vec<&str> = relative_path.split('/').collect();
-            
-            if path_parts.len() >= 2 {
-                let phase_name = extract_name_from_path(path_parts[0]);
-                let feature_name = extract_name_from_path(path_parts[1]);
-                
-                // Read progress.json
-                let progress_path = feature_dir.join("progress.json");
-                let progress = if progress_path.exists() {
-                    read_progress(&progress_path).ok()
-                } else {
-                    None
-                };
-
-                let priority = progress
-                    .as_ref()
-                    .and_then(|p| p.get("priority"))
-                    .and_then(|p| p.as_str())
-                    .unwrap_or("medium")
-                    .to_string();
-
-                let progress_pct = progress
-                    .as_ref()
-                    .and_then(|p| p.get("progress"))
-                    .and_then(|p| p.as_f64())
-                    .unwrap_or(0.0);
-
-                // Parse tasks.md
-                if let Ok(content) = fs::read_to_string(tasks_path) {
-                    parse_tasks(
-                        &content,
-                        feature_dir.to_path_buf(),
-                        phase_name,
-                        feature_name,
-                        priority,
-                        progress_pct,
-                        tasks,
-                    )?;
-                }
-            }
+/// Recommend the next task without printing anything
+pub fn recommend(state: &mut ProjectState) -> Result<NextAction> {
+    let plan_dir = utils::doplan_dir()?.join("plan");
+    if !plan_dir.exists() {
+        return Ok(NextAction::NoPlan);
+    }
+
+    let all_tasks = incomplete_tasks(&plan_dir, state)?;
+    if all_tasks.is_empty() {
+        return Ok(NextAction::AllComplete);
+    }
+    let ready: Vec<TaskInfo> = all_tasks.iter().filter(|t| t.blocked_by.is_empty()).cloned().collect();
+    if ready.is_empty() {
+        return Ok(NextAction::AllBlocked { waiting: all_tasks.len() });
+    }
+    Ok(NextAction::Task(Box::new(analyze_and_recommend(&ready, state)?)))
+}
+
+/// Incomplete tasks across the plan, each with the prerequisites it is waiting on
+fn incomplete_tasks(plan_dir: &Path, state: &mut ProjectState) -> Result<Vec<TaskInfo>> {
+    let mut all_tasks = Vec::new();
+    scan_features(plan_dir, state, &mut all_tasks)?;
+    if all_tasks.is_empty() {
+        return Ok(all_tasks);
+    }
+
+    // Tasks whose prerequisites are incomplete are never recommended
+    state.validate_dependencies()
+        .context("Fix the Depends On entries in tasks.md before asking for the next task")?;
+    let graph = DependencyGraph::build(state);
+    for task in all_tasks.iter_mut() {
+        task.blocked_by = graph.blockers(&task.node);
+    }
+    Ok(all_tasks)
+}
+
+/// Print the incomplete prerequisites of one task
+fn explain(reference: &str) -> Result<()> {
+    let Some((feature_id, task_id)) = reference.split_once('/') else {
+        println!("{}", "Usage: /next explain <feature-id>/<task-id>".bright_yellow());
+        println!("{}", "Example: /next explain F-0001/T2".bright_yellow());
+        return Ok(());
+    };
+
+    let mut state = ProjectState::load()
+        .context("Failed to load project state")?;
+    let plan_dir = utils::doplan_dir()?.join("plan");
+    if plan_dir.exists() {
+        scan_features(&plan_dir, &mut state, &mut Vec::new())?;
+    }
+
+    let feature = state.find_feature(feature_id)
+        .ok_or_else(|| anyhow::anyhow!("Feature '{}' not found", feature_id))?;
+    if !feature.tasks.iter().any(|t| t.id == task_id) {
+        anyhow::bail!("Task '{}' not found in feature {}", task_id, feature.id);
+    }
+    let node = NodeRef::Task {
+        feature: feature.id.clone(),
+        task: task_id.to_string(),
+    };
+
+    let graph = DependencyGraph::build(&state);
+    for issue in graph.issues() {
+        println!("{} {}", "⚠".bright_red(), issue.to_string().bright_red());
+    }
+
+    let blockers = graph.blockers(&node);
+    if blockers.is_empty() {
+        println!("{}", format!("✓ {} has no incomplete prerequisites", node).bright_green());
+    } else {
+        println!("{}", format!("{} is blocked by:", node).bright_yellow());
+        for blocker in &blockers {
+            println!("  {} {}", "→".bright_cyan(), blocker);
         }
     }
+    println!();
 
     Ok(())
 }
 
-fn read_progress(path: &PathBuf) -> Result<Value> {
-    let content = fs::read_to_string(path)
-        .context("Failed to read progress file")?;
-    let progress: Value = serde_json::from_str(&content)
-        .context("Failed to parse progress file")?;
-    Ok(progress)
+fn display_all_blocked(tasks: &[TaskInfo]) {
+    println!("{}", "Every incomplete task is waiting on prerequisites.".bright_yellow());
+    println!();
+    for task in tasks {
+        println!("  {} {} / {}", "→".bright_cyan(), task.feature_name.bright_white(), task.task_name.bright_white());
+        for blocker in &task.blocked_by {
+            println!("      waiting on {}", blocker);
+        }
+    }
+    println!();
 }
 
-fn parse_tasks(
-    content: &str,
-    feature_path: PathBuf,
-    phase_name: String,
-    feature_name: String,
-    priority: String,
-    progress: f64,
-    tasks: &mut Vec<TaskInfo>,
-) -> Result<()> {
-    let lines: Vec<&str> = content.lines().collect();
-    let mut current_task: Option<TaskInfo> = None;
-    let mut in_task = false;
-
-    for line in lines {
-        if line.starts_with("#### Task") {
-            // Save previous task if incomplete
-            if let Some(task) = current_task.take() {
-                if task.status != "Completed" {
-                    tasks.push(task);
-                }
-            }
-
-            // Start new task
-            let task_name = line
-                .strip_prefix("#### Task")
-                .unwrap_or("")
-                .trim()
+/// Read every feature's `tasks.md`, syncing the task lists into `state` and
+/// collecting the incomplete tasks
+fn scan_features(plan_dir: &Path, state: &mut ProjectState, tasks: &mut Vec<TaskInfo>) -> Result<()> {
+    let layout = PlanDirs::scan(plan_dir, state)?;
+    for phase_dir in &layout.phases {
+        let phase_name = phase_dir.id.as_deref()
+            .and_then(|id| state.phase_by_id(id))
+            .map(|p| p.name.clone())
+            .unwrap_or_else(|| extract_name_from_path(&phase_dir.dir_name));
+
+        for feature_dir in &phase_dir.features {
+            // Prerequisites are only known for features in the state; tasks
+            // of any other directory cannot be shown to be unblocked
+            let Some(feature_id) = feature_dir.id.clone().filter(|id| state.feature_by_id(id).is_some()) else {
+                continue;
+            };
+            let tasks_path = feature_dir.path.join("tasks.md");
+            let Ok(content) = fs::read_to_string(&tasks_path) else {
+                continue;
+            };
+
+            // Read progress.json
+            let progress_path = feature_dir.path.join("progress.json");
+            let progress = if progress_path.exists() {
+                read_progress(&progress_path).ok()
+            } else {
+                None
+            };
+
+            let priority = progress
+                .as_ref()
+                .and_then(|p| p.get("priority"))
+                .and_then(|p| p.as_str())
+                .unwrap_or("medium")
                 .to_string();
-            
-            current_task = Some(TaskInfo {
-                feature_path: feature_path.clone(),
-                phase_name: phase_name.clone(),
-                feature_name: feature_name.clone(),
-                priority: priority.clone(),
-                task_name,
-                status: "Not Started".to_string(),
-                estimated_time: "Unknown".to_string(),
-                progress,
-            });
-            in_task = true;
-        } else if in_task {
-            if let Some(ref mut task) = current_task {
-                if line.contains("**Status**") {
-                    // Parse status
-                    if line.contains("[x] Completed") || line.contains("[X] Completed") {
-                        task.status = "Completed".to_string();
-                    } else if line.contains("[x] In Progress") || line.contains("[X] In Progress") {
-                        task.status = "In Progress".to_string();
-                    } else if line.contains("[x] Blocked") || line.contains("[X] Blocked") {
-                        task.status = "Blocked".to_string();
-                    } else {
-                        task.status = "Not Started".to_string();
-                    }
-                } else if line.contains("**Estimated Time**") {
-                    // Extract estimated time
-                    if let Some(time_part) = line.split(':').nth(1) {
-                        task.estimated_time = time_part.trim().to_string();
-                    }
-                }
+
+            let progress_pct = progress
+                .as_ref()
+                .and_then(|p| p.get("progress"))
+                .and_then(|p| p.as_f64())
+                .unwrap_or(0.0);
+
+            let feature_name = state.feature_by_id(&feature_id)
+                .map(|f| f.name.clone())
+                .unwrap_or_else(|| extract_name_from_path(&feature_dir.dir_name));
+
+            let parsed = tasks_md::parse(&content);
+            for task in parsed.iter().filter(|t| t.status != TaskStatus::Completed) {
+                tasks.push(TaskInfo {
+                    feature_path: feature_dir.path.clone(),
+                    phase_name: phase_name.clone(),
+                    feature_name: feature_name.clone(),
+                    priority: priority.clone(),
+                    task_name: format!("{}: {}", task.number().unwrap_or_default(), task.title),
+                    status: task.status,
+                    estimated_time: task.estimate.clone().unwrap_or_else(|| "Unknown".to_string()),
+                    progress: progress_pct,
+                    node: NodeRef::Task {
+                        feature: feature_id.clone(),
+                        task: task.id.clone(),
+                    },
+                    blocked_by: Vec::new(),
+                });
             }
-        }
-    }
 
-    // Save last task if incomplete
-    if let Some(task) = current_task {
-        if task.status != "Completed" {
-            tasks.push(task);
+            // The edited file is the source of truth for statuses
+            if let Some(feature) = state.feature_by_id_mut(&feature_id) {
+                feature.tasks = parsed;
+            }
         }
     }
 
     Ok(())
 }
 
+fn read_progress(path: &Path) -> Result<Value> {
+    let content = fs::read_to_string(path)
+        .context("Failed to read progress file")?;
+    let progress: Value = serde_json::from_str(&content)
+        .context("Failed to parse progress file")?;
+    Ok(progress)
+}
+
+
 fn analyze_and_recommend(tasks: &[TaskInfo], _state: &ProjectState) -> Result<Recommendation> {
     // Priority order: High > Medium > Low
     // Status order: In Progress > Not Started > Blocked
@@ -234,30 +295,30 @@ fn analyze_and_recommend(tasks: &[TaskInfo], _state: &ProjectState) -> Result<Re
         }
 
         // Then by status
-        let status_order = |s: &str| match s {
-            "In Progress" => 0,
-            "Not Started" => 1,
-            "Blocked" => 2,
-            _ => 3,
+        let status_order = |s: TaskStatus| match s {
+            TaskStatus::InProgress => 0,
+            TaskStatus::NotStarted => 1,
+            TaskStatus::Blocked => 2,
+            TaskStatus::Completed => 3,
         };
 
-        status_order(&a.status).cmp(&status_order(&b.status))
+        status_order(a.status).cmp(&status_order(b.status))
     });
 
     let recommended = sorted_tasks.first()
         .context("No tasks to recommend")?;
 
-    let action = match recommended.status.as_str() {
-        "In Progress" => format!("Continue working on: {}", recommended.task_name),
-        "Not Started" => format!("Start: {}", recommended.task_name),
-        "Blocked" => format!("Unblock: {}", recommended.task_name),
-        _ => format!("Work on: {}", recommended.task_name),
+    let action = match recommended.status {
+        TaskStatus::InProgress => format!("Continue working on: {}", recommended.task_name),
+        TaskStatus::NotStarted => format!("Start: {}", recommended.task_name),
+        TaskStatus::Blocked => format!("Unblock: {}", recommended.task_name),
+        TaskStatus::Completed => format!("Work on: {}", recommended.task_name),
     };
 
     let reason = format!(
         "Priority: {} | Status: {} | Progress: {:.0}%",
         recommended.priority,
-        recommended.status,
+        recommended.status.label(),
         recommended.progress
     );
 
@@ -270,6 +331,7 @@ fn analyze_and_recommend(tasks: &[TaskInfo], _state: &ProjectState) -> Result<Re
         task_name: recommended.task_name.clone(),
         estimated_effort: recommended.estimated_time.clone(),
         reason,
+        task_ref: recommended.node.clone(),
     })
 }
 
@@ -279,7 +341,7 @@ fn display_recommendation(rec: &Recommendation, all_tasks: &[TaskInfo]) -> Resul
     println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_green());
     println!();
 
-    println!("{}", format!("{}", rec.action).bright_white().bold());
+    println!("{}", rec.action.bright_white().bold());
     println!();
 
     println!("{}", "Details:".bright_cyan());
@@ -309,9 +371,10 @@ fn display_recommendation(rec: &Recommendation, all_tasks: &[TaskInfo]) -> Resul
 
     // Show summary statistics
     let high_priority = all_tasks.iter().filter(|t| t.priority.to_lowercase() == "high").count();
-    let in_progress = all_tasks.iter().filter(|t| t.status == "In Progress").count();
-    let not_started = all_tasks.iter().filter(|t| t.status == "Not Started").count();
-    let blocked = all_tasks.iter().filter(|t| t.status == "Blocked").count();
+    let in_progress = all_tasks.iter().filter(|t| t.status == TaskStatus::InProgress).count();
+    let not_started = all_tasks.iter().filter(|t| t.status == TaskStatus::NotStarted).count();
+    let blocked = all_tasks.iter().filter(|t| t.status == TaskStatus::Blocked).count();
+    let waiting = all_tasks.iter().filter(|t| !t.blocked_by.is_empty()).count();
 
     println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
     println!("{}", "  📊 Project Status Summary".bright_cyan().bold());
@@ -324,6 +387,10 @@ fn display_recommendation(rec: &Recommendation, all_tasks: &[TaskInfo]) -> Resul
     if blocked > 0 {
         println!("  {} Blocked: {}", "→".bright_cyan(), blocked.to_string().bright_red());
     }
+    if waiting > 0 {
+        println!("  {} Waiting on prerequisites: {}", "→".bright_cyan(), waiting.to_string().bright_yellow());
+        println!("    Run /next explain <feature-id>/<task-id> to see what is blocking a task");
+    }
     println!();
 
     println!("{}", "Next Steps:".bright_yellow());
@@ -341,6 +408,5 @@ fn extract_name_from_path(path: &str) -> String {
         .skip(1)
         .collect::<Vec<&str>>()
         .join(" ")
-        .replace("-", " ")
 }
 
@@ -2,6 +2,8 @@ use anyhow::{Context, Result};
 use colored::*;
 use dialoguer::{Input, Select};
 use crate::state::{ProjectState, Phase};
+use crate::utils;
+use crate::utils::plan_dirs;
 
 /// Execute phase management commands
 pub async fn execute(args: Vec<String>) -> Result<()> {
@@ -71,15 +73,13 @@ async fn add_phase() -> Result<()> {
         .interact_text()
         .context("Failed to read features")?;
 
-    let features = if features_input.trim().is_empty() {
-        Vec::new()
-    } else {
-        features_input
-            .split(',')
-            .map(|s| s.trim().to_string())
-            .filter(|s| !s.is_empty())
-            .collect()
-    };
+    // Features may be given by name or id; membership is stored by id
+    let mut features = Vec::new();
+    for key in features_input.split(',').map(|s| s.trim()).filter(|s| !s.is_empty()) {
+        let feature = state.find_feature(key)
+            .ok_or_else(|| anyhow::anyhow!("Feature '{}' not found. Add it with 'feature add' first.", key))?;
+        features.push(feature.id.clone());
+    }
 
     // Validate phase name
     if name.trim().is_empty() {
@@ -94,6 +94,7 @@ async fn add_phase() -> Result<()> {
     }
 
     let new_phase = Phase {
+        id: state.new_phase_id(),
         name: name.clone(),
         description,
         features,
@@ -137,10 +138,11 @@ async fn list_phases() -> Result<()> {
     let phases = state.phases.as_ref().unwrap();
     
     for (idx, phase) in phases.iter().enumerate() {
-        println!("  {} Phase {}: {}", "→".bright_cyan(), idx + 1, phase.name.bright_white().bold());
+        println!("  {} Phase {}: {} ({})", "→".bright_cyan(), idx + 1, phase.name.bright_white().bold(), phase.id);
         println!("     Description: {}", phase.description);
-        if !phase.features.is_empty() {
-            println!("     Features: {}", phase.features.join(", "));
+        let feature_names: Vec<&str> = state.phase_features(phase).iter().map(|f| f.name.as_str()).collect();
+        if !feature_names.is_empty() {
+            println!("     Features: {}", feature_names.join(", "));
         } else {
             println!("     Features: {}", "None".bright_yellow());
         }
@@ -233,7 +235,7 @@ async fn update_phase(phase_name: Option<String>) -> Result<()> {
 
     // Select phase if not provided
     let phase_idx = if let Some(name) = phase_name {
-        state.phases.as_ref().unwrap().iter().position(|p| p.name == name)
+        state.phases.as_ref().unwrap().iter().position(|p| p.id == name || p.name == name)
             .ok_or_else(|| anyhow::anyhow!("Phase '{}' not found", name))?
     } else {
         let phase_names: Vec<String> = state.phases.as_ref().unwrap().iter().map(|p| p.name.clone()).collect();
@@ -277,6 +279,15 @@ async fn update_phase(phase_name: Option<String>) -> Result<()> {
         }
     }
 
+    // Move the plan directory along with the rename so progress is kept
+    if new_name != old_name {
+        let phase_id = state.phases.as_ref().unwrap()[phase_idx].id.clone();
+        let plan_dir = utils::doplan_dir()?.join("plan");
+        if let Some((from, to)) = plan_dirs::rename_phase_dir(&plan_dir, &state, &phase_id, &new_name)? {
+            println!("{}", format!("  ↻ Moved {} → {}", from.display(), to.display()).bright_cyan());
+        }
+    }
+
     // Update phase
     let phases = state.phases.as_mut().unwrap();
     phases[phase_idx].name = new_name;
@@ -311,7 +322,7 @@ async fn delete_phase(phase_name: Option<String>) -> Result<()> {
     
     // Select phase if not provided
     let phase_idx = if let Some(name) = phase_name {
-        phases.iter().position(|p| p.name == name)
+        phases.iter().position(|p| p.id == name || p.name == name)
             .ok_or_else(|| anyhow::anyhow!("Phase '{}' not found", name))?
     } else {
         let phase_names: Vec<String> = phases.iter().map(|p| p.name.clone()).collect();
@@ -1,19 +1,52 @@
 use anyhow::{Context, Result};
 use colored::*;
-use std::path::PathBuf;
-use crate::state::ProjectState;
+use std::path::{Path, PathBuf};
+use crate::config::Config;
+use crate::generators::task_rules::TaskCatalog;
+use crate::integration::llm::{self, LlmProvider};
+use crate::state::weighting::weighted_mean;
+use crate::state::{tasks_md, ProgressWeighting, ProjectState, TaskStatus, WorkingCalendar};
 use crate::utils;
+use crate::utils::plan_dirs::{self, PlanDirs};
+use crate::utils::plan_manifest::{self, PlanManifest, WriteOutcome};
 use serde_json::json;
 
+/// Counts of what a reconcile run did to the plan files
+#[derive(Debug, Default)]
+struct ReconcileReport {
+    created: usize,
+    updated: usize,
+    unchanged: usize,
+    kept: Vec<PathBuf>,
+}
+
+impl ReconcileReport {
+    fn record(&mut self, outcome: WriteOutcome, path: &Path) {
+        match outcome {
+            WriteOutcome::Created => self.created += 1,
+            WriteOutcome::Updated => self.updated += 1,
+            WriteOutcome::Unchanged => self.unchanged += 1,
+            WriteOutcome::Kept => self.kept.push(path.to_path_buf()),
+        }
+    }
+}
+
 /// Execute the /plan command
-pub async fn execute(_args: Vec<String>) -> Result<()> {
+///
+/// Existing plan directories are reconciled rather than regenerated: missing
+/// files are created, directories follow renames and reordering, and files
+/// edited since DoPlan wrote them are kept. Pass `--force` to overwrite edited
+/// files from the templates as well.
+pub async fn execute(args: Vec<String>) -> Result<()> {
+    let force = args.iter().any(|a| a == "--force" || a == "-f");
+
     println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
     println!("{}", "  DoPlan: Project Planning".bright_cyan().bold());
     println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
     println!();
 
     // Load state
-    let state = ProjectState::load()
+    let mut state = ProjectState::load()
         .context("Failed to load project state. Ensure .doplan/state.json exists")?;
 
     // Validate state
@@ -45,8 +78,7 @@ pub async fn execute(_args: Vec<String>) -> Result<()> {
         return Ok(());
     }
 
-    let phases = state.phases.as_ref().unwrap();
-    let features = state.features.as_ref();
+    let phases = state.phases.clone().unwrap_or_default();
 
     // Create plan directory structure
     let plan_dir = doplan_dir.join("plan");
@@ -56,72 +88,160 @@ pub async fn execute(_args: Vec<String>) -> Result<()> {
     println!("{}", "Creating phase and feature structure...".bright_yellow());
     println!();
 
+    let manifest_path = plan_manifest::manifest_path(&utils::dot_doplan_dir()?);
+    let mut manifest = PlanManifest::load(&manifest_path)?;
+    let mut report = ReconcileReport::default();
+
+    // Archive directories of features and phases that are no longer planned
+    let archive_root = doplan_dir.join("archive").join(chrono::Utc::now().format("%Y%m%d-%H%M%S").to_string());
+    let archived = archive_unplanned_dirs(&plan_dir, &archive_root, &state)
+        .context("Failed to archive removed plan directories")?;
+
+    // Move existing directories whose index or name changed, keyed on ids
+    let moved = relocate_existing_dirs(&plan_dir, &state)
+        .context("Failed to move renamed plan directories")?;
+    for (from, to) in &moved {
+        println!("  {} Moved {} → {}", "↻".bright_cyan(), from.display(), to.display());
+    }
+
+    // Phases that were removed but still held planned features are archived
+    // once those features have moved out
+    let archived: Vec<_> = archived
+        .into_iter()
+        .chain(archive_unplanned_dirs(&plan_dir, &archive_root, &state)
+            .context("Failed to archive removed plan directories")?)
+        .collect();
+    for (ids, from, to) in &archived {
+        for id in ids {
+            manifest.forget(id);
+        }
+        println!("  {} Archived {} → {}", "⌫".bright_yellow(), from.display(), to.display());
+    }
+
+    // Progress files are weighted the same way /progress weights them
+    let Config { progress, calendar, .. } = Config::current()?;
+    let weighting = progress.weighting;
+
+    // Features without tasks are seeded from the task rules
+    let catalog = TaskCatalog::load(&doplan_dir)?;
+    let tech_stack = state.tech_stack.clone().unwrap_or_default();
+
+    let provider = llm::configured()?;
+    if let Some(provider) = &provider {
+        println!("{}", format!("Drafting user stories and tasks with {}...", provider.model()).bright_yellow());
+        println!();
+    }
+
     // Create phases
     for (phase_idx, phase) in phases.iter().enumerate() {
-        let phase_num = format!("{:02}-{}", phase_idx + 1, sanitize_name(&phase.name));
+        let phase_num = plan_dirs::phase_dir_name(phase_idx, phase);
         let phase_dir = plan_dir.join(&phase_num);
         utils::ensure_dir(&phase_dir)
             .with_context(|| format!("Failed to create phase directory: {}", phase_num))?;
 
-        println!("  {} Creating phase: {}", "→".bright_cyan(), phase.name);
+        println!("  {} Reconciling phase: {}", "→".bright_cyan(), phase.name);
         
         // Generate phase-plan.md
-        generate_phase_plan(&phase_dir, phase, &prd_content, features)
+        let plan_path = phase_dir.join("phase-plan.md");
+        let content = generate_phase_plan(phase, &prd_content, &state)
             .context("Failed to generate phase plan")?;
-
-        // Generate phase-progress.json
-        generate_phase_progress(&phase_dir, phase)
-            .context("Failed to generate phase progress")?;
+        let outcome = manifest.write(&PlanManifest::key(&phase.id, "phase-plan.md"), &plan_path, &content, force)?;
+        report.record(outcome, &plan_path);
 
         // Create features for this phase
-        if let Some(features_list) = features {
-            for (feature_idx, feature_name) in phase.features.iter().enumerate() {
-                // Find the feature in the features list
-                if let Some(feature) = features_list.iter().find(|f| f.name == *feature_name) {
-                    let feature_num = format!("{:02}-{}", feature_idx + 1, sanitize_name(feature_name));
-                    let feature_dir = phase_dir.join(&feature_num);
-                    utils::ensure_dir(&feature_dir)
-                        .with_context(|| format!("Failed to create feature directory: {}", feature_num))?;
-
-                    println!("    {} Creating feature: {}", "→".bright_cyan(), feature_name);
-
-                    // Generate feature plan.md
-                    generate_feature_plan(&feature_dir, feature, &prd_content)
-                        .context("Failed to generate feature plan")?;
-
-                    // Generate feature design.md
-                    generate_feature_design(&feature_dir, feature)
-                        .context("Failed to generate feature design")?;
-
-                    // Generate feature tasks.md
-                    generate_feature_tasks(&feature_dir, feature)
-                        .context("Failed to generate feature tasks")?;
-
-                    // Generate feature progress.json
-                    generate_feature_progress(&feature_dir, feature)
-                        .context("Failed to generate feature progress")?;
-                }
+        let feature_ids: Vec<String> = state.phase_features(phase).iter().map(|f| f.id.clone()).collect();
+        for (feature_idx, feature_id) in feature_ids.iter().enumerate() {
+            let dependencies: Vec<String> = state.feature_by_id(feature_id)
+                .map(|f| f.depends_on.iter().map(|id| match state.feature_by_id(id) {
+                    Some(dep) => format!("{} ({})", dep.name, id),
+                    None => id.clone(),
+                }).collect())
+                .unwrap_or_default();
+            if let (Some(provider), Some(feature)) = (&provider, state.feature_by_id(feature_id)) {
+                let tasks_path = phase_dir.join(plan_dirs::feature_dir_name(feature_idx, feature)).join("tasks.md");
+                draft_with_llm(provider, &mut state, feature_id, &tasks_path).await;
             }
+            let Some(feature) = state.feature_by_id_mut(feature_id) else {
+                continue;
+            };
+            let feature_num = plan_dirs::feature_dir_name(feature_idx, feature);
+            let feature_dir = phase_dir.join(&feature_num);
+            utils::ensure_dir(&feature_dir)
+                .with_context(|| format!("Failed to create feature directory: {}", feature_num))?;
+
+            println!("    {} Reconciling feature: {}", "→".bright_cyan(), feature.name);
+
+            // Generate feature plan.md
+            let plan_path = feature_dir.join("plan.md");
+            let content = generate_feature_plan(feature, &dependencies, &prd_content)
+                .context("Failed to generate feature plan")?;
+            let outcome = manifest.write(&PlanManifest::key(&feature.id, "plan.md"), &plan_path, &content, force)?;
+            report.record(outcome, &plan_path);
+
+            // Generate feature design.md
+            let design_path = feature_dir.join("design.md");
+            let content = generate_feature_design(feature)
+                .context("Failed to generate feature design")?;
+            let outcome = manifest.write(&PlanManifest::key(&feature.id, "design.md"), &design_path, &content, force)?;
+            report.record(outcome, &design_path);
+
+            // Generate feature tasks.md
+            let tasks_path = feature_dir.join("tasks.md");
+            let content = generate_feature_tasks(&tasks_path, feature, &catalog, &tech_stack)
+                .context("Failed to generate feature tasks")?;
+            let outcome = manifest.write(&PlanManifest::key(&feature.id, "tasks.md"), &tasks_path, &content, force)?;
+            report.record(outcome, &tasks_path);
+
+            // Generate feature progress.json
+            generate_feature_progress(&feature_dir, feature, weighting, &calendar)
+                .context("Failed to generate feature progress")?;
         }
+
+        // Generate phase-progress.json from the task lists adopted above
+        generate_phase_progress(&phase_dir, phase, &state.phase_features(phase), weighting, &calendar)
+            .context("Failed to generate phase progress")?;
     }
 
+    // Persist the task lists now mirrored in each tasks.md, unless their
+    // dependencies are broken
+    state.validate_dependencies()
+        .context("Fix the Depends On entries in tasks.md and run /plan again")?;
+    state.save()
+        .context("Failed to save project state")?;
+    manifest.save(&manifest_path)?;
+
     println!();
     println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_green());
     println!("{}", "  Planning Complete!".bright_green().bold());
     println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_green());
     println!();
-    println!("{}", format!("Created {} phase(s) with features", phases.len()).bright_white());
+    println!("{}", format!("Reconciled {} phase(s) with features", phases.len()).bright_white());
+    println!(
+        "  {} created, {} updated, {} unchanged, {} moved, {} archived",
+        report.created,
+        report.updated,
+        report.unchanged,
+        moved.len(),
+        archived.len()
+    );
+    if !report.kept.is_empty() {
+        println!();
+        println!("{}", "Kept edited files (use --force to overwrite):".bright_yellow());
+        for path in &report.kept {
+            println!("  • {}", path.display());
+        }
+    }
     println!();
-    println!("{}", "Structure created:".bright_cyan());
+    println!("{}", "Plan structure:".bright_cyan());
     println!("  doplan/plan/");
     for (phase_idx, phase) in phases.iter().enumerate() {
-        let phase_num = format!("{:02}-{}", phase_idx + 1, sanitize_name(&phase.name));
+        let phase_num = plan_dirs::phase_dir_name(phase_idx, phase);
         println!("  ├── {}/", phase_num);
         println!("  │   ├── phase-plan.md");
         println!("  │   ├── phase-progress.json");
         if !phase.features.is_empty() {
-            for (feature_idx, feature_name) in phase.features.iter().enumerate() {
-                let feature_num = format!("{:02}-{}", feature_idx + 1, sanitize_name(feature_name));
+            for (feature_idx, feature) in state.phase_features(phase).iter().enumerate() {
+                let feature_num = plan_dirs::feature_dir_name(feature_idx, feature);
                 println!("  │   ├── {}/", feature_num);
                 println!("  │   │   ├── plan.md");
                 println!("  │   │   ├── design.md");
@@ -139,26 +259,103 @@ pub async fn execute(_args: Vec<String>) -> Result<()> {
     Ok(())
 }
 
-fn sanitize_name(name: &str) -> String {
-    name.to_lowercase()
-        .replace(" ", "-")
-        .replace("_", "-")
-        .replace(".", "-")
-        .chars()
-        .filter(|c| c.is_alphanumeric() || *c == '-')
-        .collect::<String>()
-        .trim_matches('-')
-        .to_string()
+/// Move phase and feature directories to the location their id now maps to.
+/// Phases move first so feature lookups see the new phase paths.
+fn relocate_existing_dirs(plan_dir: &Path, state: &ProjectState) -> Result<Vec<(PathBuf, PathBuf)>> {
+    let phases = state.phases.clone().unwrap_or_default();
+    let mut all_moves = Vec::new();
+
+    let layout = PlanDirs::scan(plan_dir, state)?;
+    let mut phase_moves = Vec::new();
+    for (phase_idx, phase) in phases.iter().enumerate() {
+        let expected = plan_dir.join(plan_dirs::phase_dir_name(phase_idx, phase));
+        if let Some(existing) = layout.phase(&phase.id) {
+            if existing.path != expected {
+                phase_moves.push((existing.path.clone(), expected));
+            }
+        }
+    }
+    check_move_targets(&layout, &phase_moves)?;
+    plan_dirs::apply_moves(&phase_moves)?;
+    all_moves.extend(phase_moves);
+
+    let layout = PlanDirs::scan(plan_dir, state)?;
+    let mut feature_moves = Vec::new();
+    for (phase_idx, phase) in phases.iter().enumerate() {
+        let phase_path = plan_dir.join(plan_dirs::phase_dir_name(phase_idx, phase));
+        for (feature_idx, feature) in state.phase_features(phase).iter().enumerate() {
+            let expected = phase_path.join(plan_dirs::feature_dir_name(feature_idx, feature));
+            if let Some((_, existing)) = layout.feature(&feature.id) {
+                if existing.path != expected {
+                    feature_moves.push((existing.path.clone(), expected));
+                }
+            }
+        }
+    }
+    check_move_targets(&layout, &feature_moves)?;
+    plan_dirs::apply_moves(&feature_moves)?;
+    all_moves.extend(feature_moves);
+
+    Ok(all_moves)
+}
+
+/// Archive directories whose feature or phase id is no longer planned. A
+/// feature counts as planned when some phase lists it; a removed phase is only
+/// archived once it holds no planned features, so features that moved to
+/// another phase are relocated first. Directories without a known id are left
+/// alone. Returns the ids inside and `from`/`to` paths of each archived directory.
+fn archive_unplanned_dirs(plan_dir: &Path, archive_root: &Path, state: &ProjectState) -> Result<Vec<(Vec<String>, PathBuf, PathBuf)>> {
+    let planned_features: Vec<&str> = state.phases.iter().flatten()
+        .flat_map(|p| state.phase_features(p))
+        .map(|f| f.id.as_str())
+        .collect();
+    let is_planned_feature = |id: &Option<String>| id.as_deref().map(|id| planned_features.contains(&id)).unwrap_or(true);
+    let is_planned_phase = |id: &Option<String>| id.as_deref().map(|id| state.phase_by_id(id).is_some()).unwrap_or(true);
+
+    let layout = PlanDirs::scan(plan_dir, state)?;
+    let mut archived = Vec::new();
+    for phase in &layout.phases {
+        let archive_phase = !is_planned_phase(&phase.id) && !phase.features.iter().any(|f| is_planned_feature(&f.id));
+        if archive_phase {
+            let target = plan_dirs::archive_dir(plan_dir, archive_root, &phase.path)?;
+            let ids = phase.id.iter().chain(phase.features.iter().filter_map(|f| f.id.as_ref())).cloned().collect();
+            archived.push((ids, phase.path.clone(), target));
+            continue;
+        }
+        for feature in phase.features.iter().filter(|f| !is_planned_feature(&f.id)) {
+            let target = plan_dirs::archive_dir(plan_dir, archive_root, &feature.path)?;
+            archived.push((feature.id.iter().cloned().collect(), feature.path.clone(), target));
+        }
+    }
+    Ok(archived)
+}
+
+/// Refuse to move onto a directory that is not itself being moved away
+fn check_move_targets(layout: &PlanDirs, moves: &[(PathBuf, PathBuf)]) -> Result<()> {
+    for (_, to) in moves {
+        let vacated = moves.iter().any(|(from, _)| from == to);
+        if to.exists() && !vacated {
+            let owner = layout
+                .phases
+                .iter()
+                .flat_map(|p| std::iter::once((p.path.as_path(), p.id.as_deref())).chain(p.features.iter().map(|f| (f.path.as_path(), f.id.as_deref()))))
+                .find(|(path, _)| *path == to.as_path())
+                .and_then(|(_, id)| id);
+            anyhow::bail!(
+                "Cannot move plan directory to {}: it is already used{}. Move or remove it and run /plan again.",
+                to.display(),
+                owner.map(|id| format!(" by {}", id)).unwrap_or_default()
+            );
+        }
+    }
+    Ok(())
 }
 
 fn generate_phase_plan(
-    phase_dir: &PathBuf,
     phase: &crate::state::Phase,
     _prd_content: &str,
-    _features: Option<&Vec<crate::state::Feature>>,
-) -> Result<PathBuf> {
-    let plan_path = phase_dir.join("phase-plan.md");
-
+    state: &ProjectState,
+) -> Result<String> {
     let mut content = String::new();
     content.push_str("# Phase Plan\n\n");
     content.push_str(&format!("**Phase:** {}\n\n", phase.name));
@@ -169,11 +366,12 @@ fn generate_phase_plan(
     content.push_str(&format!("{}\n\n", phase.description));
 
     content.push_str("## Features\n\n");
-    if !phase.features.is_empty() {
-        for feature_name in &phase.features {
-            content.push_str(&format!("- {}\n", feature_name));
+    let features = state.phase_features(phase);
+    if !features.is_empty() {
+        for feature in &features {
+            content.push_str(&format!("- {}\n", feature.name));
         }
-        content.push_str("\n");
+        content.push('\n');
     } else {
         content.push_str("_No features assigned to this phase_\n\n");
     }
@@ -198,28 +396,46 @@ fn generate_phase_plan(
     utils::validate_content(&content, 100)
         .context("Generated phase plan content is too short")?;
 
-    std::fs::write(&plan_path, &content)
-        .with_context(|| format!("Failed to write phase plan to: {}", plan_path.display()))?;
-
-    // Verify file was written successfully
-    utils::verify_file_write(&plan_path, 100)
-        .context("Phase plan file verification failed")?;
-
-    Ok(plan_path)
+    Ok(content)
 }
 
-fn generate_phase_progress(phase_dir: &PathBuf, phase: &crate::state::Phase) -> Result<PathBuf> {
+fn generate_phase_progress(
+    phase_dir: &Path,
+    phase: &crate::state::Phase,
+    features: &[&crate::state::Feature],
+    weighting: ProgressWeighting,
+    calendar: &WorkingCalendar,
+) -> Result<PathBuf> {
     let progress_path = phase_dir.join("phase-progress.json");
 
+    // Same rules as /progress: a feature's status follows its tasks and the
+    // phase percentage is the weighted mean of its feature percentages
+    let statuses: Vec<(&str, f64)> = features.iter().map(|f| feature_status(f, weighting, calendar)).collect();
+    let weights: Vec<f64> = features
+        .iter()
+        .map(|f| weighting.feature_weight(&f.tasks, &f.priority, calendar))
+        .collect();
+    let count = |status: &str| statuses.iter().filter(|(s, _)| *s == status).count();
+    let progress_pct = weighted_mean(statuses.iter().zip(&weights).map(|((_, p), w)| (*p, *w)));
+    let status = if !statuses.is_empty() && count("completed") == statuses.len() {
+        "completed"
+    } else if count("in_progress") > 0 {
+        "in_progress"
+    } else {
+        "not_started"
+    };
+
     let progress = json!({
+        "id": phase.id,
         "phase": phase.name,
-        "status": "not_started",
-        "progress": 0,
+        "status": status,
+        "progress": progress_pct,
+        "weight": weighting.phase_weight(weights.iter().copied()),
         "features": {
-            "total": phase.features.len(),
-            "completed": 0,
-            "in_progress": 0,
-            "not_started": phase.features.len()
+            "total": features.len(),
+            "completed": count("completed"),
+            "in_progress": count("in_progress"),
+            "not_started": count("not_started")
         },
         "updated_at": chrono::Utc::now().to_rfc3339()
     });
@@ -242,12 +458,10 @@ fn generate_phase_progress(phase_dir: &PathBuf, phase: &crate::state::Phase) ->
 }
 
 fn generate_feature_plan(
-    feature_dir: &PathBuf,
     feature: &crate::state::Feature,
+    dependencies: &[String],
     _prd_content: &str,
-) -> Result<PathBuf> {
-    let plan_path = feature_dir.join("plan.md");
-
+) -> Result<String> {
     let mut content = String::new();
     content.push_str("# Feature Plan\n\n");
     content.push_str(&format!("**Feature:** {}\n\n", feature.name));
@@ -264,7 +478,14 @@ fn generate_feature_plan(
     content.push_str("- Write comprehensive tests\n\n");
 
     content.push_str("## User Stories\n\n");
-    content.push_str(&format!("- As a user, I want {} so that I can achieve my goals\n\n", feature.name.to_lowercase()));
+    if feature.user_stories.is_empty() {
+        content.push_str(&format!("- As a user, I want {} so that I can achieve my goals\n\n", feature.name.to_lowercase()));
+    } else {
+        for story in &feature.user_stories {
+            content.push_str(&format!("- {}\n", story));
+        }
+        content.push('\n');
+    }
 
     content.push_str("## Requirements\n\n");
     content.push_str("### Functional Requirements\n");
@@ -294,7 +515,14 @@ fn generate_feature_plan(
     content.push_str("_Technical approach to be defined_\n\n");
 
     content.push_str("## Dependencies\n\n");
-    content.push_str("_Dependencies to be identified_\n\n");
+    if dependencies.is_empty() {
+        content.push_str("_No dependencies_\n\n");
+    } else {
+        for dependency in dependencies {
+            content.push_str(&format!("- {}\n", dependency));
+        }
+        content.push('\n');
+    }
 
     content.push_str("## Acceptance Criteria\n\n");
     content.push_str("- [ ] Feature implemented\n");
@@ -309,22 +537,10 @@ fn generate_feature_plan(
     utils::validate_content(&content, 100)
         .context("Generated feature plan content is too short")?;
 
-    std::fs::write(&plan_path, &content)
-        .with_context(|| format!("Failed to write feature plan to: {}", plan_path.display()))?;
-
-    // Verify file was written successfully
-    utils::verify_file_write(&plan_path, 100)
-        .context("Feature plan file verification failed")?;
-
-    Ok(plan_path)
+    Ok(content)
 }
 
-fn generate_feature_design(
-    feature_dir: &PathBuf,
-    feature: &crate::state::Feature,
-) -> Result<PathBuf> {
-    let design_path = feature_dir.join("design.md");
-
+fn generate_feature_design(feature: &crate::state::Feature) -> Result<String> {
     let mut content = String::new();
     content.push_str("# Design Specification\n\n");
     content.push_str(&format!("**Feature:** {}\n\n", feature.name));
@@ -360,105 +576,117 @@ fn generate_feature_design(
     utils::validate_content(&content, 100)
         .context("Generated feature design content is too short")?;
 
-    std::fs::write(&design_path, &content)
-        .with_context(|| format!("Failed to write feature design to: {}", design_path.display()))?;
-
-    // Verify file was written successfully
-    utils::verify_file_write(&design_path, 100)
-        .context("Feature design file verification failed")?;
-
-    Ok(design_path)
+    Ok(content)
 }
 
 fn generate_feature_tasks(
-    feature_dir: &PathBuf,
-    feature: &crate::state::Feature,
-) -> Result<PathBuf> {
-    let tasks_path = feature_dir.join("tasks.md");
-
-    let mut content = String::new();
-    content.push_str("# Tasks\n\n");
-    content.push_str(&format!("**Feature:** {}\n\n", feature.name));
-    content.push_str(&format!("**Date:** {}\n\n", chrono::Utc::now().format("%Y-%m-%d")));
-    content.push_str("---\n\n");
-
-    content.push_str("## Tasks\n\n");
-
-    content.push_str("#### Task 1: Setup\n");
-    content.push_str("- **Status**: [ ] Not Started | [ ] In Progress | [ ] Completed | [ ] Blocked\n");
-    content.push_str("- **Priority**: High\n");
-    content.push_str("- **Description**: Setup feature structure and initial files\n");
-    content.push_str("- **Acceptance Criteria**:\n");
-    content.push_str("  - [ ] Feature directory created\n");
-    content.push_str("  - [ ] Initial files generated\n");
-    content.push_str("- **Estimated Time**: 1 hour\n");
-    content.push_str("- **Notes**: _Additional notes_\n\n");
-
-    content.push_str("#### Task 2: Implementation\n");
-    content.push_str("- **Status**: [ ] Not Started | [ ] In Progress | [ ] Completed | [ ] Blocked\n");
-    content.push_str("- **Priority**: High\n");
-    content.push_str("- **Description**: Implement core feature functionality\n");
-    content.push_str("- **Acceptance Criteria**:\n");
-    content.push_str("  - [ ] Core functionality implemented\n");
-    content.push_str("  - [ ] Error handling added\n");
-    content.push_str("- **Estimated Time**: 4 hours\n");
-    content.push_str("- **Notes**: _Additional notes_\n\n");
-
-    content.push_str("#### Task 3: Testing\n");
-    content.push_str("- **Status**: [ ] Not Started | [ ] In Progress | [ ] Completed | [ ] Blocked\n");
-    content.push_str("- **Priority**: Medium\n");
-    content.push_str("- **Description**: Write and run tests\n");
-    content.push_str("- **Acceptance Criteria**:\n");
-    content.push_str("  - [ ] Unit tests written\n");
-    content.push_str("  - [ ] Integration tests written\n");
-    content.push_str("  - [ ] All tests passing\n");
-    content.push_str("- **Estimated Time**: 2 hours\n");
-    content.push_str("- **Notes**: _Additional notes_\n\n");
-
-    content.push_str("## Progress Tracking\n\n");
-    content.push_str("**Overall Progress**: 0%\n\n");
-    content.push_str("- Completed: 0\n");
-    content.push_str("- In Progress: 0\n");
-    content.push_str("- Not Started: 3\n");
-    content.push_str("- Blocked: 0\n\n");
-
-    content.push_str("## Dependencies\n\n");
-    content.push_str("_Dependencies to be identified_\n\n");
+    tasks_path: &Path,
+    feature: &mut crate::state::Feature,
+    catalog: &TaskCatalog,
+    tech_stack: &[String],
+) -> Result<String> {
+    // tasks.md is the editable copy; adopt it before falling back to state or the task rules
+    let existing = if tasks_path.exists() {
+        tasks_md::read(tasks_path)?
+    } else {
+        Vec::new()
+    };
+    if !existing.is_empty() {
+        feature.tasks = existing;
+    } else if feature.tasks.is_empty() {
+        feature.tasks = catalog.tasks_for(feature, tech_stack);
+    }
 
-    content.push_str("## Blockers\n\n");
-    content.push_str("_No blockers_\n\n");
+    let content = tasks_md::render(feature);
 
     // Validate content before writing
     utils::validate_content(&content, 100)
         .context("Generated feature tasks content is too short")?;
 
-    std::fs::write(&tasks_path, &content)
-        .with_context(|| format!("Failed to write feature tasks to: {}", tasks_path.display()))?;
+    Ok(content)
+}
 
-    // Verify file was written successfully
-    utils::verify_file_write(&tasks_path, 100)
-        .context("Feature tasks file verification failed")?;
+/// Draft user stories, and tasks for a feature without a `tasks.md` yet,
+/// with the LLM provider. A failed request is reported and the templates are
+/// used instead.
+async fn draft_with_llm(provider: &impl LlmProvider, state: &mut ProjectState, feature_id: &str, tasks_path: &Path) {
+    let Some(feature) = state.feature_by_id(feature_id).cloned() else {
+        return;
+    };
+
+    let stories = if feature.user_stories.is_empty() {
+        llm::user_stories(provider, state, &feature).await
+            .inspect_err(|e| println!("    {} User stories for {}: {:#}", "⚠".bright_yellow(), feature.name, e))
+            .ok()
+    } else {
+        None
+    };
+    let tasks = if feature.tasks.is_empty() && !tasks_path.exists() {
+        llm::break_down_tasks(provider, state, &feature).await
+            .inspect_err(|e| println!("    {} Tasks for {}: {:#}", "⚠".bright_yellow(), feature.name, e))
+            .ok()
+    } else {
+        None
+    };
+
+    if let Some(feature) = state.feature_by_id_mut(feature_id) {
+        if let Some(stories) = stories {
+            feature.user_stories = stories;
+        }
+        if let Some(tasks) = tasks {
+            feature.tasks = tasks;
+        }
+    }
+}
 
-    Ok(tasks_path)
+/// Status and completion percentage of a feature, derived from its tasks
+fn feature_status(
+    feature: &crate::state::Feature,
+    weighting: ProgressWeighting,
+    calendar: &WorkingCalendar,
+) -> (&'static str, f64) {
+    let count = |status: TaskStatus| feature.tasks.iter().filter(|t| t.status == status).count();
+    let total = feature.tasks.len();
+    let completed = count(TaskStatus::Completed);
+    let progress_pct = weighting.feature_progress(&feature.tasks, calendar);
+    let status = if total > 0 && completed == total {
+        "completed"
+    } else if count(TaskStatus::InProgress) > 0 {
+        "in_progress"
+    } else if count(TaskStatus::Blocked) > 0 {
+        "blocked"
+    } else {
+        "not_started"
+    };
+    (status, progress_pct)
 }
 
 fn generate_feature_progress(
-    feature_dir: &PathBuf,
+    feature_dir: &Path,
     feature: &crate::state::Feature,
+    weighting: ProgressWeighting,
+    calendar: &WorkingCalendar,
 ) -> Result<PathBuf> {
     let progress_path = feature_dir.join("progress.json");
 
+    let count = |status: TaskStatus| feature.tasks.iter().filter(|t| t.status == status).count();
+    let total = feature.tasks.len();
+    let completed = count(TaskStatus::Completed);
+    let (status, progress_pct) = feature_status(feature, weighting, calendar);
+
     let progress = json!({
+        "id": feature.id,
         "feature": feature.name,
         "priority": feature.priority,
-        "status": "not_started",
-        "progress": 0,
+        "status": status,
+        "progress": progress_pct,
+        "weight": weighting.feature_weight(&feature.tasks, &feature.priority, calendar),
         "tasks": {
-            "total": 3,
-            "completed": 0,
-            "in_progress": 0,
-            "not_started": 3,
-            "blocked": 0
+            "total": total,
+            "completed": completed,
+            "in_progress": count(TaskStatus::InProgress),
+            "not_started": count(TaskStatus::NotStarted),
+            "blocked": count(TaskStatus::Blocked)
         },
         "updated_at": chrono::Utc::now().to_rfc3339()
     });
@@ -480,3 +708,32 @@ fn generate_feature_progress(
     Ok(progress_path)
 }
 
+/// Phases, features and tasks with their statuses, as served to MCP clients
+/// and the HTTP API. Task statuses are whatever `state` holds, so sync them
+/// from `tasks.md` first.
+pub fn plan_tree(state: &ProjectState) -> serde_json::Value {
+    let feature_json = |id: &str| {
+        state.feature_by_id(id).map(|f| json!({
+            "id": f.id,
+            "name": f.name,
+            "description": f.description,
+            "priority": f.priority,
+            "depends_on": f.depends_on,
+            "tasks": f.tasks.iter().map(|t| json!({
+                "id": t.id,
+                "title": t.title,
+                "status": t.status.as_str(),
+                "estimate": t.estimate,
+                "depends_on": t.depends_on
+            })).collect::<Vec<_>>()
+        }))
+    };
+    json!({
+        "project": state.project_name,
+        "phases": state.phases.iter().flatten().map(|p| json!({
+            "id": p.id,
+            "name": p.name,
+            "features": p.features.iter().filter_map(|id| feature_json(id)).collect::<Vec<_>>()
+        })).collect::<Vec<_>>()
+    })
+}
@@ -0,0 +1,107 @@
+use anyhow::{Context, Result};
+use colored::*;
+use git2::{BranchType, Repository};
+use std::fs;
+use crate::config::Config;
+use crate::integration::git;
+use crate::integration::github::{self, SyncMap};
+use crate::state::{ProjectState, PullRequest};
+use crate::utils;
+use crate::utils::plan_dirs;
+use super::implement;
+
+/// Open a pull request for a feature branch.
+///
+/// Usage: `doplan execute pr <phase/feature | feature-id> [--base BRANCH]
+/// [--draft] [--repo owner/name] [--api-url URL]`. The head is the branch
+/// `/implement` created for the feature and the base defaults to
+/// `git.base_branch`, then `origin/HEAD`. The repository, API URL and token
+/// are found the same way as for `doplan github`. Running it again updates
+/// the open pull request instead of opening another.
+pub async fn execute(args: Vec<String>) -> Result<()> {
+    let option = |name: &str| {
+        args.iter()
+            .position(|a| a == name)
+            .and_then(|i| args.get(i + 1))
+            .cloned()
+    };
+    let draft = args.iter().any(|a| a == "--draft");
+    let target = args.iter()
+        .enumerate()
+        .find(|(i, a)| {
+            !a.starts_with("--")
+                && (*i == 0 || !["--base", "--repo", "--api-url"].contains(&args[i - 1].as_str()))
+        })
+        .map(|(_, a)| a.as_str());
+    let Some(target) = target else {
+        println!("{}", "Usage: /pr <phase-id>/<feature-id> or /pr <feature-id> [--base BRANCH] [--draft]".bright_yellow());
+        println!("{}", "Example: /pr 01-foundation/01-user-auth or /pr F-0003".bright_yellow());
+        return Ok(());
+    };
+
+    let mut state = ProjectState::load()
+        .context("Failed to load project state")?;
+    let plan_dir = utils::doplan_dir()?.join("plan");
+    let layout = plan_dirs::sync_tasks(&plan_dir, &mut state)?;
+
+    // Stable ids (F-0003) resolve to wherever the directory lives now
+    let resolved = implement::resolve_id_target(&plan_dir, &state, target)?;
+    let target = resolved.as_deref().unwrap_or(target);
+    let (phase_dir, feature_dir) = target
+        .split_once('/')
+        .and_then(|(phase, feature)| layout.feature_by_dir_names(phase, feature))
+        .with_context(|| format!("No feature '{}'. Pass phase-id/feature-id or a feature id such as F-0003", target))?;
+    let feature_id = feature_dir.id.clone()
+        .with_context(|| format!("{} has no feature id. Run /plan to record one.", feature_dir.path.display()))?;
+    let feature = state.feature_by_id(&feature_id)
+        .with_context(|| format!("Feature {} is not in the project state", feature_id))?
+        .clone();
+
+    let config = Config::current()?;
+    let head = match state.worktrees.iter().find(|w| w.feature_id == feature_id) {
+        Some(worktree) => worktree.branch.clone(),
+        None => implement::branch_name(&config, &layout, &phase_dir.dir_name, &feature_dir.dir_name)?,
+    };
+    let repo = Repository::open(utils::project_root()?).context("Failed to open the Git repository")?;
+    if repo.find_branch(&head, BranchType::Local).is_err() {
+        anyhow::bail!("Branch '{}' does not exist. Run /implement {} first.", head, feature_id);
+    }
+    let base = option("--base")
+        .or_else(|| git::base_branch(&repo, config.git.base_branch.as_deref()))
+        .filter(|base| *base != head)
+        .context("Could not tell which branch to open the pull request against. Pass --base or set git.base_branch")?;
+
+    let plan_md = fs::read_to_string(feature_dir.path.join("plan.md")).unwrap_or_default();
+    let map = SyncMap::load(&SyncMap::path(&utils::dot_doplan_dir()?))?;
+    let issue = map.issues.get(&feature_id).copied();
+    let body = github::pull_request_body(&feature, &plan_md, issue);
+    let title = format!("{}: {}", feature.id, feature.name);
+
+    let client = super::github::client(&args, &map)?;
+    let report = github::open_pull_request(&client, &head, &base, &title, &body, draft)
+        .await
+        .with_context(|| format!("Failed to open a pull request on {}", client.repo()))?;
+
+    if let Some(feature) = state.feature_by_id_mut(&feature_id) {
+        feature.pull_request = Some(PullRequest {
+            number: report.number,
+            url: report.url.clone(),
+        });
+    }
+    state.save().context("Failed to save project state")?;
+
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
+    println!("{}", format!("  Pull Request: {}", title).bright_cyan().bold());
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
+    println!();
+    let action = if report.created { "Opened" } else { "Updated" };
+    println!("{}", format!("✓ {} #{}: {} → {}", action, report.number, head, base).bright_green());
+    if !report.url.is_empty() {
+        println!("  {}", report.url.bright_white());
+    }
+    if let Some(number) = issue {
+        println!("  {}", format!("Closes issue #{}", number).bright_white());
+    }
+    println!();
+    Ok(())
+}
@@ -1,50 +1,70 @@
 use anyhow::{Context, Result};
 use colored::*;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::fs;
-use std::collections::HashMap;
-use walkdir::WalkDir;
 use serde_json::{json, Value};
-use crate::state::ProjectState;
+use crate::config::Config;
+use crate::state::weighting::weighted_mean;
+use crate::state::{tasks_md, ProgressWeighting, ProjectState, Task, TaskStatus};
 use crate::utils;
+use crate::utils::history::{self, FeatureSnapshot, PhaseSnapshot, Snapshot, TaskCounts};
+use crate::utils::plan_dirs::PlanDirs;
+use crate::utils::velocity::Forecast;
 
+/// Progress of one feature, computed from its `tasks.md`
 #[derive(Debug, Clone)]
-struct TaskStats {
-    total: usize,
-    completed: usize,
-    in_progress: usize,
-    not_started: usize,
-    blocked: usize,
+pub struct FeatureProgress {
+    pub feature_id: String,
+    pub feature_name: String,
+    pub feature_dir: PathBuf,
+    pub phase_name: String,
+    pub priority: String,
+    pub progress: f64,
+    /// Weight within the phase under the active weighting mode
+    pub weight: f64,
+    pub status: String,
+    pub tasks: TaskCounts,
 }
 
 #[derive(Debug, Clone)]
-struct FeatureProgress {
-    feature_name: String,
-    #[allow(dead_code)]
-    phase_name: String,
-    priority: String,
-    progress: f64,
-    status: String,
-    tasks: TaskStats,
+pub struct PhaseProgress {
+    pub phase_id: String,
+    pub phase_dir: PathBuf,
+    pub phase_name: String,
+    pub progress: f64,
+    /// Weight within the project under the active weighting mode
+    pub weight: f64,
+    pub features: Vec<FeatureProgress>,
 }
 
-#[derive(Debug, Clone)]
-struct PhaseProgress {
-    phase_name: String,
-    progress: f64,
-    features: Vec<FeatureProgress>,
+impl PhaseProgress {
+    pub fn is_completed(&self) -> bool {
+        self.features.iter().all(|f| f.status == "completed")
+    }
 }
 
 /// Execute the /progress command
-pub async fn execute(_args: Vec<String>) -> Result<()> {
+///
+/// `--weighting count|estimate|priority` overrides the configured
+/// `progress.weighting` for this run. Task references in commit messages are
+/// applied first (see [`super::commits`]) unless `--no-commits` is given.
+pub async fn execute(args: Vec<String>) -> Result<()> {
     println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
     println!("{}", "  DoPlan: Progress Update".bright_cyan().bold());
     println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
     println!();
 
     // Load state
-    let state = ProjectState::load()
+    let mut state = ProjectState::load()
         .context("Failed to load project state")?;
+    let config = Config::current()?;
+    let bar_width = config.display.progress_bar_width;
+    let weighting = match args.iter().position(|a| a == "--weighting").and_then(|i| args.get(i + 1)) {
+        Some(mode) => ProgressWeighting::parse(mode).ok_or_else(|| {
+            anyhow::anyhow!("Unknown weighting mode '{}' (expected count, estimate or priority)", mode)
+        })?,
+        None => config.progress.weighting,
+    };
 
     let doplan_dir = utils::doplan_dir()?;
     let plan_dir = doplan_dir.join("plan");
@@ -54,167 +74,41 @@ pub async fn execute(_args: Vec<String>) -> Result<()> {
         return Ok(());
     }
 
-    println!("{}", "Scanning features and calculating progress...".bright_yellow());
-    println!();
-
-    // Scan all features and calculate progress
-    let mut phase_progress_map: HashMap<String, Vec<FeatureProgress>> = HashMap::new();
-
-    for entry in WalkDir::new(&plan_dir)
-        .min_depth(2)
-        .max_depth(4)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        if entry.file_name() == "tasks.md" {
-            let tasks_path = entry.path();
-            let feature_dir = tasks_path.parent().unwrap();
-
-            // Extract phase and feature names
-            let relative_path = feature_dir
-                .strip_prefix(&plan_dir)
-                .unwrap()
-                .to_string_lossy()
-                .to_string();
-            let path_parts: Vec<&str> = relative_path.split('/').collect();
-
-            if path_parts.len() >= 2 {
-                let phase_name = extract_name_from_path(path_parts[0]);
-                let feature_name = extract_name_from_path(path_parts[1]);
-
-                // Read tasks.md and calculate progress
-                if let Ok(content) = fs::read_to_string(tasks_path) {
-                    let task_stats = count_tasks(&content);
-                    let progress = if task_stats.total > 0 {
-                        (task_stats.completed as f64 / task_stats.total as f64) * 100.0
-                    } else {
-                        0.0
-                    };
-
-                    // Determine status
-                    let status = if progress == 100.0 {
-                        "completed"
-                    } else if task_stats.in_progress > 0 {
-                        "in_progress"
-                    } else if task_stats.blocked > 0 {
-                        "blocked"
-                    } else {
-                        "not_started"
-                    };
-
-                    // Read priority from progress.json if exists
-                    let progress_path = feature_dir.join("progress.json");
-                    let priority = if progress_path.exists() {
-                        read_priority(&progress_path).unwrap_or_else(|| "medium".to_string())
-                    } else {
-                        "medium".to_string()
-                    };
-
-                    let feature_progress = FeatureProgress {
-                        feature_name: feature_name.clone(),
-                        phase_name: phase_name.clone(),
-                        priority,
-                        progress,
-                        status: status.to_string(),
-                        tasks: task_stats,
-                    };
-
-                    phase_progress_map
-                        .entry(phase_name)
-                        .or_insert_with(Vec::new)
-                        .push(feature_progress);
-                }
+    if !args.iter().any(|a| a == "--no-commits") {
+        if let Some(changes) = super::commits::sync(&mut state)? {
+            if !changes.is_empty() {
+                println!("{}", "Task updates from commits:".bright_cyan());
+                super::commits::print_changes(&changes);
+                println!();
             }
         }
     }
 
-    // Update feature progress.json files
-    println!("{}", "Updating feature progress files...".bright_cyan());
-    for (phase_name, features) in &phase_progress_map {
-        // Find phase directory by scanning
-        let phase_dirs: Vec<_> = fs::read_dir(&plan_dir)
-            .context("Failed to read plan directory")?
-            .filter_map(|e| e.ok())
-            .filter(|e| {
-                let name = e.file_name().to_string_lossy().to_string();
-                extract_name_from_path(&name) == *phase_name
-            })
-            .collect();
-
-        if let Some(phase_entry) = phase_dirs.first() {
-            let phase_dir = phase_entry.path();
-            
-            for feature in features {
-                // Find feature directory by scanning
-                let feature_dirs: Vec<_> = fs::read_dir(&phase_dir)
-                    .ok()
-                    .map(|entries| {
-                        entries
-                            .filter_map(|e| e.ok())
-                            .filter(|e| {
-                                let name = e.file_name().to_string_lossy().to_string();
-                                extract_name_from_path(&name) == feature.feature_name
-                            })
-                            .collect()
-                    })
-                    .unwrap_or_default();
+    println!("{}", "Scanning features and calculating progress...".bright_yellow());
+    println!();
 
-                if let Some(feature_entry) = feature_dirs.first() {
-                    let feature_dir = feature_entry.path();
-                    update_feature_progress(&feature_dir, feature)?;
-                    println!("  {} Updated: {}/{}", "→".bright_cyan(), phase_name, feature.feature_name);
-                }
-            }
+    let (phases, forecast) = regenerate(&plan_dir, &mut state, weighting)?;
+
+    println!("{}", "Updating feature progress files...".bright_cyan());
+    for phase in &phases {
+        for feature in &phase.features {
+            println!("  {} Updated: {}/{}", "→".bright_cyan(), phase.phase_name, feature.feature_name);
         }
     }
 
-    // Calculate phase progress and update phase-progress.json
     println!();
     println!("{}", "Updating phase progress files...".bright_cyan());
-    let mut phases: Vec<PhaseProgress> = Vec::new();
-    
-    for (phase_name, features) in &phase_progress_map {
-        let phase_progress = if !features.is_empty() {
-            features.iter().map(|f| f.progress).sum::<f64>() / features.len() as f64
-        } else {
-            0.0
-        };
-
-        // Find phase directory by scanning
-        let phase_dirs: Vec<_> = fs::read_dir(&plan_dir)
-            .context("Failed to read plan directory")?
-            .filter_map(|e| e.ok())
-            .filter(|e| {
-                let name = e.file_name().to_string_lossy().to_string();
-                extract_name_from_path(&name) == *phase_name
-            })
-            .collect();
-
-        if let Some(phase_entry) = phase_dirs.first() {
-            let phase_dir = phase_entry.path();
-            update_phase_progress(&phase_dir, phase_name, &features, phase_progress)?;
-            println!("  {} Updated: {}", "→".bright_cyan(), phase_name);
-        }
-
-        phases.push(PhaseProgress {
-            phase_name: phase_name.clone(),
-            progress: phase_progress,
-            features: features.clone(),
-        });
+    for phase in &phases {
+        println!("  {} Updated: {}", "→".bright_cyan(), phase.phase_name);
     }
 
-    // Generate dashboard
     println!();
     println!("{}", "Generating dashboard...".bright_cyan());
-    generate_dashboard(&state, &phases)?;
+    println!("  {} Progress snapshot recorded", "→".bright_green());
     println!("  {} Dashboard generated", "→".bright_green());
 
     // Calculate overall progress
-    let overall_progress = if !phases.is_empty() {
-        phases.iter().map(|p| p.progress).sum::<f64>() / phases.len() as f64
-    } else {
-        0.0
-    };
+    let overall_progress = overall_progress(&phases);
 
     println!();
     println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_green());
@@ -222,10 +116,11 @@ pub async fn execute(_args: Vec<String>) -> Result<()> {
     println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_green());
     println!();
     println!("{}", format!("Overall Progress: {:.1}%", overall_progress).bright_white().bold());
+    println!("{}", format!("Weighting: {}", weighting.as_str()).bright_white());
     println!();
     println!("{}", "Phase Progress:".bright_cyan());
     for phase in &phases {
-        let bar = generate_progress_bar(phase.progress);
+        let bar = generate_progress_bar(phase.progress, bar_width);
         println!("  {} {}: {:.1}% {}", 
             "→".bright_cyan(),
             phase.phase_name,
@@ -233,74 +128,194 @@ pub async fn execute(_args: Vec<String>) -> Result<()> {
             bar
         );
     }
+    if let Some(forecast) = &forecast {
+        println!();
+        match &forecast.velocity {
+            Some(velocity) => println!("{}", format!("Velocity: {:.1} tasks/day ({:.1}/week)", velocity.tasks_per_day, velocity.tasks_per_week()).bright_white()),
+            None => println!("{}", "Velocity: not enough history yet".bright_white()),
+        }
+        println!("{}", format!("Projected completion: {}", forecast.project.describe()).bright_white());
+    }
     println!();
     println!("{}", "Files updated:".bright_cyan());
     println!("  • Feature progress.json files");
     println!("  • Phase progress.json files");
     println!("  • .doplan/dashboard.json");
+    println!("  • .doplan/history.jsonl");
     println!("  • doplan/dashboard.md");
     println!();
 
     Ok(())
 }
 
-fn count_tasks(content: &str) -> TaskStats {
-    let mut total = 0;
-    let mut completed = 0;
-    let mut in_progress = 0;
-    let mut not_started = 0;
-    let mut blocked = 0;
-
-    let lines: Vec<&str> = content.lines().collect();
-    let mut in_task = false;
-    let mut current_status = "not_started";
-
-    for line in lines {
-        if line.starts_with("#### Task") {
-            // Count previous task
-            if in_task {
-                total += 1;
-                match current_status {
-                    "completed" => completed += 1,
-                    "in_progress" => in_progress += 1,
-                    "blocked" => blocked += 1,
-                    _ => not_started += 1,
-                }
-            }
-            in_task = true;
-            current_status = "not_started";
-        } else if in_task && line.contains("**Status**") {
-            if line.contains("[x] Completed") || line.contains("[X] Completed") {
-                current_status = "completed";
-            } else if line.contains("[x] In Progress") || line.contains("[X] In Progress") {
-                current_status = "in_progress";
-            } else if line.contains("[x] Blocked") || line.contains("[X] Blocked") {
-                current_status = "blocked";
-            }
+/// Recompute progress from the plan and rewrite every `progress.json`, the
+/// state, the history and the dashboard, without printing anything
+pub fn regenerate(
+    plan_dir: &Path,
+    state: &mut ProjectState,
+    weighting: ProgressWeighting,
+) -> Result<(Vec<PhaseProgress>, Option<Forecast>)> {
+    let phases = collect(plan_dir, state, weighting)?;
+
+    for phase in &phases {
+        for feature in &phase.features {
+            update_feature_progress(&feature.feature_dir, feature)?;
+        }
+        // Calculate phase progress and update phase-progress.json
+        update_phase_progress(phase)?;
+    }
+
+    if state.features.is_some() {
+        state.save()
+            .context("Failed to save project state")?;
+    }
+
+    let history_path = history::history_path(&utils::dot_doplan_dir()?);
+    history::append(&history_path, &snapshot(&phases))?;
+    let forecast = Forecast::from_history(&history::load(&history_path)?, chrono::Utc::now().date_naive());
+    generate_dashboard(state, &phases, weighting, forecast.as_ref())?;
+
+    Ok((phases, forecast))
+}
+
+/// Progress at this moment, for `.doplan/history.jsonl`
+fn snapshot(phases: &[PhaseProgress]) -> Snapshot {
+    let mut tasks = TaskCounts::default();
+    let mut phase_snapshots = Vec::new();
+    let mut feature_snapshots = Vec::new();
+
+    for phase in phases {
+        let mut phase_tasks = TaskCounts::default();
+        for feature in &phase.features {
+            phase_tasks += feature.tasks;
+            feature_snapshots.push(FeatureSnapshot {
+                id: feature.feature_id.clone(),
+                name: feature.feature_name.clone(),
+                phase_id: phase.phase_id.clone(),
+                progress: feature.progress,
+                status: feature.status.clone(),
+                tasks: feature.tasks,
+            });
         }
+        tasks += phase_tasks;
+        phase_snapshots.push(PhaseSnapshot {
+            id: phase.phase_id.clone(),
+            name: phase.phase_name.clone(),
+            progress: phase.progress,
+            tasks: phase_tasks,
+        });
     }
 
-    // Count last task
-    if in_task {
-        total += 1;
-        match current_status {
-            "completed" => completed += 1,
-            "in_progress" => in_progress += 1,
-            "blocked" => blocked += 1,
-            _ => not_started += 1,
+    Snapshot {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        overall_progress: overall_progress(phases),
+        tasks,
+        phases: phase_snapshots,
+        features: feature_snapshots,
+    }
+}
+
+pub fn overall_progress(phases: &[PhaseProgress]) -> f64 {
+    weighted_mean(phases.iter().map(|p| (p.progress, p.weight)))
+}
+
+/// Calculate the progress of every planned feature and phase from the
+/// `tasks.md` files, keyed by the ids recorded in the plan directories rather
+/// than by directory names. The task lists in `state` are synced to the files
+/// along the way.
+pub fn collect(plan_dir: &Path, state: &mut ProjectState, weighting: ProgressWeighting) -> Result<Vec<PhaseProgress>> {
+    let layout = PlanDirs::scan(plan_dir, state)?;
+    let calendar = Config::current()?.calendar;
+    let mut phases: Vec<PhaseProgress> = Vec::new();
+
+    for phase_dir in &layout.phases {
+        let phase_name = phase_dir.id.as_deref()
+            .and_then(|id| state.phase_by_id(id))
+            .map(|p| p.name.clone())
+            .unwrap_or_else(|| extract_name_from_path(&phase_dir.dir_name));
+
+        let mut features = Vec::new();
+        for feature_dir in &phase_dir.features {
+            let tasks_path = feature_dir.path.join("tasks.md");
+            let Ok(tasks) = tasks_md::read(&tasks_path) else {
+                continue;
+            };
+
+            let task_stats = count_tasks(&tasks);
+            let progress = weighting.feature_progress(&tasks, &calendar);
+
+            // Determine status
+            let status = if task_stats.total > 0 && task_stats.completed == task_stats.total {
+                "completed"
+            } else if task_stats.in_progress > 0 {
+                "in_progress"
+            } else if task_stats.blocked > 0 {
+                "blocked"
+            } else {
+                "not_started"
+            };
+
+            // Read priority from progress.json if exists
+            let progress_path = feature_dir.path.join("progress.json");
+            let priority = if progress_path.exists() {
+                read_priority(&progress_path).unwrap_or_else(|| "medium".to_string())
+            } else {
+                "medium".to_string()
+            };
+
+            let feature_id = feature_dir.id.clone().unwrap_or_default();
+            let feature_name = state.feature_by_id(&feature_id)
+                .map(|f| f.name.clone())
+                .unwrap_or_else(|| extract_name_from_path(&feature_dir.dir_name));
+
+            features.push(FeatureProgress {
+                feature_id: feature_id.clone(),
+                feature_name,
+                feature_dir: feature_dir.path.clone(),
+                phase_name: phase_name.clone(),
+                weight: weighting.feature_weight(&tasks, &priority, &calendar),
+                priority,
+                progress,
+                status: status.to_string(),
+                tasks: task_stats,
+            });
+
+            // Keep the state's task model in step with the edited file
+            if let Some(feature) = state.feature_by_id_mut(&feature_id) {
+                feature.tasks = tasks;
+            }
+        }
+
+        if features.is_empty() {
+            continue;
         }
+
+        phases.push(PhaseProgress {
+            phase_id: phase_dir.id.clone().unwrap_or_default(),
+            phase_dir: phase_dir.path.clone(),
+            phase_name,
+            progress: weighted_mean(features.iter().map(|f| (f.progress, f.weight))),
+            weight: weighting.phase_weight(features.iter().map(|f| f.weight)),
+            features,
+        });
     }
 
-    TaskStats {
-        total,
-        completed,
-        in_progress,
-        not_started,
-        blocked,
+    Ok(phases)
+}
+
+fn count_tasks(tasks: &[Task]) -> TaskCounts {
+    let count = |status: TaskStatus| tasks.iter().filter(|t| t.status == status).count();
+
+    TaskCounts {
+        total: tasks.len(),
+        completed: count(TaskStatus::Completed),
+        in_progress: count(TaskStatus::InProgress),
+        not_started: count(TaskStatus::NotStarted),
+        blocked: count(TaskStatus::Blocked),
     }
 }
 
-fn read_priority(path: &PathBuf) -> Option<String> {
+fn read_priority(path: &Path) -> Option<String> {
     if let Ok(content) = fs::read_to_string(path) {
         if let Ok(progress) = serde_json::from_str::<Value>(&content) {
             return progress.get("priority")
@@ -311,15 +326,17 @@ fn read_priority(path: &PathBuf) -> Option<String> {
     None
 }
 
-fn update_feature_progress(feature_dir: &PathBuf, feature: &FeatureProgress) -> Result<()> {
+fn update_feature_progress(feature_dir: &Path, feature: &FeatureProgress) -> Result<()> {
     utils::ensure_dir(feature_dir)?;
     let progress_path = feature_dir.join("progress.json");
     
     let progress = json!({
+        "id": feature.feature_id,
         "feature": feature.feature_name,
         "priority": feature.priority,
         "status": feature.status,
         "progress": feature.progress,
+        "weight": feature.weight,
         "tasks": {
             "total": feature.tasks.total,
             "completed": feature.tasks.completed,
@@ -339,19 +356,16 @@ fn update_feature_progress(feature_dir: &PathBuf, feature: &FeatureProgress) ->
     Ok(())
 }
 
-fn update_phase_progress(
-    phase_dir: &PathBuf,
-    phase_name: &str,
-    features: &[FeatureProgress],
-    progress: f64,
-) -> Result<()> {
-    let progress_path = phase_dir.join("phase-progress.json");
+fn update_phase_progress(phase: &PhaseProgress) -> Result<()> {
+    let progress_path = phase.phase_dir.join("phase-progress.json");
+    let features = &phase.features;
+    let progress = phase.progress;
 
     let completed = features.iter().filter(|f| f.status == "completed").count();
     let in_progress = features.iter().filter(|f| f.status == "in_progress").count();
     let not_started = features.iter().filter(|f| f.status == "not_started").count();
 
-    let status = if progress == 100.0 {
+    let status = if phase.is_completed() {
         "completed"
     } else if in_progress > 0 {
         "in_progress"
@@ -360,9 +374,11 @@ fn update_phase_progress(
     };
 
     let progress_data = json!({
-        "phase": phase_name,
+        "id": phase.phase_id,
+        "phase": phase.phase_name,
         "status": status,
         "progress": progress,
+        "weight": phase.weight,
         "features": {
             "total": features.len(),
             "completed": completed,
@@ -381,32 +397,35 @@ fn update_phase_progress(
     Ok(())
 }
 
-fn generate_dashboard(state: &ProjectState, phases: &[PhaseProgress]) -> Result<()> {
-    let doplan_dir = utils::doplan_dir()?;
-    let dot_doplan = utils::dot_doplan_dir()?;
-    
-    // Calculate overall progress
-    let overall_progress = if !phases.is_empty() {
-        phases.iter().map(|p| p.progress).sum::<f64>() / phases.len() as f64
-    } else {
-        0.0
-    };
-
-    // Generate dashboard.json
-    let dashboard_json_path = dot_doplan.join("dashboard.json");
-    let dashboard_json = json!({
+/// The contents of `.doplan/dashboard.json`
+pub fn dashboard_json(
+    state: &ProjectState,
+    phases: &[PhaseProgress],
+    weighting: ProgressWeighting,
+    forecast: Option<&Forecast>,
+) -> Value {
+    let overall_progress = overall_progress(phases);
+    json!({
         "project_name": state.project_name.as_ref().unwrap_or(&"Untitled Project".to_string()),
         "overall_progress": overall_progress,
+        "weighting": {
+            "mode": weighting.as_str(),
+            "description": weighting.description()
+        },
         "phases": phases.iter().map(|p| {
             json!({
+                "id": p.phase_id,
                 "name": p.phase_name,
                 "progress": p.progress,
-                "status": if p.progress == 100.0 { "completed" } else if p.progress > 0.0 { "in_progress" } else { "not_started" },
+                "weight": p.weight,
+                "status": if p.is_completed() { "completed" } else if p.progress > 0.0 { "in_progress" } else { "not_started" },
                 "features": p.features.iter().map(|f| {
                     json!({
+                        "id": f.feature_id,
                         "name": f.feature_name,
                         "priority": f.priority,
                         "progress": f.progress,
+                        "weight": f.weight,
                         "status": f.status,
                         "tasks": {
                             "total": f.tasks.total,
@@ -419,8 +438,37 @@ fn generate_dashboard(state: &ProjectState, phases: &[PhaseProgress]) -> Result<
                 }).collect::<Vec<_>>()
             })
         }).collect::<Vec<_>>(),
+        "forecast": forecast.map(|f| f.to_json()),
         "updated_at": chrono::Utc::now().to_rfc3339()
-    });
+    })
+}
+
+/// Dashboard data for the plan as it is on disk now, without writing any
+/// files or recording history
+pub fn current_dashboard(plan_dir: &Path, state: &mut ProjectState) -> Result<Value> {
+    let weighting = Config::current()?.progress.weighting;
+    let phases = if plan_dir.exists() { collect(plan_dir, state, weighting)? } else { Vec::new() };
+    let snapshots = history::load(&history::history_path(&utils::dot_doplan_dir()?))?;
+    let forecast = Forecast::from_history(&snapshots, chrono::Utc::now().date_naive());
+    Ok(dashboard_json(state, &phases, weighting, forecast.as_ref()))
+}
+
+fn generate_dashboard(
+    state: &ProjectState,
+    phases: &[PhaseProgress],
+    weighting: ProgressWeighting,
+    forecast: Option<&Forecast>,
+) -> Result<()> {
+    let doplan_dir = utils::doplan_dir()?;
+    let dot_doplan = utils::dot_doplan_dir()?;
+    let bar_width = Config::current()?.display.progress_bar_width;
+    
+    // Calculate overall progress
+    let overall_progress = overall_progress(phases);
+
+    // Generate dashboard.json
+    let dashboard_json_path = dot_doplan.join("dashboard.json");
+    let dashboard_json = dashboard_json(state, phases, weighting, forecast);
 
     let json_content = serde_json::to_string_pretty(&dashboard_json)
         .context("Failed to serialize dashboard JSON")?;
@@ -440,13 +488,14 @@ fn generate_dashboard(state: &ProjectState, phases: &[PhaseProgress]) -> Result<
 
     md_content.push_str("## Overall Progress\n\n");
     md_content.push_str(&format!("**{:.1}%** Complete\n\n", overall_progress));
-    md_content.push_str(&format!("{}\n\n", generate_progress_bar(overall_progress)));
+    md_content.push_str(&format!("_Weighting: {} ({})_\n\n", weighting.as_str(), weighting.description().to_lowercase()));
+    md_content.push_str(&format!("{}\n\n", generate_progress_bar(overall_progress, bar_width)));
 
     md_content.push_str("## Phase Progress\n\n");
     for phase in phases {
         md_content.push_str(&format!("### {}\n\n", phase.phase_name));
         md_content.push_str(&format!("**{:.1}%** Complete\n\n", phase.progress));
-        md_content.push_str(&format!("{}\n\n", generate_progress_bar(phase.progress)));
+        md_content.push_str(&format!("{}\n\n", generate_progress_bar(phase.progress, bar_width)));
 
         md_content.push_str("#### Features\n\n");
         for feature in &phase.features {
@@ -457,7 +506,7 @@ fn generate_dashboard(state: &ProjectState, phases: &[PhaseProgress]) -> Result<
                 feature.status
             ));
         }
-        md_content.push_str("\n");
+        md_content.push('\n');
     }
 
     md_content.push_str("## Task Summary\n\n");
@@ -489,7 +538,13 @@ fn generate_dashboard(state: &ProjectState, phases: &[PhaseProgress]) -> Result<
     if blocked_tasks > 0 {
         md_content.push_str(&format!("- **Blocked:** {}\n", blocked_tasks));
     }
-    md_content.push_str("\n");
+    md_content.push('\n');
+
+    md_content.push_str("## Forecast\n\n");
+    match forecast {
+        Some(forecast) => md_content.push_str(&forecast_markdown(forecast)),
+        None => md_content.push_str("_No progress history yet._\n\n"),
+    }
 
     fs::write(&dashboard_md_path, md_content)
         .context("Failed to write dashboard markdown")?;
@@ -497,9 +552,39 @@ fn generate_dashboard(state: &ProjectState, phases: &[PhaseProgress]) -> Result<
     Ok(())
 }
 
-fn generate_progress_bar(progress: f64) -> String {
-    let width = 30;
-    let filled = (progress / 100.0 * width as f64) as usize;
+fn forecast_markdown(forecast: &Forecast) -> String {
+    let mut md = String::new();
+    match &forecast.velocity {
+        Some(velocity) => {
+            md.push_str(&format!(
+                "- **Velocity:** {:.1} tasks/day ({:.1} tasks/week, last {} days)\n",
+                velocity.tasks_per_day,
+                velocity.tasks_per_week(),
+                velocity.window_days
+            ));
+        }
+        None => md.push_str("- **Velocity:** not enough history yet (run /progress on at least two days)\n"),
+    }
+    md.push_str(&format!("- **Projected Completion:** {}\n\n", forecast.project.describe()));
+
+    if !forecast.phases.is_empty() {
+        md.push_str("| Phase | Remaining Tasks | Projected Finish |\n");
+        md.push_str("|-------|-----------------|------------------|\n");
+        for phase in &forecast.phases {
+            md.push_str(&format!(
+                "| {} | {} | {} |\n",
+                phase.name,
+                phase.projection.remaining_tasks,
+                phase.projection.describe()
+            ));
+        }
+        md.push('\n');
+    }
+    md
+}
+
+fn generate_progress_bar(progress: f64, width: usize) -> String {
+    let filled = ((progress / 100.0 * width as f64) as usize).min(width);
     let empty = width - filled;
     format!("[{}{}]", "█".repeat(filled), "░".repeat(empty))
 }
@@ -509,19 +594,6 @@ fn extract_name_from_path(path: &str) -> String {
         .skip(1)
         .collect::<Vec<&str>>()
         .join(" ")
-        .replace("-", " ")
-}
-
-#[allow(dead_code)]
-fn sanitize_name(name: &str) -> String {
-    name.to_lowercase()
-        .replace(" ", "-")
-        .replace("_", "-")
-        .chars()
-        .filter(|c| c.is_alphanumeric() || *c == '-')
-        .collect::<String>()
-        .trim_matches('-')
-        .to_string()
 }
 
 
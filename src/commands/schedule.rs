@@ -0,0 +1,384 @@
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use colored::*;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use crate::config::Config;
+use crate::state::deps::{DependencyGraph, NodeRef};
+use crate::state::{ProjectState, TaskStatus, WorkingCalendar};
+use crate::utils;
+use crate::utils::plan_dirs;
+
+/// A task placed on the schedule. Times are working hours from the start date.
+#[derive(Debug, Clone)]
+pub struct ScheduledTask {
+    pub node: NodeRef,
+    pub title: String,
+    pub status: TaskStatus,
+    /// Remaining work; zero for completed tasks
+    pub hours: f64,
+    /// Whether `hours` came from a parsed estimate rather than the default
+    pub estimated: bool,
+    pub earliest_start: f64,
+    pub earliest_finish: f64,
+    /// How long the task can slip without moving the projected completion
+    pub slack: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct PhaseForecast {
+    pub phase_id: String,
+    pub phase_name: String,
+    pub remaining_hours: f64,
+    pub earliest_finish: f64,
+    pub finish_date: NaiveDate,
+}
+
+/// Critical-path schedule computed from task estimates and dependencies.
+///
+/// Tasks that do not depend on each other are assumed to run in parallel, so
+/// the projected completion is driven by the longest dependency chain.
+#[derive(Debug, Clone)]
+pub struct Schedule {
+    pub start: NaiveDate,
+    pub calendar: WorkingCalendar,
+    pub tasks: Vec<ScheduledTask>,
+    pub critical_path: Vec<NodeRef>,
+    pub phases: Vec<PhaseForecast>,
+    pub remaining_hours: f64,
+    pub critical_path_hours: f64,
+    pub projected_completion: NaiveDate,
+}
+
+impl Schedule {
+    pub fn compute(state: &ProjectState, calendar: &WorkingCalendar, start: NaiveDate) -> Result<Self> {
+        state.validate_dependencies()?;
+        let calendar = calendar.clone();
+        let graph = DependencyGraph::build(state);
+
+        let mut tasks: Vec<ScheduledTask> = Vec::new();
+        for feature in state.features.iter().flatten() {
+            for task in &feature.tasks {
+                let (hours, estimated) = calendar.task_hours(task.estimate.as_deref());
+                tasks.push(ScheduledTask {
+                    node: NodeRef::Task { feature: feature.id.clone(), task: task.id.clone() },
+                    title: task.title.clone(),
+                    status: task.status,
+                    hours: if task.status == TaskStatus::Completed { 0.0 } else { hours },
+                    estimated,
+                    earliest_start: 0.0,
+                    earliest_finish: 0.0,
+                    slack: 0.0,
+                });
+            }
+        }
+
+        let index: BTreeMap<NodeRef, usize> = tasks.iter().enumerate().map(|(i, t)| (t.node.clone(), i)).collect();
+        let prerequisites: Vec<Vec<usize>> = tasks
+            .iter()
+            .map(|t| graph.task_prerequisites(&t.node).iter().filter_map(|p| index.get(p).copied()).collect())
+            .collect();
+
+        // Forward pass. The graph is acyclic, so resolving prerequisites
+        // depth-first always terminates; `topo` records tasks in the order
+        // they were resolved, prerequisites first.
+        let mut finish: Vec<Option<f64>> = vec![None; tasks.len()];
+        let mut topo = Vec::with_capacity(tasks.len());
+        fn earliest_finish(
+            i: usize,
+            tasks: &[ScheduledTask],
+            prerequisites: &[Vec<usize>],
+            finish: &mut Vec<Option<f64>>,
+            topo: &mut Vec<usize>,
+        ) -> f64 {
+            if let Some(done) = finish[i] {
+                return done;
+            }
+            let start = prerequisites[i]
+                .iter()
+                .map(|&p| earliest_finish(p, tasks, prerequisites, finish, topo))
+                .fold(0.0, f64::max);
+            let done = start + tasks[i].hours;
+            finish[i] = Some(done);
+            topo.push(i);
+            done
+        }
+        for i in 0..tasks.len() {
+            let done = earliest_finish(i, &tasks, &prerequisites, &mut finish, &mut topo);
+            tasks[i].earliest_finish = done;
+            tasks[i].earliest_start = done - tasks[i].hours;
+        }
+
+        let critical_path_hours = tasks.iter().map(|t| t.earliest_finish).fold(0.0, f64::max);
+
+        // Backward pass: latest finish is bounded by the latest start of every successor
+        let mut latest_finish = vec![critical_path_hours; tasks.len()];
+        for &i in topo.iter().rev() {
+            let latest_start = latest_finish[i] - tasks[i].hours;
+            for &p in &prerequisites[i] {
+                latest_finish[p] = latest_finish[p].min(latest_start);
+            }
+        }
+        for (i, task) in tasks.iter_mut().enumerate() {
+            task.slack = (latest_finish[i] - task.earliest_finish).max(0.0);
+        }
+
+        // Walk back from the last task to finish along the prerequisite that
+        // finishes last
+        let mut critical_path = Vec::new();
+        let mut current = (0..tasks.len())
+            .filter(|&i| tasks[i].hours > 0.0)
+            .max_by(|a, b| tasks[*a].earliest_finish.total_cmp(&tasks[*b].earliest_finish));
+        while let Some(i) = current {
+            critical_path.push(tasks[i].node.clone());
+            current = prerequisites[i]
+                .iter()
+                .copied()
+                .filter(|&p| tasks[p].hours > 0.0)
+                .max_by(|a, b| tasks[*a].earliest_finish.total_cmp(&tasks[*b].earliest_finish));
+        }
+        critical_path.reverse();
+
+        let phases = state
+            .phases
+            .iter()
+            .flatten()
+            .map(|phase| {
+                let members: Vec<&ScheduledTask> = tasks
+                    .iter()
+                    .filter(|t| matches!(&t.node, NodeRef::Task { feature, .. } if phase.features.contains(feature)))
+                    .collect();
+                let earliest_finish = members.iter().map(|t| t.earliest_finish).fold(0.0, f64::max);
+                PhaseForecast {
+                    phase_id: phase.id.clone(),
+                    phase_name: phase.name.clone(),
+                    remaining_hours: members.iter().map(|t| t.hours).sum(),
+                    earliest_finish,
+                    finish_date: calendar.finish_date(start, earliest_finish),
+                }
+            })
+            .collect();
+
+        Ok(Schedule {
+            start,
+            remaining_hours: tasks.iter().map(|t| t.hours).sum(),
+            critical_path_hours,
+            projected_completion: calendar.finish_date(start, critical_path_hours),
+            calendar,
+            tasks,
+            critical_path,
+            phases,
+        })
+    }
+
+    pub fn is_critical(&self, node: &NodeRef) -> bool {
+        self.critical_path.contains(node)
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "start": self.start.to_string(),
+            "calendar": {
+                "hours_per_day": self.calendar.hours_per_day,
+                "work_days": self.calendar.work_days,
+                "hours_per_point": self.calendar.hours_per_point,
+                "default_task_hours": self.calendar.default_task_hours
+            },
+            "remaining_hours": self.remaining_hours,
+            "critical_path_hours": self.critical_path_hours,
+            "projected_completion": self.projected_completion.to_string(),
+            "critical_path": self.critical_path.iter().map(|n| n.to_string()).collect::<Vec<_>>(),
+            "phases": self.phases.iter().map(|p| json!({
+                "id": p.phase_id,
+                "name": p.phase_name,
+                "remaining_hours": p.remaining_hours,
+                "earliest_finish_hours": p.earliest_finish,
+                "projected_finish": p.finish_date.to_string()
+            })).collect::<Vec<_>>(),
+            "tasks": self.tasks.iter().map(|t| json!({
+                "id": t.node.to_string(),
+                "title": t.title,
+                "status": t.status.as_str(),
+                "hours": t.hours,
+                "estimated": t.estimated,
+                "earliest_start_hours": t.earliest_start,
+                "earliest_finish_hours": t.earliest_finish,
+                "slack_hours": t.slack,
+                "critical": self.is_critical(&t.node)
+            })).collect::<Vec<_>>(),
+            "generated_at": chrono::Utc::now().to_rfc3339()
+        })
+    }
+
+    pub fn to_markdown(&self, project_name: &str) -> String {
+        let mut md = String::new();
+        md.push_str("# Schedule Forecast\n\n");
+        md.push_str(&format!("**Project:** {}\n\n", project_name));
+        md.push_str(&format!("**Start:** {}\n\n", self.start));
+        md.push_str(&format!(
+            "**Working Time:** {}h/day, {}\n\n",
+            self.calendar.hours_per_day,
+            self.calendar.work_days.join(", ")
+        ));
+        md.push_str("---\n\n");
+
+        md.push_str("## Summary\n\n");
+        md.push_str(&format!("- **Remaining Work:** {:.1}h\n", self.remaining_hours));
+        md.push_str(&format!("- **Critical Path:** {:.1}h\n", self.critical_path_hours));
+        md.push_str(&format!("- **Projected Completion:** {}\n\n", self.projected_completion));
+        md.push_str("_Tasks that do not depend on each other are assumed to run in parallel._\n\n");
+
+        md.push_str("## Critical Path\n\n");
+        if self.critical_path.is_empty() {
+            md.push_str("_No remaining work_\n\n");
+        } else {
+            for (i, node) in self.critical_path.iter().enumerate() {
+                if let Some(task) = self.tasks.iter().find(|t| t.node == *node) {
+                    md.push_str(&format!(
+                        "{}. {} {} ({:.1}h, done by {})\n",
+                        i + 1,
+                        node,
+                        task.title,
+                        task.hours,
+                        self.calendar.finish_date(self.start, task.earliest_finish)
+                    ));
+                }
+            }
+            md.push('\n');
+        }
+
+        md.push_str("## Phase Forecast\n\n");
+        md.push_str("| Phase | Remaining | Earliest Finish | Projected Finish |\n");
+        md.push_str("|-------|-----------|-----------------|------------------|\n");
+        for phase in &self.phases {
+            md.push_str(&format!(
+                "| {} | {:.1}h | {:.1}h | {} |\n",
+                phase.phase_name, phase.remaining_hours, phase.earliest_finish, phase.finish_date
+            ));
+        }
+        md.push('\n');
+
+        md.push_str("## Tasks\n\n");
+        md.push_str("| Task | Title | Status | Hours | Start | Finish | Slack |\n");
+        md.push_str("|------|-------|--------|-------|-------|--------|-------|\n");
+        for task in &self.tasks {
+            md.push_str(&format!(
+                "| {}{} | {} | {} | {:.1}{} | {:.1} | {:.1} | {:.1} |\n",
+                task.node,
+                if self.is_critical(&task.node) { " ★" } else { "" },
+                task.title,
+                task.status.label(),
+                task.hours,
+                if task.estimated { "" } else { "*" },
+                task.earliest_start,
+                task.earliest_finish,
+                task.slack
+            ));
+        }
+        md.push('\n');
+        if self.tasks.iter().any(|t| !t.estimated && t.status != TaskStatus::Completed) {
+            md.push_str(&format!(
+                "\\* No usable estimate; assumed {}h.\n\n",
+                self.calendar.default_task_hours
+            ));
+        }
+
+        md
+    }
+}
+
+/// Execute the schedule command
+///
+/// Options: `--start YYYY-MM-DD` (defaults to today), `--hours-per-day N` to
+/// override the configured calendar for one run, and `--json` to print the
+/// JSON forecast instead of the summary.
+pub async fn execute(args: Vec<String>) -> Result<()> {
+    let option = |name: &str| {
+        args.iter()
+            .position(|a| a == name)
+            .and_then(|i| args.get(i + 1))
+            .cloned()
+    };
+    let json_output = args.iter().any(|a| a == "--json");
+
+    let mut state = ProjectState::load()
+        .context("Failed to load project state")?;
+
+    let mut calendar = Config::current()?.calendar;
+    if let Some(hours) = option("--hours-per-day") {
+        calendar.hours_per_day = hours.trim().parse::<f64>()
+            .ok()
+            .filter(|h| *h > 0.0 && *h <= 24.0)
+            .with_context(|| format!("Invalid --hours-per-day value: {} (expected more than 0 and at most 24)", hours))?;
+    }
+    let start = match option("--start") {
+        Some(date) => NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+            .with_context(|| format!("Invalid --start date (expected YYYY-MM-DD): {}", date))?,
+        None => chrono::Local::now().date_naive(),
+    };
+
+    let doplan_dir = utils::doplan_dir()?;
+    let plan_dir = doplan_dir.join("plan");
+    if !plan_dir.exists() {
+        println!("{}", "No plan structure found. Run /plan first.".bright_yellow());
+        return Ok(());
+    }
+    plan_dirs::sync_tasks(&plan_dir, &mut state)?;
+
+    let schedule = Schedule::compute(&state, &calendar, start)
+        .context("Failed to compute schedule")?;
+
+    let project_name = state.project_name.clone().unwrap_or_else(|| "Untitled Project".to_string());
+    let json_path = utils::dot_doplan_dir()?.join("schedule.json");
+    let json_content = serde_json::to_string_pretty(&schedule.to_json())
+        .context("Failed to serialize schedule")?;
+    fs::write(&json_path, &json_content)
+        .context("Failed to write schedule JSON")?;
+    let md_path = doplan_dir.join("schedule.md");
+    fs::write(&md_path, schedule.to_markdown(&project_name))
+        .context("Failed to write schedule report")?;
+
+    if json_output {
+        println!("{}", json_content);
+        return Ok(());
+    }
+
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
+    println!("{}", "  DoPlan: Schedule Forecast".bright_cyan().bold());
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
+    println!();
+    println!("{}", format!("Remaining work: {:.1}h", schedule.remaining_hours).bright_white());
+    println!("{}", format!("Critical path: {:.1}h", schedule.critical_path_hours).bright_white());
+    println!("{}", format!("Projected completion: {}", schedule.projected_completion).bright_green().bold());
+    println!();
+
+    if !schedule.critical_path.is_empty() {
+        println!("{}", "Critical path:".bright_cyan());
+        for node in &schedule.critical_path {
+            if let Some(task) = schedule.tasks.iter().find(|t| t.node == *node) {
+                println!("  {} {} {} ({:.1}h)", "→".bright_cyan(), node, task.title.bright_white(), task.hours);
+            }
+        }
+        println!();
+    }
+
+    println!("{}", "Phase forecast:".bright_cyan());
+    for phase in &schedule.phases {
+        println!("  {} {}: {:.1}h remaining, done by {}", "→".bright_cyan(), phase.phase_name, phase.remaining_hours, phase.finish_date);
+    }
+    println!();
+
+    let unestimated = schedule.tasks.iter().filter(|t| !t.estimated && t.status != TaskStatus::Completed).count();
+    if unestimated > 0 {
+        println!("{}", format!("⚠ {} task(s) have no usable estimate; assumed {}h each", unestimated, schedule.calendar.default_task_hours).bright_yellow());
+        println!();
+    }
+
+    println!("{}", "Files updated:".bright_cyan());
+    println!("  • doplan/schedule.md");
+    println!("  • .doplan/schedule.json");
+    println!();
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use anyhow::{Context, Result};
+use colored::*;
+use tokio::net::TcpListener;
+use crate::server::{self, watch};
+use crate::state::ProjectState;
+
+/// Serve the REST API and web dashboard for the current project.
+///
+/// Usage: `doplan server [--port 7878] [--host 127.0.0.1] [--allow-host NAME]...`.
+/// `--allow-host` names the hosts or addresses other machines use to reach a
+/// server bound to `0.0.0.0`. See [`crate::server`] for the routes.
+pub async fn execute(args: Vec<String>) -> Result<()> {
+    let option = |name: &str| {
+        args.iter()
+            .position(|a| a == name)
+            .and_then(|i| args.get(i + 1))
+            .cloned()
+    };
+    let port = match option("--port") {
+        Some(port) => port.parse::<u16>()
+            .with_context(|| format!("Invalid --port value: {}", port))?,
+        None => server::DEFAULT_PORT,
+    };
+    let host = option("--host").unwrap_or_else(|| "127.0.0.1".to_string());
+    let allowed_hosts: Vec<String> = args
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i > 0 && args[i - 1] == "--allow-host")
+        .flat_map(|(_, value)| value.split(',').map(|v| v.trim().to_string()))
+        .filter(|v| !v.is_empty())
+        .collect();
+
+    // Fail early rather than serving errors for every request
+    ProjectState::load()
+        .context("Failed to load project state")?;
+
+    let listener = TcpListener::bind((host.as_str(), port))
+        .await
+        .with_context(|| format!("Failed to bind {}:{}", host, port))?;
+    let address = listener.local_addr()?;
+
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
+    println!("{}", "  DoPlan Server".bright_cyan().bold());
+    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan());
+    println!();
+    println!("  {} http://{}/", "Dashboard:".bright_white(), address);
+    println!("  {} http://{}/api", "API:".bright_white(), address);
+    println!();
+    println!("{}", "Press Ctrl+C to stop.".bright_black());
+
+    server::serve(listener, watch::POLL_INTERVAL, allowed_hosts).await
+}
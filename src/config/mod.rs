@@ -1,3 +1,654 @@
-// Configuration management module
-// Will be implemented later
+//! Layered configuration.
+//!
+//! Each layer overrides the one before it:
+//!
+//! 1. built-in defaults ([`Config::default`])
+//! 2. the user file `~/.config/doplan/config.toml` (under `$XDG_CONFIG_HOME`
+//!    when that is set)
+//! 3. the project file `doplan.toml` in the project root
+//! 4. environment variables named after the key: `llm.base_url` is read from
+//!    `DOPLAN_LLM_BASE_URL`. Lists are comma-separated; empty variables are
+//!    ignored.
+//!
+//! ```toml
+//! [paths]
+//! docs_dir = "doplan"
+//! state_dir = ".doplan"
+//!
+//! [git]
+//! branch_template = "{type}/{phase_id}-phase-{feature_id}-{feature_slug}"
+//! branch_suffix = ""
+//!
+//! [plan]
+//! priorities = ["high", "medium", "low"]
+//!
+//! [display]
+//! progress_bar_width = 30
+//!
+//! [progress]
+//! weighting = "count"
+//!
+//! [calendar]
+//! hours_per_day = 8.0
+//! work_days = ["mon", "tue", "wed", "thu", "fri"]
+//!
+//! [llm]
+//! base_url = "http://localhost:11434/v1"
+//! model = "llama3.1"
+//! ```
+//!
+//! [`KEYS`] lists every setting. `doplan config list` shows the resolved
+//! values and the layer each one came from.
 
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use crate::error::DoPlanError;
+use crate::integration::git;
+use crate::integration::github;
+use crate::integration::llm::openai;
+use crate::state::{ProgressWeighting, WorkingCalendar};
+
+pub const PROJECT_FILE: &str = "doplan.toml";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub paths: PathsConfig,
+    pub git: GitConfig,
+    pub plan: PlanConfig,
+    pub display: DisplayConfig,
+    pub progress: ProgressConfig,
+    /// Working time used by `doplan schedule` and estimate weighting
+    pub calendar: WorkingCalendar,
+    pub discuss: DiscussConfig,
+    pub llm: LlmConfig,
+    pub github: GithubConfig,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PathsConfig {
+    /// Documents and the plan tree, relative to the project root
+    pub docs_dir: String,
+    /// State and caches, relative to the project root
+    pub state_dir: String,
+}
+
+impl Default for PathsConfig {
+    fn default() -> Self {
+        Self { docs_dir: "doplan".to_string(), state_dir: ".doplan".to_string() }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GitConfig {
+    /// Feature branch name; see [`git::BRANCH_PLACEHOLDERS`]
+    pub branch_template: String,
+    /// Value of `{type}` in the template
+    pub branch_type: String,
+    /// Appended to every feature branch name
+    pub branch_suffix: String,
+    /// Branch feature branches start from; defaults to `origin/HEAD`, then
+    /// the current branch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_branch: Option<String>,
+    /// Where `/implement --worktree` puts worktrees, relative to the project
+    /// root; defaults to `worktrees` under `paths.state_dir`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub worktree_dir: Option<String>,
+}
+
+impl Default for GitConfig {
+    fn default() -> Self {
+        Self {
+            branch_template: git::DEFAULT_BRANCH_TEMPLATE.to_string(),
+            branch_type: "feature".to_string(),
+            branch_suffix: String::new(),
+            base_branch: None,
+            worktree_dir: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PlanConfig {
+    /// Feature priority levels, most urgent first
+    pub priorities: Vec<String>,
+}
+
+impl Default for PlanConfig {
+    fn default() -> Self {
+        Self { priorities: ["high", "medium", "low"].map(String::from).to_vec() }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DisplayConfig {
+    /// Characters in the progress bars of `/progress` and the dashboard
+    pub progress_bar_width: usize,
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        Self { progress_bar_width: 30 }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProgressConfig {
+    /// How `/progress` weights tasks, features and phases into percentages
+    pub weighting: ProgressWeighting,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DiscussConfig {
+    /// Choices offered by the tech stack step of `/discuss`
+    pub tech_options: Vec<String>,
+}
+
+impl Default for DiscussConfig {
+    fn default() -> Self {
+        let options = [
+            "Frontend: React/Next.js",
+            "Frontend: Vue/Nuxt",
+            "Frontend: Svelte/SvelteKit",
+            "Backend: Node.js/Express",
+            "Backend: Rust/Axum",
+            "Backend: Python/FastAPI",
+            "Backend: Go/Gin",
+            "Database: PostgreSQL",
+            "Database: MongoDB",
+            "Database: SQLite",
+            "Database: Redis",
+            "Deployment: Vercel",
+            "Deployment: Railway",
+            "Deployment: Docker",
+        ];
+        Self { tech_options: options.map(String::from).to_vec() }
+    }
+}
+
+/// OpenAI-compatible provider; see [`crate::integration::llm`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LlmConfig {
+    /// Setting this enables the provider
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    pub model: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+}
+
+impl Default for LlmConfig {
+    fn default() -> Self {
+        Self { base_url: None, model: openai::DEFAULT_MODEL.to_string(), api_key: None }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GithubConfig {
+    pub api_url: String,
+    /// `owner/name`; defaults to the `origin` remote
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repo: Option<String>,
+}
+
+impl Default for GithubConfig {
+    fn default() -> Self {
+        Self { api_url: github::DEFAULT_API_URL.to_string(), repo: None }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    Text,
+    Integer,
+    Float,
+    List,
+}
+
+pub struct Key {
+    pub name: &'static str,
+    pub kind: ValueKind,
+    /// Hidden by `config list` and only written to the user file
+    pub secret: bool,
+}
+
+const fn key(name: &'static str, kind: ValueKind) -> Key {
+    Key { name, kind, secret: false }
+}
+
+pub const KEYS: &[Key] = &[
+    key("paths.docs_dir", ValueKind::Text),
+    key("paths.state_dir", ValueKind::Text),
+    key("git.branch_template", ValueKind::Text),
+    key("git.branch_type", ValueKind::Text),
+    key("git.branch_suffix", ValueKind::Text),
+    key("git.base_branch", ValueKind::Text),
+    key("git.worktree_dir", ValueKind::Text),
+    key("plan.priorities", ValueKind::List),
+    key("display.progress_bar_width", ValueKind::Integer),
+    key("progress.weighting", ValueKind::Text),
+    key("calendar.hours_per_day", ValueKind::Float),
+    key("calendar.work_days", ValueKind::List),
+    key("calendar.hours_per_point", ValueKind::Float),
+    key("calendar.default_task_hours", ValueKind::Float),
+    key("discuss.tech_options", ValueKind::List),
+    key("llm.base_url", ValueKind::Text),
+    key("llm.model", ValueKind::Text),
+    Key { name: "llm.api_key", kind: ValueKind::Text, secret: true },
+    key("github.api_url", ValueKind::Text),
+    key("github.repo", ValueKind::Text),
+];
+
+pub fn find_key(name: &str) -> Result<&'static Key> {
+    KEYS.iter().find(|key| key.name == name).ok_or_else(|| {
+        let known: Vec<&str> = KEYS.iter().map(|key| key.name).collect();
+        config_error(format!("unknown key '{}' (expected one of: {})", name, known.join(", ")))
+    })
+}
+
+/// Environment variable that overrides `key`
+pub fn env_var(key: &str) -> String {
+    format!("DOPLAN_{}", key.replace('.', "_").to_uppercase())
+}
+
+/// Where a resolved value came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Default,
+    User(PathBuf),
+    Project(PathBuf),
+    Env(String),
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Default => write!(f, "default"),
+            Source::User(path) | Source::Project(path) => write!(f, "{}", path.display()),
+            Source::Env(var) => write!(f, "${}", var),
+        }
+    }
+}
+
+/// A loaded configuration and the layer behind each key
+pub struct Resolved {
+    pub config: Config,
+    pub sources: BTreeMap<&'static str, Source>,
+}
+
+impl Config {
+    /// The configuration for the project in the current directory
+    pub fn current() -> Result<Config> {
+        Self::load(&crate::utils::project_root()?)
+    }
+
+    /// The configuration for the project in `project_root`
+    pub fn load(project_root: &Path) -> Result<Config> {
+        Ok(Self::resolve(project_root)?.config)
+    }
+
+    pub fn resolve(project_root: &Path) -> Result<Resolved> {
+        let mut table = to_table(&Config::default())?;
+        let mut sources: BTreeMap<&'static str, Source> = KEYS.iter().map(|key| (key.name, Source::Default)).collect();
+
+        let mut files = Vec::new();
+        if let Some(path) = user_config_path() {
+            files.push((Source::User(path.clone()), path));
+        }
+        let project_path = project_root.join(PROJECT_FILE);
+        files.push((Source::Project(project_path.clone()), project_path));
+        for (source, path) in files {
+            if let Some(layer) = read_layer(&path)? {
+                merge(&mut table, layer, &mut sources, &source);
+            }
+        }
+
+        for key in KEYS {
+            let var = env_var(key.name);
+            let Ok(raw) = std::env::var(&var) else {
+                continue;
+            };
+            if raw.trim().is_empty() {
+                continue;
+            }
+            let value = parse_value(key, &raw).map_err(|e| config_error(format!("${}: {}", var, e)))?;
+            set_value(&mut table, key.name, value);
+            sources.insert(key.name, Source::Env(var));
+        }
+
+        let config: Config = toml::Value::Table(table)
+            .try_into()
+            .map_err(|e| config_error(e.to_string()))?;
+        config.validate()?;
+        Ok(Resolved { config, sources })
+    }
+
+    /// Check values that parse but make no sense
+    pub fn validate(&self) -> Result<()> {
+        for (key, dir) in [("paths.docs_dir", &self.paths.docs_dir), ("paths.state_dir", &self.paths.state_dir)] {
+            let path = Path::new(dir);
+            if dir.trim().is_empty() || path.is_absolute() || path.components().any(|c| c == std::path::Component::ParentDir) {
+                return Err(config_error(format!("{}: '{}' must be a directory inside the project", key, dir)));
+            }
+        }
+        if self.paths.docs_dir == self.paths.state_dir {
+            return Err(config_error("paths.docs_dir and paths.state_dir must differ".to_string()));
+        }
+        if let Some(c) = self.git.branch_suffix.chars().find(|c| c.is_whitespace() || "~^:?*[\\".contains(*c)) {
+            return Err(config_error(format!("git.branch_suffix: '{}' is not allowed in a branch name", c)));
+        }
+        let sample = git::BranchVars { kind: self.git.branch_type.clone(), ..git::BranchVars::sample() };
+        git::render_branch(&format!("{}{}", self.git.branch_template, self.git.branch_suffix), &sample)
+            .map_err(|e| config_error(format!("git.branch_template: {}", e)))?;
+        if let Some(base) = &self.git.base_branch {
+            if !git2::Branch::name_is_valid(base).unwrap_or(false) {
+                return Err(config_error(format!("git.base_branch: '{}' is not a valid branch name", base)));
+            }
+        }
+        if self.plan.priorities.is_empty() {
+            return Err(config_error("plan.priorities: needs at least one level".to_string()));
+        }
+        for (i, priority) in self.plan.priorities.iter().enumerate() {
+            if priority.is_empty() || !priority.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-') {
+                return Err(config_error(format!("plan.priorities: '{}' must be lowercase letters, digits or '-'", priority)));
+            }
+            if self.plan.priorities[..i].contains(priority) {
+                return Err(config_error(format!("plan.priorities: '{}' is listed twice", priority)));
+            }
+        }
+        if !(5..=200).contains(&self.display.progress_bar_width) {
+            return Err(config_error(format!(
+                "display.progress_bar_width: {} is outside 5..=200",
+                self.display.progress_bar_width
+            )));
+        }
+        let calendar = &self.calendar;
+        if !(calendar.hours_per_day > 0.0 && calendar.hours_per_day <= 24.0) {
+            return Err(config_error(format!("calendar.hours_per_day: {} is outside (0, 24]", calendar.hours_per_day)));
+        }
+        if calendar.work_days.is_empty() {
+            return Err(config_error("calendar.work_days: needs at least one day".to_string()));
+        }
+        if let Some(day) = calendar.work_days.iter().find(|d| d.parse::<chrono::Weekday>().is_err()) {
+            return Err(config_error(format!("calendar.work_days: '{}' is not a weekday (mon, tue, ...)", day)));
+        }
+        for (key, hours) in [
+            ("calendar.hours_per_point", calendar.hours_per_point),
+            ("calendar.default_task_hours", calendar.default_task_hours),
+        ] {
+            if !(hours.is_finite() && hours > 0.0) {
+                return Err(config_error(format!("{}: {} must be a positive number of hours", key, hours)));
+            }
+        }
+        if self.discuss.tech_options.is_empty() {
+            return Err(config_error("discuss.tech_options: needs at least one option".to_string()));
+        }
+        for (key, url) in [("llm.base_url", self.llm.base_url.as_deref()), ("github.api_url", Some(self.github.api_url.as_str()))] {
+            if let Some(url) = url {
+                if !(url.starts_with("http://") || url.starts_with("https://")) {
+                    return Err(config_error(format!("{}: '{}' is not an http(s) URL", key, url)));
+                }
+            }
+        }
+        if let Some(repo) = &self.github.repo {
+            if repo.split('/').filter(|part| !part.is_empty()).count() != 2 {
+                return Err(config_error(format!("github.repo: '{}' is not owner/name", repo)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Display form of `key`: lists comma-separated, `None` for unset keys
+    pub fn get(&self, key: &str) -> Result<Option<String>> {
+        find_key(key)?;
+        let table = to_table(self)?;
+        let (section, name) = key.split_once('.').unwrap_or(("", key));
+        Ok(table.get(section).and_then(|s| s.get(name)).map(|value| match value {
+            toml::Value::String(text) => text.clone(),
+            toml::Value::Array(items) => items
+                .iter()
+                .map(|item| item.as_str().map_or_else(|| item.to_string(), str::to_string))
+                .collect::<Vec<_>>()
+                .join(", "),
+            other => other.to_string(),
+        }))
+    }
+
+    /// Directory that holds the worktrees of `/implement --worktree`
+    pub fn worktree_dir(&self, project_root: &Path) -> PathBuf {
+        match &self.git.worktree_dir {
+            Some(dir) => project_root.join(dir),
+            None => project_root.join(&self.paths.state_dir).join("worktrees"),
+        }
+    }
+
+    /// The level between the most and least urgent, used when none is chosen
+    pub fn middle_priority(&self) -> &str {
+        &self.plan.priorities[self.plan.priorities.len() / 2]
+    }
+
+    /// Priority given to a feature listed `index`th without one: the first
+    /// three get the first level, the next three the second, and so on
+    pub fn default_priority(&self, index: usize) -> &str {
+        let levels = &self.plan.priorities;
+        &levels[(index / 3).min(levels.len() - 1)]
+    }
+}
+
+/// `~/.config/doplan/config.toml`, or under `$XDG_CONFIG_HOME`
+pub fn user_config_path() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME")
+                .or_else(|| std::env::var_os("USERPROFILE"))
+                .map(|home| PathBuf::from(home).join(".config"))
+        })?;
+    Some(base.join("doplan").join("config.toml"))
+}
+
+/// Set `key` to `value` in the config file at `path`, keeping its comments
+/// and layout. An empty value removes the key so lower layers apply again.
+pub fn set_in_file(path: &Path, key: &str, value: &str) -> Result<()> {
+    let info = find_key(key)?;
+    let content = if path.exists() {
+        std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?
+    } else {
+        String::new()
+    };
+    let mut document: toml_edit::DocumentMut = content
+        .parse()
+        .map_err(|e| config_error(format!("{}: {}", path.display(), e)))?;
+
+    let (section, name) = key.split_once('.').unwrap_or(("", key));
+    if value.trim().is_empty() {
+        if let Some(table) = document.get_mut(section).and_then(|item| item.as_table_like_mut()) {
+            table.remove(name);
+        }
+    } else {
+        let edited = match parse_value(info, value).map_err(|e| config_error(format!("{}: {}", key, e)))? {
+            toml::Value::String(text) => toml_edit::value(text),
+            toml::Value::Integer(number) => toml_edit::value(number),
+            toml::Value::Float(number) => toml_edit::value(number),
+            toml::Value::Array(items) => {
+                toml_edit::value(items.iter().filter_map(|item| item.as_str()).collect::<toml_edit::Array>())
+            }
+            other => toml_edit::value(other.to_string()),
+        };
+        if !document.contains_key(section) {
+            document.insert(section, toml_edit::table());
+        }
+        document[section][name] = edited;
+    }
+
+    // The file on its own must still load over the defaults
+    let content = document.to_string();
+    let layer: toml::Table = toml::from_str(&content).map_err(|e| config_error(format!("{}: {}", path.display(), e)))?;
+    let mut table = to_table(&Config::default())?;
+    merge(&mut table, layer, &mut BTreeMap::new(), &Source::Default);
+    let config: Config = toml::Value::Table(table)
+        .try_into()
+        .map_err(|e| config_error(format!("{}: {}", path.display(), e)))?;
+    config.validate()?;
+
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    std::fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+fn config_error(message: String) -> anyhow::Error {
+    DoPlanError::Config(message).into()
+}
+
+fn to_table(config: &Config) -> Result<toml::Table> {
+    match toml::Value::try_from(config).context("Failed to serialize configuration")? {
+        toml::Value::Table(table) => Ok(table),
+        _ => unreachable!("Config serializes to a table"),
+    }
+}
+
+/// A config file, checked on its own so errors name the file
+fn read_layer(path: &Path) -> Result<Option<toml::Table>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let layer: toml::Table = toml::from_str(&content).map_err(|e| config_error(format!("{}: {}", path.display(), e)))?;
+    Config::deserialize(toml::Value::Table(layer.clone())).map_err(|e| config_error(format!("{}: {}", path.display(), e)))?;
+    Ok(Some(layer))
+}
+
+fn merge(table: &mut toml::Table, layer: toml::Table, sources: &mut BTreeMap<&'static str, Source>, source: &Source) {
+    for (section, values) in layer {
+        let toml::Value::Table(values) = values else {
+            continue;
+        };
+        for (name, value) in values {
+            let key = format!("{}.{}", section, name);
+            set_value(table, &key, value);
+            if let Some(known) = KEYS.iter().find(|k| k.name == key) {
+                sources.insert(known.name, source.clone());
+            }
+        }
+    }
+}
+
+fn set_value(table: &mut toml::Table, key: &str, value: toml::Value) {
+    let (section, name) = key.split_once('.').unwrap_or(("", key));
+    if let toml::Value::Table(section) = table
+        .entry(section)
+        .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+    {
+        section.insert(name.to_string(), value);
+    }
+}
+
+fn parse_value(key: &Key, raw: &str) -> std::result::Result<toml::Value, String> {
+    match key.kind {
+        ValueKind::Text => Ok(toml::Value::String(raw.trim().to_string())),
+        ValueKind::Integer => raw
+            .trim()
+            .parse::<i64>()
+            .map(toml::Value::Integer)
+            .map_err(|_| format!("expected a whole number, got '{}'", raw)),
+        ValueKind::Float => raw
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|number| number.is_finite())
+            .map(toml::Value::Float)
+            .ok_or_else(|| format!("expected a number, got '{}'", raw)),
+        ValueKind::List => Ok(toml::Value::Array(
+            raw.split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| toml::Value::String(item.to_string()))
+                .collect(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_are_valid() {
+        let config = Config::default();
+        config.validate().unwrap();
+        assert_eq!(config.get("display.progress_bar_width").unwrap().as_deref(), Some("30"));
+        assert_eq!(config.get("plan.priorities").unwrap().as_deref(), Some("high, medium, low"));
+        assert_eq!(config.get("llm.base_url").unwrap(), None);
+        assert_eq!(config.get("progress.weighting").unwrap().as_deref(), Some("count"));
+        assert_eq!(config.get("calendar.hours_per_day").unwrap().as_deref(), Some("8.0"));
+        assert!(config.get("display.colour").is_err());
+        assert_eq!((config.default_priority(2), config.default_priority(3), config.default_priority(9)), ("high", "medium", "low"));
+        assert_eq!(config.middle_priority(), "medium");
+    }
+
+    #[test]
+    fn test_validation() {
+        let error = |edit: fn(&mut Config)| {
+            let mut config = Config::default();
+            edit(&mut config);
+            config.validate().unwrap_err().to_string()
+        };
+        assert_eq!(
+            error(|c| c.paths.docs_dir = "../docs".to_string()),
+            "Configuration error: paths.docs_dir: '../docs' must be a directory inside the project"
+        );
+        assert_eq!(
+            error(|c| c.git.branch_template = "{type}/{ticket}".to_string()),
+            "Configuration error: git.branch_template: unknown placeholder {ticket} (expected one of: type, phase_id, \
+             phase_slug, phase_key, feature_id, feature_slug, feature_key)"
+        );
+        assert_eq!(
+            error(|c| c.git.branch_type = "my work".to_string()),
+            "Configuration error: git.branch_template: 'my work/01-phase-01-user-auth' is not a valid branch name"
+        );
+        assert_eq!(
+            error(|c| c.git.branch_suffix = "-my app".to_string()),
+            "Configuration error: git.branch_suffix: ' ' is not allowed in a branch name"
+        );
+        assert_eq!(
+            error(|c| c.plan.priorities = vec!["p1".to_string(), "p1".to_string()]),
+            "Configuration error: plan.priorities: 'p1' is listed twice"
+        );
+        assert_eq!(
+            error(|c| c.display.progress_bar_width = 2),
+            "Configuration error: display.progress_bar_width: 2 is outside 5..=200"
+        );
+        assert_eq!(
+            error(|c| c.calendar.hours_per_day = 30.0),
+            "Configuration error: calendar.hours_per_day: 30 is outside (0, 24]"
+        );
+        assert_eq!(
+            error(|c| c.calendar.work_days = vec!["mon".to_string(), "funday".to_string()]),
+            "Configuration error: calendar.work_days: 'funday' is not a weekday (mon, tue, ...)"
+        );
+        assert_eq!(
+            error(|c| c.llm.base_url = Some("localhost:11434".to_string())),
+            "Configuration error: llm.base_url: 'localhost:11434' is not an http(s) URL"
+        );
+    }
+
+    #[test]
+    fn test_env_var_names() {
+        assert_eq!(env_var("llm.base_url"), "DOPLAN_LLM_BASE_URL");
+        assert_eq!(env_var("display.progress_bar_width"), "DOPLAN_DISPLAY_PROGRESS_BAR_WIDTH");
+    }
+}
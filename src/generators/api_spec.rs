@@ -20,8 +20,7 @@ pub fn generate(state: &ProjectState, _idea_notes: &Option<String>) -> Result<Pa
     utils::validate_write_path(&api_spec_path)
         .context("Invalid path for api-spec.json")?;
 
-    let project_name = state.project_name.as_ref()
-        .map(|s| s.as_str())
+    let project_name = state.project_name.as_deref()
         .unwrap_or("Untitled Project");
 
     // Create OpenAPI 3.0 specification
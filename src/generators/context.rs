@@ -15,8 +15,7 @@ pub fn generate(state: &ProjectState) -> Result<PathBuf> {
     utils::validate_write_path(&context_path)
         .context("Invalid path for CONTEXT.md")?;
 
-    let project_name = state.project_name.as_ref()
-        .map(|s| s.as_str())
+    let project_name = state.project_name.as_deref()
         .unwrap_or("Untitled Project");
 
     let mut content = String::new();
@@ -31,7 +30,7 @@ pub fn generate(state: &ProjectState) -> Result<PathBuf> {
             for tech in tech_stack {
                 content.push_str(&format!("- {}\n", tech));
             }
-            content.push_str("\n");
+            content.push('\n');
         }
     } else {
         content.push_str("_Technology stack to be defined_\n\n");
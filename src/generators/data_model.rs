@@ -19,8 +19,7 @@ pub fn generate(state: &ProjectState, _idea_notes: &Option<String>) -> Result<Pa
     utils::validate_write_path(&data_model_path)
         .context("Invalid path for data-model.md")?;
 
-    let project_name = state.project_name.as_ref()
-        .map(|s| s.as_str())
+    let project_name = state.project_name.as_deref()
         .unwrap_or("Untitled Project");
 
     let mut content = String::new();
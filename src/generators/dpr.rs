@@ -227,8 +227,7 @@ fn generate_dpr_md(
     components: &[String],
     cards: &[String],
 ) -> Result<()> {
-    let project_name = state.project_name.as_ref()
-        .map(|s| s.as_str())
+    let project_name = state.project_name.as_deref()
         .unwrap_or("Untitled Project");
 
     let mut content = String::new();
@@ -247,7 +246,7 @@ fn generate_dpr_md(
         for page in pages {
             content.push_str(&format!("- {}\n", page));
         }
-        content.push_str("\n");
+        content.push('\n');
     }
 
     // Sections
@@ -256,7 +255,7 @@ fn generate_dpr_md(
         for section in sections {
             content.push_str(&format!("- {}\n", section));
         }
-        content.push_str("\n");
+        content.push('\n');
     }
 
     // Components
@@ -265,7 +264,7 @@ fn generate_dpr_md(
         for component in components {
             content.push_str(&format!("- {}\n", component));
         }
-        content.push_str("\n");
+        content.push('\n');
     }
 
     // Cards/UI Elements
@@ -274,7 +273,7 @@ fn generate_dpr_md(
         for card in cards {
             content.push_str(&format!("- {}\n", card));
         }
-        content.push_str("\n");
+        content.push('\n');
     }
 
     // Design Principles
@@ -428,7 +427,7 @@ fn generate_design_rules(
         for page in pages {
             content.push_str(&format!("- {}\n", page));
         }
-        content.push_str("\n");
+        content.push('\n');
     }
 
     if !sections.is_empty() {
@@ -436,7 +435,7 @@ fn generate_design_rules(
         for section in sections {
             content.push_str(&format!("- {}\n", section));
         }
-        content.push_str("\n");
+        content.push('\n');
     }
 
     if !components.is_empty() {
@@ -444,7 +443,7 @@ fn generate_design_rules(
         for component in components {
             content.push_str(&format!("- {}\n", component));
         }
-        content.push_str("\n");
+        content.push('\n');
     }
 
     if !cards.is_empty() {
@@ -452,7 +451,7 @@ fn generate_design_rules(
         for card in cards {
             content.push_str(&format!("- {}\n", card));
         }
-        content.push_str("\n");
+        content.push('\n');
     }
 
     content.push_str("## Design Guidelines\n\n");
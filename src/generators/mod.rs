@@ -8,4 +8,5 @@ pub mod sops;
 pub mod rakd;
 pub mod context;
 pub mod readme;
+pub mod task_rules;
 
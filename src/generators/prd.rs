@@ -18,12 +18,10 @@ pub fn generate(state: &ProjectState, idea_notes: &Option<String>) -> Result<Pat
     utils::validate_write_path(&prd_path)
         .context("Invalid path for PRD.md")?;
 
-    let project_name = state.project_name.as_ref()
-        .map(|s| s.as_str())
+    let project_name = state.project_name.as_deref()
         .unwrap_or("Untitled Project");
 
-    let idea = state.idea.as_ref()
-        .map(|s| s.as_str())
+    let idea = state.idea.as_deref()
         .unwrap_or("No idea description provided");
 
     let mut content = String::new();
@@ -31,7 +29,7 @@ pub fn generate(state: &ProjectState, idea_notes: &Option<String>) -> Result<Pat
     // Header
     content.push_str("# Product Requirements Document (PRD)\n\n");
     content.push_str(&format!("**Project:** {}\n\n", project_name));
-    content.push_str(&format!("**Version:** 1.0\n"));
+    content.push_str("**Version:** 1.0\n");
     content.push_str(&format!("**Date:** {}\n\n", chrono::Utc::now().format("%Y-%m-%d")));
     content.push_str("---\n\n");
 
@@ -42,55 +40,21 @@ pub fn generate(state: &ProjectState, idea_notes: &Option<String>) -> Result<Pat
 
     // Problem Statement
     content.push_str("## Problem Statement\n\n");
-    if let Some(notes) = idea_notes {
-        // Try to extract problem from Q&A section
-        if let Some(problem_start) = notes.find("What problem does this solve?") {
-            if let Some(answer_start) = notes[problem_start..].find("\n\n") {
-                let problem_section = &notes[problem_start + answer_start + 2..];
-                if let Some(problem_end) = problem_section.find("\n\n###") {
-                    let problem = problem_section[..problem_end].trim();
-                    if !problem.is_empty() {
-                        content.push_str(&format!("{}\n\n", problem));
-                    } else {
-                        content.push_str("_To be defined based on project requirements_\n\n");
-                    }
-                } else {
-                    content.push_str("_To be defined based on project requirements_\n\n");
-                }
-            } else {
-                content.push_str("_To be defined based on project requirements_\n\n");
-            }
-        } else {
-            content.push_str("_To be defined based on project requirements_\n\n");
-        }
-    } else {
-        content.push_str("_To be defined based on project requirements_\n\n");
+    match discussion_answer(state, idea_notes, "problem", "What problem does this solve?") {
+        Some(problem) => content.push_str(&format!("{}\n\n", problem)),
+        None => content.push_str("_To be defined based on project requirements_\n\n"),
     }
 
     // Target Audience
     content.push_str("## Target Audience\n\n");
-    if let Some(notes) = idea_notes {
-        if let Some(audience_start) = notes.find("Who is your target audience?") {
-            if let Some(answer_start) = notes[audience_start..].find("\n\n") {
-                let audience_section = &notes[audience_start + answer_start + 2..];
-                if let Some(audience_end) = audience_section.find("\n\n###") {
-                    let audience = audience_section[..audience_end].trim();
-                    if !audience.is_empty() {
-                        content.push_str(&format!("{}\n\n", audience));
-                    } else {
-                        content.push_str("_To be defined_\n\n");
-                    }
-                } else {
-                    content.push_str("_To be defined_\n\n");
-                }
-            } else {
-                content.push_str("_To be defined_\n\n");
-            }
-        } else {
-            content.push_str("_To be defined_\n\n");
-        }
-    } else {
-        content.push_str("_To be defined_\n\n");
+    match discussion_answer(state, idea_notes, "target_audience", "Who is your target audience?") {
+        Some(audience) => content.push_str(&format!("{}\n\n", audience)),
+        None => content.push_str("_To be defined_\n\n"),
+    }
+
+    if let Some(unique) = state.answer("unique_value") {
+        content.push_str("## Unique Value\n\n");
+        content.push_str(&format!("{}\n\n", unique));
     }
 
     // Features
@@ -117,9 +81,17 @@ pub fn generate(state: &ProjectState, idea_notes: &Option<String>) -> Result<Pat
             for tech in tech_stack {
                 content.push_str(&format!("- {}\n", tech));
             }
-            content.push_str("\n");
+            content.push('\n');
         }
     }
+    if let Some(platforms) = state.answer("platforms") {
+        content.push_str("### Platforms\n\n");
+        content.push_str(&format!("{}\n\n", platforms));
+    }
+    if let Some(constraints) = state.answer("constraints") {
+        content.push_str("### Constraints\n\n");
+        content.push_str(&format!("{}\n\n", constraints));
+    }
     content.push_str("### Additional Requirements\n\n");
     content.push_str("- Cross-platform compatibility\n");
     content.push_str("- Responsive design\n");
@@ -144,7 +116,10 @@ pub fn generate(state: &ProjectState, idea_notes: &Option<String>) -> Result<Pat
             }
         }
     }
-    content.push_str("_Detailed timeline to be defined_\n\n");
+    match state.answer("timeline") {
+        Some(timeline) => content.push_str(&format!("**Target:** {}\n\n", timeline)),
+        None => content.push_str("_Detailed timeline to be defined_\n\n"),
+    }
 
     // Risks and Mitigation
     content.push_str("## Risks and Mitigation\n\n");
@@ -172,3 +147,18 @@ pub fn generate(state: &ProjectState, idea_notes: &Option<String>) -> Result<Pat
     Ok(prd_path)
 }
 
+
+/// Answer to a `/discuss` question from state, falling back to the Q&A
+/// section of `idea-notes.md` for projects discussed before answers were
+/// stored in state
+fn discussion_answer(state: &ProjectState, idea_notes: &Option<String>, key: &str, question: &str) -> Option<String> {
+    if let Some(answer) = state.answer(key) {
+        return Some(answer.to_string());
+    }
+    let notes = idea_notes.as_deref()?;
+    let section = &notes[notes.find(question)?..];
+    let answer_start = section.find("\n\n")? + 2;
+    let answer = &section[answer_start..];
+    let answer = answer[..answer.find("\n\n###")?].trim();
+    (!answer.is_empty()).then(|| answer.to_string())
+}
@@ -78,7 +78,7 @@ pub fn generate(state: &ProjectState) -> Result<PathBuf> {
             };
             content.push_str(&format!("- **{}**: {}\n", key, status));
         }
-        content.push_str("\n");
+        content.push('\n');
 
         content.push_str("## Configuration\n\n");
         content.push_str("Add the following to your `.env` file:\n\n");
@@ -15,12 +15,10 @@ pub fn generate(state: &ProjectState) -> Result<PathBuf> {
     utils::validate_write_path(&readme_path)
         .context("Invalid path for README.md")?;
 
-    let project_name = state.project_name.as_ref()
-        .map(|s| s.as_str())
+    let project_name = state.project_name.as_deref()
         .unwrap_or("Untitled Project");
 
-    let idea = state.idea.as_ref()
-        .map(|s| s.as_str())
+    let idea = state.idea.as_deref()
         .unwrap_or("No description available");
 
     let mut content = String::new();
@@ -35,7 +33,7 @@ pub fn generate(state: &ProjectState) -> Result<PathBuf> {
                 content.push_str(&format!("- **{}** ({}) - {}\n", 
                     feature.name, feature.priority, feature.description));
             }
-            content.push_str("\n");
+            content.push('\n');
         }
     } else {
         content.push_str("_Features to be defined_\n\n");
@@ -47,7 +45,7 @@ pub fn generate(state: &ProjectState) -> Result<PathBuf> {
             for tech in tech_stack {
                 content.push_str(&format!("- {}\n", tech));
             }
-            content.push_str("\n");
+            content.push('\n');
         }
     } else {
         content.push_str("_Technology stack to be defined_\n\n");
@@ -100,12 +98,13 @@ pub fn generate(state: &ProjectState) -> Result<PathBuf> {
             for (i, phase) in phases.iter().enumerate() {
                 content.push_str(&format!("### Phase {}: {}\n\n", i + 1, phase.name));
                 content.push_str(&format!("{}\n\n", phase.description));
-                if !phase.features.is_empty() {
+                let phase_features = state.phase_features(phase);
+                if !phase_features.is_empty() {
                     content.push_str("**Features:**\n");
-                    for feature_name in &phase.features {
-                        content.push_str(&format!("- {}\n", feature_name));
+                    for feature in phase_features {
+                        content.push_str(&format!("- {}\n", feature.name));
                     }
-                    content.push_str("\n");
+                    content.push('\n');
                 }
             }
         }
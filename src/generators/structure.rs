@@ -18,8 +18,7 @@ pub fn generate(state: &ProjectState, _idea_notes: &Option<String>) -> Result<Pa
     utils::validate_write_path(&structure_path)
         .context("Invalid path for structure.md")?;
 
-    let project_name = state.project_name.as_ref()
-        .map(|s| s.as_str())
+    let project_name = state.project_name.as_deref()
         .unwrap_or("Untitled Project");
 
     let mut content = String::new();
@@ -42,7 +41,7 @@ pub fn generate(state: &ProjectState, _idea_notes: &Option<String>) -> Result<Pa
             for tech in tech_stack {
                 content.push_str(&format!("- {}\n", tech));
             }
-            content.push_str("\n");
+            content.push('\n');
         }
     } else {
         content.push_str("_Technology stack to be defined_\n\n");
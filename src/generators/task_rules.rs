@@ -0,0 +1,297 @@
+//! Rule catalog that seeds the tasks of a new feature.
+//!
+//! The catalog is YAML: the built-in one is `task_rules.yaml` next to this
+//! file, and `/generate` copies it to `doplan/templates/task-rules.yaml` for
+//! the project to edit. See that file for the format. Projects without the
+//! file get only the setup, fallback and closing tasks, which are the three
+//! starter tasks DoPlan has always seeded.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use crate::state::estimate::Estimate;
+use crate::state::{AcceptanceCriterion, Feature, Task};
+
+pub const CATALOG_FILE: &str = "task-rules.yaml";
+
+/// The built-in catalog, also written out by `/generate`
+pub const DEFAULT_CATALOG: &str = include_str!("task_rules.yaml");
+
+const PRIORITIES: [&str; 3] = ["High", "Medium", "Low"];
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TaskCatalog {
+    #[serde(default)]
+    pub setup: Vec<TaskTemplate>,
+    #[serde(default)]
+    pub rules: Vec<TaskRule>,
+    #[serde(default)]
+    pub fallback: Vec<TaskTemplate>,
+    #[serde(default)]
+    pub closing: Vec<TaskTemplate>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TaskRule {
+    pub name: String,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    #[serde(default)]
+    pub tech: Vec<String>,
+    pub tasks: Vec<TaskTemplate>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TaskTemplate {
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default = "default_priority")]
+    pub priority: String,
+    pub estimate: Option<String>,
+    #[serde(default)]
+    pub acceptance_criteria: Vec<String>,
+}
+
+fn default_priority() -> String {
+    "Medium".to_string()
+}
+
+impl TaskRule {
+    /// Whether this rule applies to `feature` in a project built with `tech_stack`
+    pub fn matches(&self, feature: &Feature, tech_stack: &[String]) -> bool {
+        let text = format!("{} {}", feature.name, feature.description);
+        let stack = tech_stack.join(" ");
+        (self.keywords.is_empty() || self.keywords.iter().any(|k| contains_word(&text, k)))
+            && (self.tech.is_empty() || self.tech.iter().any(|t| starts_word(&stack, t)))
+    }
+}
+
+impl TaskCatalog {
+    /// Path of the project's catalog under `doplan/`
+    pub fn path(doplan_dir: &Path) -> PathBuf {
+        doplan_dir.join("templates").join(CATALOG_FILE)
+    }
+
+    pub fn builtin() -> Self {
+        Self::parse(DEFAULT_CATALOG).expect("built-in task catalog is valid")
+    }
+
+    /// The built-in catalog without its rules: Setup, Implementation and Testing
+    pub fn starter() -> Self {
+        Self { rules: Vec::new(), ..Self::builtin() }
+    }
+
+    /// The project's catalog, or [`starter`](Self::starter) when there is none
+    pub fn load(doplan_dir: &Path) -> Result<Self> {
+        let path = Self::path(doplan_dir);
+        if !path.exists() {
+            return Ok(Self::starter());
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&content).with_context(|| format!("Invalid task catalog {}", path.display()))
+    }
+
+    pub fn parse(content: &str) -> Result<Self> {
+        let catalog: Self = serde_yaml::from_str(content)?;
+        catalog.validate()?;
+        Ok(catalog)
+    }
+
+    fn validate(&self) -> Result<()> {
+        let check = |path: String, tasks: &[TaskTemplate]| -> Result<()> {
+            for (i, task) in tasks.iter().enumerate() {
+                if task.title.trim().is_empty() {
+                    anyhow::bail!("{}[{}]: title is empty", path, i);
+                }
+                if !PRIORITIES.iter().any(|p| p.eq_ignore_ascii_case(task.priority.trim())) {
+                    anyhow::bail!(
+                        "{}[{}] ({}): unknown priority '{}' (expected {})",
+                        path,
+                        i,
+                        task.title,
+                        task.priority,
+                        PRIORITIES.join(", ")
+                    );
+                }
+                if let Some(estimate) = &task.estimate {
+                    if Estimate::parse(estimate).is_none() {
+                        anyhow::bail!("{}[{}] ({}): '{}' is not an estimate", path, i, task.title, estimate);
+                    }
+                }
+            }
+            Ok(())
+        };
+
+        check("setup".to_string(), &self.setup)?;
+        for (i, rule) in self.rules.iter().enumerate() {
+            if rule.keywords.is_empty() && rule.tech.is_empty() {
+                anyhow::bail!("rules[{}] ({}): needs keywords, tech or both", i, rule.name);
+            }
+            check(format!("rules[{}].tasks", i), &rule.tasks)?;
+        }
+        check("fallback".to_string(), &self.fallback)?;
+        check("closing".to_string(), &self.closing)
+    }
+
+    /// The rules that apply to `feature`
+    pub fn matching_rules(&self, feature: &Feature, tech_stack: &[String]) -> Vec<&TaskRule> {
+        self.rules.iter().filter(|rule| rule.matches(feature, tech_stack)).collect()
+    }
+
+    /// Starter tasks for `feature`, numbered from `T1`.
+    ///
+    /// Setup tasks run in order; each matched rule then runs its own tasks in
+    /// order after setup, in parallel with the other rules; closing tasks wait
+    /// for the last task of every rule. A title already used is skipped.
+    pub fn tasks_for(&self, feature: &Feature, tech_stack: &[String]) -> Vec<Task> {
+        let rules = self.matching_rules(feature, tech_stack);
+        let mut groups: Vec<&[TaskTemplate]> = rules.iter().map(|rule| rule.tasks.as_slice()).collect();
+        if !rules.iter().any(|rule| !rule.keywords.is_empty()) {
+            groups.push(&self.fallback);
+        }
+
+        let mut builder = TaskListBuilder::default();
+        let setup_end = builder.chain(&self.setup, Vec::new());
+        let mut group_ends: Vec<String> = Vec::new();
+        for group in groups {
+            for id in builder.chain(group, setup_end.clone()) {
+                if !group_ends.contains(&id) {
+                    group_ends.push(id);
+                }
+            }
+        }
+        let before_closing = if group_ends.is_empty() { setup_end } else { group_ends };
+        builder.chain(&self.closing, before_closing);
+        builder.tasks
+    }
+}
+
+#[derive(Default)]
+struct TaskListBuilder {
+    tasks: Vec<Task>,
+    titles: HashSet<String>,
+}
+
+impl TaskListBuilder {
+    /// Add `templates` one after another, the first depending on `after`.
+    /// Returns the ids the next group should depend on.
+    fn chain(&mut self, templates: &[TaskTemplate], after: Vec<String>) -> Vec<String> {
+        let mut previous = after;
+        for template in templates {
+            if !self.titles.insert(template.title.trim().to_lowercase()) {
+                continue;
+            }
+            let id = format!("T{}", self.tasks.len() + 1);
+            let priority = PRIORITIES
+                .iter()
+                .find(|p| p.eq_ignore_ascii_case(template.priority.trim()))
+                .map_or("Medium", |p| *p);
+            self.tasks.push(Task {
+                priority: priority.to_string(),
+                description: template.description.trim().to_string(),
+                estimate: template.estimate.clone(),
+                acceptance_criteria: template
+                    .acceptance_criteria
+                    .iter()
+                    .map(|text| AcceptanceCriterion { text: text.clone(), done: false })
+                    .collect(),
+                depends_on: previous,
+                ..Task::new(id.clone(), template.title.trim())
+            });
+            previous = vec![id];
+        }
+        previous
+    }
+}
+
+/// Whether `term` occurs in `text` at the start of a word, ignoring case
+fn starts_word(text: &str, term: &str) -> bool {
+    word_matches(text, term, |_| true)
+}
+
+/// Whether `term`, or its plural, occurs in `text` as a whole word, ignoring case
+fn contains_word(text: &str, term: &str) -> bool {
+    word_matches(text, term, |rest| {
+        let rest = rest.strip_prefix("es").or_else(|| rest.strip_prefix('s')).map_or(rest, |r| {
+            if r.starts_with(char::is_alphanumeric) { rest } else { r }
+        });
+        !rest.starts_with(char::is_alphanumeric)
+    })
+}
+
+fn word_matches(text: &str, term: &str, ends_well: impl Fn(&str) -> bool) -> bool {
+    let text = text.to_lowercase();
+    let term = term.trim().to_lowercase();
+    !term.is_empty()
+        && text.match_indices(&term).any(|(i, _)| {
+            text[..i].chars().next_back().is_none_or(|c| !c.is_alphanumeric()) && ends_well(&text[i + term.len()..])
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feature(name: &str) -> Feature {
+        Feature { name: name.to_string(), description: format!("Feature: {}", name), ..Default::default() }
+    }
+
+    fn titles(tasks: &[Task]) -> Vec<&str> {
+        tasks.iter().map(|t| t.title.as_str()).collect()
+    }
+
+    #[test]
+    fn test_builtin_catalog() {
+        let catalog = TaskCatalog::builtin();
+
+        let tasks = catalog.tasks_for(&feature("Recipe Sharing"), &[]);
+        assert_eq!(titles(&tasks), ["Setup", "Implementation", "Testing"]);
+        assert_eq!(tasks[2].depends_on, ["T2"]);
+
+        let stack = vec!["Database: PostgreSQL".to_string()];
+        let tasks = catalog.tasks_for(&feature("User Login"), &stack);
+        assert_eq!(
+            titles(&tasks),
+            ["Setup", "Session handling", "Password reset", "Rate limiting", "Database migrations", "Testing"]
+        );
+        assert_eq!(tasks[1].depends_on, ["T1"]);
+        assert_eq!(tasks[2].depends_on, ["T2"]);
+        assert_eq!(tasks[4].depends_on, ["T1"]);
+        assert_eq!(tasks[5].depends_on, ["T4", "T5"]);
+
+        // Without the project's catalog, rules do not apply
+        let tasks = TaskCatalog::starter().tasks_for(&feature("User Login"), &stack);
+        assert_eq!(titles(&tasks), ["Setup", "Implementation", "Testing"]);
+
+        // Keywords are whole words or their plurals: "author" is not "auth"
+        assert!(catalog.matching_rules(&feature("Author Pages"), &[]).is_empty());
+        let rules: Vec<&str> = catalog
+            .matching_rules(&feature("Photo uploads and Reports"), &[])
+            .iter()
+            .map(|rule| rule.name.as_str())
+            .collect();
+        assert_eq!(rules, ["uploads", "reporting"]);
+    }
+
+    #[test]
+    fn test_catalog_validation() {
+        let error = |yaml: &str| format!("{:#}", TaskCatalog::parse(yaml).unwrap_err());
+        assert_eq!(
+            error("setup:\n  - title: Setup\n    estimate: soon\n"),
+            "setup[0] (Setup): 'soon' is not an estimate"
+        );
+        assert_eq!(
+            error("rules:\n  - name: x\n    tasks:\n      - title: A\n"),
+            "rules[0] (x): needs keywords, tech or both"
+        );
+        assert!(error("rules:\n  - name: x\n    keywords: [a]\n    tasks:\n      - title: A\n        priority: urgent\n")
+            .starts_with("rules[0].tasks[0] (A): unknown priority 'urgent'"));
+        assert!(error("stages: []\n").contains("unknown field `stages`"));
+    }
+}
@@ -1,15 +1,14 @@
 use anyhow::{Context, Result};
 use std::path::PathBuf;
+use crate::generators::task_rules::{self, TaskCatalog};
 use crate::utils;
 
 pub fn generate_all() -> Result<Vec<PathBuf>> {
-    let mut generated = Vec::new();
-
-    generated.push(generate_plan_template()?);
-    generated.push(generate_design_template()?);
-    generated.push(generate_tasks_template()?);
-
-    Ok(generated)
+    Ok(vec![
+        generate_plan_template()?,
+        generate_design_template()?,
+        generate_tasks_template()?,
+    ])
 }
 
 pub fn generate_plan_template() -> Result<PathBuf> {
@@ -281,3 +280,26 @@ Additional notes about tasks and progress.
     Ok(template_path)
 }
 
+/// Write the built-in task rules for the project to edit. An existing
+/// catalog is the project's own and is left alone.
+pub fn generate_task_rules() -> Result<PathBuf> {
+    let doplan_dir = utils::doplan_dir()
+        .context("Failed to get doplan directory")?;
+    let rules_path = TaskCatalog::path(&doplan_dir);
+    if rules_path.exists() {
+        return Ok(rules_path);
+    }
+    utils::ensure_dir(rules_path.parent().unwrap_or(&doplan_dir))
+        .context("Failed to create templates directory")?;
+    utils::validate_write_path(&rules_path)
+        .with_context(|| format!("Invalid path for {}", task_rules::CATALOG_FILE))?;
+
+    std::fs::write(&rules_path, task_rules::DEFAULT_CATALOG)
+        .with_context(|| format!("Failed to write task rules to: {}", rules_path.display()))?;
+
+    // Verify file was written successfully
+    utils::verify_file_write(&rules_path, 100)
+        .context("Task rules file verification failed")?;
+
+    Ok(rules_path)
+}
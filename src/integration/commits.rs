@@ -0,0 +1,298 @@
+//! Task status from commit messages.
+//!
+//! A commit can mention tasks in a trailer (`Task: F-0003/T2 done`) or after
+//! a keyword (`Refs T2`, `Fixes F-0003/T4`). [`apply`] walks branches, moves
+//! the tasks they mention to In Progress or Completed in `tasks.md`, and
+//! appends each change to `.doplan/task-log.jsonl` with the commit that made
+//! it. The log is also what stops a commit being applied twice, so a status
+//! changed by hand afterwards is left alone.
+
+use anyhow::{Context, Result};
+use git2::{Commit, Repository, Sort};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use crate::state::{ProjectState, TaskStatus};
+use crate::utils::plan_dirs;
+use super::git;
+
+/// Keywords that mark a task as finished; any other mention means it is in progress
+const DONE_WORDS: [&str; 14] = [
+    "done", "complete", "completed", "completes", "close", "closed", "closes",
+    "fix", "fixed", "fixes", "resolve", "resolved", "resolves", "finished",
+];
+
+/// One task mentioned by a commit message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskRef {
+    /// `F-0003` when the reference names the feature; bare `T2` belongs to
+    /// the feature of the branch the commit is on
+    pub feature_id: Option<String>,
+    pub task_id: String,
+    pub status: TaskStatus,
+}
+
+/// A branch to read task references from
+#[derive(Debug, Clone)]
+pub struct Source {
+    pub branch: String,
+    /// Feature that bare task ids on this branch belong to. Without one only
+    /// references that name their feature are applied.
+    pub feature_id: Option<String>,
+    /// Branches whose commits are skipped, typically the base branch
+    pub hide: Vec<String>,
+}
+
+/// One status change, as recorded in `.doplan/task-log.jsonl`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatusChange {
+    /// RFC 3339 time the change was applied, in UTC
+    pub timestamp: String,
+    pub feature_id: String,
+    pub task_id: String,
+    pub from: TaskStatus,
+    pub to: TaskStatus,
+    /// Full SHA of the commit that mentioned the task
+    pub commit: String,
+    /// First line of the commit message
+    pub summary: String,
+}
+
+pub fn log_path(dot_doplan: &Path) -> PathBuf {
+    dot_doplan.join("task-log.jsonl")
+}
+
+pub fn append_log(path: &Path, change: &StatusChange) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        crate::utils::ensure_dir(parent)?;
+    }
+    let line = serde_json::to_string(change)
+        .context("Failed to serialize task status change")?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open task log: {}", path.display()))?;
+    writeln!(file, "{}", line)
+        .with_context(|| format!("Failed to append to task log: {}", path.display()))
+}
+
+/// Read every recorded change in `path`, oldest first. A missing file is an empty log.
+pub fn load_log(path: &Path) -> Result<Vec<StatusChange>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read task log: {}", path.display()))?;
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str::<StatusChange>(line)
+                .with_context(|| format!("Failed to parse {} line {}", path.display(), i + 1))
+        })
+        .collect()
+}
+
+/// Every task reference in a commit message. A task mentioned more than once
+/// keeps the furthest status.
+///
+/// Trailers take the form `Task: <refs> [status]`, where the status is a word
+/// such as `done` or `fixed` (Completed) or anything else (In Progress).
+/// Elsewhere a reference counts when it follows a keyword: `Refs`, `Part of`,
+/// `See` or `WIP` for In Progress, `Fixes`, `Closes`, `Completes`,
+/// `Resolves` or `Done` for Completed.
+pub fn parse_message(message: &str) -> Vec<TaskRef> {
+    static TRAILER: OnceLock<Regex> = OnceLock::new();
+    static KEYWORD: OnceLock<Regex> = OnceLock::new();
+    static TASK: OnceLock<Regex> = OnceLock::new();
+    let trailer = TRAILER.get_or_init(|| Regex::new(r"(?i)^\s*tasks?\s*:\s*(.*)$").unwrap());
+    let keyword = KEYWORD.get_or_init(|| {
+        Regex::new(
+            r"(?i)\b(refs?|references|part of|see|wip|fix(?:es|ed)?|close[sd]?|complete[sd]?|resolve[sd]?|done)\b:?\s+((?:(?:F-\d+/)?T\d+\b(?:\s*,\s*|\s+and\s+|\s*)?)+)",
+        )
+        .unwrap()
+    });
+    let task = TASK.get_or_init(|| Regex::new(r"(?i)\b(?:(F-\d+)/)?(T\d+)\b").unwrap());
+
+    let mut refs: Vec<TaskRef> = Vec::new();
+    let mut add = |captures: regex::Captures, status: TaskStatus| {
+        let task_ref = TaskRef {
+            feature_id: captures.get(1).map(|m| m.as_str().to_uppercase()),
+            task_id: captures[2].to_uppercase(),
+            status,
+        };
+        match refs.iter_mut().find(|r| r.feature_id == task_ref.feature_id && r.task_id == task_ref.task_id) {
+            Some(existing) if status == TaskStatus::Completed => existing.status = status,
+            Some(_) => {}
+            None => refs.push(task_ref),
+        }
+    };
+
+    for line in message.lines() {
+        if let Some(captures) = trailer.captures(line) {
+            let value = &captures[1];
+            let rest = task.replace_all(value, " ").to_lowercase();
+            let status = if rest.split(|c: char| !c.is_alphanumeric()).any(|w| DONE_WORDS.contains(&w)) {
+                TaskStatus::Completed
+            } else {
+                TaskStatus::InProgress
+            };
+            for found in task.captures_iter(value) {
+                add(found, status);
+            }
+            continue;
+        }
+        for captures in keyword.captures_iter(line) {
+            let word = captures[1].to_lowercase();
+            let status = if DONE_WORDS.contains(&word.as_str()) {
+                TaskStatus::Completed
+            } else {
+                TaskStatus::InProgress
+            };
+            for found in task.captures_iter(&captures[2]) {
+                add(found, status);
+            }
+        }
+    }
+    refs
+}
+
+/// Whether a commit mentioning a task should move it from `from` to `to`.
+/// Statuses only move forward: a later `Refs T2` does not reopen a completed task.
+fn advances(from: TaskStatus, to: TaskStatus) -> bool {
+    match to {
+        TaskStatus::Completed => from != TaskStatus::Completed,
+        TaskStatus::InProgress => matches!(from, TaskStatus::NotStarted | TaskStatus::Blocked),
+        _ => false,
+    }
+}
+
+/// Commits on `branch` that none of the `hide` branches have, oldest first
+fn commits_on<'r>(repo: &'r Repository, branch: &str, hide: &[String]) -> Result<Vec<Commit<'r>>> {
+    let tip = git::branch_commit(repo, branch)?;
+    let mut walk = repo.revwalk()?;
+    walk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+    walk.push(tip.id())?;
+    for hidden in hide.iter().filter(|h| *h != branch) {
+        if let Ok(commit) = git::branch_commit(repo, hidden) {
+            walk.hide(commit.id())?;
+        }
+    }
+    walk.map(|id| Ok(repo.find_commit(id?)?)).collect()
+}
+
+/// Apply the task references on every source branch to `tasks.md` and
+/// `state` (which is not saved), and record each change in the log at
+/// `log_path`. Returns the changes in the order they were made.
+pub fn apply(
+    repo: &Repository,
+    plan_dir: &Path,
+    state: &mut ProjectState,
+    sources: &[Source],
+    log_path: &Path,
+) -> Result<Vec<StatusChange>> {
+    let layout = plan_dirs::sync_tasks(plan_dir, state)?;
+    let mut applied: HashSet<(String, String, String)> = load_log(log_path)?
+        .into_iter()
+        .map(|c| (c.commit, c.feature_id, c.task_id))
+        .collect();
+
+    let mut changes = Vec::new();
+    for source in sources {
+        for commit in commits_on(repo, &source.branch, &source.hide)? {
+            let message = commit.message().unwrap_or_default();
+            for task_ref in parse_message(message) {
+                let Some(feature_id) = task_ref.feature_id.clone().or_else(|| source.feature_id.clone()) else {
+                    continue;
+                };
+                let key = (commit.id().to_string(), feature_id.clone(), task_ref.task_id.clone());
+                if applied.contains(&key) || layout.feature(&feature_id).is_none() {
+                    continue;
+                }
+                let Some(from) = state
+                    .feature_by_id(&feature_id)
+                    .and_then(|f| f.tasks.iter().find(|t| t.id == task_ref.task_id))
+                    .map(|t| t.status)
+                else {
+                    continue;
+                };
+                if !advances(from, task_ref.status) {
+                    continue;
+                }
+
+                plan_dirs::set_task_status(plan_dir, state, &feature_id, &task_ref.task_id, task_ref.status)?;
+                let change = StatusChange {
+                    timestamp: chrono::Utc::now().to_rfc3339(),
+                    feature_id,
+                    task_id: task_ref.task_id,
+                    from,
+                    to: task_ref.status,
+                    commit: key.0.clone(),
+                    summary: commit.summary().unwrap_or_default().to_string(),
+                };
+                append_log(log_path, &change)?;
+                applied.insert(key);
+                changes.push(change);
+            }
+        }
+    }
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(feature_id: Option<&str>, task_id: &str, status: TaskStatus) -> TaskRef {
+        TaskRef {
+            feature_id: feature_id.map(str::to_string),
+            task_id: task_id.to_string(),
+            status,
+        }
+    }
+
+    #[test]
+    fn test_parse_trailers() {
+        let message = "Add login form\n\nTask: F-0003/T2 done\nTask: T4, T5\nTasks: t6 in progress\n";
+        assert_eq!(
+            parse_message(message),
+            vec![
+                task(Some("F-0003"), "T2", TaskStatus::Completed),
+                task(None, "T4", TaskStatus::InProgress),
+                task(None, "T5", TaskStatus::InProgress),
+                task(None, "T6", TaskStatus::InProgress),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_keywords() {
+        assert_eq!(parse_message("Refs T2"), vec![task(None, "T2", TaskStatus::InProgress)]);
+        assert_eq!(
+            parse_message("Fixes F-0001/T3 and T4, refs: T5"),
+            vec![
+                task(Some("F-0001"), "T3", TaskStatus::Completed),
+                task(None, "T4", TaskStatus::Completed),
+                task(None, "T5", TaskStatus::InProgress),
+            ]
+        );
+        // The furthest status wins
+        assert_eq!(parse_message("Refs T2\n\nCloses T2"), vec![task(None, "T2", TaskStatus::Completed)]);
+        // Ids without a keyword are not references
+        assert!(parse_message("Bump T2 timeout to 30s").is_empty());
+    }
+
+    #[test]
+    fn test_statuses_only_move_forward() {
+        assert!(advances(TaskStatus::NotStarted, TaskStatus::InProgress));
+        assert!(advances(TaskStatus::InProgress, TaskStatus::Completed));
+        assert!(!advances(TaskStatus::Completed, TaskStatus::InProgress));
+        assert!(!advances(TaskStatus::InProgress, TaskStatus::InProgress));
+    }
+}
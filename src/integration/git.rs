@@ -0,0 +1,353 @@
+//! Git helpers for `/implement`: branch names from a template, the base
+//! branch new feature branches start from, the committer identity, and
+//! commits that contain only the files asked for.
+
+use anyhow::{Context, Result};
+use git2::build::TreeUpdateBuilder;
+use git2::{Branch, BranchType, Commit, FileMode, Oid, Repository, Signature, Status, StatusOptions};
+use std::path::Path;
+
+/// Branch name used when `git.branch_template` is not set
+pub const DEFAULT_BRANCH_TEMPLATE: &str = "{type}/{phase_id}-phase-{feature_id}-{feature_slug}";
+
+/// Placeholders a branch template may use
+pub const BRANCH_PLACEHOLDERS: [&str; 7] =
+    ["type", "phase_id", "phase_slug", "phase_key", "feature_id", "feature_slug", "feature_key"];
+
+/// Values for the placeholders of a branch template
+#[derive(Debug, Clone, Default)]
+pub struct BranchVars {
+    /// `git.branch_type`, e.g. `feature`
+    pub kind: String,
+    /// Number of the phase directory, e.g. `01`
+    pub phase_id: String,
+    pub phase_slug: String,
+    /// Stable phase id, e.g. `P-01`; empty when the phase is not in the state
+    pub phase_key: String,
+    /// Number of the feature directory within its phase, e.g. `02`
+    pub feature_id: String,
+    pub feature_slug: String,
+    /// Stable feature id, e.g. `F-0003`; empty when the feature is not in the state
+    pub feature_key: String,
+}
+
+impl BranchVars {
+    fn get(&self, name: &str) -> Option<&str> {
+        Some(match name {
+            "type" => &self.kind,
+            "phase_id" => &self.phase_id,
+            "phase_slug" => &self.phase_slug,
+            "phase_key" => &self.phase_key,
+            "feature_id" => &self.feature_id,
+            "feature_slug" => &self.feature_slug,
+            "feature_key" => &self.feature_key,
+            _ => return None,
+        })
+    }
+
+    /// Stand-in values, for checking a template before there is a feature
+    pub fn sample() -> Self {
+        Self {
+            kind: "feature".to_string(),
+            phase_id: "01".to_string(),
+            phase_slug: "foundation".to_string(),
+            phase_key: "P-01".to_string(),
+            feature_id: "01".to_string(),
+            feature_slug: "user-auth".to_string(),
+            feature_key: "F-0001".to_string(),
+        }
+    }
+}
+
+/// Fill in `{placeholder}`s in `template` and check the result is a valid
+/// branch name. `{{` and `}}` stand for literal braces.
+pub fn render_branch(template: &str, vars: &BranchVars) -> Result<String> {
+    let mut name = String::new();
+    let mut rest = template;
+    while let Some(i) = rest.find(['{', '}']) {
+        name.push_str(&rest[..i]);
+        let brace = rest.as_bytes()[i];
+        rest = &rest[i + 1..];
+        if rest.as_bytes().first() == Some(&brace) {
+            name.push(brace as char);
+            rest = &rest[1..];
+            continue;
+        }
+        if brace == b'}' {
+            anyhow::bail!("unmatched '}}' in '{}'", template);
+        }
+        let end = rest.find('}').with_context(|| format!("unclosed '{{' in '{}'", template))?;
+        let placeholder = &rest[..end];
+        let value = vars.get(placeholder).with_context(|| {
+            format!("unknown placeholder {{{}}} (expected one of: {})", placeholder, BRANCH_PLACEHOLDERS.join(", "))
+        })?;
+        name.push_str(value);
+        rest = &rest[end + 1..];
+    }
+    name.push_str(rest);
+
+    if !Branch::name_is_valid(&name).unwrap_or(false) {
+        anyhow::bail!("'{}' is not a valid branch name", name);
+    }
+    Ok(name)
+}
+
+/// Lowercase `name` with runs of anything but letters and digits turned into `-`
+pub fn slug(name: &str) -> String {
+    name.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// The branch new feature branches start from: `configured` when given,
+/// otherwise the one `origin/HEAD` points at, otherwise the current branch.
+/// `None` in a repository without commits or with a detached HEAD.
+pub fn base_branch(repo: &Repository, configured: Option<&str>) -> Option<String> {
+    if let Some(name) = configured.filter(|name| !name.is_empty()) {
+        return Some(name.to_string());
+    }
+    let remote_head = repo
+        .find_reference("refs/remotes/origin/HEAD")
+        .ok()
+        .and_then(|head| head.symbolic_target().map(str::to_string))
+        .and_then(|target| target.strip_prefix("refs/remotes/origin/").map(str::to_string));
+    remote_head.or_else(|| {
+        let head = repo.head().ok()?;
+        if head.is_branch() { head.shorthand().map(str::to_string) } else { None }
+    })
+}
+
+/// The commit `branch` points at, locally or on `origin`
+pub fn branch_commit<'r>(repo: &'r Repository, branch: &str) -> Result<Commit<'r>> {
+    let found = repo
+        .find_branch(branch, BranchType::Local)
+        .or_else(|_| repo.find_branch(&format!("origin/{}", branch), BranchType::Remote))
+        .with_context(|| format!("Base branch '{}' not found locally or on origin", branch))?;
+    found
+        .get()
+        .peel_to_commit()
+        .with_context(|| format!("Base branch '{}' has no commits", branch))
+}
+
+/// The branch HEAD will create with the first commit, e.g. `refs/heads/main`
+/// when `init.defaultBranch` is `main`
+pub fn unborn_head(repo: &Repository) -> Result<String> {
+    let head = repo.find_reference("HEAD").context("Failed to read HEAD")?;
+    Ok(head.symbolic_target().unwrap_or("refs/heads/master").to_string())
+}
+
+/// The author and committer from `user.name` and `user.email` in git config
+pub fn identity(repo: &Repository) -> Result<Signature<'static>> {
+    repo.signature().context(
+        "No git identity configured. Set one with `git config user.name \"Your Name\"` \
+         and `git config user.email you@example.com`",
+    )
+}
+
+/// Uncommitted changes to tracked files. Untracked files are left out:
+/// checkouts keep them and they are never committed by accident.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorktreeStatus {
+    /// Paths with changes in the index
+    pub staged: Vec<String>,
+    /// Paths changed in the working tree but not staged
+    pub modified: Vec<String>,
+}
+
+impl WorktreeStatus {
+    pub fn is_clean(&self) -> bool {
+        self.staged.is_empty() && self.modified.is_empty()
+    }
+}
+
+pub fn worktree_status(repo: &Repository) -> Result<WorktreeStatus> {
+    let mut options = StatusOptions::new();
+    options.include_untracked(false).include_ignored(false);
+    let statuses = repo.statuses(Some(&mut options)).context("Failed to read the working tree status")?;
+
+    let staged_flags = Status::INDEX_NEW | Status::INDEX_MODIFIED | Status::INDEX_DELETED
+        | Status::INDEX_RENAMED | Status::INDEX_TYPECHANGE;
+    let modified_flags = Status::WT_MODIFIED | Status::WT_DELETED | Status::WT_RENAMED
+        | Status::WT_TYPECHANGE | Status::CONFLICTED;
+    let mut status = WorktreeStatus::default();
+    for entry in statuses.iter() {
+        let path = entry.path().unwrap_or_default().to_string();
+        if entry.status().intersects(staged_flags) {
+            status.staged.push(path.clone());
+        }
+        if entry.status().intersects(modified_flags) {
+            status.modified.push(path);
+        }
+    }
+    Ok(status)
+}
+
+/// Commit the current contents of `paths` (relative to the work tree) on
+/// top of `parent`, and nothing else. The index is left alone; see
+/// [`stage_paths`] when the commit lands on the checked-out branch.
+///
+/// Returns `None`, without committing, when those files are already
+/// committed as they are.
+pub fn commit_paths(
+    repo: &Repository,
+    update_ref: &str,
+    parent: Option<&Commit>,
+    paths: &[&Path],
+    message: &str,
+    signature: &Signature,
+) -> Result<Option<Oid>> {
+    let workdir = repo.workdir().context("The repository has no working tree")?;
+    let base = match parent {
+        Some(commit) => commit.tree().context("Failed to read the parent tree")?,
+        None => repo.find_tree(repo.treebuilder(None)?.write()?)?,
+    };
+
+    let mut update = TreeUpdateBuilder::new();
+    for path in paths {
+        let blob = repo
+            .blob_path(&workdir.join(path))
+            .with_context(|| format!("Failed to add {}", path.display()))?;
+        update.upsert(path.to_string_lossy().replace('\\', "/"), blob, FileMode::Blob);
+    }
+    let tree_id = update.create_updated(repo, &base).context("Failed to build the commit tree")?;
+    if parent.is_some() && tree_id == base.id() {
+        return Ok(None);
+    }
+
+    let tree = repo.find_tree(tree_id)?;
+    let parents: Vec<&Commit> = parent.into_iter().collect();
+    let commit_id = repo
+        .commit(Some(update_ref), signature, signature, message, &tree, &parents)
+        .context("Failed to create commit")?;
+    Ok(Some(commit_id))
+}
+
+/// Record the working tree contents of `paths` in the index, leaving every
+/// other entry as it is
+pub fn stage_paths(repo: &Repository, paths: &[&Path]) -> Result<()> {
+    let mut index = repo.index().context("Failed to get repository index")?;
+    for path in paths {
+        index.add_path(path).with_context(|| format!("Failed to stage {}", path.display()))?;
+    }
+    index.write().context("Failed to write index")
+}
+
+/// Check out `commit` in the main working tree without overwriting anything
+/// uncommitted. Untracked files that already match `commit`, such as
+/// planning documents committed from this working tree on another branch,
+/// are replaced by the checkout instead of counted as conflicts.
+pub fn safe_checkout(repo: &Repository, commit: &Commit) -> Result<()> {
+    let workdir = repo.workdir().context("The repository has no working tree")?;
+    let current = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+    let diff = repo.diff_tree_to_tree(current.as_ref(), Some(&commit.tree()?), None)?;
+
+    // Untracked copies identical to what the checkout writes can go; put
+    // them back if it fails
+    let mut identical = Vec::new();
+    for delta in diff.deltas().filter(|d| d.status() == git2::Delta::Added) {
+        let Some(path) = delta.new_file().path() else {
+            continue;
+        };
+        let file = workdir.join(path);
+        let untracked = repo.status_file(path).is_ok_and(|status| status == Status::WT_NEW);
+        if untracked && Oid::hash_file(git2::ObjectType::Blob, &file).ok() == Some(delta.new_file().id()) {
+            std::fs::remove_file(&file).with_context(|| format!("Failed to replace {}", file.display()))?;
+            identical.push((file, delta.new_file().id()));
+        }
+    }
+
+    let result = repo.checkout_tree(commit.as_object(), Some(git2::build::CheckoutBuilder::new().safe()));
+    if result.is_err() {
+        for (file, blob) in &identical {
+            std::fs::write(file, repo.find_blob(*blob)?.content())
+                .with_context(|| format!("Failed to restore {}", file.display()))?;
+        }
+    }
+    Ok(result?)
+}
+
+/// How [`merge_branch`] brought a branch in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeOutcome {
+    /// The target already contained the branch
+    UpToDate,
+    FastForward(Oid),
+    MergeCommit(Oid),
+}
+
+/// Merge local branch `branch` into local branch `into`, with a merge commit
+/// unless a fast-forward will do. When `into` is checked out in the main
+/// working tree, that tree is updated with a safe checkout first, so the
+/// merge stops rather than overwrite uncommitted changes. Conflicting
+/// changes are left for the user to merge by hand.
+pub fn merge_branch(repo: &Repository, branch: &str, into: &str, signature: &Signature) -> Result<MergeOutcome> {
+    let theirs = repo
+        .find_branch(branch, BranchType::Local)
+        .with_context(|| format!("Branch '{}' not found", branch))?
+        .get()
+        .peel_to_commit()?;
+    let target = repo
+        .find_branch(into, BranchType::Local)
+        .with_context(|| format!("Branch '{}' not found locally", into))?;
+    let target_ref = target.get().name().context("Branch name is not UTF-8")?.to_string();
+    let ours = target.get().peel_to_commit()?;
+
+    if ours.id() == theirs.id() || repo.graph_descendant_of(ours.id(), theirs.id())? {
+        return Ok(MergeOutcome::UpToDate);
+    }
+    let (new_id, outcome) = if repo.graph_descendant_of(theirs.id(), ours.id())? {
+        (theirs.id(), MergeOutcome::FastForward(theirs.id()))
+    } else {
+        let mut index = repo.merge_commits(&ours, &theirs, None).context("Failed to merge")?;
+        if index.has_conflicts() {
+            anyhow::bail!(
+                "'{}' conflicts with '{}'. Merge it by hand with `git merge {}`",
+                branch,
+                into,
+                branch
+            );
+        }
+        let tree = repo.find_tree(index.write_tree_to(repo)?)?;
+        let message = format!("Merge branch '{}' into {}", branch, into);
+        let id = repo
+            .commit(None, signature, signature, &message, &tree, &[&ours, &theirs])
+            .context("Failed to create merge commit")?;
+        (id, MergeOutcome::MergeCommit(id))
+    };
+
+    let checked_out = repo.head().ok().and_then(|head| head.name().map(str::to_string)) == Some(target_ref.clone());
+    if checked_out {
+        safe_checkout(repo, &repo.find_commit(new_id)?)
+            .with_context(|| format!("Failed to update '{}': uncommitted changes in the working tree conflict with the merge", into))?;
+    }
+    repo.reference(&target_ref, new_id, true, &format!("doplan: merge {}", branch))
+        .with_context(|| format!("Failed to update '{}'", into))?;
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_branch() {
+        let vars = BranchVars::sample();
+        assert_eq!(render_branch(DEFAULT_BRANCH_TEMPLATE, &vars).unwrap(), "feature/01-phase-01-user-auth");
+        assert_eq!(render_branch("{type}/{feature_key}-{feature_slug}", &vars).unwrap(), "feature/F-0001-user-auth");
+        assert_eq!(render_branch("dev/{{x}}-{phase_slug}", &vars).unwrap(), "dev/{x}-foundation");
+
+        let error = |template: &str| render_branch(template, &vars).unwrap_err().to_string();
+        assert!(error("{type}/{ticket}").starts_with("unknown placeholder {ticket}"));
+        assert_eq!(error("{type/x"), "unclosed '{' in '{type/x'");
+        assert_eq!(error("x}"), "unmatched '}' in 'x}'");
+        assert_eq!(error("{type}/a..b"), "'feature/a..b' is not a valid branch name");
+    }
+
+    #[test]
+    fn test_slug() {
+        assert_eq!(slug("User Auth & Sign-up!"), "user-auth-sign-up");
+        assert_eq!(slug("  "), "");
+    }
+}
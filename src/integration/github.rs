@@ -0,0 +1,520 @@
+//! Two-way sync between the plan and GitHub issues and milestones.
+//!
+//! | DoPlan  | GitHub                                                    |
+//! |---------|-----------------------------------------------------------|
+//! | Phase   | Milestone `P-01: Foundation`, closed when every task is done |
+//! | Feature | Issue with a `priority:<level>` label in its phase's milestone |
+//! | Task    | Checklist item `- [ ] T1: Title` in the issue body        |
+//!
+//! Completion flows back: a checked item completes its task and an issue
+//! closed as completed completes all of its feature's tasks, both written to
+//! `tasks.md`. Issues closed as not planned leave the tasks alone.
+//! Unchecking an item on GitHub does not reopen a task; the next push
+//! re-checks it. Issue and milestone numbers are kept in
+//! `.doplan/github.json` so renames update the same issue.
+//!
+//! [`open_pull_request`] opens the pull request for a feature branch, with a
+//! body from [`pull_request_body`] that closes the feature's issue.
+
+use anyhow::{Context, Result};
+use reqwest::{Method, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::state::{Feature, Phase, ProjectState, TaskStatus};
+use crate::utils::plan_dirs;
+
+pub const DEFAULT_API_URL: &str = "https://api.github.com";
+const PRIORITY_LABEL_PREFIX: &str = "priority:";
+
+/// Issue and milestone numbers created by previous syncs
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncMap {
+    /// `owner/name` the numbers belong to
+    pub repo: Option<String>,
+    /// Phase id -> milestone number
+    #[serde(default)]
+    pub milestones: BTreeMap<String, u64>,
+    /// Feature id -> issue number
+    #[serde(default)]
+    pub issues: BTreeMap<String, u64>,
+    /// Labels known to exist in the repository
+    #[serde(default)]
+    pub labels: BTreeSet<String>,
+    /// RFC 3339 time of the last successful sync
+    pub synced_at: Option<String>,
+}
+
+impl SyncMap {
+    pub fn path(dot_doplan_dir: &Path) -> PathBuf {
+        dot_doplan_dir.join("github.json")
+    }
+
+    /// Load the map, or an empty one if there has been no sync yet
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)
+            .context("Failed to serialize GitHub sync map")?;
+        fs::write(path, content)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Minimal REST client for one repository
+pub struct GitHubClient {
+    http: reqwest::Client,
+    api_url: String,
+    repo: String,
+    token: String,
+}
+
+impl GitHubClient {
+    pub fn new(api_url: &str, repo: &str, token: &str) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            api_url: api_url.trim_end_matches('/').to_string(),
+            repo: repo.to_string(),
+            token: token.to_string(),
+        }
+    }
+
+    pub fn repo(&self) -> &str {
+        &self.repo
+    }
+
+    /// Send a request to `/repos/{repo}{path}` and return the status and JSON body
+    async fn send(&self, method: Method, path: &str, body: Option<&Value>) -> Result<(StatusCode, Value)> {
+        let url = format!("{}/repos/{}{}", self.api_url, self.repo, path);
+        let mut request = self.http
+            .request(method.clone(), &url)
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "doplan")
+            .bearer_auth(&self.token);
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+        let response = request
+            .send()
+            .await
+            .with_context(|| format!("{} {} failed", method, url))?;
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        let value = serde_json::from_str(&text).unwrap_or(Value::Null);
+        Ok((status, value))
+    }
+
+    /// Like [`send`](Self::send), failing on any non-success status
+    async fn call(&self, method: Method, path: &str, body: Option<&Value>) -> Result<Value> {
+        let (status, value) = self.send(method.clone(), path, body).await?;
+        if !status.is_success() {
+            let message = value["message"].as_str().unwrap_or("no message");
+            anyhow::bail!("GitHub API {} {} returned {}: {}", method, path, status, message);
+        }
+        Ok(value)
+    }
+}
+
+/// What a sync changed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    pub milestones_created: usize,
+    pub issues_created: usize,
+    pub issues_updated: usize,
+    /// `F-0001/T2` refs completed from GitHub
+    pub tasks_completed: Vec<String>,
+}
+
+/// Pull completions from GitHub into `tasks.md`, then push phases, features
+/// and tasks as milestones and issues. `state` and `map` are updated but not
+/// saved, also when an error stops the sync part way.
+pub async fn sync(client: &GitHubClient, plan_dir: &Path, state: &mut ProjectState, map: &mut SyncMap) -> Result<SyncReport> {
+    match map.repo.as_deref() {
+        Some(repo) if repo != client.repo() => anyhow::bail!(
+            "The GitHub sync map is for {} but this sync targets {}. Delete .doplan/github.json to start over.",
+            repo,
+            client.repo()
+        ),
+        _ => map.repo = Some(client.repo().to_string()),
+    }
+    plan_dirs::sync_tasks(plan_dir, state)?;
+    let mut report = SyncReport::default();
+
+    // Pull: issues closed as completed and checked items complete tasks
+    let mut remote_issues: BTreeMap<String, Value> = BTreeMap::new();
+    for (feature_id, number) in map.issues.clone() {
+        let (status, issue) = client.send(Method::GET, &format!("/issues/{}", number), None).await?;
+        if status == StatusCode::NOT_FOUND || status == StatusCode::GONE {
+            map.issues.remove(&feature_id);
+            continue;
+        }
+        if !status.is_success() {
+            anyhow::bail!("GitHub API GET /issues/{} returned {}", number, status);
+        }
+        let Some(feature) = state.feature_by_id(&feature_id) else {
+            continue;
+        };
+        let closed = issue["state"] == "closed" && issue["state_reason"] == "completed";
+        let checked = checked_tasks(issue["body"].as_str().unwrap_or(""));
+        let completed: Vec<String> = feature
+            .tasks
+            .iter()
+            .filter(|t| t.status != TaskStatus::Completed && (closed || checked.contains(&t.id)))
+            .map(|t| t.id.clone())
+            .collect();
+        for task_id in completed {
+            plan_dirs::set_task_status(plan_dir, state, &feature_id, &task_id, TaskStatus::Completed)?;
+            report.tasks_completed.push(format!("{}/{}", feature_id, task_id));
+        }
+        remote_issues.insert(feature_id, issue);
+    }
+
+    // Push: milestones for phases
+    for phase in state.phases.iter().flatten() {
+        let body = json!({
+            "title": milestone_title(phase),
+            "description": phase.description,
+            "state": if phase_completed(state, phase) { "closed" } else { "open" }
+        });
+        if let Some(number) = map.milestones.get(&phase.id) {
+            let (status, _) = client.send(Method::PATCH, &format!("/milestones/{}", number), Some(&body)).await?;
+            if status.is_success() {
+                continue;
+            }
+            if status != StatusCode::NOT_FOUND {
+                anyhow::bail!("GitHub API PATCH /milestones/{} returned {}", number, status);
+            }
+        }
+        let created = client.call(Method::POST, "/milestones", Some(&body)).await?;
+        let number = created["number"].as_u64().context("GitHub returned a milestone without a number")?;
+        map.milestones.insert(phase.id.clone(), number);
+        report.milestones_created += 1;
+    }
+
+    // Push: priority labels, then one issue per feature
+    for feature in state.features.iter().flatten() {
+        let label = priority_label(feature);
+        if map.labels.contains(&label) {
+            continue;
+        }
+        let (status, _) = client
+            .send(Method::POST, "/labels", Some(&json!({ "name": label, "color": priority_color(&feature.priority) })))
+            .await?;
+        // 422: the label already exists
+        if !status.is_success() && status != StatusCode::UNPROCESSABLE_ENTITY {
+            anyhow::bail!("GitHub API POST /labels returned {}", status);
+        }
+        map.labels.insert(label);
+    }
+
+    for feature in state.features.iter().flatten() {
+        let milestone = state
+            .phase_of(&feature.id)
+            .and_then(|phase| map.milestones.get(&phase.id));
+        let closed = !feature.tasks.is_empty() && feature.tasks.iter().all(|t| t.status == TaskStatus::Completed);
+        let mut desired = json!({
+            "title": feature.name,
+            "body": issue_body(feature),
+            "milestone": milestone,
+            "state": if closed { "closed" } else { "open" }
+        });
+
+        match remote_issues.get(&feature.id) {
+            Some(remote) => {
+                // Keep labels added on GitHub; only the priority label is ours
+                let mut labels: Vec<String> = remote["labels"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|l| l["name"].as_str())
+                    .filter(|name| !name.starts_with(PRIORITY_LABEL_PREFIX))
+                    .map(str::to_string)
+                    .collect();
+                labels.push(priority_label(feature));
+                desired["labels"] = json!(labels);
+                if issue_matches(remote, &desired) {
+                    continue;
+                }
+                let number = map.issues[&feature.id];
+                client.call(Method::PATCH, &format!("/issues/{}", number), Some(&desired)).await?;
+                report.issues_updated += 1;
+            }
+            None => {
+                desired["labels"] = json!([priority_label(feature)]);
+                let created = client.call(Method::POST, "/issues", Some(&desired)).await?;
+                let number = created["number"].as_u64().context("GitHub returned an issue without a number")?;
+                map.issues.insert(feature.id.clone(), number);
+                report.issues_created += 1;
+                if closed {
+                    // Issues are always created open
+                    client.call(Method::PATCH, &format!("/issues/{}", number), Some(&json!({ "state": "closed" }))).await?;
+                }
+            }
+        }
+    }
+
+    map.synced_at = Some(chrono::Utc::now().to_rfc3339());
+    Ok(report)
+}
+
+fn milestone_title(phase: &Phase) -> String {
+    format!("{}: {}", phase.id, phase.name)
+}
+
+fn phase_completed(state: &ProjectState, phase: &Phase) -> bool {
+    let features = state.phase_features(phase);
+    !features.is_empty()
+        && features.iter().all(|f| !f.tasks.is_empty() && f.tasks.iter().all(|t| t.status == TaskStatus::Completed))
+}
+
+pub fn priority_label(feature: &Feature) -> String {
+    let priority = if feature.priority.trim().is_empty() { "medium" } else { feature.priority.trim() };
+    format!("{}{}", PRIORITY_LABEL_PREFIX, priority.to_lowercase())
+}
+
+fn priority_color(priority: &str) -> &'static str {
+    match priority.to_lowercase().as_str() {
+        "critical" | "high" => "d73a4a",
+        "low" => "0e8a16",
+        _ => "fbca04",
+    }
+}
+
+/// Issue body: the description followed by a checklist of the feature's tasks
+pub fn issue_body(feature: &Feature) -> String {
+    let mut body = String::new();
+    if !feature.description.trim().is_empty() {
+        body.push_str(feature.description.trim());
+        body.push_str("\n\n");
+    }
+    body.push_str("## Tasks\n\n");
+    for task in &feature.tasks {
+        let mark = if task.status == TaskStatus::Completed { "x" } else { " " };
+        body.push_str(&format!("- [{}] {}: {}\n", mark, task.id, task.title));
+    }
+    body.push_str(&format!(
+        "\n<!-- doplan:{} Synced by `doplan github`. Checked items are written back to tasks.md. -->\n",
+        feature.id
+    ));
+    body
+}
+
+/// Task ids whose checklist item is checked in an issue body
+pub fn checked_tasks(body: &str) -> BTreeSet<String> {
+    body.lines()
+        .filter_map(|line| {
+            let item = line.trim_start().strip_prefix("- ").or_else(|| line.trim_start().strip_prefix("* "))?;
+            let rest = item.strip_prefix("[x]").or_else(|| item.strip_prefix("[X]"))?;
+            let (id, _) = rest.trim_start().split_once(':')?;
+            let id = id.trim();
+            let number = id.strip_prefix('T')?;
+            (!number.is_empty() && number.chars().all(|c| c.is_ascii_digit())).then(|| id.to_string())
+        })
+        .collect()
+}
+
+/// Whether the remote issue already has every field of `desired`
+fn issue_matches(remote: &Value, desired: &Value) -> bool {
+    let remote_labels: BTreeSet<&str> = remote["labels"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|l| l["name"].as_str())
+        .collect();
+    let desired_labels: BTreeSet<&str> = desired["labels"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|l| l.as_str())
+        .collect();
+    // GitHub may return the body with CRLF line endings
+    let remote_body = remote["body"].as_str().unwrap_or("").replace("\r\n", "\n");
+    remote["title"] == desired["title"]
+        && remote_body == desired["body"].as_str().unwrap_or("")
+        && remote["state"] == desired["state"]
+        && remote["milestone"]["number"] == desired["milestone"]
+        && remote_labels == desired_labels
+}
+
+/// A pull request opened or updated by [`open_pull_request`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PullRequestReport {
+    pub number: u64,
+    pub url: String,
+    /// False when a pull request for the branch was already open and was updated
+    pub created: bool,
+}
+
+/// Open a pull request from `head` into `base`. When one is already open for
+/// `head`, its title and body are updated instead.
+pub async fn open_pull_request(
+    client: &GitHubClient,
+    head: &str,
+    base: &str,
+    title: &str,
+    body: &str,
+    draft: bool,
+) -> Result<PullRequestReport> {
+    let owner = client.repo().split('/').next().unwrap_or_default();
+    let open = client
+        .call(Method::GET, &format!("/pulls?state=open&head={}:{}", owner, head), None)
+        .await?;
+    if let Some(existing) = open.as_array().and_then(|pulls| pulls.first()) {
+        let number = existing["number"].as_u64().context("GitHub returned a pull request without a number")?;
+        let updated = client
+            .call(Method::PATCH, &format!("/pulls/{}", number), Some(&json!({ "title": title, "body": body })))
+            .await?;
+        return Ok(PullRequestReport {
+            number,
+            url: updated["html_url"].as_str().or(existing["html_url"].as_str()).unwrap_or_default().to_string(),
+            created: false,
+        });
+    }
+
+    let created = client
+        .call(
+            Method::POST,
+            "/pulls",
+            Some(&json!({ "title": title, "head": head, "base": base, "body": body, "draft": draft })),
+        )
+        .await?;
+    Ok(PullRequestReport {
+        number: created["number"].as_u64().context("GitHub returned a pull request without a number")?,
+        url: created["html_url"].as_str().unwrap_or_default().to_string(),
+        created: true,
+    })
+}
+
+/// Pull request body: the overview and acceptance criteria from the
+/// feature's `plan.md`, its completed tasks, and `Closes #n` for its issue
+pub fn pull_request_body(feature: &Feature, plan_md: &str, issue: Option<u64>) -> String {
+    let mut body = String::from("## Overview\n\n");
+    let overview = markdown_section(plan_md, "Overview").unwrap_or_else(|| feature.description.trim().to_string());
+    body.push_str(&overview);
+    body.push_str("\n\n");
+
+    if let Some(criteria) = markdown_section(plan_md, "Acceptance Criteria") {
+        body.push_str("## Acceptance Criteria\n\n");
+        body.push_str(&criteria);
+        body.push_str("\n\n");
+    }
+
+    body.push_str("## Completed Tasks\n\n");
+    let completed: Vec<_> = feature.tasks.iter().filter(|t| t.status == TaskStatus::Completed).collect();
+    if completed.is_empty() {
+        body.push_str("_None yet_\n");
+    }
+    for task in completed {
+        body.push_str(&format!("- [x] {}: {}\n", task.id, task.title));
+    }
+
+    if let Some(number) = issue {
+        body.push_str(&format!("\nCloses #{}\n", number));
+    }
+    body.push_str(&format!(
+        "\n<!-- doplan:{} Opened by `doplan execute pr` from plan.md and tasks.md. -->\n",
+        feature.id
+    ));
+    body
+}
+
+/// Text under the `## heading` of a markdown document, up to the next `##`
+/// heading, trimmed. `None` when the heading is missing or the section is empty.
+fn markdown_section(content: &str, heading: &str) -> Option<String> {
+    let mut lines = content.lines().skip_while(|line| line.trim() != format!("## {}", heading));
+    lines.next()?;
+    let section = lines
+        .take_while(|line| !line.starts_with("## "))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let section = section.trim();
+    (!section.is_empty()).then(|| section.to_string())
+}
+
+/// `owner/name` from a GitHub remote URL (HTTPS or SSH)
+pub fn parse_repo_url(url: &str) -> Option<String> {
+    let path = url
+        .strip_prefix("git@github.com:")
+        .or_else(|| url.strip_prefix("ssh://git@github.com/"))
+        .or_else(|| url.strip_prefix("https://github.com/"))
+        .or_else(|| url.strip_prefix("http://github.com/"))?;
+    let path = path.trim_end_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    let (owner, name) = path.split_once('/')?;
+    (!owner.is_empty() && !name.is_empty() && !name.contains('/')).then(|| format!("{}/{}", owner, name))
+}
+
+/// `owner/name` of the `origin` remote of the repository containing `dir`
+pub fn detect_repo(dir: &Path) -> Option<String> {
+    let repo = git2::Repository::discover(dir).ok()?;
+    let remote = repo.find_remote("origin").ok()?;
+    parse_repo_url(remote.url()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::Task;
+
+    #[test]
+    fn test_parse_repo_url() {
+        assert_eq!(parse_repo_url("git@github.com:DoPlan-dev/CLI.git").as_deref(), Some("DoPlan-dev/CLI"));
+        assert_eq!(parse_repo_url("https://github.com/DoPlan-dev/CLI").as_deref(), Some("DoPlan-dev/CLI"));
+        assert_eq!(parse_repo_url("ssh://git@github.com/a/b.git/").as_deref(), Some("a/b"));
+        assert_eq!(parse_repo_url("https://gitlab.com/a/b"), None);
+        assert_eq!(parse_repo_url("https://github.com/a"), None);
+    }
+
+    #[test]
+    fn test_issue_body_round_trip() {
+        let mut feature = Feature {
+            id: "F-0001".to_string(),
+            name: "Auth".to_string(),
+            description: "Login and signup".to_string(),
+            priority: "High".to_string(),
+            ..Default::default()
+        };
+        feature.tasks = vec![Task::new("T1", "Schema"), Task::new("T2", "Endpoints: login")];
+        feature.tasks[0].status = TaskStatus::Completed;
+
+        let body = issue_body(&feature);
+        assert!(body.starts_with("Login and signup\n\n## Tasks\n\n- [x] T1: Schema\n- [ ] T2: Endpoints: login\n"));
+        assert_eq!(checked_tasks(&body), BTreeSet::from(["T1".to_string()]));
+        assert_eq!(checked_tasks(&body.replace("- [ ] T2", "* [X] T2")).len(), 2);
+        assert!(checked_tasks("- [x] Txt: no id\n- [x] no colon").is_empty());
+        assert_eq!(priority_label(&feature), "priority:high");
+    }
+
+    #[test]
+    fn test_pull_request_body() {
+        let mut feature = Feature {
+            id: "F-0001".to_string(),
+            name: "Auth".to_string(),
+            description: "Login and signup".to_string(),
+            ..Default::default()
+        };
+        feature.tasks = vec![Task::new("T1", "Schema"), Task::new("T2", "Endpoints")];
+        feature.tasks[0].status = TaskStatus::Completed;
+        let plan = "# Feature Plan\n\n## Overview\n\nPasswordless login.\n\n## Goals\n\n- Ship\n\n## Acceptance Criteria\n\n- [ ] Feature implemented\n- [x] Code reviewed\n\n## Timeline\n\n_TBD_\n";
+
+        let body = pull_request_body(&feature, plan, Some(17));
+        assert!(body.starts_with(
+            "## Overview\n\nPasswordless login.\n\n## Acceptance Criteria\n\n- [ ] Feature implemented\n- [x] Code reviewed\n\n## Completed Tasks\n\n- [x] T1: Schema\n\nCloses #17\n"
+        ));
+        assert!(!body.contains("T2"));
+
+        // Without a plan the description stands in; without an issue nothing is closed
+        let body = pull_request_body(&feature, "", None);
+        assert!(body.starts_with("## Overview\n\nLogin and signup\n\n## Completed Tasks\n\n- [x] T1: Schema\n\n<!--"));
+    }
+}
@@ -1,23 +1,70 @@
+//! IDE integrations: the DoPlan slash commands in each editor's native format.
+//!
+//! | IDE      | Files                                                         |
+//! |----------|---------------------------------------------------------------|
+//! | cursor   | `.cursor/commands/<name>.md`, rules in `.cursor/rules/doplan.mdc` |
+//! | claude   | `.claude/commands/<name>.md` with YAML frontmatter            |
+//! | gemini   | `.gemini/commands/<name>.toml`                                |
+//! | windsurf | `.windsurf/workflows/<name>.md`                               |
+//! | vscode   | `DoPlan: ...` tasks merged into `.vscode/tasks.json`          |
+
 use anyhow::{Context, Result};
-use std::path::PathBuf;
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
 use crate::utils;
 
-/// Generate IDE command file for /discuss
-pub fn generate_discuss_command(ide_type: &str) -> Result<PathBuf> {
-    let commands_dir = utils::ai_commands_dir()?;
-    utils::ensure_dir(&commands_dir)?;
-
-    let filename = match ide_type {
-        "cursor" => "discuss.md",
-        "gemini" => "discuss.md",
-        "claude" => "discuss.md",
-        _ => "discuss.md",
-    };
-
-    let command_path = commands_dir.join(filename);
+/// One DoPlan slash command, independent of the IDE it is rendered for
+#[derive(Debug, Clone, Copy)]
+pub struct CommandSpec {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Shown by IDEs that prompt for arguments; `None` when the command takes none
+    pub argument_hint: Option<&'static str>,
+    /// Markdown instructions shared by every IDE
+    pub body: &'static str,
+}
 
-    let content = r#"--- Cursor Command: discuss.md ---
-# Discuss
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec {
+        name: "discuss",
+        description: "Refine the app idea, organize features into phases and pick a tech stack",
+        argument_hint: None,
+        body: DISCUSS_BODY,
+    },
+    CommandSpec {
+        name: "generate",
+        description: "Generate the PRD, project structure and API contracts",
+        argument_hint: None,
+        body: GENERATE_BODY,
+    },
+    CommandSpec {
+        name: "plan",
+        description: "Create the phase and feature plan structure under doplan/plan",
+        argument_hint: Some("[--force]"),
+        body: PLAN_BODY,
+    },
+    CommandSpec {
+        name: "implement",
+        description: "Start implementing a feature on its own branch",
+        argument_hint: Some("<phase-id>[/<feature-id>]"),
+        body: IMPLEMENT_BODY,
+    },
+    CommandSpec {
+        name: "next",
+        description: "Recommend the next task to work on",
+        argument_hint: Some("[explain <feature-id>/<task-id>]"),
+        body: NEXT_BODY,
+    },
+    CommandSpec {
+        name: "progress",
+        description: "Recalculate progress and regenerate the dashboard",
+        argument_hint: Some("[--weighting count|estimate|priority]"),
+        body: PROGRESS_BODY,
+    },
+];
+
+const DISCUSS_BODY: &str = r#"# Discuss
 
 ## Overview
 Start idea discussion and refinement workflow. This command helps refine your project idea, suggest improvements, organize features, and select the best tech stack.
@@ -45,32 +92,9 @@ This command is executed via:
 ```bash
 doplan execute discuss
 ```
-
---- End Command ---
 "#;
 
-    std::fs::write(&command_path, content)
-        .context("Failed to write discuss command file")?;
-
-    Ok(command_path)
-}
-
-/// Generate IDE command file for /generate
-pub fn generate_generate_command(ide_type: &str) -> Result<PathBuf> {
-    let commands_dir = utils::ai_commands_dir()?;
-    utils::ensure_dir(&commands_dir)?;
-
-    let filename = match ide_type {
-        "cursor" => "generate.md",
-        "gemini" => "generate.md",
-        "claude" => "generate.md",
-        _ => "generate.md",
-    };
-
-    let command_path = commands_dir.join(filename);
-
-    let content = r#"--- Cursor Command: generate.md ---
-# Generate
+const GENERATE_BODY: &str = r#"# Generate
 
 ## Overview
 Generate Product Requirements Document (PRD), project structure document, and API contracts based on the refined idea.
@@ -98,32 +122,9 @@ This command is executed via:
 ```bash
 doplan execute generate
 ```
-
---- End Command ---
 "#;
 
-    std::fs::write(&command_path, content)
-        .context("Failed to write generate command file")?;
-
-    Ok(command_path)
-}
-
-/// Generate IDE command file for /plan
-pub fn generate_plan_command(ide_type: &str) -> Result<PathBuf> {
-    let commands_dir = utils::ai_commands_dir()?;
-    utils::ensure_dir(&commands_dir)?;
-
-    let filename = match ide_type {
-        "cursor" => "plan.md",
-        "gemini" => "plan.md",
-        "claude" => "plan.md",
-        _ => "plan.md",
-    };
-
-    let command_path = commands_dir.join(filename);
-
-    let content = r#"--- Cursor Command: plan.md ---
-# Plan
+const PLAN_BODY: &str = r#"# Plan
 
 ## Overview
 Generate the project plan with phases and features. Create the directory structure following DoPlan workflow.
@@ -143,6 +144,11 @@ Generate the project plan with phases and features. Create the directory structu
    - `progress.json`
 7. Update dashboard with new structure
 
+Re-running `/plan` reconciles the existing structure: only missing files are
+created, directories follow renamed or reordered phases and features, and
+directories of removed features are moved to `doplan/archive/`. Files you have
+edited are never overwritten unless `--force` is given.
+
 ## Structure
 ```
 doplan/plan/
@@ -165,33 +171,11 @@ Run `/plan` in your IDE to generate the project plan structure.
 This command is executed via:
 ```bash
 doplan execute plan
+doplan execute plan --force   # also overwrite edited plan files
 ```
-
---- End Command ---
 "#;
 
-    std::fs::write(&command_path, content)
-        .context("Failed to write plan command file")?;
-
-    Ok(command_path)
-}
-
-/// Generate IDE command file for /implement
-pub fn generate_implement_command(ide_type: &str) -> Result<PathBuf> {
-    let commands_dir = utils::ai_commands_dir()?;
-    utils::ensure_dir(&commands_dir)?;
-
-    let filename = match ide_type {
-        "cursor" => "implement.md",
-        "gemini" => "implement.md",
-        "claude" => "implement.md",
-        _ => "implement.md",
-    };
-
-    let command_path = commands_dir.join(filename);
-
-    let content = r#"--- Cursor Command: implement.md ---
-# Implement
+const IMPLEMENT_BODY: &str = r#"# Implement
 
 ## Overview
 Start implementing a feature. This command helps guide implementation based on the feature's planning documents and automatically creates a GitHub branch.
@@ -199,10 +183,12 @@ Start implementing a feature. This command helps guide implementation based on t
 ## Workflow
 1. Check current feature context from `.doplan/state.json`
 2. **Automatically create GitHub branch:**
-   - Format: `feature/XX-phase-XX-feature-name`
-   - Create branch: `git checkout -b {branch-name}`
+   - Name from `git.branch_template` (default `feature/XX-phase-XX-feature-name`)
+   - Started from `git.base_branch`, or the branch `origin/HEAD` points at
+   - Uncommitted changes: stash, abort or carry over (`--dirty stash|abort|continue`)
+   - With `--worktree`, checked out in its own git worktree instead of switching this one
 3. **Initial commit:**
-   - Add plan.md, design.md, tasks.md files
+   - Only plan.md, design.md and tasks.md, signed with your git identity
    - Commit message: `docs: add planning docs for {feature-name}`
    - Push: `git push origin {branch-name}`
 4. Update state with branch name
@@ -217,6 +203,7 @@ Start implementing a feature. This command helps guide implementation based on t
 - Check off tasks in tasks.md as you complete them
 - Commit regularly with clear messages
 - Update progress as you work
+- When the feature is ready, open a pull request with `doplan execute pr <feature-id>`. Its description comes from plan.md and the completed tasks, and it closes the feature's GitHub issue.
 
 ## Usage
 Run `/implement <phase-id>` or `/implement <phase-id>/<feature-id>` in your IDE.
@@ -227,33 +214,16 @@ This command is executed via:
 doplan execute implement <phase-id>
 # or
 doplan execute implement <phase-id>/<feature-id>
+# or, in a separate worktree
+doplan execute implement <feature-id> --worktree
+doplan execute implement list
+doplan execute implement finish <feature-id> --merge
+# then
+doplan execute pr <feature-id>
 ```
-
---- End Command ---
 "#;
 
-    std::fs::write(&command_path, content)
-        .context("Failed to write implement command file")?;
-
-    Ok(command_path)
-}
-
-/// Generate IDE command file for /next
-pub fn generate_next_command(ide_type: &str) -> Result<PathBuf> {
-    let commands_dir = utils::ai_commands_dir()?;
-    utils::ensure_dir(&commands_dir)?;
-
-    let filename = match ide_type {
-        "cursor" => "next.md",
-        "gemini" => "next.md",
-        "claude" => "next.md",
-        _ => "next.md",
-    };
-
-    let command_path = commands_dir.join(filename);
-
-    let content = r#"--- Cursor Command: next.md ---
-# Next
+const NEXT_BODY: &str = r#"# Next
 
 ## Overview
 Analyze the current project state and recommend the next best action. Check progress, incomplete tasks, and suggest what to work on next.
@@ -262,7 +232,7 @@ Analyze the current project state and recommend the next best action. Check prog
 1. Read current state from `.doplan/state.json`
 2. Scan all feature directories for incomplete tasks
 3. Check progress.json files
-4. Consider dependencies between features
+4. Skip tasks whose `Depends On` prerequisites are incomplete
 5. Recommend highest priority action
 6. Display recommendation in dashboard format
 
@@ -287,55 +257,38 @@ Run `/next` in your IDE to get the next recommended action.
 This command is executed via:
 ```bash
 doplan execute next
+doplan execute next explain F-0001/T2   # show what is blocking a task
 ```
-
---- End Command ---
 "#;
 
-    std::fs::write(&command_path, content)
-        .context("Failed to write next command file")?;
-
-    Ok(command_path)
-}
-
-/// Generate IDE command file for /progress
-pub fn generate_progress_command(ide_type: &str) -> Result<PathBuf> {
-    let commands_dir = utils::ai_commands_dir()?;
-    utils::ensure_dir(&commands_dir)?;
-
-    let filename = match ide_type {
-        "cursor" => "progress.md",
-        "gemini" => "progress.md",
-        "claude" => "progress.md",
-        _ => "progress.md",
-    };
-
-    let command_path = commands_dir.join(filename);
-
-    let content = r#"--- Cursor Command: progress.md ---
-# Progress
+const PROGRESS_BODY: &str = r#"# Progress
 
 ## Overview
 Update all progress tracking files. Recalculate progress bars for phases and features, update dashboard, and sync progress.json files.
 
 ## Workflow
-1. Scan all feature directories in `doplan/`
-2. Read tasks.md files from each feature
-3. Count completed tasks (marked with [x])
-4. Calculate completion percentages
-5. Update progress.json files:
+1. Apply task references from commit messages (`Refs T2`, `Task: F-0003/T2 done`) to tasks.md, logging each change with its commit SHA in `.doplan/task-log.jsonl` (skip with `--no-commits`)
+2. Scan all feature directories in `doplan/`
+3. Read tasks.md files from each feature
+4. Count completed tasks (marked with [x])
+5. Calculate completion percentages
+6. Update progress.json files:
    - Feature-level: `doplan/XX-phase/XX-feature/progress.json`
    - Phase-level: `doplan/XX-phase/phase-progress.json`
-6. Regenerate dashboard:
+7. Regenerate dashboard:
    - `.doplan/dashboard.json`
    - `doplan/dashboard.md`
-7. Sync GitHub data (if enabled)
-8. Update state file: `.doplan/state.json`
+8. Append a progress snapshot to `.doplan/history.jsonl` (used by `doplan burndown`)
+9. Sync GitHub data (if enabled)
+10. Update state file: `.doplan/state.json`
 
 ## Progress Calculation
 - Feature progress: (completed tasks / total tasks) * 100
 - Phase progress: Average of all feature progress in phase
 - Overall progress: Average of all phase progress
+- Weighting: set `progress.weighting` in `doplan.toml` (`doplan config set progress.weighting estimate`, or pass `--weighting`) to `count` (default, as above), `estimate` (tasks, features and phases weighted by estimated hours) or `priority` (features weighted by priority); the mode is recorded in `.doplan/dashboard.json`
+- Velocity: tasks completed per day over the last 14 days of `.doplan/history.jsonl`
+- Forecast: projected finish date (with a confidence range) for each phase and the project, written to the `forecast` section of `.doplan/dashboard.json`
 
 ## Usage
 Run `/progress` in your IDE to update all progress tracking.
@@ -345,29 +298,267 @@ This command is executed via:
 ```bash
 doplan execute progress
 ```
-
---- End Command ---
 "#;
 
-    std::fs::write(&command_path, content)
-        .context("Failed to write progress command file")?;
+/// Label prefix that marks the tasks DoPlan owns in `.vscode/tasks.json`
+const VSCODE_TASK_PREFIX: &str = "DoPlan: ";
+const VSCODE_INPUT_PREFIX: &str = "doplan-";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ide {
+    Cursor,
+    Claude,
+    Gemini,
+    Windsurf,
+    Vscode,
+}
+
+/// A generated file, relative to the project root
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandFile {
+    pub path: PathBuf,
+    pub content: String,
+}
+
+impl Ide {
+    pub const ALL: [Ide; 5] = [Self::Cursor, Self::Claude, Self::Gemini, Self::Windsurf, Self::Vscode];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Cursor => "cursor",
+            Self::Claude => "claude",
+            Self::Gemini => "gemini",
+            Self::Windsurf => "windsurf",
+            Self::Vscode => "vscode",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|ide| ide.as_str() == value.trim().to_lowercase())
+    }
+
+    /// Config directory whose presence means the IDE is used in a project
+    pub fn config_dir(self) -> &'static str {
+        match self {
+            Self::Cursor => ".cursor",
+            Self::Claude => ".claude",
+            Self::Gemini => ".gemini",
+            Self::Windsurf => ".windsurf",
+            Self::Vscode => ".vscode",
+        }
+    }
+
+    /// Every file this IDE needs for the DoPlan commands. VS Code's
+    /// `tasks.json` is merged with the one already under `root`, if any.
+    pub fn render(self, root: &Path) -> Result<Vec<CommandFile>> {
+        let file = |path: String, content: String| CommandFile { path: PathBuf::from(path), content };
+        Ok(match self {
+            Self::Cursor => {
+                let mut files: Vec<CommandFile> = COMMANDS
+                    .iter()
+                    .map(|c| file(format!(".cursor/commands/{}.md", c.name), c.body.to_string()))
+                    .collect();
+                files.push(file(".cursor/rules/doplan.mdc".to_string(), cursor_rule()));
+                files
+            }
+            Self::Claude => COMMANDS
+                .iter()
+                .map(|c| file(format!(".claude/commands/{}.md", c.name), claude_command(c)))
+                .collect(),
+            Self::Gemini => COMMANDS
+                .iter()
+                .map(|c| file(format!(".gemini/commands/{}.toml", c.name), gemini_command(c)))
+                .collect(),
+            Self::Windsurf => COMMANDS
+                .iter()
+                .map(|c| file(format!(".windsurf/workflows/{}.md", c.name), windsurf_workflow(c)))
+                .collect(),
+            Self::Vscode => {
+                let path = root.join(".vscode").join("tasks.json");
+                let existing = if path.exists() {
+                    let content = fs::read_to_string(&path)
+                        .with_context(|| format!("Failed to read {}", path.display()))?;
+                    Some(serde_json::from_str(&content).with_context(|| {
+                        format!("Failed to parse {} (comments are not supported; remove them or add the DoPlan tasks by hand)", path.display())
+                    })?)
+                } else {
+                    None
+                };
+                vec![file(".vscode/tasks.json".to_string(), vscode_tasks(existing))]
+            }
+        })
+    }
+}
+
+/// IDEs that already have a config directory (`.cursor`, `.claude`, ...) under `root`
+pub fn detect_ides(root: &Path) -> Vec<Ide> {
+    Ide::ALL.into_iter().filter(|ide| root.join(ide.config_dir()).is_dir()).collect()
+}
+
+fn run_line(command: &CommandSpec, placeholder: &str) -> String {
+    match command.argument_hint {
+        Some(_) => format!("Run `doplan execute {} {}` and walk the user through the result.\n", command.name, placeholder),
+        None => format!("Run `doplan execute {}` and walk the user through the result.\n", command.name),
+    }
+}
+
+fn claude_command(command: &CommandSpec) -> String {
+    let mut out = format!("---\ndescription: {}\n", command.description);
+    if let Some(hint) = command.argument_hint {
+        out.push_str(&format!("argument-hint: {}\n", hint));
+    }
+    out.push_str(&format!("allowed-tools: Bash(doplan execute {}:*)\n---\n\n", command.name));
+    out.push_str(command.body);
+    out.push('\n');
+    out.push_str(&run_line(command, "$ARGUMENTS"));
+    out
+}
+
+fn gemini_command(command: &CommandSpec) -> String {
+    // A multi-line basic string only needs backslashes and `"""` escaped
+    let prompt = format!("{}\n{}", command.body, run_line(command, "{{args}}"))
+        .replace('\\', "\\\\")
+        .replace("\"\"\"", "\\\"\\\"\\\"");
+    let description = command.description.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("description = \"{}\"\nprompt = \"\"\"\n{}\"\"\"\n", description, prompt)
+}
+
+fn windsurf_workflow(command: &CommandSpec) -> String {
+    format!("---\ndescription: {}\n---\n\n{}", command.description, command.body)
+}
+
+fn cursor_rule() -> String {
+    let mut out = String::from(
+        "---\ndescription: DoPlan project workflow and where its files live\nglobs:\nalwaysApply: true\n---\n\n\
+         # DoPlan\n\n\
+         This project is planned and tracked with DoPlan.\n\n\
+         ## Files\n\
+         - `.doplan/state.json` - project state: idea, features, phases and tasks\n\
+         - `doplan/PRD.md`, `doplan/structure.md`, `doplan/contracts/` - generated project documents\n\
+         - `doplan/plan/NN-phase/NN-feature/` - `plan.md`, `design.md`, `tasks.md` and `progress.json` for each feature\n\
+         - `doplan/dashboard.md` - progress dashboard\n\n\
+         ## Working on a feature\n\
+         - Follow the feature's `plan.md` and `design.md`\n\
+         - Update each task's Status line in `tasks.md` as you go, then run `/progress`\n\
+         - Use `/next` to pick the next task; it skips tasks whose `Depends On` prerequisites are incomplete\n\n\
+         ## Commands\n",
+    );
+    for command in COMMANDS {
+        out.push_str(&format!("- `/{}` - {}\n", command.name, command.description));
+    }
+    out
+}
+
+/// `tasks.json` with one task per command, keeping every task and input
+/// that DoPlan does not own
+fn vscode_tasks(existing: Option<Value>) -> String {
+    let mut doc = match existing {
+        Some(Value::Object(map)) => Value::Object(map),
+        _ => json!({ "version": "2.0.0" }),
+    };
+
+    let owned_task = |t: &Value| t["label"].as_str().is_some_and(|l| l.starts_with(VSCODE_TASK_PREFIX));
+    let owned_input = |i: &Value| i["id"].as_str().is_some_and(|id| id.starts_with(VSCODE_INPUT_PREFIX));
+    let mut tasks: Vec<Value> = doc["tasks"].as_array().cloned().unwrap_or_default();
+    tasks.retain(|t| !owned_task(t));
+    let mut inputs: Vec<Value> = doc["inputs"].as_array().cloned().unwrap_or_default();
+    inputs.retain(|i| !owned_input(i));
+
+    for command in COMMANDS {
+        // One command line rather than `args`: VS Code quotes each `args`
+        // entry, which would pass `explain F-0001/T2` as a single argument
+        let mut command_line = format!("doplan execute {}", command.name);
+        if let Some(hint) = command.argument_hint {
+            let id = format!("{}{}-args", VSCODE_INPUT_PREFIX, command.name);
+            command_line.push_str(&format!(" ${{input:{}}}", id));
+            inputs.push(json!({
+                "id": id,
+                "type": "promptString",
+                "description": format!("Arguments for /{}: {}", command.name, hint),
+                "default": ""
+            }));
+        }
+        let mut name = command.name.to_string();
+        name[..1].make_ascii_uppercase();
+        tasks.push(json!({
+            "label": format!("{}{}", VSCODE_TASK_PREFIX, name),
+            "detail": command.description,
+            "type": "shell",
+            "command": command_line,
+            "problemMatcher": []
+        }));
+    }
+
+    doc["tasks"] = Value::Array(tasks);
+    doc["inputs"] = Value::Array(inputs);
+    let mut content = serde_json::to_string_pretty(&doc).unwrap_or_default();
+    content.push('\n');
+    content
+}
 
-    Ok(command_path)
+/// Write `ide`'s command files under `root`, returning their paths
+pub fn generate_commands(root: &Path, ide: Ide) -> Result<Vec<PathBuf>> {
+    let mut generated = Vec::new();
+    for file in ide.render(root)? {
+        let path = root.join(&file.path);
+        if let Some(parent) = path.parent() {
+            utils::ensure_dir(parent)?;
+        }
+        fs::write(&path, &file.content)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        generated.push(path);
+    }
+    Ok(generated)
 }
 
-/// Generate all IDE command files
+/// Generate all IDE command files in the current project
 pub fn generate_all_commands(ide_types: &[String]) -> Result<Vec<PathBuf>> {
+    let root = utils::project_root()?;
     let mut generated = Vec::new();
-    
+
     for ide_type in ide_types {
-        generated.push(generate_discuss_command(ide_type)?);
-        generated.push(generate_generate_command(ide_type)?);
-        generated.push(generate_plan_command(ide_type)?);
-        generated.push(generate_implement_command(ide_type)?);
-        generated.push(generate_next_command(ide_type)?);
-        generated.push(generate_progress_command(ide_type)?);
+        let ide = Ide::parse(ide_type)
+            .ok_or_else(|| anyhow::anyhow!("Unsupported IDE '{}'", ide_type))?;
+        generated.extend(generate_commands(&root, ide)?);
     }
 
     Ok(generated)
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gemini_prompt_is_valid_toml_string() {
+        let command = CommandSpec {
+            name: "demo",
+            description: "Say \"hi\"",
+            argument_hint: Some("<name>"),
+            body: "Path C:\\doplan and \"\"\" quotes\n",
+        };
+        let rendered = gemini_command(&command);
+        assert!(rendered.starts_with("description = \"Say \\\"hi\\\"\"\n"));
+        assert!(rendered.contains("C:\\\\doplan"));
+        assert!(rendered.contains("\\\"\\\"\\\" quotes"));
+        assert!(rendered.contains("`doplan execute demo {{args}}`"));
+    }
+
+    #[test]
+    fn test_vscode_tasks_keep_user_tasks() {
+        let existing = json!({
+            "version": "2.0.0",
+            "tasks": [
+                { "label": "build", "type": "shell", "command": "cargo build" },
+                { "label": "DoPlan: Old", "type": "shell", "command": "doplan" }
+            ],
+            "inputs": [{ "id": "mine", "type": "promptString" }]
+        });
+        let doc: Value = serde_json::from_str(&vscode_tasks(Some(existing))).unwrap();
+        let labels: Vec<&str> = doc["tasks"].as_array().unwrap().iter().map(|t| t["label"].as_str().unwrap()).collect();
+        assert_eq!(labels[0], "build");
+        assert!(!labels.contains(&"DoPlan: Old"));
+        assert_eq!(labels.len(), 1 + COMMANDS.len());
+        assert_eq!(doc["inputs"][0]["id"], "mine");
+    }
+}
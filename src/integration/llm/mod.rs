@@ -0,0 +1,300 @@
+//! Optional LLM provider for refining the idea and drafting plan content.
+//!
+//! Nothing here runs unless a provider is configured (see
+//! [`openai::OpenAiProvider::from_config`]). Every request renders a template
+//! from [`prompts`], asks for a JSON reply and checks it against a schema with
+//! [`schema::validate`] before any of it reaches the project state. Callers
+//! fall back to the rule-based content when a request fails.
+
+pub mod openai;
+pub mod prompts;
+pub mod schema;
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::future::Future;
+use crate::config::Config;
+use crate::state::estimate::Estimate;
+use crate::state::{AcceptanceCriterion, Feature, ProjectState, Task, UserStory};
+use prompts::PromptTemplate;
+
+pub use openai::OpenAiProvider;
+
+/// A chat model that answers a system and a user message with text
+pub trait LlmProvider {
+    fn complete(&self, system: &str, user: &str) -> impl Future<Output = Result<String>> + Send;
+}
+
+/// The provider configured for the current project, if any
+pub fn configured() -> Result<Option<OpenAiProvider>> {
+    Ok(OpenAiProvider::from_config(&Config::current()?.llm))
+}
+
+/// Suggestions for improving the plan, shown by `/discuss`
+pub async fn suggest_improvements<P: LlmProvider>(provider: &P, state: &ProjectState) -> Result<Vec<String>> {
+    #[derive(Deserialize)]
+    struct Reply {
+        improvements: Vec<String>,
+    }
+
+    let schema = json!({
+        "type": "object",
+        "required": ["improvements"],
+        "additionalProperties": false,
+        "properties": {
+            "improvements": {
+                "type": "array",
+                "minItems": 1,
+                "maxItems": 10,
+                "items": { "type": "string", "minLength": 1, "maxLength": 300 }
+            }
+        }
+    });
+    let values = BTreeMap::from([("features", feature_list(state.features.iter().flatten()))]);
+    let reply: Reply = request(provider, &prompts::IMPROVEMENTS, &schema, values, state).await?;
+    Ok(reply.improvements.into_iter().map(|s| s.trim().to_string()).collect())
+}
+
+/// Descriptions for `features`, keyed by feature id. Features the model
+/// skipped are missing from the map.
+pub async fn describe_features<P: LlmProvider>(
+    provider: &P,
+    state: &ProjectState,
+    features: &[&Feature],
+) -> Result<BTreeMap<String, String>> {
+    #[derive(Deserialize)]
+    struct Reply {
+        descriptions: Vec<Description>,
+    }
+    #[derive(Deserialize)]
+    struct Description {
+        id: String,
+        description: String,
+    }
+
+    let ids: Vec<&str> = features.iter().map(|f| f.id.as_str()).collect();
+    let schema = json!({
+        "type": "object",
+        "required": ["descriptions"],
+        "additionalProperties": false,
+        "properties": {
+            "descriptions": {
+                "type": "array",
+                "maxItems": features.len(),
+                "items": {
+                    "type": "object",
+                    "required": ["id", "description"],
+                    "additionalProperties": false,
+                    "properties": {
+                        "id": { "enum": ids },
+                        "description": { "type": "string", "minLength": 1, "maxLength": 1000 }
+                    }
+                }
+            }
+        }
+    });
+    let values = BTreeMap::from([("features", feature_list(features.iter().copied()))]);
+    let reply: Reply = request(provider, &prompts::FEATURE_DESCRIPTIONS, &schema, values, state).await?;
+    Ok(reply
+        .descriptions
+        .into_iter()
+        .map(|d| (d.id, d.description.trim().to_string()))
+        .collect())
+}
+
+/// User stories for `feature`, rendered into its `plan.md`
+pub async fn user_stories<P: LlmProvider>(provider: &P, state: &ProjectState, feature: &Feature) -> Result<Vec<UserStory>> {
+    #[derive(Deserialize)]
+    struct Reply {
+        user_stories: Vec<UserStory>,
+    }
+
+    let text = json!({ "type": "string", "minLength": 1, "maxLength": 300 });
+    let schema = json!({
+        "type": "object",
+        "required": ["user_stories"],
+        "additionalProperties": false,
+        "properties": {
+            "user_stories": {
+                "type": "array",
+                "minItems": 1,
+                "maxItems": 8,
+                "items": {
+                    "type": "object",
+                    "required": ["role", "goal", "benefit"],
+                    "additionalProperties": false,
+                    "properties": { "role": text, "goal": text, "benefit": text }
+                }
+            }
+        }
+    });
+    let reply: Reply = request(provider, &prompts::USER_STORIES, &schema, feature_values(feature), state).await?;
+    Ok(reply.user_stories)
+}
+
+/// Tasks for `feature`, numbered `T1`, `T2`, ... in the order given
+pub async fn break_down_tasks<P: LlmProvider>(provider: &P, state: &ProjectState, feature: &Feature) -> Result<Vec<Task>> {
+    #[derive(Deserialize)]
+    struct Reply {
+        tasks: Vec<DraftTask>,
+    }
+    #[derive(Deserialize)]
+    struct DraftTask {
+        title: String,
+        description: String,
+        priority: String,
+        estimate: String,
+        #[serde(default)]
+        acceptance_criteria: Vec<String>,
+        #[serde(default)]
+        depends_on: Vec<String>,
+    }
+
+    let text = json!({ "type": "string", "minLength": 1, "maxLength": 300 });
+    let schema = json!({
+        "type": "object",
+        "required": ["tasks"],
+        "additionalProperties": false,
+        "properties": {
+            "tasks": {
+                "type": "array",
+                "minItems": 1,
+                "maxItems": 15,
+                "items": {
+                    "type": "object",
+                    "required": ["title", "description", "priority", "estimate"],
+                    "additionalProperties": false,
+                    "properties": {
+                        "title": { "type": "string", "minLength": 1, "maxLength": 80 },
+                        "description": { "type": "string", "maxLength": 1000 },
+                        "priority": { "enum": ["High", "Medium", "Low"] },
+                        "estimate": { "type": "string", "minLength": 1, "maxLength": 30 },
+                        "acceptance_criteria": { "type": "array", "maxItems": 8, "items": text },
+                        "depends_on": { "type": "array", "items": { "type": "string" } }
+                    }
+                }
+            }
+        }
+    });
+    let reply: Reply = request(provider, &prompts::TASK_BREAKDOWN, &schema, feature_values(feature), state).await?;
+
+    let mut tasks = Vec::new();
+    for (i, draft) in reply.tasks.into_iter().enumerate() {
+        let id = format!("T{}", i + 1);
+        if Estimate::parse(&draft.estimate).is_none() {
+            anyhow::bail!(
+                "The model's task_breakdown reply failed validation: $.tasks[{}].estimate: '{}' is not an estimate",
+                i,
+                draft.estimate
+            );
+        }
+        for dependency in &draft.depends_on {
+            let earlier = dependency
+                .trim()
+                .strip_prefix('T')
+                .and_then(|n| n.parse::<usize>().ok())
+                .is_some_and(|n| n >= 1 && n <= i);
+            if !earlier {
+                anyhow::bail!(
+                    "The model's task_breakdown reply failed validation: $.tasks[{}].depends_on: '{}' is not an earlier task",
+                    i,
+                    dependency
+                );
+            }
+        }
+        tasks.push(Task {
+            priority: draft.priority,
+            description: draft.description.trim().to_string(),
+            estimate: Some(draft.estimate.trim().to_string()),
+            acceptance_criteria: draft
+                .acceptance_criteria
+                .into_iter()
+                .map(|text| AcceptanceCriterion { text: text.trim().to_string(), done: false })
+                .collect(),
+            depends_on: draft.depends_on.into_iter().map(|d| d.trim().to_string()).collect(),
+            ..Task::new(id, draft.title.trim())
+        });
+    }
+    Ok(tasks)
+}
+
+/// Render `template`, send it and parse the reply once it matches `schema`
+async fn request<P: LlmProvider, T: DeserializeOwned>(
+    provider: &P,
+    template: &PromptTemplate,
+    schema: &Value,
+    mut values: BTreeMap<&str, String>,
+    state: &ProjectState,
+) -> Result<T> {
+    values.insert("context", prompts::project_context(&context_values(state))?);
+    values.insert("schema", serde_json::to_string_pretty(schema)?);
+    let (system, user) = template.render(&values)?;
+
+    let reply = provider
+        .complete(&system, &user)
+        .await
+        .with_context(|| format!("The {} request failed", template.name))?;
+    let value: Value = serde_json::from_str(strip_code_fence(&reply))
+        .with_context(|| format!("The model's {} reply is not JSON", template.name))?;
+    schema::validate(&value, schema)
+        .map_err(|error| anyhow::anyhow!("The model's {} reply failed validation: {}", template.name, error))?;
+    serde_json::from_value(value)
+        .with_context(|| format!("The model's {} reply failed validation", template.name))
+}
+
+/// Models asked for bare JSON still wrap it in a fence now and then
+fn strip_code_fence(reply: &str) -> &str {
+    let reply = reply.trim();
+    match reply.strip_prefix("```") {
+        Some(rest) => {
+            let body = rest.split_once('\n').map_or("", |(_, body)| body);
+            body.trim_end().strip_suffix("```").unwrap_or(body).trim()
+        }
+        None => reply,
+    }
+}
+
+fn context_values(state: &ProjectState) -> BTreeMap<&'static str, String> {
+    let discussion: Vec<String> = state
+        .discussion
+        .iter()
+        .filter(|entry| !entry.answer.trim().is_empty())
+        .map(|entry| format!("- {}: {}", entry.question, entry.answer.trim()))
+        .collect();
+    BTreeMap::from([
+        ("project_name", state.project_name.clone().unwrap_or_default()),
+        ("idea", state.idea.clone().unwrap_or_default()),
+        ("tech_stack", state.tech_stack.as_deref().unwrap_or_default().join(", ")),
+        ("discussion", if discussion.is_empty() { "(none)".to_string() } else { discussion.join("\n") }),
+    ])
+}
+
+fn feature_list<'a>(features: impl Iterator<Item = &'a Feature>) -> String {
+    features
+        .map(|f| format!("- {} ({}, {} priority): {}", f.id, f.name, f.priority, f.description))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn feature_values(feature: &Feature) -> BTreeMap<&'static str, String> {
+    BTreeMap::from([
+        ("feature_name", feature.name.clone()),
+        ("feature_priority", feature.priority.clone()),
+        ("feature_description", feature.description.clone()),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_code_fence() {
+        assert_eq!(strip_code_fence(" {\"a\": 1} "), "{\"a\": 1}");
+        assert_eq!(strip_code_fence("```json\n{\"a\": 1}\n```"), "{\"a\": 1}");
+        assert_eq!(strip_code_fence("```\n{}\n```\n"), "{}");
+    }
+}
@@ -0,0 +1,83 @@
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::future::Future;
+use std::time::Duration;
+use super::LlmProvider;
+use crate::config::LlmConfig;
+
+pub const DEFAULT_MODEL: &str = "gpt-4o-mini";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Client for any server implementing the OpenAI chat completions API
+#[derive(Debug, Clone)]
+pub struct OpenAiProvider {
+    http: reqwest::Client,
+    base_url: String,
+    model: String,
+    api_key: Option<String>,
+}
+
+impl OpenAiProvider {
+    pub fn new(base_url: &str, model: &str, api_key: Option<String>) -> Self {
+        Self {
+            http: reqwest::Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            model: model.to_string(),
+            api_key: api_key.filter(|key| !key.is_empty()),
+        }
+    }
+
+    /// The provider described by the `[llm]` settings, or `None` when
+    /// `llm.base_url` is not set
+    pub fn from_config(config: &LlmConfig) -> Option<Self> {
+        let base_url = config.base_url.as_deref().filter(|url| !url.trim().is_empty())?;
+        Some(Self::new(base_url, &config.model, config.api_key.clone()))
+    }
+
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+}
+
+impl LlmProvider for OpenAiProvider {
+    fn complete(&self, system: &str, user: &str) -> impl Future<Output = Result<String>> + Send {
+        let url = format!("{}/chat/completions", self.base_url);
+        let body = json!({
+            "model": self.model,
+            "temperature": 0.2,
+            "response_format": { "type": "json_object" },
+            "messages": [
+                { "role": "system", "content": system },
+                { "role": "user", "content": user }
+            ]
+        });
+        let mut request = self.http.post(url.clone()).json(&body);
+        if let Some(key) = &self.api_key {
+            request = request.bearer_auth(key);
+        }
+
+        async move {
+            let response = request
+                .send()
+                .await
+                .with_context(|| format!("Request to {} failed", url))?;
+            let status = response.status();
+            let reply: Value = response
+                .json()
+                .await
+                .with_context(|| format!("{} returned a response that is not JSON", url))?;
+            if !status.is_success() {
+                let message = reply["error"]["message"].as_str().unwrap_or("no message");
+                anyhow::bail!("{} returned {}: {}", url, status, message);
+            }
+            reply["choices"][0]["message"]["content"]
+                .as_str()
+                .map(str::to_string)
+                .with_context(|| format!("{} returned no message content", url))
+        }
+    }
+}
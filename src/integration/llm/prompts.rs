@@ -0,0 +1,119 @@
+//! Prompt templates. Placeholders are written `{{name}}` and every one must
+//! be given a value when the template is rendered.
+
+use anyhow::Result;
+use std::collections::BTreeMap;
+
+pub struct PromptTemplate {
+    pub name: &'static str,
+    pub system: &'static str,
+    pub user: &'static str,
+}
+
+const SYSTEM: &str = "You are a senior product manager and software architect helping plan a software project. \
+Reply with a single JSON document that matches the JSON Schema in the request and nothing else: no prose and no code fences.";
+
+const PROJECT_CONTEXT: &str = "Project: {{project_name}}
+Idea: {{idea}}
+Tech stack: {{tech_stack}}
+
+Discussion:
+{{discussion}}
+";
+
+pub const IMPROVEMENTS: PromptTemplate = PromptTemplate {
+    name: "improvements",
+    system: SYSTEM,
+    user: "{{context}}
+Features:
+{{features}}
+
+Suggest concrete improvements to this plan: missing capabilities, risks to address early and ways to reduce scope for a first release. Each suggestion is one sentence.
+
+JSON Schema:
+{{schema}}",
+};
+
+pub const FEATURE_DESCRIPTIONS: PromptTemplate = PromptTemplate {
+    name: "feature_descriptions",
+    system: SYSTEM,
+    user: "{{context}}
+Write a two or three sentence description of what each of these features does for its users. Keep the ids unchanged.
+
+Features:
+{{features}}
+
+JSON Schema:
+{{schema}}",
+};
+
+pub const USER_STORIES: PromptTemplate = PromptTemplate {
+    name: "user_stories",
+    system: SYSTEM,
+    user: "{{context}}
+Write the user stories for the feature \"{{feature_name}}\" ({{feature_priority}} priority): {{feature_description}}
+
+Each story has a role (\"a returning customer\"), a goal (\"to reorder a previous purchase\") and a benefit (\"I save time\").
+
+JSON Schema:
+{{schema}}",
+};
+
+pub const TASK_BREAKDOWN: PromptTemplate = PromptTemplate {
+    name: "task_breakdown",
+    system: SYSTEM,
+    user: "{{context}}
+Break the feature \"{{feature_name}}\" ({{feature_priority}} priority) into implementation tasks: {{feature_description}}
+
+List the tasks in the order they should be done. Estimates are written like \"2 hours\" or \"1 day\". A task may only depend on tasks listed before it, referenced by position as T1, T2 and so on.
+
+JSON Schema:
+{{schema}}",
+};
+
+impl PromptTemplate {
+    /// The system and user messages with every placeholder filled in
+    pub fn render(&self, values: &BTreeMap<&str, String>) -> Result<(String, String)> {
+        Ok((render(self.system, values)?, render(self.user, values)?))
+    }
+}
+
+/// Common placeholder filling `{{context}}` in every template
+pub fn project_context(values: &BTreeMap<&str, String>) -> Result<String> {
+    render(PROJECT_CONTEXT, values)
+}
+
+/// Replace every `{{name}}` in `template` with its value
+pub fn render(template: &str, values: &BTreeMap<&str, String>) -> Result<String> {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after
+            .find("}}")
+            .ok_or_else(|| anyhow::anyhow!("Unclosed placeholder in prompt template: {}", &rest[start..]))?;
+        let name = after[..end].trim();
+        let value = values
+            .get(name)
+            .ok_or_else(|| anyhow::anyhow!("No value for prompt placeholder {{{{{}}}}}", name))?;
+        output.push_str(value);
+        rest = &after[end + 2..];
+    }
+    output.push_str(rest);
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let values = BTreeMap::from([("name", "Recipe {{Box}}".to_string())]);
+        // Values are inserted as-is, not rendered again
+        assert_eq!(render("Hi {{name}}, {{ name }}!", &values).unwrap(), "Hi Recipe {{Box}}, Recipe {{Box}}!");
+        assert_eq!(render("{{other}}", &values).unwrap_err().to_string(), "No value for prompt placeholder {{other}}");
+        assert!(render("{{name", &values).is_err());
+    }
+}
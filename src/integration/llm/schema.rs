@@ -0,0 +1,136 @@
+//! The subset of JSON Schema used to check model replies.
+//!
+//! Supported keywords: `type` (`object`, `array`, `string`, `integer`,
+//! `number`, `boolean`), `properties`, `required`, `additionalProperties:
+//! false`, `items`, `minItems`, `maxItems`, `minLength`, `maxLength` and
+//! `enum`. Anything else in a schema is ignored.
+
+use serde_json::Value;
+
+/// Check `value` against `schema`. The error names the first offending path,
+/// such as `$.tasks[2].title: expected a string`.
+pub fn validate(value: &Value, schema: &Value) -> Result<(), String> {
+    check(value, schema, "$")
+}
+
+fn check(value: &Value, schema: &Value, path: &str) -> Result<(), String> {
+    if let Some(expected) = schema["type"].as_str() {
+        let matches = match expected {
+            "object" => value.is_object(),
+            "array" => value.is_array(),
+            "string" => value.is_string(),
+            "integer" => value.is_i64() || value.is_u64(),
+            "number" => value.is_number(),
+            "boolean" => value.is_boolean(),
+            _ => true,
+        };
+        if !matches {
+            return Err(format!("{}: expected {} {}", path, article(expected), expected));
+        }
+    }
+
+    if let Some(allowed) = schema["enum"].as_array() {
+        if !allowed.contains(value) {
+            let allowed: Vec<String> = allowed.iter().map(Value::to_string).collect();
+            return Err(format!("{}: {} is not one of {}", path, value, allowed.join(", ")));
+        }
+    }
+
+    match value {
+        Value::Object(object) => {
+            for name in schema["required"].as_array().into_iter().flatten().filter_map(Value::as_str) {
+                if !object.contains_key(name) {
+                    return Err(format!("{}: missing required property '{}'", path, name));
+                }
+            }
+            let properties = schema["properties"].as_object();
+            for (name, property) in object {
+                match properties.and_then(|p| p.get(name)) {
+                    Some(property_schema) => check(property, property_schema, &format!("{}.{}", path, name))?,
+                    None if schema["additionalProperties"] == Value::Bool(false) => {
+                        return Err(format!("{}: unexpected property '{}'", path, name));
+                    }
+                    None => {}
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(min) = schema["minItems"].as_u64() {
+                if (items.len() as u64) < min {
+                    return Err(format!("{}: expected at least {} item(s), got {}", path, min, items.len()));
+                }
+            }
+            if let Some(max) = schema["maxItems"].as_u64() {
+                if items.len() as u64 > max {
+                    return Err(format!("{}: expected at most {} item(s), got {}", path, max, items.len()));
+                }
+            }
+            if !schema["items"].is_null() {
+                for (i, item) in items.iter().enumerate() {
+                    check(item, &schema["items"], &format!("{}[{}]", path, i))?;
+                }
+            }
+        }
+        Value::String(text) => {
+            let length = text.trim().chars().count() as u64;
+            if let Some(min) = schema["minLength"].as_u64() {
+                if length < min {
+                    return Err(format!("{}: expected at least {} character(s)", path, min));
+                }
+            }
+            if let Some(max) = schema["maxLength"].as_u64() {
+                if length > max {
+                    return Err(format!("{}: expected at most {} characters, got {}", path, max, length));
+                }
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn article(type_name: &str) -> &'static str {
+    match type_name {
+        "object" | "array" | "integer" => "an",
+        _ => "a",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_validate() {
+        let schema = json!({
+            "type": "object",
+            "required": ["items"],
+            "additionalProperties": false,
+            "properties": {
+                "items": {
+                    "type": "array",
+                    "minItems": 1,
+                    "items": {
+                        "type": "object",
+                        "required": ["title"],
+                        "properties": {
+                            "title": { "type": "string", "minLength": 1, "maxLength": 10 },
+                            "kind": { "enum": ["a", "b"] }
+                        }
+                    }
+                }
+            }
+        });
+
+        assert!(validate(&json!({ "items": [{ "title": "ok", "kind": "a", "extra": 1 }] }), &schema).is_ok());
+        let error = |value: Value| validate(&value, &schema).unwrap_err();
+        assert_eq!(error(json!([])), "$: expected an object");
+        assert_eq!(error(json!({})), "$: missing required property 'items'");
+        assert_eq!(error(json!({ "items": [], })), "$.items: expected at least 1 item(s), got 0");
+        assert_eq!(error(json!({ "items": [{ "title": 3 }] })), "$.items[0].title: expected a string");
+        assert_eq!(error(json!({ "items": [{ "title": "  " }] })), "$.items[0].title: expected at least 1 character(s)");
+        assert_eq!(error(json!({ "items": [{ "title": "x", "kind": "c" }] })), "$.items[0].kind: \"c\" is not one of \"a\", \"b\"");
+        assert_eq!(error(json!({ "items": [{ "title": "x" }], "more": true })), "$: unexpected property 'more'");
+    }
+}
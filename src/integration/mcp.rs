@@ -0,0 +1,425 @@
+//! Model Context Protocol server for `doplan mcp`.
+//!
+//! Speaks JSON-RPC 2.0 over stdio, one message per line. Tools wrap the same
+//! functions the slash commands use; resources expose the PRD, every file
+//! under `doplan/plan` and `.doplan/dashboard.json`. Nothing else may be
+//! written to stdout while the server runs, so diagnostics go to stderr.
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::fs;
+use std::io::{BufRead, Write};
+use std::path::{Component, Path, PathBuf};
+use crate::commands::next::{self, NextAction};
+use crate::commands::{feature, plan, progress};
+use crate::config::Config;
+use crate::state::{ProgressWeighting, ProjectState, TaskStatus};
+use crate::utils;
+use crate::utils::plan_dirs;
+
+/// Protocol revisions this server understands, newest first
+pub const PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+const PRD_URI: &str = "doplan://prd";
+const DASHBOARD_URI: &str = "doplan://dashboard";
+const PLAN_URI_PREFIX: &str = "doplan://plan/";
+
+/// A JSON-RPC error returned instead of a result
+#[derive(Debug)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self { code, message: message.into() }
+    }
+}
+
+/// Serve requests from stdin until it is closed
+pub fn serve_stdio() -> Result<()> {
+    let stdin = std::io::stdin();
+    let stdout = std::io::stdout();
+    serve(stdin.lock(), stdout.lock())
+}
+
+/// Serve line-delimited JSON-RPC messages from `reader`, writing responses to
+/// `writer`. Returns when `reader` reaches end of input.
+pub fn serve<R: BufRead, W: Write>(reader: R, mut writer: W) -> Result<()> {
+    for line in reader.lines() {
+        let line = line.context("Failed to read MCP message")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = handle_message(&line) {
+            serde_json::to_writer(&mut writer, &response).context("Failed to write MCP response")?;
+            writer.write_all(b"\n")?;
+            writer.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// Handle one raw message. Notifications get no response.
+pub fn handle_message(line: &str) -> Option<Value> {
+    let message: Value = match serde_json::from_str(line) {
+        Ok(message) => message,
+        Err(err) => return Some(error_response(Value::Null, RpcError::new(PARSE_ERROR, err.to_string()))),
+    };
+    let id = message.get("id").cloned();
+    let Some(method) = message["method"].as_str() else {
+        return Some(error_response(id.unwrap_or(Value::Null), RpcError::new(INVALID_REQUEST, "Missing method")));
+    };
+    let params = message.get("params").cloned().unwrap_or_else(|| json!({}));
+
+    let result = dispatch(method, &params);
+    let id = id?;
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(err) => error_response(id, err),
+    })
+}
+
+fn error_response(id: Value, err: RpcError) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": err.code, "message": err.message } })
+}
+
+fn dispatch(method: &str, params: &Value) -> std::result::Result<Value, RpcError> {
+    match method {
+        "initialize" => Ok(initialize(params)),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+        "tools/call" => {
+            let name = params["name"]
+                .as_str()
+                .ok_or_else(|| RpcError::new(INVALID_PARAMS, "Missing tool name"))?;
+            let arguments = params.get("arguments").cloned().unwrap_or_else(|| json!({}));
+            // Tool failures are reported to the model, not as protocol errors
+            Ok(match call_tool(name, &arguments) {
+                Ok(Some(value)) => tool_result(&value, false),
+                Ok(None) => return Err(RpcError::new(INVALID_PARAMS, format!("Unknown tool: {}", name))),
+                Err(err) => json!({ "content": [{ "type": "text", "text": format!("{:#}", err) }], "isError": true }),
+            })
+        }
+        "resources/list" => list_resources()
+            .map(|resources| json!({ "resources": resources }))
+            .map_err(|err| RpcError::new(INVALID_PARAMS, format!("{:#}", err))),
+        "resources/read" => {
+            let uri = params["uri"]
+                .as_str()
+                .ok_or_else(|| RpcError::new(INVALID_PARAMS, "Missing resource uri"))?;
+            read_resource(uri).map_err(|err| RpcError::new(INVALID_PARAMS, format!("{:#}", err)))
+        }
+        method if method.starts_with("notifications/") => Ok(Value::Null),
+        _ => Err(RpcError::new(METHOD_NOT_FOUND, format!("Method not found: {}", method))),
+    }
+}
+
+fn initialize(params: &Value) -> Value {
+    let requested = params["protocolVersion"].as_str().unwrap_or_default();
+    let version = PROTOCOL_VERSIONS
+        .iter()
+        .find(|v| **v == requested)
+        .unwrap_or(&PROTOCOL_VERSIONS[0]);
+    json!({
+        "protocolVersion": version,
+        "capabilities": { "tools": {}, "resources": {} },
+        "serverInfo": { "name": "doplan", "version": env!("CARGO_PKG_VERSION") },
+        "instructions": "DoPlan tracks this project's phases, features and tasks. Use next_task to pick work, \
+                         set_task_status as tasks move, and update_progress to refresh the dashboard."
+    })
+}
+
+fn tool_result(value: &Value, is_error: bool) -> Value {
+    let text = serde_json::to_string_pretty(value).unwrap_or_default();
+    json!({ "content": [{ "type": "text", "text": text }], "isError": is_error })
+}
+
+fn tool_definitions() -> Value {
+    let statuses: Vec<&str> = TaskStatus::ALL.iter().map(|s| s.as_str()).collect();
+    let weightings: Vec<&str> = ProgressWeighting::ALL.iter().map(|w| w.as_str()).collect();
+    let priorities = Config::current().unwrap_or_default().plan.priorities;
+    json!([
+        {
+            "name": "next_task",
+            "description": "Recommend the next task to work on, skipping tasks whose prerequisites are incomplete",
+            "inputSchema": { "type": "object", "properties": {} }
+        },
+        {
+            "name": "set_task_status",
+            "description": "Set a task's status in its tasks.md and in .doplan/state.json",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "task": { "type": "string", "description": "Task reference, e.g. F-0001/T2" },
+                    "status": { "type": "string", "enum": statuses }
+                },
+                "required": ["task", "status"]
+            }
+        },
+        {
+            "name": "add_feature",
+            "description": "Add a feature to the project state. Run /plan afterwards to create its plan files.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string" },
+                    "description": { "type": "string" },
+                    "priority": { "type": "string", "enum": priorities },
+                    "depends_on": { "type": "array", "items": { "type": "string" }, "description": "Ids or names of features this one depends on" },
+                    "phase": { "type": "string", "description": "Id or name of the phase to add the feature to" }
+                },
+                "required": ["name"]
+            }
+        },
+        {
+            "name": "update_progress",
+            "description": "Recalculate progress from tasks.md files and regenerate progress.json files and the dashboard",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "weighting": { "type": "string", "enum": weightings }
+                }
+            }
+        },
+        {
+            "name": "read_plan",
+            "description": "Phases, features and tasks with their statuses, or one feature's plan.md, design.md and tasks.md",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "feature": { "type": "string", "description": "Feature id or name; omit for the whole plan" }
+                }
+            }
+        }
+    ])
+}
+
+/// Run a tool; `Ok(None)` when there is no tool called `name`
+fn call_tool(name: &str, args: &Value) -> Result<Option<Value>> {
+    let string_arg = |key: &str| args[key].as_str().map(str::to_string);
+    let required = |key: &str| string_arg(key).ok_or_else(|| anyhow::anyhow!("Missing argument: {}", key));
+
+    let value = match name {
+        "next_task" => next_task()?,
+        "set_task_status" => set_task_status(&required("task")?, &required("status")?)?,
+        "add_feature" => {
+            let depends_on: Vec<String> = args["depends_on"]
+                .as_array()
+                .map(|deps| deps.iter().filter_map(|d| d.as_str().map(str::to_string)).collect())
+                .unwrap_or_default();
+            let config = Config::current()?;
+            let priority = match string_arg("priority") {
+                Some(priority) if config.plan.priorities.contains(&priority) => priority,
+                Some(priority) => anyhow::bail!(
+                    "Unknown priority '{}' (expected {})",
+                    priority,
+                    config.plan.priorities.join(", ")
+                ),
+                None => config.middle_priority().to_string(),
+            };
+            add_feature(
+                &required("name")?,
+                string_arg("description").unwrap_or_default(),
+                priority,
+                &depends_on,
+                string_arg("phase").as_deref(),
+            )?
+        }
+        "update_progress" => update_progress(string_arg("weighting").as_deref())?,
+        "read_plan" => read_plan(string_arg("feature").as_deref())?,
+        _ => return Ok(None),
+    };
+    Ok(Some(value))
+}
+
+fn next_task() -> Result<Value> {
+    let mut state = ProjectState::load().context("Failed to load project state")?;
+    Ok(match next::recommend(&mut state)? {
+        NextAction::NoPlan => json!({ "status": "no_plan", "message": "No plan structure found. Run /plan first." }),
+        NextAction::AllComplete => json!({ "status": "all_complete", "message": "All tasks are complete." }),
+        NextAction::AllBlocked { waiting } => json!({
+            "status": "all_blocked",
+            "message": format!("All {} incomplete tasks are waiting on prerequisites.", waiting)
+        }),
+        NextAction::Task(rec) => json!({
+            "status": "task",
+            "task": rec.task_ref.to_string(),
+            "action": rec.action,
+            "task_name": rec.task_name,
+            "feature": rec.feature_name,
+            "phase": rec.phase_name,
+            "priority": rec.priority,
+            "estimated_effort": rec.estimated_effort,
+            "feature_path": rec.feature_path,
+            "reason": rec.reason
+        }),
+    })
+}
+
+fn set_task_status(reference: &str, status: &str) -> Result<Value> {
+    let (feature, task) = match reference.split_once('/') {
+        Some((feature, task)) if !feature.trim().is_empty() && !task.trim().is_empty() => (feature.trim(), task.trim()),
+        _ => anyhow::bail!("Expected a task reference like F-0001/T2, got '{}'", reference),
+    };
+    let status = TaskStatus::parse(status)
+        .ok_or_else(|| anyhow::anyhow!("Unknown status '{}' (expected not_started, in_progress, completed or blocked)", status))?;
+
+    let mut state = ProjectState::load().context("Failed to load project state")?;
+    let plan_dir = utils::doplan_dir()?.join("plan");
+    plan_dirs::set_task_status(&plan_dir, &mut state, feature, task, status)?;
+    state.save().context("Failed to save project state")?;
+
+    Ok(json!({ "task": format!("{}/{}", feature, task), "status": status.as_str() }))
+}
+
+fn add_feature(name: &str, description: String, priority: String, depends_on: &[String], phase: Option<&str>) -> Result<Value> {
+    let mut state = ProjectState::load().context("Failed to load project state")?;
+    if state.project_name.is_none() {
+        anyhow::bail!("Project state is incomplete: missing project_name. Run /discuss first.");
+    }
+    let depends_on = feature::resolve_feature_dependencies(&state, &depends_on.join(","))?;
+    let phase_id = match phase {
+        Some(key) => Some(
+            state
+                .find_phase(key)
+                .map(|p| p.id.clone())
+                .ok_or_else(|| anyhow::anyhow!("Phase '{}' not found", key))?,
+        ),
+        None => None,
+    };
+
+    let id = feature::create_feature(&mut state, name, description, priority, depends_on)?;
+    if let Some(phase_id) = &phase_id {
+        if let Some(phase) = state.phases.iter_mut().flatten().find(|p| &p.id == phase_id) {
+            phase.features.push(id.clone());
+        }
+        state.save().context("Failed to save project state")?;
+    }
+
+    Ok(json!({ "id": id, "name": name, "phase": phase_id, "next": "Run /plan to create the feature's plan files" }))
+}
+
+fn update_progress(weighting: Option<&str>) -> Result<Value> {
+    let mut state = ProjectState::load().context("Failed to load project state")?;
+    let weighting = match weighting {
+        Some(mode) => ProgressWeighting::parse(mode).ok_or_else(|| {
+            anyhow::anyhow!("Unknown weighting mode '{}' (expected count, estimate or priority)", mode)
+        })?,
+        None => Config::current()?.progress.weighting,
+    };
+    let plan_dir = utils::doplan_dir()?.join("plan");
+    if !plan_dir.exists() {
+        anyhow::bail!("No plan structure found. Run /plan first.");
+    }
+
+    let (phases, forecast) = progress::regenerate(&plan_dir, &mut state, weighting)?;
+    Ok(json!({
+        "overall_progress": progress::overall_progress(&phases),
+        "weighting": weighting.as_str(),
+        "phases": phases.iter().map(|p| json!({
+            "id": p.phase_id,
+            "name": p.phase_name,
+            "progress": p.progress,
+            "features": p.features.iter().map(|f| json!({
+                "id": f.feature_id,
+                "name": f.feature_name,
+                "progress": f.progress,
+                "status": f.status
+            })).collect::<Vec<_>>()
+        })).collect::<Vec<_>>(),
+        "projected_finish": forecast.map(|f| f.project.describe())
+    }))
+}
+
+fn read_plan(feature: Option<&str>) -> Result<Value> {
+    let mut state = ProjectState::load().context("Failed to load project state")?;
+    let plan_dir = utils::doplan_dir()?.join("plan");
+    let layout = plan_dirs::sync_tasks(&plan_dir, &mut state)?;
+
+    if let Some(key) = feature {
+        let feature = state
+            .find_feature(key)
+            .ok_or_else(|| anyhow::anyhow!("Feature '{}' not found", key))?;
+        let (_, feature_dir) = layout
+            .feature(&feature.id)
+            .ok_or_else(|| anyhow::anyhow!("No plan directory for {}. Run /plan first.", feature.id))?;
+        let read = |name: &str| fs::read_to_string(feature_dir.path.join(name)).ok();
+        return Ok(json!({
+            "id": feature.id,
+            "name": feature.name,
+            "plan": read("plan.md"),
+            "design": read("design.md"),
+            "tasks": read("tasks.md")
+        }));
+    }
+
+    Ok(plan::plan_tree(&state))
+}
+
+fn mime_type(path: &Path) -> &'static str {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("json") => "application/json",
+        Some("md") => "text/markdown",
+        _ => "text/plain",
+    }
+}
+
+fn list_resources() -> Result<Vec<Value>> {
+    let doplan_dir = utils::doplan_dir()?;
+    let mut resources = Vec::new();
+
+    let prd = doplan_dir.join("PRD.md");
+    if prd.exists() {
+        resources.push(json!({ "uri": PRD_URI, "name": "PRD.md", "description": "Product Requirements Document", "mimeType": "text/markdown" }));
+    }
+    let dashboard = utils::dot_doplan_dir()?.join("dashboard.json");
+    if dashboard.exists() {
+        resources.push(json!({ "uri": DASHBOARD_URI, "name": "dashboard.json", "description": "Progress dashboard", "mimeType": "application/json" }));
+    }
+
+    let plan_dir = doplan_dir.join("plan");
+    if plan_dir.exists() {
+        let mut files: Vec<PathBuf> = walkdir::WalkDir::new(&plan_dir)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| entry.into_path())
+            .collect();
+        files.sort();
+        for path in files {
+            let relative = path.strip_prefix(&plan_dir)?.to_string_lossy().replace('\\', "/");
+            resources.push(json!({
+                "uri": format!("{}{}", PLAN_URI_PREFIX, relative),
+                "name": relative,
+                "mimeType": mime_type(&path)
+            }));
+        }
+    }
+    Ok(resources)
+}
+
+fn read_resource(uri: &str) -> Result<Value> {
+    let path = match uri {
+        PRD_URI => utils::doplan_dir()?.join("PRD.md"),
+        DASHBOARD_URI => utils::dot_doplan_dir()?.join("dashboard.json"),
+        _ => {
+            let relative = uri
+                .strip_prefix(PLAN_URI_PREFIX)
+                .ok_or_else(|| anyhow::anyhow!("Unknown resource: {}", uri))?;
+            let relative = Path::new(relative);
+            if relative.components().any(|c| !matches!(c, Component::Normal(_))) {
+                anyhow::bail!("Invalid plan resource: {}", uri);
+            }
+            utils::doplan_dir()?.join("plan").join(relative)
+        }
+    };
+    let text = fs::read_to_string(&path).with_context(|| format!("Resource not found: {}", uri))?;
+    Ok(json!({ "contents": [{ "uri": uri, "mimeType": mime_type(&path), "text": text }] }))
+}
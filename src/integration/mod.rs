@@ -1,2 +1,7 @@
+pub mod commits;
+pub mod git;
+pub mod github;
 pub mod ide;
+pub mod llm;
+pub mod mcp;
 
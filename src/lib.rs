@@ -4,6 +4,8 @@ pub mod context;
 pub mod error;
 pub mod generators;
 pub mod integration;
+pub mod server;
 pub mod state;
+pub mod tui;
 pub mod utils;
 
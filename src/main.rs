@@ -2,7 +2,7 @@ use anyhow::Result;
 use clap::{Parser, Subcommand};
 
 // Re-export from lib for binary
-use doplan::commands::{discuss, generate, plan, implement, next, progress, phase, feature, dashboard};
+use doplan::commands::{install, discuss, generate, plan, implement, next, progress, phase, feature, dashboard, schedule, burndown, server, github, config, commits, pr};
 
 #[derive(Parser)]
 #[command(name = "doplan")]
@@ -20,50 +20,217 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// Install DoPlan in your project
-    Install,
+    Install {
+        /// IDEs to generate commands for (cursor, claude, gemini, windsurf, vscode); asks when omitted
+        #[arg(long, value_delimiter = ',')]
+        ide: Vec<String>,
+    },
     /// Show project dashboard
     Dashboard,
-    /// Run development server
-    Server,
-    /// Sync GitHub data
-    Github,
+    /// Serve a REST API and web dashboard for the project
+    Server {
+        /// Port to listen on
+        #[arg(long, default_value_t = doplan::server::DEFAULT_PORT)]
+        port: u16,
+        /// Address to bind; use 0.0.0.0 to allow other machines, together
+        /// with --allow-host for the names they reach this one by
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+        /// Other host names or addresses clients may use, e.g. 192.168.1.20
+        #[arg(long, value_delimiter = ',')]
+        allow_host: Vec<String>,
+    },
+    /// Run a Model Context Protocol server on stdio for AI assistants
+    Mcp,
+    /// Sync phases, features and tasks with GitHub milestones and issues
+    Github {
+        /// Repository as owner/name; defaults to the origin remote
+        #[arg(long)]
+        repo: Option<String>,
+        /// GitHub API base URL; defaults to github.api_url
+        #[arg(long)]
+        api_url: Option<String>,
+    },
+    /// Open a pull request for a feature branch, described from its plan
+    Pr {
+        /// Feature as phase-id/feature-id or a feature id such as F-0003
+        target: String,
+        /// Branch to merge into; defaults to git.base_branch, then origin/HEAD
+        #[arg(long)]
+        base: Option<String>,
+        /// Open the pull request as a draft
+        #[arg(long)]
+        draft: bool,
+        /// Repository as owner/name; defaults to the origin remote
+        #[arg(long)]
+        repo: Option<String>,
+        /// GitHub API base URL; defaults to github.api_url
+        #[arg(long)]
+        api_url: Option<String>,
+    },
+    /// Forecast the schedule and critical path from task estimates
+    Schedule {
+        /// First working day of the forecast (YYYY-MM-DD, defaults to today)
+        #[arg(long)]
+        start: Option<String>,
+        /// Working hours per day (more than 0, at most 24), overriding the configured calendar
+        #[arg(long)]
+        hours_per_day: Option<f64>,
+        /// Print the forecast as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Render burndown and burnup charts from progress history
+    Burndown {
+        /// Limit the charts to one phase (e.g. P-01)
+        #[arg(long)]
+        phase: Option<String>,
+    },
+    /// Show or change settings from doplan.toml, the user config and DOPLAN_* variables
+    Config {
+        #[command(subcommand)]
+        action: Option<ConfigAction>,
+    },
+    /// Update task statuses from "Refs T2" / "Task: F-0003/T2 done" in commit messages
+    Commits {
+        #[command(subcommand)]
+        action: Option<CommitsAction>,
+    },
     /// Execute IDE command (internal use)
     #[command(hide = true)]
     Execute {
         /// Command name
         command: String,
         /// Command arguments
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
     },
 }
 
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// List every setting with its value and where it came from
+    List,
+    /// Print one setting, e.g. git.branch_suffix
+    Get {
+        key: String,
+    },
+    /// Change a setting in doplan.toml; an empty value removes it
+    Set {
+        key: String,
+        value: String,
+        /// Write the user config (~/.config/doplan/config.toml) instead
+        #[arg(long)]
+        global: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum CommitsAction {
+    /// Apply the task references on every feature branch (the default)
+    Scan,
+    /// Install a post-commit hook that scans after every commit
+    InstallHook {
+        /// Replace an existing post-commit hook
+        #[arg(long)]
+        force: bool,
+    },
+    /// Remove the post-commit hook installed by install-hook
+    UninstallHook,
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
 
     // Handle TUI flag
     if cli.tui {
-        // TODO: Launch TUI
-        println!("TUI not yet implemented");
-        return Ok(());
+        return doplan::tui::run();
     }
 
     // Handle commands
     match cli.command {
-        Some(Commands::Install) => {
-            println!("Install command not yet implemented");
-            Ok(())
+        Some(Commands::Install { ide }) => {
+            let args = if ide.is_empty() { Vec::new() } else { vec!["--ide".to_string(), ide.join(",")] };
+            install::execute(args).await
         }
         Some(Commands::Dashboard) => {
             dashboard::execute(vec![]).await
         }
-        Some(Commands::Server) => {
-            println!("Server command not yet implemented");
-            Ok(())
+        Some(Commands::Server { port, host, allow_host }) => {
+            let mut args = vec!["--port".to_string(), port.to_string(), "--host".to_string(), host];
+            for name in allow_host {
+                args.extend(["--allow-host".to_string(), name]);
+            }
+            server::execute(args).await
         }
-        Some(Commands::Github) => {
-            println!("Github command not yet implemented");
-            Ok(())
+        Some(Commands::Mcp) => doplan::integration::mcp::serve_stdio(),
+        Some(Commands::Github { repo, api_url }) => {
+            let mut args = Vec::new();
+            if let Some(repo) = repo {
+                args.extend(["--repo".to_string(), repo]);
+            }
+            if let Some(api_url) = api_url {
+                args.extend(["--api-url".to_string(), api_url]);
+            }
+            github::execute(args).await
+        }
+        Some(Commands::Pr { target, base, draft, repo, api_url }) => {
+            let mut args = vec![target];
+            for (flag, value) in [("--base", base), ("--repo", repo), ("--api-url", api_url)] {
+                if let Some(value) = value {
+                    args.extend([flag.to_string(), value]);
+                }
+            }
+            if draft {
+                args.push("--draft".to_string());
+            }
+            pr::execute(args).await
+        }
+        Some(Commands::Schedule { start, hours_per_day, json }) => {
+            let mut args = Vec::new();
+            if let Some(start) = start {
+                args.extend(["--start".to_string(), start]);
+            }
+            if let Some(hours) = hours_per_day {
+                args.extend(["--hours-per-day".to_string(), hours.to_string()]);
+            }
+            if json {
+                args.push("--json".to_string());
+            }
+            schedule::execute(args).await
+        }
+        Some(Commands::Burndown { phase }) => {
+            let args = phase.map(|p| vec!["--phase".to_string(), p]).unwrap_or_default();
+            burndown::execute(args).await
+        }
+        Some(Commands::Config { action }) => {
+            let args = match action {
+                None | Some(ConfigAction::List) => vec!["list".to_string()],
+                Some(ConfigAction::Get { key }) => vec!["get".to_string(), key],
+                Some(ConfigAction::Set { key, value, global }) => {
+                    let mut args = vec!["set".to_string(), key, value];
+                    if global {
+                        args.push("--global".to_string());
+                    }
+                    args
+                }
+            };
+            config::execute(args).await
+        }
+        Some(Commands::Commits { action }) => {
+            let args = match action {
+                None | Some(CommitsAction::Scan) => vec!["scan".to_string()],
+                Some(CommitsAction::InstallHook { force }) => {
+                    let mut args = vec!["install-hook".to_string()];
+                    if force {
+                        args.push("--force".to_string());
+                    }
+                    args
+                }
+                Some(CommitsAction::UninstallHook) => vec!["uninstall-hook".to_string()],
+            };
+            commits::execute(args).await
         }
         Some(Commands::Execute { command, args }) => {
             match command.as_str() {
@@ -75,17 +242,19 @@ async fn main() -> Result<()> {
                 "progress" => progress::execute(args).await,
                 "phase" => phase::execute(args).await,
                 "feature" => feature::execute(args).await,
+                "schedule" => schedule::execute(args).await,
+                "burndown" => burndown::execute(args).await,
+                "config" => config::execute(args).await,
+                "commits" => commits::execute(args).await,
+                "pr" => pr::execute(args).await,
                 _ => {
                     eprintln!("Unknown command: {}", command);
                     Ok(())
                 }
             }
         }
-        None => {
-            // Show minimal TUI menu (context-aware)
-            println!("TUI not yet implemented");
-            Ok(())
-        }
+        // Context-aware: the TUI opens on the discuss wizard when there is no project yet
+        None => doplan::tui::run(),
     }
 }
 
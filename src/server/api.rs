@@ -0,0 +1,224 @@
+use axum::extract::{Path, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::Next;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::convert::Infallible;
+use std::fs;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::{Stream, StreamExt};
+use crate::commands::{plan, progress};
+use crate::config::Config;
+use crate::state::{ProjectState, TaskStatus, CURRENT_SCHEMA_VERSION};
+use crate::utils;
+use crate::utils::plan_dirs;
+use super::ServerState;
+
+/// An error response: `{"error": "..."}` with a status code
+pub struct ApiError {
+    status: StatusCode,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self { status, message: message.into() }
+    }
+
+    fn not_found(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::NOT_FOUND, message)
+    }
+
+    fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, message)
+    }
+
+    fn forbidden(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::FORBIDDEN, message)
+    }
+}
+
+impl From<anyhow::Error> for ApiError {
+    fn from(err: anyhow::Error) -> Self {
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, format!("{:#}", err))
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, Json(json!({ "error": self.message }))).into_response()
+    }
+}
+
+type ApiResult<T> = std::result::Result<Json<T>, ApiError>;
+
+fn load_state() -> Result<ProjectState, ApiError> {
+    Ok(ProjectState::load()?)
+}
+
+/// State with task statuses read from the `tasks.md` files
+fn load_synced_state() -> Result<ProjectState, ApiError> {
+    let mut state = load_state()?;
+    plan_dirs::sync_tasks(&utils::doplan_dir()?.join("plan"), &mut state)?;
+    Ok(state)
+}
+
+/// Middleware on every route that turns away requests not addressed to this
+/// server: a page on another site sends its own `Origin`, and a DNS name
+/// rebound to this machine arrives with its own `Host`
+pub async fn check_origin(State(server): State<ServerState>, request: Request, next: Next) -> Result<Response, ApiError> {
+    let address = server.address;
+    let mut hosts: Vec<&str> = server.allowed_hosts.iter().map(String::as_str).collect();
+    if address.ip().is_loopback() || address.ip().is_unspecified() {
+        hosts.extend(["localhost", "127.0.0.1", "[::1]"]);
+    }
+    let allowed = |authority: &str| {
+        authority == address.to_string()
+            || hosts.iter().any(|host| authority.eq_ignore_ascii_case(&format!("{}:{}", host, address.port())))
+    };
+    let headers = request.headers();
+    let host = headers.get(header::HOST).and_then(|h| h.to_str().ok()).unwrap_or("");
+    if !allowed(host) {
+        return Err(ApiError::forbidden(format!(
+            "Requests must be addressed to {}, not '{}'. Start the server with --allow-host to accept other names.",
+            address, host
+        )));
+    }
+    if let Some(origin) = headers.get(header::ORIGIN) {
+        let origin = origin.to_str().unwrap_or("");
+        if !origin.strip_prefix("http://").is_some_and(allowed) {
+            return Err(ApiError::forbidden(format!("Cross-origin request from '{}' refused", origin)));
+        }
+    }
+    Ok(next.run(request).await)
+}
+
+pub async fn dashboard_page() -> impl IntoResponse {
+    axum::response::Html(include_str!("dashboard.html"))
+}
+
+pub async fn get_state() -> ApiResult<ProjectState> {
+    Ok(Json(load_state()?))
+}
+
+/// Replace `.doplan/state.json`. The body must be a complete state document
+/// of the schema version this build writes; missing ids are assigned as on load.
+pub async fn put_state(Json(mut state): Json<ProjectState>) -> ApiResult<ProjectState> {
+    if state.schema_version != CURRENT_SCHEMA_VERSION {
+        return Err(ApiError::bad_request(format!(
+            "schema_version must be {}, got {}",
+            CURRENT_SCHEMA_VERSION, state.schema_version
+        )));
+    }
+    state.assign_ids();
+    state.validate_ids()
+        .and_then(|_| state.validate_dependencies())
+        .map_err(|err| ApiError::bad_request(format!("{:#}", err)))?;
+    state.save()?;
+    Ok(Json(state))
+}
+
+pub async fn get_plan() -> ApiResult<Value> {
+    Ok(Json(plan::plan_tree(&load_synced_state()?)))
+}
+
+/// One feature with its plan files
+pub async fn get_feature(Path(key): Path<String>) -> ApiResult<Value> {
+    let state = load_synced_state()?;
+    let feature = state
+        .find_feature(&key)
+        .ok_or_else(|| ApiError::not_found(format!("Feature '{}' not found", key)))?;
+    let layout = plan_dirs::PlanDirs::scan(&utils::doplan_dir()?.join("plan"), &state)?;
+    let read = |name: &str| {
+        layout
+            .feature(&feature.id)
+            .and_then(|(_, dir)| fs::read_to_string(dir.path.join(name)).ok())
+    };
+    let mut body = serde_json::to_value(feature).map_err(anyhow::Error::from)?;
+    body["phase"] = json!(state.phase_of(&feature.id).map(|p| &p.id));
+    body["files"] = json!({
+        "plan": read("plan.md"),
+        "design": read("design.md"),
+        "tasks": read("tasks.md")
+    });
+    Ok(Json(body))
+}
+
+pub async fn get_tasks() -> ApiResult<Value> {
+    let state = load_synced_state()?;
+    let tasks: Vec<Value> = state
+        .features
+        .iter()
+        .flatten()
+        .flat_map(|f| {
+            f.tasks.iter().map(move |t| json!({
+                "ref": format!("{}/{}", f.id, t.id),
+                "feature": f.id,
+                "id": t.id,
+                "title": t.title,
+                "status": t.status.as_str(),
+                "estimate": t.estimate
+            }))
+        })
+        .collect();
+    Ok(Json(json!(tasks)))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StatusUpdate {
+    status: String,
+}
+
+/// Set a task's status in its `tasks.md` and in the state
+pub async fn put_task_status(
+    Path((feature_id, task_id)): Path<(String, String)>,
+    Json(update): Json<StatusUpdate>,
+) -> ApiResult<Value> {
+    let status = TaskStatus::parse(&update.status).ok_or_else(|| {
+        ApiError::bad_request(format!(
+            "Unknown status '{}' (expected not_started, in_progress, completed or blocked)",
+            update.status
+        ))
+    })?;
+    let mut state = load_state()?;
+    if state.feature_by_id(&feature_id).is_none() {
+        return Err(ApiError::not_found(format!("Feature '{}' not found", feature_id)));
+    }
+    let plan_dir = utils::doplan_dir()?.join("plan");
+    plan_dirs::set_task_status(&plan_dir, &mut state, &feature_id, &task_id, status)
+        .map_err(|err| ApiError::not_found(format!("{:#}", err)))?;
+    state.save()?;
+    Ok(Json(json!({ "ref": format!("{}/{}", feature_id, task_id), "status": status.as_str() })))
+}
+
+/// Dashboard data computed from the plan as it is now
+pub async fn get_dashboard() -> ApiResult<Value> {
+    let mut state = load_state()?;
+    let plan_dir = utils::doplan_dir()?.join("plan");
+    Ok(Json(progress::current_dashboard(&plan_dir, &mut state)?))
+}
+
+/// Run `/progress`: rewrite the progress files, history and dashboard
+pub async fn post_progress() -> ApiResult<Value> {
+    let mut state = load_state()?;
+    let plan_dir = utils::doplan_dir()?.join("plan");
+    if !plan_dir.exists() {
+        return Err(ApiError::not_found("No plan structure found. Run /plan first."));
+    }
+    let weighting = Config::current()?.progress.weighting;
+    let (phases, forecast) = progress::regenerate(&plan_dir, &mut state, weighting)?;
+    Ok(Json(progress::dashboard_json(&state, &phases, weighting, forecast.as_ref())))
+}
+
+/// `plan-changed` events whenever files under `doplan/plan` change
+pub async fn events(State(server): State<ServerState>) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let stream = BroadcastStream::new(server.events.subscribe()).filter_map(|change| {
+        // A lagging client skips the missed events and waits for the next one
+        let change = change.ok()?;
+        Event::default().event("plan-changed").json_data(&change).ok().map(Ok)
+    });
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
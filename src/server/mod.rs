@@ -0,0 +1,73 @@
+//! Local HTTP API and web dashboard for `doplan server`.
+//!
+//! | Route                                 | Method | Purpose                                   |
+//! |---------------------------------------|--------|-------------------------------------------|
+//! | `/`                                   | GET    | HTML dashboard                            |
+//! | `/api/state`                          | GET/PUT| `.doplan/state.json`                      |
+//! | `/api/plan`                           | GET    | Phases, features and tasks                |
+//! | `/api/features/{id}`                  | GET    | One feature with its plan files           |
+//! | `/api/tasks`                          | GET    | Every task with its status                |
+//! | `/api/tasks/{feature_id}/{task_id}`   | PUT    | `{"status": "..."}` written to `tasks.md` |
+//! | `/api/dashboard`                      | GET    | Same data as `dashboard.json`, computed now |
+//! | `/api/progress`                       | POST   | Run `/progress`                           |
+//! | `/api/events`                         | GET    | Server-Sent Events: `plan-changed`        |
+//!
+//! Paths are resolved against the directory the server was started in. Every
+//! route only answers requests addressed to the server itself: their `Host`,
+//! and `Origin` when a browser sends one, must name the bound address,
+//! `localhost` when bound to a loopback or unspecified address, or one of the
+//! names passed with `--allow-host`, each with the bound port.
+
+pub mod api;
+pub mod watch;
+
+use anyhow::{Context, Result};
+use axum::routing::{get, post, put};
+use axum::{middleware, Router};
+use std::net::SocketAddr;
+use tokio::net::TcpListener;
+use tokio::sync::broadcast;
+use watch::PlanChange;
+
+pub const DEFAULT_PORT: u16 = 7878;
+
+/// Shared by every request handler
+#[derive(Clone)]
+pub struct ServerState {
+    pub events: broadcast::Sender<PlanChange>,
+    /// Address the listener is bound to
+    pub address: SocketAddr,
+    /// Extra host names or addresses clients may use to reach the server
+    pub allowed_hosts: Vec<String>,
+}
+
+pub fn router(state: ServerState) -> Router {
+    Router::new()
+        .route("/", get(api::dashboard_page))
+        .route("/api/state", get(api::get_state).put(api::put_state))
+        .route("/api/plan", get(api::get_plan))
+        .route("/api/features/:id", get(api::get_feature))
+        .route("/api/tasks", get(api::get_tasks))
+        .route("/api/tasks/:feature_id/:task_id", put(api::put_task_status))
+        .route("/api/dashboard", get(api::get_dashboard))
+        .route("/api/progress", post(api::post_progress))
+        .route("/api/events", get(api::events))
+        .layer(middleware::from_fn_with_state(state.clone(), api::check_origin))
+        .with_state(state)
+}
+
+/// Serve the API on `listener`, watching `doplan/plan` for changes every
+/// `poll_interval`. Requests may also use the names in `allowed_hosts`. Runs
+/// until the task is cancelled.
+pub async fn serve(listener: TcpListener, poll_interval: std::time::Duration, allowed_hosts: Vec<String>) -> Result<()> {
+    let (events, _) = broadcast::channel(64);
+    let address = listener.local_addr().context("Failed to read the server address")?;
+    let plan_dir = crate::utils::doplan_dir()?.join("plan");
+    let watcher = watch::spawn(plan_dir, poll_interval, events.clone());
+
+    let result = axum::serve(listener, router(ServerState { events, address, allowed_hosts }))
+        .await
+        .context("HTTP server failed");
+    watcher.abort();
+    result
+}
@@ -0,0 +1,79 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+/// How often `doplan/plan` is rescanned for changes
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Files under `doplan/plan` that were created, modified or removed since the
+/// previous scan, relative to the plan directory
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlanChange {
+    pub paths: Vec<String>,
+}
+
+type Fingerprint = BTreeMap<PathBuf, (Option<SystemTime>, u64)>;
+
+fn fingerprint(plan_dir: &Path) -> Fingerprint {
+    walkdir::WalkDir::new(plan_dir)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            Some((entry.into_path(), (metadata.modified().ok(), metadata.len())))
+        })
+        .collect()
+}
+
+/// Paths whose fingerprint differs between `before` and `after`
+fn changed_paths(plan_dir: &Path, before: &Fingerprint, after: &Fingerprint) -> Vec<String> {
+    let mut paths: Vec<&PathBuf> = after
+        .iter()
+        .filter(|(path, meta)| before.get(*path) != Some(meta))
+        .map(|(path, _)| path)
+        .chain(before.keys().filter(|path| !after.contains_key(*path)))
+        .collect();
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| path.strip_prefix(plan_dir).unwrap_or(path).to_string_lossy().replace('\\', "/"))
+        .collect()
+}
+
+/// Poll `plan_dir` every `interval` and broadcast a [`PlanChange`] whenever
+/// files in it change. Runs until the task is dropped.
+pub fn spawn(plan_dir: PathBuf, interval: Duration, events: broadcast::Sender<PlanChange>) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut previous = fingerprint(&plan_dir);
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            let current = fingerprint(&plan_dir);
+            let paths = changed_paths(&plan_dir, &previous, &current);
+            if !paths.is_empty() {
+                // No subscribers is fine; the change is simply not delivered
+                let _ = events.send(PlanChange { paths });
+            }
+            previous = current;
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changed_paths() {
+        let dir = Path::new("/plan");
+        let entry = |name: &str, len: u64| (dir.join(name), (None, len));
+        let before: Fingerprint = [entry("a/tasks.md", 10), entry("b/tasks.md", 5), entry("c/plan.md", 1)].into();
+        let after: Fingerprint = [entry("a/tasks.md", 10), entry("b/tasks.md", 6), entry("d/plan.md", 1)].into();
+
+        assert_eq!(changed_paths(dir, &before, &after), vec!["b/tasks.md", "c/plan.md", "d/plan.md"]);
+        assert!(changed_paths(dir, &after, &after).is_empty());
+    }
+}
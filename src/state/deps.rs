@@ -0,0 +1,418 @@
+//! Dependency graph over features and tasks.
+//!
+//! Tasks declare `depends_on` references in one of three forms:
+//!
+//! - `T2` - another task in the same feature
+//! - `F-0003/T2` - a task in another feature
+//! - `F-0003` - a whole feature, satisfied once all of its tasks are completed
+//!
+//! Features declare `depends_on` as feature ids. A feature dependency applies
+//! to every task of the dependent feature.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use super::{Feature, ProjectState, Task, TaskStatus};
+
+/// A node in the dependency graph
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NodeRef {
+    Feature(String),
+    Task { feature: String, task: String },
+}
+
+impl fmt::Display for NodeRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NodeRef::Feature(id) => write!(f, "{}", id),
+            NodeRef::Task { feature, task } => write!(f, "{}/{}", feature, task),
+        }
+    }
+}
+
+impl NodeRef {
+    /// Parse a reference as written in `depends_on`, resolving bare task ids
+    /// against `current_feature`.
+    pub fn parse(reference: &str, current_feature: &str) -> Option<NodeRef> {
+        let reference = reference.trim();
+        if reference.is_empty() {
+            return None;
+        }
+        match reference.split_once('/') {
+            Some((feature, task)) => Some(NodeRef::Task {
+                feature: feature.trim().to_string(),
+                task: task.trim().to_string(),
+            }),
+            None if reference.starts_with("F-") => Some(NodeRef::Feature(reference.to_string())),
+            None => Some(NodeRef::Task {
+                feature: current_feature.to_string(),
+                task: reference.to_string(),
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DependencyIssue {
+    /// `from` refers to a feature or task that does not exist
+    UnknownReference { from: NodeRef, reference: String },
+    /// The listed nodes depend on each other in a loop; the first node is
+    /// repeated at the end
+    Cycle(Vec<NodeRef>),
+}
+
+impl fmt::Display for DependencyIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DependencyIssue::UnknownReference { from, reference } => {
+                write!(f, "{} depends on unknown reference '{}'", from, reference)
+            }
+            DependencyIssue::Cycle(path) => {
+                let path = path.iter().map(|n| n.to_string()).collect::<Vec<_>>().join(" → ");
+                write!(f, "dependency cycle: {}", path)
+            }
+        }
+    }
+}
+
+/// An incomplete prerequisite that keeps a task from being started
+#[derive(Debug, Clone, PartialEq)]
+pub struct Blocker {
+    pub node: NodeRef,
+    pub title: String,
+    pub status: TaskStatus,
+    /// The chain of references from the blocked task to this blocker
+    pub via: Vec<NodeRef>,
+}
+
+impl fmt::Display for Blocker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}) is {}", self.node, self.title, self.status.label())?;
+        if self.via.len() > 2 {
+            let chain = self.via.iter().map(|n| n.to_string()).collect::<Vec<_>>().join(" → ");
+            write!(f, " via {}", chain)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct DependencyGraph<'a> {
+    features: BTreeMap<&'a str, &'a Feature>,
+    edges: BTreeMap<NodeRef, Vec<NodeRef>>,
+    issues: Vec<DependencyIssue>,
+}
+
+impl<'a> DependencyGraph<'a> {
+    pub fn build(state: &'a ProjectState) -> Self {
+        let features: BTreeMap<&str, &Feature> = state
+            .features
+            .iter()
+            .flatten()
+            .map(|f| (f.id.as_str(), f))
+            .collect();
+        let mut graph = DependencyGraph {
+            features,
+            edges: BTreeMap::new(),
+            issues: Vec::new(),
+        };
+
+        for feature in graph.features.values().copied().collect::<Vec<_>>() {
+            let feature_node = NodeRef::Feature(feature.id.clone());
+            let mut feature_edges: Vec<NodeRef> = feature
+                .tasks
+                .iter()
+                .map(|t| task_node(feature, t))
+                .collect();
+
+            let mut inherited = Vec::new();
+            for reference in &feature.depends_on {
+                match graph.resolve(reference, &feature.id) {
+                    Some(node) => inherited.push(node),
+                    None => graph.issues.push(DependencyIssue::UnknownReference {
+                        from: feature_node.clone(),
+                        reference: reference.clone(),
+                    }),
+                }
+            }
+            feature_edges.extend(inherited.iter().cloned());
+            graph.edges.insert(feature_node, feature_edges);
+
+            for task in &feature.tasks {
+                let node = task_node(feature, task);
+                let mut task_edges = inherited.clone();
+                for reference in &task.depends_on {
+                    match graph.resolve(reference, &feature.id) {
+                        Some(target) => task_edges.push(target),
+                        None => graph.issues.push(DependencyIssue::UnknownReference {
+                            from: node.clone(),
+                            reference: reference.clone(),
+                        }),
+                    }
+                }
+                graph.edges.insert(node, task_edges);
+            }
+        }
+
+        let cycles = graph.find_cycles();
+        graph.issues.extend(cycles.into_iter().map(DependencyIssue::Cycle));
+        graph
+    }
+
+    /// Unknown references and cycles found while building the graph
+    pub fn issues(&self) -> &[DependencyIssue] {
+        &self.issues
+    }
+
+    /// Whether every prerequisite of `node` is complete
+    pub fn is_ready(&self, node: &NodeRef) -> bool {
+        self.blockers(node).is_empty()
+    }
+
+    /// Incomplete tasks standing between `node` and being startable. Feature
+    /// prerequisites are expanded into their unfinished tasks, and blockers of
+    /// blockers are followed so the root cause is reported.
+    pub fn blockers(&self, node: &NodeRef) -> Vec<Blocker> {
+        let mut blockers = Vec::new();
+        let mut visited = BTreeSet::new();
+        visited.insert(node.clone());
+        for dep in self.direct_prerequisites(node) {
+            self.collect_blockers(&dep, vec![node.clone(), dep.clone()], &mut visited, &mut blockers);
+        }
+        blockers
+    }
+
+    fn collect_blockers(&self, node: &NodeRef, via: Vec<NodeRef>, visited: &mut BTreeSet<NodeRef>, out: &mut Vec<Blocker>) {
+        if !visited.insert(node.clone()) {
+            return;
+        }
+        match node {
+            NodeRef::Feature(_) => {
+                // A feature is complete when its tasks are; walk into them
+                for child in self.edges.get(node).into_iter().flatten() {
+                    let mut via = via.clone();
+                    via.push(child.clone());
+                    self.collect_blockers(child, via, visited, out);
+                }
+            }
+            NodeRef::Task { .. } => {
+                let Some((title, status)) = self.task_info(node) else {
+                    return;
+                };
+                if status == TaskStatus::Completed {
+                    return;
+                }
+                out.push(Blocker {
+                    node: node.clone(),
+                    title,
+                    status,
+                    via: via.clone(),
+                });
+                for dep in self.direct_prerequisites(node) {
+                    let mut via = via.clone();
+                    via.push(dep.clone());
+                    self.collect_blockers(&dep, via, visited, out);
+                }
+            }
+        }
+    }
+
+    /// Tasks that must finish before the task `node` can start, with feature
+    /// prerequisites expanded into every task of that feature
+    pub fn task_prerequisites(&self, node: &NodeRef) -> Vec<NodeRef> {
+        let mut tasks = Vec::new();
+        for dep in self.direct_prerequisites(node) {
+            match &dep {
+                NodeRef::Feature(_) => {
+                    for child in self.edges.get(&dep).into_iter().flatten() {
+                        if matches!(child, NodeRef::Task { .. }) && !tasks.contains(child) {
+                            tasks.push(child.clone());
+                        }
+                    }
+                }
+                NodeRef::Task { .. } => {
+                    if !tasks.contains(&dep) {
+                        tasks.push(dep);
+                    }
+                }
+            }
+        }
+        tasks
+    }
+
+    fn direct_prerequisites(&self, node: &NodeRef) -> Vec<NodeRef> {
+        match node {
+            // For a feature, prerequisites are its feature dependencies, not its own tasks
+            NodeRef::Feature(id) => self
+                .features
+                .get(id.as_str())
+                .map(|f| f.depends_on.iter().filter_map(|r| self.resolve(r, id)).collect())
+                .unwrap_or_default(),
+            NodeRef::Task { .. } => self.edges.get(node).cloned().unwrap_or_default(),
+        }
+    }
+
+    fn task_info(&self, node: &NodeRef) -> Option<(String, TaskStatus)> {
+        let NodeRef::Task { feature, task } = node else {
+            return None;
+        };
+        let task = self.features.get(feature.as_str())?.tasks.iter().find(|t| t.id == *task)?;
+        Some((task.title.clone(), task.status))
+    }
+
+    fn resolve(&self, reference: &str, current_feature: &str) -> Option<NodeRef> {
+        let node = NodeRef::parse(reference, current_feature)?;
+        let exists = match &node {
+            NodeRef::Feature(id) => self.features.contains_key(id.as_str()),
+            NodeRef::Task { feature, task } => self
+                .features
+                .get(feature.as_str())
+                .map(|f| f.tasks.iter().any(|t| t.id == *task))
+                .unwrap_or(false),
+        };
+        exists.then_some(node)
+    }
+
+    /// Depth-first search for back edges. Each cycle is reported once, starting
+    /// from the node where it was first entered.
+    fn find_cycles(&self) -> Vec<Vec<NodeRef>> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum Mark {
+            Visiting,
+            Done,
+        }
+
+        fn visit(
+            graph: &BTreeMap<NodeRef, Vec<NodeRef>>,
+            node: &NodeRef,
+            marks: &mut BTreeMap<NodeRef, Mark>,
+            stack: &mut Vec<NodeRef>,
+            cycles: &mut Vec<Vec<NodeRef>>,
+        ) {
+            marks.insert(node.clone(), Mark::Visiting);
+            stack.push(node.clone());
+            for next in graph.get(node).into_iter().flatten() {
+                match marks.get(next) {
+                    Some(Mark::Visiting) => {
+                        let start = stack.iter().position(|n| n == next).unwrap_or(0);
+                        let mut cycle = stack[start..].to_vec();
+                        cycle.push(next.clone());
+                        cycles.push(cycle);
+                    }
+                    Some(Mark::Done) => {}
+                    None => visit(graph, next, marks, stack, cycles),
+                }
+            }
+            stack.pop();
+            marks.insert(node.clone(), Mark::Done);
+        }
+
+        let mut marks = BTreeMap::new();
+        let mut cycles = Vec::new();
+        for node in self.edges.keys() {
+            if !marks.contains_key(node) {
+                visit(&self.edges, node, &mut marks, &mut Vec::new(), &mut cycles);
+            }
+        }
+        cycles
+    }
+}
+
+fn task_node(feature: &Feature, task: &Task) -> NodeRef {
+    NodeRef::Task {
+        feature: feature.id.clone(),
+        task: task.id.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feature(id: &str, tasks: Vec<Task>) -> Feature {
+        Feature {
+            id: id.to_string(),
+            name: id.to_string(),
+            tasks,
+            ..Default::default()
+        }
+    }
+
+    fn task(id: &str, status: TaskStatus, depends_on: &[&str]) -> Task {
+        Task {
+            status,
+            depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+            ..Task::new(id, id)
+        }
+    }
+
+    fn state(features: Vec<Feature>) -> ProjectState {
+        ProjectState {
+            features: Some(features),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_reference_forms() {
+        assert_eq!(NodeRef::parse("T2", "F-0001"), Some(NodeRef::Task { feature: "F-0001".into(), task: "T2".into() }));
+        assert_eq!(NodeRef::parse("F-0003/T1", "F-0001"), Some(NodeRef::Task { feature: "F-0003".into(), task: "T1".into() }));
+        assert_eq!(NodeRef::parse("F-0003", "F-0001"), Some(NodeRef::Feature("F-0003".into())));
+        assert_eq!(NodeRef::parse(" ", "F-0001"), None);
+    }
+
+    #[test]
+    fn test_detects_task_cycle() {
+        let state = state(vec![feature("F-0001", vec![
+            task("T1", TaskStatus::NotStarted, &["T2"]),
+            task("T2", TaskStatus::NotStarted, &["T1"]),
+        ])]);
+        let graph = DependencyGraph::build(&state);
+        assert!(graph.issues().iter().any(|i| matches!(i, DependencyIssue::Cycle(path) if path.len() == 3)));
+    }
+
+    #[test]
+    fn test_detects_cycle_through_feature_dependency() {
+        let mut a = feature("F-0001", vec![task("T1", TaskStatus::NotStarted, &[])]);
+        a.depends_on = vec!["F-0002".to_string()];
+        let b = feature("F-0002", vec![task("T1", TaskStatus::NotStarted, &["F-0001/T1"])]);
+        let state = state(vec![a, b]);
+        assert!(DependencyGraph::build(&state).issues().iter().any(|i| matches!(i, DependencyIssue::Cycle(_))));
+    }
+
+    #[test]
+    fn test_reports_unknown_references() {
+        let state = state(vec![feature("F-0001", vec![task("T1", TaskStatus::NotStarted, &["T9", "F-0404"])])]);
+        let graph = DependencyGraph::build(&state);
+        assert_eq!(graph.issues().len(), 2);
+        assert_eq!(graph.issues()[0].to_string(), "F-0001/T1 depends on unknown reference 'T9'");
+    }
+
+    #[test]
+    fn test_blockers_follow_chain_to_root_cause() {
+        let state = state(vec![feature("F-0001", vec![
+            task("T1", TaskStatus::InProgress, &[]),
+            task("T2", TaskStatus::NotStarted, &["T1"]),
+            task("T3", TaskStatus::NotStarted, &["T2"]),
+        ])]);
+        let graph = DependencyGraph::build(&state);
+        let t3 = NodeRef::parse("T3", "F-0001").unwrap();
+        let blockers = graph.blockers(&t3);
+        assert_eq!(blockers.len(), 2);
+        assert_eq!(blockers[1].node.to_string(), "F-0001/T1");
+        assert!(graph.is_ready(&NodeRef::parse("T1", "F-0001").unwrap()));
+    }
+
+    #[test]
+    fn test_feature_dependency_blocks_all_tasks() {
+        let mut a = feature("F-0001", vec![task("T1", TaskStatus::NotStarted, &[])]);
+        a.depends_on = vec!["F-0002".to_string()];
+        let b = feature("F-0002", vec![
+            task("T1", TaskStatus::Completed, &[]),
+            task("T2", TaskStatus::NotStarted, &[]),
+        ]);
+        let state = state(vec![a, b]);
+        let graph = DependencyGraph::build(&state);
+        let blockers = graph.blockers(&NodeRef::parse("T1", "F-0001").unwrap());
+        assert_eq!(blockers.len(), 1);
+        assert_eq!(blockers[0].node.to_string(), "F-0002/T2");
+    }
+}
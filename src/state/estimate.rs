@@ -0,0 +1,168 @@
+//! Task estimates as durations, and the working calendar that turns them into
+//! dates.
+//!
+//! `tasks.md` stores estimates as free text (`**Estimated Time**: 4 hours`).
+//! [`Estimate::parse`] understands the common forms: minutes, hours, days,
+//! weeks and story points, with or without a space and in short or long form
+//! (`30m`, `2h`, `1.5 d`, `3 days`, `1w`, `5 sp`, `3 points`). Ranges such as
+//! `2-4 hours` use the upper bound.
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EstimateUnit {
+    Minutes,
+    Hours,
+    Days,
+    Weeks,
+    StoryPoints,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Estimate {
+    pub amount: f64,
+    pub unit: EstimateUnit,
+}
+
+impl Estimate {
+    pub fn parse(text: &str) -> Option<Estimate> {
+        let text = text.trim().to_lowercase();
+        let number_end = text
+            .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-' || c == '–' || c.is_whitespace()))
+            .unwrap_or(text.len());
+        let (number, unit) = text.split_at(number_end);
+
+        // "2-4" or "2 – 4": take the upper bound
+        let amount = number
+            .split(['-', '–'])
+            .filter_map(|n| n.trim().parse::<f64>().ok())
+            .fold(None, |max: Option<f64>, n| Some(max.map_or(n, |m| m.max(n))))?;
+        if amount < 0.0 {
+            return None;
+        }
+
+        let unit = match unit.trim().trim_end_matches('.') {
+            "m" | "min" | "mins" | "minute" | "minutes" => EstimateUnit::Minutes,
+            "h" | "hr" | "hrs" | "hour" | "hours" => EstimateUnit::Hours,
+            "d" | "day" | "days" => EstimateUnit::Days,
+            "w" | "wk" | "wks" | "week" | "weeks" => EstimateUnit::Weeks,
+            "sp" | "pt" | "pts" | "point" | "points" | "story point" | "story points" => EstimateUnit::StoryPoints,
+            _ => return None,
+        };
+
+        Some(Estimate { amount, unit })
+    }
+
+    /// Working hours this estimate stands for under `calendar`
+    pub fn hours(&self, calendar: &WorkingCalendar) -> f64 {
+        match self.unit {
+            EstimateUnit::Minutes => self.amount / 60.0,
+            EstimateUnit::Hours => self.amount,
+            EstimateUnit::Days => self.amount * calendar.hours_per_day,
+            EstimateUnit::Weeks => self.amount * calendar.hours_per_day * calendar.working_days().len() as f64,
+            EstimateUnit::StoryPoints => self.amount * calendar.hours_per_point,
+        }
+    }
+}
+
+/// Working time used to convert estimates into calendar dates; the
+/// `[calendar]` section of the configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WorkingCalendar {
+    pub hours_per_day: f64,
+    /// Working weekdays as three-letter names (`mon`, `tue`, ...)
+    pub work_days: Vec<String>,
+    pub hours_per_point: f64,
+    /// Hours assumed for tasks without a usable estimate
+    pub default_task_hours: f64,
+}
+
+impl Default for WorkingCalendar {
+    fn default() -> Self {
+        Self {
+            hours_per_day: 8.0,
+            work_days: ["mon", "tue", "wed", "thu", "fri"].iter().map(|d| d.to_string()).collect(),
+            hours_per_point: 4.0,
+            default_task_hours: 4.0,
+        }
+    }
+}
+
+impl WorkingCalendar {
+    pub fn working_days(&self) -> Vec<Weekday> {
+        let days: Vec<Weekday> = self
+            .work_days
+            .iter()
+            .filter_map(|d| d.parse::<Weekday>().ok())
+            .collect();
+        if days.is_empty() {
+            // An empty or unreadable list would never finish anything
+            vec![Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri]
+        } else {
+            days
+        }
+    }
+
+    /// Hours for a task estimate, falling back to `default_task_hours`
+    pub fn task_hours(&self, estimate: Option<&str>) -> (f64, bool) {
+        match estimate.and_then(Estimate::parse) {
+            Some(estimate) => (estimate.hours(self), true),
+            None => (self.default_task_hours, false),
+        }
+    }
+
+    /// The working day on which `hours` of work started on `start` are done.
+    /// Zero hours finish on `start` itself (or the next working day).
+    pub fn finish_date(&self, start: NaiveDate, hours: f64) -> NaiveDate {
+        let working_days = self.working_days();
+        let hours_per_day = if self.hours_per_day > 0.0 { self.hours_per_day } else { 8.0 };
+        let mut date = start;
+        while !working_days.contains(&date.weekday()) {
+            date += Duration::days(1);
+        }
+
+        let mut remaining = (hours / hours_per_day).ceil() as i64 - 1;
+        while remaining > 0 {
+            date += Duration::days(1);
+            if working_days.contains(&date.weekday()) {
+                remaining -= 1;
+            }
+        }
+        date
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_estimate_forms() {
+        let hours = |s: &str| Estimate::parse(s).map(|e| e.hours(&WorkingCalendar::default()));
+        assert_eq!(hours("1 hour"), Some(1.0));
+        assert_eq!(hours("4 hours"), Some(4.0));
+        assert_eq!(hours("2h"), Some(2.0));
+        assert_eq!(hours("30m"), Some(0.5));
+        assert_eq!(hours("1.5 d"), Some(12.0));
+        assert_eq!(hours("1w"), Some(40.0));
+        assert_eq!(hours("3 SP"), Some(12.0));
+        assert_eq!(hours("2 story points"), Some(8.0));
+        assert_eq!(hours("2-4 hours"), Some(4.0));
+        assert_eq!(hours("Unknown"), None);
+        assert_eq!(hours("soon"), None);
+    }
+
+    #[test]
+    fn test_finish_date_skips_weekends() {
+        let calendar = WorkingCalendar::default();
+        let friday = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        assert_eq!(calendar.finish_date(friday, 0.0), friday);
+        assert_eq!(calendar.finish_date(friday, 8.0), friday);
+        assert_eq!(calendar.finish_date(friday, 9.0), NaiveDate::from_ymd_opt(2026, 10, 19).unwrap());
+        let saturday = NaiveDate::from_ymd_opt(2026, 10, 17).unwrap();
+        assert_eq!(calendar.finish_date(saturday, 4.0), NaiveDate::from_ymd_opt(2026, 10, 19).unwrap());
+    }
+}
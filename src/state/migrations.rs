@@ -0,0 +1,188 @@
+use anyhow::Result;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use crate::error::DoPlanError;
+
+/// Schema version written by this build of DoPlan.
+///
+/// Bump this together with a new entry in [`MIGRATIONS`] whenever a field is
+/// added to `ProjectState`, even one older files deserialize without: an older
+/// build would otherwise load the file, drop the field and save it back.
+pub const CURRENT_SCHEMA_VERSION: u32 = 8;
+
+type Migration = fn(Value) -> Result<Value>;
+
+/// Migration chain. `MIGRATIONS[n]` upgrades a version `n` document to `n + 1`.
+const MIGRATIONS: &[Migration] = &[
+    migrate_v0_to_v1,
+    migrate_v1_to_v2,
+    migrate_v2_to_v3,
+    migrate_v3_to_v4,
+    migrate_v4_to_v5,
+    migrate_v5_to_v6,
+    migrate_v6_to_v7,
+    migrate_v7_to_v8,
+];
+
+/// Read the schema version of a raw state document. Files written before
+/// versioning was introduced have no `schema_version` and are treated as v0.
+pub fn schema_version_of(raw: &Value) -> Result<u32> {
+    match raw.get("schema_version") {
+        None | Some(Value::Null) => Ok(0),
+        Some(v) => v
+            .as_u64()
+            .and_then(|n| u32::try_from(n).ok())
+            .ok_or_else(|| DoPlanError::State(format!("Invalid schema_version in state file: {}", v)).into()),
+    }
+}
+
+/// Run every migration from `from_version` up to [`CURRENT_SCHEMA_VERSION`].
+pub fn migrate(mut raw: Value, from_version: u32) -> Result<Value> {
+    for version in from_version..CURRENT_SCHEMA_VERSION {
+        let step = MIGRATIONS
+            .get(version as usize)
+            .ok_or_else(|| DoPlanError::State(format!("No migration registered for schema version {}", version)))?;
+        raw = step(raw)?;
+        raw["schema_version"] = Value::from(version + 1);
+    }
+    Ok(raw)
+}
+
+/// Location of the backup taken before a version `version` file is rewritten.
+pub fn backup_path(state_path: &Path, version: u32) -> PathBuf {
+    let file_name = state_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "state.json".to_string());
+    state_path.with_file_name(format!("{}.v{}.bak", file_name, version))
+}
+
+fn ensure_object(raw: &Value) -> Result<()> {
+    if !raw.is_object() {
+        return Err(DoPlanError::State("State file must contain a JSON object".to_string()).into());
+    }
+    Ok(())
+}
+
+/// v0 -> v1: introduce `schema_version`. The field layout is otherwise unchanged.
+fn migrate_v0_to_v1(raw: Value) -> Result<Value> {
+    ensure_object(&raw)?;
+    Ok(raw)
+}
+
+/// v1 -> v2: features carry their own task list. Tasks are populated from
+/// `tasks.md` the next time `/plan` or `/progress` runs.
+fn migrate_v1_to_v2(mut raw: Value) -> Result<Value> {
+    ensure_object(&raw)?;
+    if let Some(features) = raw.get_mut("features").and_then(|f| f.as_array_mut()) {
+        for feature in features.iter_mut().filter_map(|f| f.as_object_mut()) {
+            feature.entry("tasks").or_insert_with(|| Value::Array(Vec::new()));
+        }
+    }
+    Ok(raw)
+}
+
+/// v2 -> v3: features and phases gain stable ids and phases list feature ids
+/// instead of names. The ids themselves are allocated by
+/// `ProjectState::assign_ids` once the document deserializes, so the counter
+/// logic lives in one place.
+fn migrate_v2_to_v3(mut raw: Value) -> Result<Value> {
+    ensure_object(&raw)?;
+    if let Some(obj) = raw.as_object_mut() {
+        obj.entry("id_counters").or_insert_with(|| serde_json::json!({ "feature": 0, "phase": 0 }));
+    }
+    Ok(raw)
+}
+
+/// v3 -> v4: the working calendar and progress weighting moved to the
+/// `calendar` and `progress` sections of `doplan.toml`. Copies left in the
+/// state file by earlier builds are dropped.
+fn migrate_v3_to_v4(mut raw: Value) -> Result<Value> {
+    ensure_object(&raw)?;
+    if let Some(obj) = raw.as_object_mut() {
+        obj.remove("calendar");
+        obj.remove("progress_weighting");
+    }
+    Ok(raw)
+}
+
+/// v4 -> v5: `install` records what `doplan install` set up. Absent means not
+/// installed, so there is nothing to fill in.
+fn migrate_v4_to_v5(raw: Value) -> Result<Value> {
+    ensure_object(&raw)?;
+    Ok(raw)
+}
+
+/// v5 -> v6: `discussion` keeps the `/discuss` answers; absent means none.
+fn migrate_v5_to_v6(raw: Value) -> Result<Value> {
+    ensure_object(&raw)?;
+    Ok(raw)
+}
+
+/// v6 -> v7: `worktrees` lists the worktrees of `/implement --worktree`;
+/// absent means none.
+fn migrate_v6_to_v7(raw: Value) -> Result<Value> {
+    ensure_object(&raw)?;
+    Ok(raw)
+}
+
+/// v7 -> v8: features gain `pull_request`, set by `doplan execute pr`; absent
+/// means no pull request has been opened.
+fn migrate_v7_to_v8(raw: Value) -> Result<Value> {
+    ensure_object(&raw)?;
+    Ok(raw)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_schema_version_defaults_to_zero() {
+        assert_eq!(schema_version_of(&json!({"project_name": "x"})).unwrap(), 0);
+        assert_eq!(schema_version_of(&json!({"schema_version": 3})).unwrap(), 3);
+        assert!(schema_version_of(&json!({"schema_version": "one"})).is_err());
+    }
+
+    #[test]
+    fn test_migrate_sets_current_version() {
+        let migrated = migrate(json!({"project_name": "x"}), 0).unwrap();
+        assert_eq!(migrated["schema_version"], json!(CURRENT_SCHEMA_VERSION));
+        assert_eq!(migrated["project_name"], json!("x"));
+    }
+
+    #[test]
+    fn test_migrate_v1_adds_feature_tasks() {
+        let migrated = migrate(json!({
+            "schema_version": 1,
+            "features": [{ "name": "Auth", "description": "", "priority": "high" }]
+        }), 1).unwrap();
+        assert_eq!(migrated["features"][0]["tasks"], json!([]));
+    }
+
+    #[test]
+    fn test_migrate_v3_drops_settings_now_in_config() {
+        let migrated = migrate(json!({
+            "schema_version": 3,
+            "calendar": { "hours_per_day": 6.0 },
+            "progress_weighting": "estimate",
+            "discussion": [{ "question": "q", "answer": "a" }]
+        }), 3).unwrap();
+        assert_eq!(migrated["schema_version"], json!(CURRENT_SCHEMA_VERSION));
+        assert!(migrated.get("calendar").is_none());
+        assert!(migrated.get("progress_weighting").is_none());
+        assert_eq!(migrated["discussion"][0]["answer"], json!("a"));
+    }
+
+    #[test]
+    fn test_migrate_rejects_non_object() {
+        assert!(migrate(json!([1, 2, 3]), 0).is_err());
+    }
+
+    #[test]
+    fn test_backup_path() {
+        let path = Path::new("/tmp/.doplan/state.json");
+        assert_eq!(backup_path(path, 0), PathBuf::from("/tmp/.doplan/state.json.v0.bak"));
+    }
+}
@@ -0,0 +1,500 @@
+pub mod deps;
+pub mod estimate;
+pub mod migrations;
+pub mod tasks_md;
+pub mod weighting;
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use anyhow::{Context, Result};
+use crate::error::DoPlanError;
+use crate::utils;
+
+pub use estimate::WorkingCalendar;
+pub use migrations::CURRENT_SCHEMA_VERSION;
+pub use weighting::ProgressWeighting;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectState {
+    pub schema_version: u32,
+    pub project_name: Option<String>,
+    pub idea: Option<String>,
+    pub tech_stack: Option<Vec<String>>,
+    pub features: Option<Vec<Feature>>,
+    pub phases: Option<Vec<Phase>>,
+    pub improvements: Option<Vec<String>>,
+    pub notes: Option<String>,
+    /// Last sequence numbers handed out for feature and phase ids
+    #[serde(default)]
+    pub id_counters: IdCounters,
+    /// Written by `doplan install`; `None` until the repository is installed
+    #[serde(default)]
+    pub install: Option<InstallInfo>,
+    /// Answers to the `/discuss` questions, in the order they were asked
+    #[serde(default)]
+    pub discussion: Vec<DiscussionAnswer>,
+    /// Git worktrees created by `/implement --worktree`, one per feature
+    #[serde(default)]
+    pub worktrees: Vec<WorktreeInfo>,
+}
+
+/// A feature branch checked out in its own git worktree
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorktreeInfo {
+    /// Stable feature id, or the feature directory when there is none
+    pub feature_id: String,
+    pub branch: String,
+    /// Absolute path of the worktree
+    pub path: String,
+    /// RFC 3339 timestamp
+    pub created_at: String,
+}
+
+/// One `/discuss` question and its answer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiscussionAnswer {
+    /// Stable key, such as `target_audience`
+    pub key: String,
+    pub question: String,
+    pub answer: String,
+}
+
+/// What `doplan install` set up, so re-runs can reuse the same choices
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstallInfo {
+    /// DoPlan version that last wrote the integration files
+    pub version: String,
+    /// IDE integrations that were generated
+    pub ides: Vec<String>,
+    /// RFC 3339 timestamp of the first install
+    pub installed_at: String,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdCounters {
+    pub feature: u32,
+    pub phase: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Feature {
+    /// Immutable identifier (`F-0007`). Survives renames and reordering.
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub priority: String,
+    #[serde(default)]
+    pub tasks: Vec<Task>,
+    /// Ids of features that must be completed before work on this one starts
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// Stories drafted by the LLM provider, rendered into `plan.md`
+    #[serde(default)]
+    pub user_stories: Vec<UserStory>,
+    /// Pull request opened for the feature branch by `doplan execute pr`
+    #[serde(default)]
+    pub pull_request: Option<PullRequest>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PullRequest {
+    pub number: u64,
+    /// Web page of the pull request
+    pub url: String,
+}
+
+/// "As a `role`, I want `goal` so that `benefit`"
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserStory {
+    pub role: String,
+    pub goal: String,
+    pub benefit: String,
+}
+
+impl std::fmt::Display for UserStory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "As {}, I want {} so that {}", self.role, self.goal, self.benefit)
+    }
+}
+
+/// A single unit of work inside a feature, mirrored to the feature's `tasks.md`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Task {
+    /// Stable identifier within the feature (`T1`, `T2`, ...). Never reused.
+    pub id: String,
+    pub title: String,
+    #[serde(default)]
+    pub status: TaskStatus,
+    #[serde(default)]
+    pub priority: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub estimate: Option<String>,
+    #[serde(default)]
+    pub acceptance_criteria: Vec<AcceptanceCriterion>,
+    #[serde(default)]
+    pub notes: Option<String>,
+    /// Prerequisites: `T2`, `F-0003/T2` or a whole feature `F-0003`.
+    /// See [`deps`] for how they are resolved.
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AcceptanceCriterion {
+    pub text: String,
+    #[serde(default)]
+    pub done: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStatus {
+    #[default]
+    NotStarted,
+    InProgress,
+    Completed,
+    Blocked,
+}
+
+impl TaskStatus {
+    pub const ALL: [TaskStatus; 4] = [
+        TaskStatus::NotStarted,
+        TaskStatus::InProgress,
+        TaskStatus::Completed,
+        TaskStatus::Blocked,
+    ];
+
+    /// Machine-readable form used in progress.json and dashboard.json.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskStatus::NotStarted => "not_started",
+            TaskStatus::InProgress => "in_progress",
+            TaskStatus::Completed => "completed",
+            TaskStatus::Blocked => "blocked",
+        }
+    }
+
+    /// Accepts either the machine-readable or the human-readable form.
+    pub fn parse(value: &str) -> Option<TaskStatus> {
+        let value = value.trim();
+        Self::ALL
+            .into_iter()
+            .find(|s| s.as_str().eq_ignore_ascii_case(value) || s.label().eq_ignore_ascii_case(value))
+    }
+
+    /// Human-readable form used in tasks.md.
+    pub fn label(&self) -> &'static str {
+        match self {
+            TaskStatus::NotStarted => "Not Started",
+            TaskStatus::InProgress => "In Progress",
+            TaskStatus::Completed => "Completed",
+            TaskStatus::Blocked => "Blocked",
+        }
+    }
+}
+
+impl Task {
+    pub fn new(id: impl Into<String>, title: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            title: title.into(),
+            status: TaskStatus::NotStarted,
+            priority: "Medium".to_string(),
+            description: String::new(),
+            estimate: None,
+            acceptance_criteria: Vec::new(),
+            notes: None,
+            depends_on: Vec::new(),
+        }
+    }
+
+    /// Numeric part of a `T<n>` id.
+    pub fn number(&self) -> Option<u32> {
+        self.id.strip_prefix('T').and_then(|n| n.parse().ok())
+    }
+}
+
+impl Feature {
+    /// Next unused task id for this feature.
+    pub fn next_task_id(&self) -> String {
+        let max = self.tasks.iter().filter_map(|t| t.number()).max().unwrap_or(0);
+        format!("T{}", max + 1)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Phase {
+    /// Immutable identifier (`P-02`). Survives renames and reordering.
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// Ids of the features that belong to this phase, in plan order
+    pub features: Vec<String>,
+}
+
+impl Default for ProjectState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProjectState {
+    pub fn new() -> Self {
+        Self {
+            schema_version: CURRENT_SCHEMA_VERSION,
+            project_name: None,
+            idea: None,
+            tech_stack: None,
+            features: None,
+            phases: None,
+            improvements: None,
+            notes: None,
+            id_counters: IdCounters::default(),
+            install: None,
+            discussion: Vec::new(),
+            worktrees: Vec::new(),
+        }
+    }
+
+    /// Non-empty answer to the `/discuss` question with `key`
+    pub fn answer(&self, key: &str) -> Option<&str> {
+        self.discussion
+            .iter()
+            .find(|a| a.key == key)
+            .map(|a| a.answer.trim())
+            .filter(|a| !a.is_empty())
+    }
+
+    pub fn load() -> Result<Self> {
+        let state_path = Self::state_path()?;
+        Self::load_from(&state_path)
+    }
+
+    /// Load state from an explicit path, migrating older schema versions in place.
+    ///
+    /// When a migration is needed, the original file is copied to
+    /// `state.json.v<N>.bak` before the upgraded state is written back.
+    pub fn load_from(state_path: &Path) -> Result<Self> {
+        if !state_path.exists() {
+            return Ok(Self::new());
+        }
+
+        let content = std::fs::read_to_string(state_path)
+            .context("Failed to read state file")?;
+
+        let raw: serde_json::Value = serde_json::from_str(&content)
+            .context("Failed to parse state file")?;
+
+        let version = migrations::schema_version_of(&raw)?;
+        if version > CURRENT_SCHEMA_VERSION {
+            return Err(DoPlanError::State(format!(
+                "{} was written by a newer DoPlan (schema version {}, this build supports up to {}). Upgrade DoPlan to open it.",
+                state_path.display(),
+                version,
+                CURRENT_SCHEMA_VERSION
+            ))
+            .into());
+        }
+
+        if version == CURRENT_SCHEMA_VERSION {
+            let mut state: ProjectState = serde_json::from_value(raw)
+                .context("Failed to parse state file")?;
+            if state.assign_ids() {
+                state.save_to(state_path)?;
+            }
+            return Ok(state);
+        }
+
+        let migrated = migrations::migrate(raw, version)
+            .with_context(|| format!("Failed to migrate state file from schema version {}", version))?;
+        let mut state: ProjectState = serde_json::from_value(migrated)
+            .context("Failed to parse migrated state file")?;
+        state.assign_ids();
+
+        let backup_path = migrations::backup_path(state_path, version);
+        std::fs::copy(state_path, &backup_path)
+            .with_context(|| format!("Failed to back up state file to: {}", backup_path.display()))?;
+        state.save_to(state_path)?;
+
+        Ok(state)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let state_path = Self::state_path()?;
+        self.save_to(&state_path)
+    }
+
+    pub fn save_to(&self, state_path: &Path) -> Result<()> {
+        if let Some(parent) = state_path.parent() {
+            utils::ensure_dir(parent)?;
+        }
+
+        let content = serde_json::to_string_pretty(self)
+            .context("Failed to serialize state")?;
+
+        std::fs::write(state_path, content)
+            .context("Failed to write state file")?;
+
+        Ok(())
+    }
+
+    /// Give every feature and phase an id and rewrite phase membership from
+    /// feature names to feature ids. Returns whether anything changed.
+    ///
+    /// Hand-written or pre-v3 state refers to features by name; names that do
+    /// not resolve to a feature are left in place for validation to report.
+    pub fn assign_ids(&mut self) -> bool {
+        let mut changed = false;
+
+        let max_seq = |ids: Vec<&str>, prefix: &str| {
+            ids.into_iter()
+                .filter_map(|id| id.strip_prefix(prefix).and_then(|n| n.parse::<u32>().ok()))
+                .max()
+                .unwrap_or(0)
+        };
+        let feature_max = max_seq(self.features.iter().flatten().map(|f| f.id.as_str()).collect(), "F-");
+        let phase_max = max_seq(self.phases.iter().flatten().map(|p| p.id.as_str()).collect(), "P-");
+        if self.id_counters.feature < feature_max {
+            self.id_counters.feature = feature_max;
+            changed = true;
+        }
+        if self.id_counters.phase < phase_max {
+            self.id_counters.phase = phase_max;
+            changed = true;
+        }
+
+        let missing_features = self.features.iter().flatten().filter(|f| f.id.is_empty()).count();
+        for _ in 0..missing_features {
+            let id = self.new_feature_id();
+            if let Some(feature) = self.features.iter_mut().flatten().find(|f| f.id.is_empty()) {
+                feature.id = id;
+                changed = true;
+            }
+        }
+
+        let missing_phases = self.phases.iter().flatten().filter(|p| p.id.is_empty()).count();
+        for _ in 0..missing_phases {
+            let id = self.new_phase_id();
+            if let Some(phase) = self.phases.iter_mut().flatten().find(|p| p.id.is_empty()) {
+                phase.id = id;
+                changed = true;
+            }
+        }
+
+        let features = self.features.clone().unwrap_or_default();
+        for phase in self.phases.iter_mut().flatten() {
+            for member in phase.features.iter_mut() {
+                if features.iter().any(|f| f.id == *member) {
+                    continue;
+                }
+                if let Some(feature) = features.iter().find(|f| f.name == *member) {
+                    *member = feature.id.clone();
+                    changed = true;
+                }
+            }
+        }
+
+        changed
+    }
+
+    /// Allocate the next feature id. Ids are never reused, even after deletion.
+    pub fn new_feature_id(&mut self) -> String {
+        self.id_counters.feature += 1;
+        format!("F-{:04}", self.id_counters.feature)
+    }
+
+    /// Allocate the next phase id. Ids are never reused, even after deletion.
+    pub fn new_phase_id(&mut self) -> String {
+        self.id_counters.phase += 1;
+        format!("P-{:02}", self.id_counters.phase)
+    }
+
+    pub fn feature_by_id(&self, id: &str) -> Option<&Feature> {
+        self.features.as_ref()?.iter().find(|f| f.id == id)
+    }
+
+    pub fn feature_by_id_mut(&mut self, id: &str) -> Option<&mut Feature> {
+        self.features.as_mut()?.iter_mut().find(|f| f.id == id)
+    }
+
+    pub fn phase_by_id(&self, id: &str) -> Option<&Phase> {
+        self.phases.as_ref()?.iter().find(|p| p.id == id)
+    }
+
+    /// Look up a feature by id or, failing that, by display name
+    pub fn find_feature(&self, key: &str) -> Option<&Feature> {
+        self.feature_by_id(key)
+            .or_else(|| self.features.as_ref()?.iter().find(|f| f.name == key))
+    }
+
+    /// Look up a phase by id or, failing that, by display name
+    pub fn find_phase(&self, key: &str) -> Option<&Phase> {
+        self.phase_by_id(key)
+            .or_else(|| self.phases.as_ref()?.iter().find(|p| p.name == key))
+    }
+
+    /// Features belonging to a phase, in phase order. Unknown ids are skipped.
+    pub fn phase_features(&self, phase: &Phase) -> Vec<&Feature> {
+        phase.features
+            .iter()
+            .filter_map(|key| self.find_feature(key))
+            .collect()
+    }
+
+    /// Check that feature and phase ids are unique and that phases list
+    /// existing features. Run [`assign_ids`](Self::assign_ids) first.
+    pub fn validate_ids(&self) -> Result<()> {
+        let mut problems = Vec::new();
+        let mut seen = std::collections::BTreeSet::new();
+        for feature in self.features.iter().flatten() {
+            if !seen.insert(feature.id.as_str()) {
+                problems.push(format!("duplicate feature id {}", feature.id));
+            }
+        }
+        let mut seen = std::collections::BTreeSet::new();
+        for phase in self.phases.iter().flatten() {
+            if !seen.insert(phase.id.as_str()) {
+                problems.push(format!("duplicate phase id {}", phase.id));
+            }
+            for member in &phase.features {
+                if self.feature_by_id(member).is_none() {
+                    problems.push(format!("phase {} lists unknown feature '{}'", phase.id, member));
+                }
+            }
+        }
+        if problems.is_empty() {
+            return Ok(());
+        }
+        let details = problems.iter().map(|p| format!("  - {}", p)).collect::<Vec<_>>().join("\n");
+        Err(DoPlanError::State(format!("Invalid ids:\n{}", details)).into())
+    }
+
+    /// Check feature and task dependencies for unknown references and cycles
+    pub fn validate_dependencies(&self) -> Result<()> {
+        let graph = deps::DependencyGraph::build(self);
+        if graph.issues().is_empty() {
+            return Ok(());
+        }
+        let details = graph
+            .issues()
+            .iter()
+            .map(|issue| format!("  - {}", issue))
+            .collect::<Vec<_>>()
+            .join("\n");
+        Err(DoPlanError::State(format!("Invalid task dependencies:\n{}", details)).into())
+    }
+
+    /// The phase a feature is assigned to, if any
+    pub fn phase_of(&self, feature_id: &str) -> Option<&Phase> {
+        self.phases.as_ref()?.iter().find(|p| p.features.iter().any(|f| f == feature_id))
+    }
+
+    pub fn state_path() -> Result<PathBuf> {
+        let dot_doplan = utils::dot_doplan_dir()?;
+        Ok(dot_doplan.join("state.json"))
+    }
+}
@@ -0,0 +1,363 @@
+//! Rendering and parsing of a feature's `tasks.md`.
+//!
+//! `tasks.md` is the human-editable view of [`Feature::tasks`]. Everything that
+//! reads task progress (`/progress`, `/next`, `/implement`) goes through
+//! [`parse`] so that the file and the state agree on what a task is.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use super::{AcceptanceCriterion, Feature, Task, TaskStatus};
+
+const NOTES_PLACEHOLDER: &str = "_Additional notes_";
+const NO_DEPENDENCIES: &str = "_No dependencies_";
+
+/// Render the full `tasks.md` document for a feature.
+pub fn render(feature: &Feature) -> String {
+    let mut content = String::new();
+    content.push_str("# Tasks\n\n");
+    content.push_str(&format!("**Feature:** {}\n\n", feature.name));
+    content.push_str(&format!("**Date:** {}\n\n", chrono::Utc::now().format("%Y-%m-%d")));
+    content.push_str("---\n\n");
+
+    content.push_str("## Tasks\n\n");
+    for task in &feature.tasks {
+        content.push_str(&render_task(task));
+    }
+
+    let count = |status: TaskStatus| feature.tasks.iter().filter(|t| t.status == status).count();
+    let completed = count(TaskStatus::Completed);
+    let overall = if feature.tasks.is_empty() {
+        0.0
+    } else {
+        completed as f64 / feature.tasks.len() as f64 * 100.0
+    };
+
+    content.push_str("## Progress Tracking\n\n");
+    content.push_str(&format!("**Overall Progress**: {:.0}%\n\n", overall));
+    content.push_str(&format!("- Completed: {}\n", completed));
+    content.push_str(&format!("- In Progress: {}\n", count(TaskStatus::InProgress)));
+    content.push_str(&format!("- Not Started: {}\n", count(TaskStatus::NotStarted)));
+    content.push_str(&format!("- Blocked: {}\n\n", count(TaskStatus::Blocked)));
+
+    content.push_str("## Dependencies\n\n");
+    if feature.depends_on.is_empty() {
+        content.push_str(&format!("{}\n\n", NO_DEPENDENCIES));
+    } else {
+        for dependency in &feature.depends_on {
+            content.push_str(&format!("- {}\n", dependency));
+        }
+        content.push('\n');
+    }
+
+    content.push_str("## Blockers\n\n");
+    content.push_str("_No blockers_\n\n");
+
+    content
+}
+
+/// Render a single `#### Task` block.
+pub fn render_task(task: &Task) -> String {
+    let mut content = String::new();
+    let heading_id = task
+        .number()
+        .map(|n| n.to_string())
+        .unwrap_or_else(|| task.id.clone());
+    content.push_str(&format!("#### Task {}: {}\n", heading_id, task.title));
+
+    content.push_str(&format!("- **Status**: {}\n", status_checkboxes(task.status)));
+    content.push_str(&format!("- **Priority**: {}\n", task.priority));
+    content.push_str(&format!("- **Description**: {}\n", task.description));
+    content.push_str("- **Acceptance Criteria**:\n");
+    for criterion in &task.acceptance_criteria {
+        content.push_str(&format!(
+            "  - [{}] {}\n",
+            if criterion.done { "x" } else { " " },
+            criterion.text
+        ));
+    }
+    content.push_str(&format!(
+        "- **Estimated Time**: {}\n",
+        task.estimate.as_deref().unwrap_or("Unknown")
+    ));
+    content.push_str(&format!(
+        "- **Depends On**: {}\n",
+        if task.depends_on.is_empty() { "None".to_string() } else { task.depends_on.join(", ") }
+    ));
+    content.push_str(&format!(
+        "- **Notes**: {}\n\n",
+        task.notes.as_deref().unwrap_or(NOTES_PLACEHOLDER)
+    ));
+    content
+}
+
+/// The `[x] Not Started | [ ] In Progress | ...` value of a Status line
+fn status_checkboxes(status: TaskStatus) -> String {
+    TaskStatus::ALL
+        .iter()
+        .map(|s| format!("[{}] {}", if *s == status { "x" } else { " " }, s.label()))
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+/// Change one task's status in an existing `tasks.md`, leaving every other
+/// line as the user wrote it. Returns `None` when the task is not in the
+/// document.
+pub fn set_status(content: &str, task_id: &str, status: TaskStatus) -> Option<String> {
+    set_field(content, task_id, "Status", &status_checkboxes(status))
+}
+
+/// Change one task's Depends On line in an existing `tasks.md`, like
+/// [`set_status`]
+pub fn set_depends_on(content: &str, task_id: &str, depends_on: &[String]) -> Option<String> {
+    let value = if depends_on.is_empty() { "None".to_string() } else { depends_on.join(", ") };
+    set_field(content, task_id, "Depends On", &value)
+}
+
+fn set_field(content: &str, task_id: &str, field: &str, value: &str) -> Option<String> {
+    // Task blocks appear in the same order `parse` returns them
+    let block = parse(content).iter().position(|t| t.id == task_id)?;
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut current_block: Option<usize> = None;
+    let mut blocks_seen = 0;
+    let mut replaced = false;
+    for line in content.lines() {
+        if line.starts_with("#### Task") {
+            current_block = Some(blocks_seen);
+            blocks_seen += 1;
+        } else if line.starts_with('#') {
+            current_block = None;
+        } else if !replaced
+            && current_block == Some(block)
+            && parse_field(line).is_some_and(|(name, _)| name == field)
+        {
+            let indent = &line[..line.len() - line.trim_start().len()];
+            lines.push(format!("{}- **{}**: {}", indent, field, value));
+            replaced = true;
+            continue;
+        }
+        lines.push(line.to_string());
+    }
+    if !replaced {
+        return None;
+    }
+
+    let mut updated = lines.join("\n");
+    if content.ends_with('\n') {
+        updated.push('\n');
+    }
+    Some(updated)
+}
+
+/// Parse every `#### Task` block in a `tasks.md` document.
+///
+/// Headings may carry a bare number (`#### Task 2: Build`) or a full id
+/// (`#### Task T2: Build`); both map to id `T2`. Headings without a number are
+/// assigned the next free id in document order.
+pub fn parse(content: &str) -> Vec<Task> {
+    let mut tasks: Vec<Task> = Vec::new();
+    let mut current: Option<Task> = None;
+    let mut in_criteria = false;
+
+    for line in content.lines() {
+        if let Some(rest) = line.strip_prefix("#### Task") {
+            if let Some(task) = current.take() {
+                tasks.push(task);
+            }
+            current = Some(parse_heading(rest));
+            in_criteria = false;
+            continue;
+        }
+
+        // Any other heading closes the current task block
+        if line.starts_with('#') {
+            if let Some(task) = current.take() {
+                tasks.push(task);
+            }
+            in_criteria = false;
+            continue;
+        }
+
+        let Some(task) = current.as_mut() else {
+            continue;
+        };
+
+        if let Some((field, value)) = parse_field(line) {
+            in_criteria = false;
+            match field {
+                "Status" => task.status = parse_status(value),
+                "Priority" => task.priority = value.to_string(),
+                "Description" => task.description = value.to_string(),
+                "Acceptance Criteria" => in_criteria = true,
+                "Estimated Time" => {
+                    task.estimate = match value {
+                        "" | "Unknown" => None,
+                        v => Some(v.to_string()),
+                    }
+                }
+                "Depends On" => task.depends_on = parse_references(value),
+                "Notes" => {
+                    task.notes = match value {
+                        "" | NOTES_PLACEHOLDER => None,
+                        v => Some(v.to_string()),
+                    }
+                }
+                _ => {}
+            }
+        } else if in_criteria {
+            if let Some(criterion) = parse_checkbox(line.trim_start()) {
+                task.acceptance_criteria.push(criterion);
+            }
+        }
+    }
+
+    if let Some(task) = current.take() {
+        tasks.push(task);
+    }
+
+    assign_missing_ids(&mut tasks);
+    tasks
+}
+
+/// Read and parse a `tasks.md` file.
+pub fn read(path: &Path) -> Result<Vec<Task>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read tasks from: {}", path.display()))?;
+    Ok(parse(&content))
+}
+
+/// Render and write `tasks.md` into a feature directory.
+pub fn write(feature_dir: &Path, feature: &Feature) -> Result<PathBuf> {
+    let tasks_path = feature_dir.join("tasks.md");
+    std::fs::write(&tasks_path, render(feature))
+        .with_context(|| format!("Failed to write feature tasks to: {}", tasks_path.display()))?;
+    Ok(tasks_path)
+}
+
+fn parse_heading(rest: &str) -> Task {
+    let rest = rest.trim();
+    let (id_part, title) = match rest.split_once(':') {
+        Some((id, title)) => (id.trim(), title.trim()),
+        None => ("", rest),
+    };
+
+    let number = id_part
+        .strip_prefix('T')
+        .or_else(|| id_part.strip_prefix('t'))
+        .unwrap_or(id_part)
+        .parse::<u32>()
+        .ok();
+
+    let (id, title) = match number {
+        Some(n) => (format!("T{}", n), title.to_string()),
+        // "#### Task Setup" or "#### Task foo: bar" - keep the whole text as the title
+        None => (String::new(), rest.to_string()),
+    };
+
+    let mut task = Task::new(id, title);
+    task.priority = String::new();
+    task
+}
+
+fn parse_field(line: &str) -> Option<(&str, &str)> {
+    let rest = line.trim_start().strip_prefix("- **")?;
+    let (field, value) = rest.split_once("**")?;
+    let value = value.trim_start().strip_prefix(':').unwrap_or(value);
+    Some((field.trim(), value.trim()))
+}
+
+fn parse_references(value: &str) -> Vec<String> {
+    if value.eq_ignore_ascii_case("none") {
+        return Vec::new();
+    }
+    value
+        .split(',')
+        .map(|r| r.trim())
+        .filter(|r| !r.is_empty())
+        .map(|r| r.to_string())
+        .collect()
+}
+
+fn parse_status(value: &str) -> TaskStatus {
+    let checked = |status: TaskStatus| {
+        value.contains(&format!("[x] {}", status.label()))
+            || value.contains(&format!("[X] {}", status.label()))
+    };
+    // Completed wins over everything else so a forgotten "In Progress" tick
+    // does not hide finished work.
+    [TaskStatus::Completed, TaskStatus::InProgress, TaskStatus::Blocked]
+        .into_iter()
+        .find(|s| checked(*s))
+        .unwrap_or(TaskStatus::NotStarted)
+}
+
+fn parse_checkbox(line: &str) -> Option<AcceptanceCriterion> {
+    let rest = line.strip_prefix("- [")?;
+    let (mark, text) = rest.split_once(']')?;
+    Some(AcceptanceCriterion {
+        text: text.trim().to_string(),
+        done: mark.eq_ignore_ascii_case("x"),
+    })
+}
+
+fn assign_missing_ids(tasks: &mut [Task]) {
+    let mut next = tasks.iter().filter_map(|t| t.number()).max().unwrap_or(0);
+    for task in tasks.iter_mut().filter(|t| t.id.is_empty()) {
+        next += 1;
+        task.id = format!("T{}", next);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_heading_forms() {
+        assert_eq!(parse_heading(" 1: Setup").id, "T1");
+        assert_eq!(parse_heading(" T7: Build").id, "T7");
+        let untitled = parse_heading(" Setup");
+        assert_eq!(untitled.id, "");
+        assert_eq!(untitled.title, "Setup");
+    }
+
+    #[test]
+    fn test_parse_dependencies() {
+        assert_eq!(parse_references("None"), Vec::<String>::new());
+        assert_eq!(parse_references("T1, F-0002/T3"), vec!["T1", "F-0002/T3"]);
+    }
+
+    #[test]
+    fn test_parse_status_precedence() {
+        assert_eq!(parse_status("[ ] Not Started | [ ] In Progress | [ ] Completed | [ ] Blocked"), TaskStatus::NotStarted);
+        assert_eq!(parse_status("[x] Not Started | [X] In Progress | [ ] Completed | [ ] Blocked"), TaskStatus::InProgress);
+        assert_eq!(parse_status("[ ] Not Started | [x] In Progress | [x] Completed | [ ] Blocked"), TaskStatus::Completed);
+    }
+
+    #[test]
+    fn test_set_status_only_touches_one_task() {
+        let content = "# Tasks\n\n#### Task 1: Setup\n- **Status**: [x] Not Started | [ ] In Progress\n- **Notes**: keep me\n\n\
+                       #### Task 2: Build\n- **Status**: [x] Not Started | [ ] In Progress\n\n## Blockers\n\n- **Status**: unrelated\n";
+
+        let updated = set_status(content, "T2", TaskStatus::Completed).unwrap();
+        let tasks = parse(&updated);
+        assert_eq!(tasks[0].status, TaskStatus::NotStarted);
+        assert_eq!(tasks[1].status, TaskStatus::Completed);
+        assert!(updated.contains("- **Notes**: keep me\n"));
+        assert!(updated.ends_with("- **Status**: unrelated\n"));
+        assert_eq!(updated.lines().count(), content.lines().count());
+
+        assert_eq!(set_status(content, "T9", TaskStatus::Completed), None);
+    }
+
+    #[test]
+    fn test_set_depends_on() {
+        let content = "#### Task 1: Setup\n- **Depends On**: F-0002/T1, T2\n- **Notes**: keep me\n";
+
+        let updated = set_depends_on(content, "T1", &["T2".to_string()]).unwrap();
+        assert_eq!(updated, "#### Task 1: Setup\n- **Depends On**: T2\n- **Notes**: keep me\n");
+        let updated = set_depends_on(&updated, "T1", &[]).unwrap();
+        assert_eq!(parse(&updated)[0].depends_on, Vec::<String>::new());
+        assert!(updated.contains("- **Depends On**: None\n"));
+    }
+}
@@ -0,0 +1,147 @@
+//! How task statuses roll up into feature, phase and overall percentages.
+//!
+//! - `count` (the default): every task counts the same, a phase is the plain
+//!   mean of its features and the project the mean of its phases.
+//! - `estimate`: tasks are weighted by their parsed estimate, features by the
+//!   hours in their tasks and phases by the hours in their features.
+//! - `priority`: tasks are weighted by estimate as above, but features are
+//!   weighted by priority (high 3, medium 2, low 1) and phases by the sum of
+//!   their feature weights.
+
+use serde::{Deserialize, Serialize};
+use super::{Task, TaskStatus, WorkingCalendar};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProgressWeighting {
+    #[default]
+    Count,
+    Estimate,
+    Priority,
+}
+
+impl ProgressWeighting {
+    pub const ALL: [ProgressWeighting; 3] = [Self::Count, Self::Estimate, Self::Priority];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Count => "count",
+            Self::Estimate => "estimate",
+            Self::Priority => "priority",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|w| w.as_str() == value.trim().to_lowercase())
+    }
+
+    /// One-line explanation for dashboards
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Count => "Each task counts equally; phases and the project average their parts",
+            Self::Estimate => "Tasks weighted by estimated hours; features and phases by the hours they contain",
+            Self::Priority => "Tasks weighted by estimated hours; features by priority (high 3, medium 2, low 1); phases by feature weight",
+        }
+    }
+
+    pub fn task_weight(self, task: &Task, calendar: &WorkingCalendar) -> f64 {
+        match self {
+            Self::Count => 1.0,
+            Self::Estimate | Self::Priority => calendar.task_hours(task.estimate.as_deref()).0,
+        }
+    }
+
+    /// Completed share of `tasks`, as a percentage
+    pub fn feature_progress(self, tasks: &[Task], calendar: &WorkingCalendar) -> f64 {
+        let total: f64 = tasks.iter().map(|t| self.task_weight(t, calendar)).sum();
+        let completed: f64 = tasks
+            .iter()
+            .filter(|t| t.status == TaskStatus::Completed)
+            .map(|t| self.task_weight(t, calendar))
+            .sum();
+        if total > 0.0 {
+            completed / total * 100.0
+        } else {
+            0.0
+        }
+    }
+
+    /// Weight of a feature within its phase
+    pub fn feature_weight(self, tasks: &[Task], priority: &str, calendar: &WorkingCalendar) -> f64 {
+        match self {
+            Self::Count => 1.0,
+            Self::Estimate => tasks.iter().map(|t| self.task_weight(t, calendar)).sum(),
+            Self::Priority => priority_weight(priority),
+        }
+    }
+
+    /// Weight of a phase within the project, from its feature weights
+    pub fn phase_weight(self, feature_weights: impl IntoIterator<Item = f64>) -> f64 {
+        match self {
+            Self::Count => 1.0,
+            Self::Estimate | Self::Priority => feature_weights.into_iter().sum(),
+        }
+    }
+}
+
+pub fn priority_weight(priority: &str) -> f64 {
+    match priority.to_lowercase().as_str() {
+        "high" | "critical" => 3.0,
+        "low" => 1.0,
+        _ => 2.0,
+    }
+}
+
+/// Mean of `(progress, weight)` pairs; 0 when there is no weight at all
+pub fn weighted_mean(items: impl IntoIterator<Item = (f64, f64)>) -> f64 {
+    let (sum, weight) = items
+        .into_iter()
+        .fold((0.0, 0.0), |(sum, weight), (progress, w)| (sum + progress * w, weight + w));
+    if weight > 0.0 {
+        sum / weight
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(estimate: &str, status: TaskStatus) -> Task {
+        Task {
+            estimate: Some(estimate.to_string()),
+            status,
+            ..Task::new("T1", "Task")
+        }
+    }
+
+    #[test]
+    fn test_estimate_weighting_favours_long_tasks() {
+        let calendar = WorkingCalendar::default();
+        let tasks = vec![
+            task("1 hour", TaskStatus::Completed),
+            task("4 days", TaskStatus::NotStarted),
+        ];
+
+        assert_eq!(ProgressWeighting::Count.feature_progress(&tasks, &calendar), 50.0);
+        let weighted = ProgressWeighting::Estimate.feature_progress(&tasks, &calendar);
+        assert!((weighted - 100.0 / 33.0).abs() < 1e-9);
+        assert_eq!(ProgressWeighting::Estimate.feature_weight(&tasks, "low", &calendar), 33.0);
+        assert_eq!(ProgressWeighting::Priority.feature_weight(&tasks, "high", &calendar), 3.0);
+    }
+
+    #[test]
+    fn test_weighted_mean() {
+        assert_eq!(weighted_mean([(100.0, 3.0), (0.0, 1.0)]), 75.0);
+        assert_eq!(weighted_mean([(100.0, 0.0)]), 0.0);
+        assert_eq!(weighted_mean(Vec::new()), 0.0);
+    }
+
+    #[test]
+    fn test_parse_modes() {
+        assert_eq!(ProgressWeighting::parse("Estimate"), Some(ProgressWeighting::Estimate));
+        assert_eq!(ProgressWeighting::parse("priority"), Some(ProgressWeighting::Priority));
+        assert_eq!(ProgressWeighting::parse("hours"), None);
+    }
+}
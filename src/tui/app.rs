@@ -0,0 +1,258 @@
+use anyhow::{Context, Result};
+use crossterm::event::KeyCode;
+use crate::commands::next::{self, NextAction};
+use crate::commands::progress::{self, PhaseProgress};
+use crate::config::Config;
+use crate::state::{Feature, ProgressWeighting, ProjectState, Task, TaskStatus};
+use crate::utils;
+use crate::utils::plan_dirs;
+use super::wizard::Wizard;
+
+#[derive(Debug)]
+pub enum Screen {
+    /// No project yet: collect the basics the way /discuss does
+    Wizard(Wizard),
+    Main,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Focus {
+    Tree,
+    Tasks,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TreeNode {
+    Phase(String),
+    Feature(String),
+}
+
+/// One row of the phase/feature tree
+#[derive(Debug, Clone)]
+pub struct TreeRow {
+    pub node: TreeNode,
+    pub label: String,
+    pub depth: usize,
+    pub progress: Option<f64>,
+}
+
+pub struct App {
+    pub screen: Screen,
+    pub state: ProjectState,
+    /// Same per-phase numbers `/progress` writes to `dashboard.json`
+    pub phases: Vec<PhaseProgress>,
+    pub overall_progress: f64,
+    /// `progress.weighting` from the configuration
+    pub weighting: ProgressWeighting,
+    pub next: Option<NextAction>,
+    pub tree: Vec<TreeRow>,
+    pub tree_index: usize,
+    pub task_index: usize,
+    pub focus: Focus,
+    /// One-line status shown in the footer
+    pub message: Option<String>,
+    pub should_quit: bool,
+}
+
+impl App {
+    /// Open on the wizard when the project has not been discussed yet (no
+    /// state, or only what `doplan install` records), otherwise on the project view
+    pub fn load() -> Result<Self> {
+        let state = ProjectState::load().context("Failed to load project state")?;
+        let has_state = state.project_name.is_some();
+        let mut app = App {
+            screen: if has_state { Screen::Main } else { Screen::Wizard(Wizard::new()) },
+            state,
+            phases: Vec::new(),
+            overall_progress: 0.0,
+            weighting: ProgressWeighting::default(),
+            next: None,
+            tree: Vec::new(),
+            tree_index: 0,
+            task_index: 0,
+            focus: Focus::Tree,
+            message: None,
+            should_quit: false,
+        };
+        if has_state {
+            app.refresh()?;
+        }
+        Ok(app)
+    }
+
+    /// Re-read the plan from disk and recompute progress and the next action
+    pub fn refresh(&mut self) -> Result<()> {
+        let plan_dir = utils::doplan_dir()?.join("plan");
+        self.weighting = Config::current()?.progress.weighting;
+        self.phases = if plan_dir.exists() {
+            progress::collect(&plan_dir, &mut self.state, self.weighting)?
+        } else {
+            Vec::new()
+        };
+        self.overall_progress = progress::overall_progress(&self.phases);
+        self.next = match next::recommend(&mut self.state) {
+            Ok(action) => Some(action),
+            Err(err) => {
+                self.message = Some(format!("{:#}", err));
+                None
+            }
+        };
+        self.build_tree();
+        Ok(())
+    }
+
+    fn build_tree(&mut self) {
+        let feature_progress = |id: &str| {
+            self.phases
+                .iter()
+                .flat_map(|p| &p.features)
+                .find(|f| f.feature_id == id)
+                .map(|f| f.progress)
+        };
+
+        let mut rows = Vec::new();
+        for phase in self.state.phases.iter().flatten() {
+            rows.push(TreeRow {
+                node: TreeNode::Phase(phase.id.clone()),
+                label: phase.name.clone(),
+                depth: 0,
+                progress: self.phases.iter().find(|p| p.phase_id == phase.id).map(|p| p.progress),
+            });
+            for feature in self.state.phase_features(phase) {
+                rows.push(TreeRow {
+                    node: TreeNode::Feature(feature.id.clone()),
+                    label: feature.name.clone(),
+                    depth: 1,
+                    progress: feature_progress(&feature.id),
+                });
+            }
+        }
+
+        // Features not yet placed in a phase still get a row
+        for feature in self.state.features.iter().flatten() {
+            if self.state.phase_of(&feature.id).is_none() {
+                rows.push(TreeRow {
+                    node: TreeNode::Feature(feature.id.clone()),
+                    label: feature.name.clone(),
+                    depth: 0,
+                    progress: feature_progress(&feature.id),
+                });
+            }
+        }
+
+        self.tree = rows;
+        self.tree_index = self.tree_index.min(self.tree.len().saturating_sub(1));
+        self.task_index = self.task_index.min(self.tasks().len().saturating_sub(1));
+    }
+
+    pub fn selected_feature(&self) -> Option<&Feature> {
+        match &self.tree.get(self.tree_index)?.node {
+            TreeNode::Feature(id) => self.state.feature_by_id(id),
+            TreeNode::Phase(_) => None,
+        }
+    }
+
+    pub fn tasks(&self) -> &[Task] {
+        self.selected_feature().map(|f| f.tasks.as_slice()).unwrap_or(&[])
+    }
+
+    pub fn selected_task(&self) -> Option<&Task> {
+        self.tasks().get(self.task_index)
+    }
+
+    pub fn handle_key(&mut self, key: KeyCode) -> Result<()> {
+        if let Screen::Wizard(wizard) = &mut self.screen {
+            match key {
+                KeyCode::Esc => self.should_quit = true,
+                KeyCode::Enter if wizard.is_last_field() => self.finish_wizard()?,
+                _ => wizard.handle_key(key),
+            }
+            return Ok(());
+        }
+
+        self.message = None;
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => self.should_quit = true,
+            KeyCode::Tab => {
+                self.focus = match self.focus {
+                    Focus::Tree if self.selected_feature().is_some() => Focus::Tasks,
+                    _ => Focus::Tree,
+                }
+            }
+            KeyCode::Right | KeyCode::Enter if self.selected_feature().is_some() => self.focus = Focus::Tasks,
+            KeyCode::Left => self.focus = Focus::Tree,
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            KeyCode::Char(' ') | KeyCode::Char('x') if self.focus == Focus::Tasks => {
+                if let Some(task) = self.selected_task() {
+                    let status = match task.status {
+                        TaskStatus::NotStarted => TaskStatus::InProgress,
+                        TaskStatus::InProgress => TaskStatus::Completed,
+                        TaskStatus::Completed | TaskStatus::Blocked => TaskStatus::NotStarted,
+                    };
+                    self.set_task_status(status)?;
+                }
+            }
+            KeyCode::Char('b') if self.focus == Focus::Tasks => {
+                if let Some(task) = self.selected_task() {
+                    let status = if task.status == TaskStatus::Blocked { TaskStatus::NotStarted } else { TaskStatus::Blocked };
+                    self.set_task_status(status)?;
+                }
+            }
+            KeyCode::Char('r') => {
+                self.state = ProjectState::load().context("Failed to load project state")?;
+                self.refresh()?;
+                self.message = Some("Reloaded".to_string());
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let task_count = self.tasks().len();
+        let (index, len) = match self.focus {
+            Focus::Tree => (&mut self.tree_index, self.tree.len()),
+            Focus::Tasks => (&mut self.task_index, task_count),
+        };
+        if len == 0 {
+            return;
+        }
+        *index = (*index as isize + delta).clamp(0, len as isize - 1) as usize;
+        if self.focus == Focus::Tree {
+            self.task_index = 0;
+        }
+    }
+
+    /// Write the selected task's new status into its `tasks.md` and refresh
+    fn set_task_status(&mut self, status: TaskStatus) -> Result<()> {
+        let (Some(feature), Some(task)) = (self.selected_feature(), self.selected_task()) else {
+            return Ok(());
+        };
+        let (feature_id, task_id) = (feature.id.clone(), task.id.clone());
+
+        let plan_dir = utils::doplan_dir()?.join("plan");
+        plan_dirs::set_task_status(&plan_dir, &mut self.state, &feature_id, &task_id, status)?;
+
+        self.refresh()?;
+        self.state.save().context("Failed to save project state")?;
+        self.message = Some(format!("{}/{} → {}", feature_id, task_id, status.label()));
+        Ok(())
+    }
+
+    fn finish_wizard(&mut self) -> Result<()> {
+        let Screen::Wizard(wizard) = &self.screen else {
+            return Ok(());
+        };
+        if wizard.value(Wizard::PROJECT_NAME).trim().is_empty() {
+            self.message = Some("A project name is required".to_string());
+            return Ok(());
+        }
+
+        wizard.apply(&mut self.state)?;
+        self.screen = Screen::Main;
+        self.refresh()?;
+        self.message = Some("Saved .doplan/state.json. Run /generate and then /plan next.".to_string());
+        Ok(())
+    }
+}
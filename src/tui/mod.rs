@@ -0,0 +1,53 @@
+//! Full-screen terminal UI, opened by `doplan --tui` or a bare `doplan`.
+//!
+//! The UI reads the same plan files as the commands: task statuses come from
+//! each feature's `tasks.md` and are written back there, progress is computed
+//! by [`crate::commands::progress::collect`] and the next action by
+//! [`crate::commands::next::recommend`].
+
+pub mod app;
+pub mod ui;
+pub mod wizard;
+
+pub use app::{App, Focus, Screen, TreeNode};
+
+use anyhow::{Context, Result};
+use crossterm::event::{self, Event, KeyEventKind};
+use crossterm::execute;
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+use ratatui::backend::{Backend, CrosstermBackend};
+use ratatui::Terminal;
+use std::io;
+
+/// Run the TUI until the user quits, restoring the terminal afterwards
+pub fn run() -> Result<()> {
+    let mut app = App::load()?;
+
+    enable_raw_mode().context("Failed to enable raw mode")?;
+    execute!(io::stdout(), EnterAlternateScreen).context("Failed to enter alternate screen")?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))
+        .context("Failed to initialise terminal")?;
+
+    let result = event_loop(&mut terminal, &mut app);
+
+    disable_raw_mode().context("Failed to disable raw mode")?;
+    execute!(io::stdout(), LeaveAlternateScreen).context("Failed to leave alternate screen")?;
+    terminal.show_cursor().context("Failed to show cursor")?;
+    result
+}
+
+fn event_loop<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()> {
+    while !app.should_quit {
+        terminal.draw(|frame| ui::draw(frame, app))?;
+        if let Event::Key(key) = event::read().context("Failed to read terminal event")? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            // Keep the UI up when an action fails; the error goes in the footer
+            if let Err(err) = app.handle_key(key.code) {
+                app.message = Some(format!("{:#}", err));
+            }
+        }
+    }
+    Ok(())
+}
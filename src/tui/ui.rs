@@ -0,0 +1,224 @@
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Gauge, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::Frame;
+use crate::commands::next::NextAction;
+use crate::state::TaskStatus;
+use crate::utils::history::TaskCounts;
+use super::app::{App, Focus, Screen, TreeNode};
+use super::wizard::Wizard;
+
+pub fn draw(frame: &mut Frame, app: &App) {
+    match &app.screen {
+        Screen::Wizard(wizard) => draw_wizard(frame, app, wizard),
+        Screen::Main => draw_main(frame, app),
+    }
+}
+
+fn draw_wizard(frame: &mut Frame, app: &App, wizard: &Wizard) {
+    let [body, footer] = split_footer(frame.size());
+
+    let mut lines = vec![
+        Line::from("No DoPlan project here yet. Tell us about your idea to get started."),
+        Line::from(""),
+    ];
+    for (i, field) in wizard.fields.iter().enumerate() {
+        let selected = i == wizard.index;
+        let marker = if selected { "› " } else { "  " };
+        let label_style = if selected {
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Gray)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{}{}: ", marker, field.label), label_style),
+            Span::raw(field.value.clone()),
+            Span::styled(if selected { "▏" } else { "" }, Style::default().fg(Color::Cyan)),
+        ]));
+        if selected && field.value.is_empty() {
+            lines.push(Line::from(Span::styled(format!("    {}", field.hint), Style::default().fg(Color::DarkGray))));
+        }
+    }
+
+    frame.render_widget(
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(Block::default().borders(Borders::ALL).title(" DoPlan: Discuss ")),
+        body,
+    );
+    draw_footer(frame, footer, app, "Tab/Enter next field · Shift-Tab back · Enter on the last field saves · Esc quit");
+}
+
+fn draw_main(frame: &mut Frame, app: &App) {
+    let [body, footer] = split_footer(frame.size());
+    let [left, right] = Layout::horizontal([Constraint::Percentage(35), Constraint::Percentage(65)]).areas(body);
+    let [tasks_area, bottom] = Layout::vertical([Constraint::Percentage(55), Constraint::Percentage(45)]).areas(right);
+    let [progress_area, next_area] = Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(bottom);
+
+    draw_tree(frame, app, left);
+    draw_tasks(frame, app, tasks_area);
+    draw_progress(frame, app, progress_area);
+    draw_next(frame, app, next_area);
+    draw_footer(frame, footer, app, "↑↓ move · Tab/←→ switch pane · Space cycle status · b blocked · r reload · q quit");
+}
+
+fn split_footer(area: Rect) -> [Rect; 2] {
+    Layout::vertical([Constraint::Min(3), Constraint::Length(1)]).areas(area)
+}
+
+fn pane(title: &str, focused: bool) -> Block<'_> {
+    let style = if focused { Style::default().fg(Color::Cyan) } else { Style::default() };
+    Block::default().borders(Borders::ALL).border_style(style).title(format!(" {} ", title))
+}
+
+fn draw_tree(frame: &mut Frame, app: &App, area: Rect) {
+    let title = app.state.project_name.as_deref().unwrap_or("Project");
+    let items: Vec<ListItem> = app
+        .tree
+        .iter()
+        .map(|row| {
+            let progress = row.progress.map(|p| format!(" {:>3.0}%", p)).unwrap_or_default();
+            let style = match row.node {
+                TreeNode::Phase(_) => Style::default().add_modifier(Modifier::BOLD),
+                TreeNode::Feature(_) => Style::default(),
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{}{}", "  ".repeat(row.depth), row.label), style),
+                Span::styled(progress, Style::default().fg(Color::Green)),
+            ]))
+        })
+        .collect();
+
+    let list = if items.is_empty() {
+        List::new(vec![ListItem::new("No phases yet. Run /discuss.")])
+    } else {
+        List::new(items)
+    };
+    let mut list_state = ListState::default().with_selected(if app.tree.is_empty() { None } else { Some(app.tree_index) });
+    frame.render_stateful_widget(
+        list.block(pane(title, app.focus == Focus::Tree))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED)),
+        area,
+        &mut list_state,
+    );
+}
+
+fn status_marker(status: TaskStatus) -> Span<'static> {
+    match status {
+        TaskStatus::NotStarted => Span::raw("[ ]"),
+        TaskStatus::InProgress => Span::styled("[~]", Style::default().fg(Color::Yellow)),
+        TaskStatus::Completed => Span::styled("[x]", Style::default().fg(Color::Green)),
+        TaskStatus::Blocked => Span::styled("[!]", Style::default().fg(Color::Red)),
+    }
+}
+
+fn draw_tasks(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(feature) = app.selected_feature() else {
+        frame.render_widget(
+            Paragraph::new("Select a feature to see its tasks.").block(pane("Tasks", false)),
+            area,
+        );
+        return;
+    };
+
+    let title = format!("Tasks: {} ({})", feature.name, feature.id);
+    if feature.tasks.is_empty() {
+        frame.render_widget(
+            Paragraph::new("No tasks yet. Run /plan to generate them.").block(pane(&title, app.focus == Focus::Tasks)),
+            area,
+        );
+        return;
+    }
+
+    let items: Vec<ListItem> = feature
+        .tasks
+        .iter()
+        .map(|task| {
+            ListItem::new(Line::from(vec![
+                status_marker(task.status),
+                Span::raw(format!(" {} {}", task.id, task.title)),
+                Span::styled(
+                    task.estimate.as_deref().map(|e| format!("  ({})", e)).unwrap_or_default(),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]))
+        })
+        .collect();
+    let mut list_state = ListState::default().with_selected((app.focus == Focus::Tasks).then_some(app.task_index));
+    frame.render_stateful_widget(
+        List::new(items)
+            .block(pane(&title, app.focus == Focus::Tasks))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED)),
+        area,
+        &mut list_state,
+    );
+}
+
+fn draw_progress(frame: &mut Frame, app: &App, area: Rect) {
+    let block = pane("Progress", false);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let [gauge_area, details] = Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(inner);
+
+    frame.render_widget(
+        Gauge::default()
+            .gauge_style(Style::default().fg(Color::Green))
+            .ratio((app.overall_progress / 100.0).clamp(0.0, 1.0))
+            .label(format!("Overall {:.1}%", app.overall_progress)),
+        gauge_area,
+    );
+
+    let mut tasks = TaskCounts::default();
+    for feature in app.phases.iter().flat_map(|p| &p.features) {
+        tasks += feature.tasks;
+    }
+    let mut lines = vec![
+        Line::from(format!("Weighting: {}", app.weighting.as_str())),
+        Line::from(format!(
+            "Tasks: {} done, {} in progress, {} not started, {} blocked",
+            tasks.completed, tasks.in_progress, tasks.not_started, tasks.blocked
+        )),
+    ];
+    for phase in &app.phases {
+        lines.push(Line::from(format!("{} {:.1}%", phase.phase_name, phase.progress)));
+    }
+    if app.phases.is_empty() {
+        lines.push(Line::from("No plan yet. Run /plan."));
+    }
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), details);
+}
+
+fn draw_next(frame: &mut Frame, app: &App, area: Rect) {
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let lines: Vec<Line> = match &app.next {
+        Some(NextAction::Task(rec)) => vec![
+            Line::from(Span::styled(rec.action.clone(), bold)),
+            Line::from(format!("Feature: {} ({})", rec.feature_name, rec.phase_name)),
+            Line::from(format!("Estimate: {}", rec.estimated_effort)),
+            Line::from(rec.reason.clone()),
+        ],
+        Some(NextAction::NoPlan) if app.state.features.as_ref().is_none_or(|f| f.is_empty()) => {
+            vec![Line::from("Run /discuss to describe your features.")]
+        }
+        Some(NextAction::NoPlan) => vec![Line::from("Run /generate and /plan to create the task plan.")],
+        Some(NextAction::AllComplete) => vec![Line::from(Span::styled("All tasks are complete!", bold))],
+        Some(NextAction::AllBlocked { waiting }) => vec![Line::from(format!(
+            "All {} incomplete tasks are waiting on prerequisites.",
+            waiting
+        ))],
+        None => vec![Line::from("No recommendation available.")],
+    };
+    frame.render_widget(
+        Paragraph::new(lines).wrap(Wrap { trim: true }).block(pane("Next Action", false)),
+        area,
+    );
+}
+
+fn draw_footer(frame: &mut Frame, area: Rect, app: &App, help: &str) {
+    let line = match &app.message {
+        Some(message) => Line::from(Span::styled(message.clone(), Style::default().fg(Color::Yellow))),
+        None => Line::from(Span::styled(help.to_string(), Style::default().fg(Color::DarkGray))),
+    };
+    frame.render_widget(Paragraph::new(line), area);
+}
@@ -0,0 +1,117 @@
+use anyhow::{Context, Result};
+use crossterm::event::KeyCode;
+use crate::commands::discuss;
+use crate::config::Config;
+use crate::state::{Phase, ProjectState};
+
+#[derive(Debug, Clone)]
+pub struct WizardField {
+    pub label: &'static str,
+    pub hint: &'static str,
+    pub value: String,
+}
+
+/// The questions `/discuss` asks first, as a single form
+#[derive(Debug, Clone)]
+pub struct Wizard {
+    pub fields: Vec<WizardField>,
+    pub index: usize,
+}
+
+impl Wizard {
+    pub const PROJECT_NAME: usize = 0;
+    pub const IDEA: usize = 1;
+    pub const FEATURES: usize = 2;
+    pub const PHASES: usize = 3;
+    pub const TECH_STACK: usize = 4;
+
+    pub fn new() -> Self {
+        let field = |label, hint| WizardField { label, hint, value: String::new() };
+        Wizard {
+            fields: vec![
+                field("Project name", "What's your project name?"),
+                field("Idea", "Describe your app idea"),
+                field("Features", "Main features, comma-separated"),
+                field("Phases", "Development phases, comma-separated (default: Phase 1)"),
+                field("Tech stack", "Technologies, comma-separated"),
+            ],
+            index: 0,
+        }
+    }
+
+    pub fn value(&self, field: usize) -> &str {
+        &self.fields[field].value
+    }
+
+    pub fn is_last_field(&self) -> bool {
+        self.index + 1 == self.fields.len()
+    }
+
+    pub fn handle_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Char(c) => self.fields[self.index].value.push(c),
+            KeyCode::Backspace => {
+                self.fields[self.index].value.pop();
+            }
+            KeyCode::Tab | KeyCode::Down | KeyCode::Enter => {
+                self.index = (self.index + 1).min(self.fields.len() - 1);
+            }
+            KeyCode::BackTab | KeyCode::Up => self.index = self.index.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    /// Fill `state` from the answers and save it along with `doplan/idea-notes.md`.
+    /// Features are spread over the phases in the order both were listed.
+    pub fn apply(&self, state: &mut ProjectState) -> Result<()> {
+        let list = |field: usize| -> Vec<String> {
+            self.value(field)
+                .split(',')
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+                .collect()
+        };
+
+        let config = Config::current()?;
+        let features = discuss::build_features(state, &config, self.value(Self::FEATURES));
+        let mut phase_names = list(Self::PHASES);
+        if phase_names.is_empty() {
+            phase_names.push("Phase 1".to_string());
+        }
+        let per_phase = features.len().div_ceil(phase_names.len()).max(1);
+        let mut phases = Vec::new();
+        for (i, name) in phase_names.into_iter().enumerate() {
+            let id = match state.find_phase(&name) {
+                Some(phase) => phase.id.clone(),
+                None => state.new_phase_id(),
+            };
+            phases.push(Phase {
+                id,
+                name,
+                description: String::new(),
+                features: features.iter().skip(i * per_phase).take(per_phase).map(|f| f.id.clone()).collect(),
+            });
+        }
+
+        let idea = self.value(Self::IDEA).trim().to_string();
+        state.project_name = Some(self.value(Self::PROJECT_NAME).trim().to_string());
+        state.improvements = Some(discuss::generate_improvements(&idea, &features));
+        state.idea = Some(idea);
+        state.tech_stack = Some(list(Self::TECH_STACK));
+        state.features = Some(features);
+        state.phases = Some(phases);
+        state.notes = Some(format!("Discussion completed on {}", chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC")));
+
+        state.save()
+            .context("Failed to save project state")?;
+        discuss::generate_idea_notes(state)
+            .context("Failed to generate idea notes")?;
+        Ok(())
+    }
+}
+
+impl Default for Wizard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,199 @@
+//! Progress snapshots appended by every `/progress` run.
+//!
+//! `.doplan/dashboard.json` only holds the latest numbers; `.doplan/history.jsonl`
+//! keeps one JSON [`Snapshot`] per line so burndown charts and forecasts can
+//! look back over time. The file is append-only and safe to commit.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskCounts {
+    pub total: usize,
+    pub completed: usize,
+    pub in_progress: usize,
+    pub not_started: usize,
+    pub blocked: usize,
+}
+
+impl TaskCounts {
+    pub fn remaining(&self) -> usize {
+        self.total.saturating_sub(self.completed)
+    }
+}
+
+impl std::ops::AddAssign for TaskCounts {
+    fn add_assign(&mut self, other: Self) {
+        self.total += other.total;
+        self.completed += other.completed;
+        self.in_progress += other.in_progress;
+        self.not_started += other.not_started;
+        self.blocked += other.blocked;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PhaseSnapshot {
+    pub id: String,
+    pub name: String,
+    pub progress: f64,
+    pub tasks: TaskCounts,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeatureSnapshot {
+    pub id: String,
+    pub name: String,
+    pub phase_id: String,
+    pub progress: f64,
+    pub status: String,
+    pub tasks: TaskCounts,
+}
+
+/// Project progress at one point in time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// RFC 3339 time the snapshot was taken, in UTC
+    pub timestamp: String,
+    pub overall_progress: f64,
+    pub tasks: TaskCounts,
+    #[serde(default)]
+    pub phases: Vec<PhaseSnapshot>,
+    #[serde(default)]
+    pub features: Vec<FeatureSnapshot>,
+}
+
+impl Snapshot {
+    pub fn date(&self) -> Option<NaiveDate> {
+        DateTime::parse_from_rfc3339(&self.timestamp).ok().map(|t| t.date_naive())
+    }
+
+    /// Task counts for one phase, or for the whole project when `phase_id` is `None`
+    pub fn tasks_for(&self, phase_id: Option<&str>) -> Option<TaskCounts> {
+        match phase_id {
+            None => Some(self.tasks),
+            Some(id) => self.phases.iter().find(|p| p.id == id).map(|p| p.tasks),
+        }
+    }
+}
+
+/// One point on a burndown chart: the last snapshot taken on `date`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DailyPoint {
+    pub date: NaiveDate,
+    pub tasks: TaskCounts,
+}
+
+pub fn history_path(dot_doplan: &Path) -> PathBuf {
+    dot_doplan.join("history.jsonl")
+}
+
+pub fn append(path: &Path, snapshot: &Snapshot) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        super::ensure_dir(parent)?;
+    }
+    let line = serde_json::to_string(snapshot)
+        .context("Failed to serialize progress snapshot")?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open progress history: {}", path.display()))?;
+    writeln!(file, "{}", line)
+        .with_context(|| format!("Failed to append to progress history: {}", path.display()))
+}
+
+/// Read every snapshot in `path`, oldest first. A missing file is an empty history.
+pub fn load(path: &Path) -> Result<Vec<Snapshot>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read progress history: {}", path.display()))?;
+    let mut snapshots = content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str::<Snapshot>(line)
+                .with_context(|| format!("Failed to parse {} line {}", path.display(), i + 1))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    // Timestamps are all UTC RFC 3339, so they sort as strings
+    snapshots.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+    Ok(snapshots)
+}
+
+/// Collapse snapshots to one point per day, keeping each day's latest counts.
+/// Snapshots that do not cover `phase_id` are skipped.
+pub fn daily_points(snapshots: &[Snapshot], phase_id: Option<&str>) -> Vec<DailyPoint> {
+    let mut points: Vec<DailyPoint> = Vec::new();
+    for snapshot in snapshots {
+        let (Some(tasks), Some(date)) = (snapshot.tasks_for(phase_id), snapshot.date()) else {
+            continue;
+        };
+        match points.last_mut() {
+            Some(last) if last.date == date => last.tasks = tasks,
+            _ => points.push(DailyPoint { date, tasks }),
+        }
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn snapshot(timestamp: &str, total: usize, completed: usize) -> Snapshot {
+        Snapshot {
+            timestamp: timestamp.to_string(),
+            overall_progress: completed as f64 / total as f64 * 100.0,
+            tasks: TaskCounts { total, completed, not_started: total - completed, ..Default::default() },
+            phases: vec![PhaseSnapshot {
+                id: "P-01".to_string(),
+                name: "Foundation".to_string(),
+                progress: 0.0,
+                tasks: TaskCounts { total: 2, ..Default::default() },
+            }],
+            features: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_append_and_load_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let path = history_path(dir.path());
+        assert!(load(&path).unwrap().is_empty());
+
+        let first = snapshot("2026-10-19T09:00:00Z", 6, 1);
+        let second = snapshot("2026-10-20T09:00:00Z", 6, 3);
+        append(&path, &first).unwrap();
+        append(&path, &second).unwrap();
+
+        assert_eq!(load(&path).unwrap(), vec![first, second]);
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 2);
+    }
+
+    #[test]
+    fn test_daily_points_keep_last_snapshot_of_each_day() {
+        let snapshots = vec![
+            snapshot("2026-10-19T09:00:00Z", 6, 1),
+            snapshot("2026-10-19T17:00:00Z", 7, 2),
+            snapshot("2026-10-21T09:00:00Z", 7, 5),
+        ];
+
+        let points = daily_points(&snapshots, None);
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].tasks.total, 7);
+        assert_eq!(points[0].tasks.remaining(), 5);
+        assert_eq!(points[1].tasks.completed, 5);
+
+        assert_eq!(daily_points(&snapshots, Some("P-01"))[0].tasks.total, 2);
+        assert!(daily_points(&snapshots, Some("P-09")).is_empty());
+    }
+}
@@ -1,5 +1,11 @@
+pub mod history;
+pub mod plan_dirs;
+pub mod plan_manifest;
+pub mod velocity;
+
 use std::path::{Path, PathBuf};
 use anyhow::{Context, Result};
+use crate::config::{self, Config};
 
 pub fn ensure_dir(path: &Path) -> Result<()> {
     if !path.exists() {
@@ -15,19 +21,54 @@ pub fn project_root() -> Result<PathBuf> {
     Ok(current_dir)
 }
 
+/// Nearest ancestor of `start` (itself included) that already has a state
+/// directory (`paths.state_dir`) or a `doplan.toml`, or is a git checkout.
+/// Falls back to `start`.
+pub fn find_project_root(start: &Path) -> PathBuf {
+    let has_state_dir = |dir: &Path| {
+        Config::load(dir)
+            .map(|config| dir.join(config.paths.state_dir).is_dir())
+            .unwrap_or(false)
+    };
+    start
+        .ancestors()
+        .find(|dir| dir.join(config::PROJECT_FILE).is_file() || has_state_dir(dir))
+        .or_else(|| start.ancestors().find(|dir| dir.join(".git").exists()))
+        .unwrap_or(start)
+        .to_path_buf()
+}
+
+/// Documents and plan directory, `paths.docs_dir` in the configuration
 pub fn doplan_dir() -> Result<PathBuf> {
     let root = project_root()?;
-    Ok(root.join("doplan"))
+    let config = Config::load(&root)?;
+    Ok(root.join(config.paths.docs_dir))
 }
 
+/// State directory, `paths.state_dir` in the configuration
 pub fn dot_doplan_dir() -> Result<PathBuf> {
     let root = project_root()?;
-    Ok(root.join(".doplan"))
+    let config = Config::load(&root)?;
+    Ok(root.join(config.paths.state_dir))
 }
 
-pub fn ai_commands_dir() -> Result<PathBuf> {
-    let dot_doplan = dot_doplan_dir()?;
-    Ok(dot_doplan.join("ai").join("commands"))
+/// Turn a display name into the slug used for plan directory names
+pub fn sanitize_name(name: &str) -> String {
+    name.to_lowercase()
+        .replace([' ', '_', '.'], "-")
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == '-')
+        .collect::<String>()
+        .trim_matches('-')
+        .to_string()
+}
+
+/// Strip the `NN-` ordering prefix from a plan directory name (`01-user-auth` -> `user-auth`)
+pub fn strip_index_prefix(dir_name: &str) -> &str {
+    match dir_name.split_once('-') {
+        Some((prefix, rest)) if !prefix.is_empty() && prefix.chars().all(|c| c.is_ascii_digit()) => rest,
+        _ => dir_name,
+    }
 }
 
 /// Verify that a file was written successfully by checking its existence and size
@@ -105,6 +146,33 @@ mod tests {
         assert!(test_path.is_dir());
     }
 
+    #[test]
+    fn test_find_project_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let nested = temp_dir.path().join("repo").join("src").join("bin");
+        std::fs::create_dir_all(&nested).unwrap();
+        assert_eq!(find_project_root(&nested), nested);
+
+        std::fs::create_dir(temp_dir.path().join("repo").join(".git")).unwrap();
+        assert_eq!(find_project_root(&nested), temp_dir.path().join("repo"));
+
+        // An existing install wins over the enclosing checkout
+        std::fs::create_dir(nested.join(".doplan")).unwrap();
+        assert_eq!(find_project_root(&nested), nested);
+    }
+
+    #[test]
+    fn test_sanitize_name() {
+        assert_eq!(sanitize_name("User Auth"), "user-auth");
+        assert_eq!(sanitize_name("v1.2 API_Keys!"), "v1-2-api-keys");
+    }
+
+    #[test]
+    fn test_strip_index_prefix() {
+        assert_eq!(strip_index_prefix("01-user-auth"), "user-auth");
+        assert_eq!(strip_index_prefix("user-auth"), "user-auth");
+    }
+
     #[test]
     fn test_verify_file_write() {
         let temp_dir = TempDir::new().unwrap();
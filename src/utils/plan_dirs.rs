@@ -0,0 +1,384 @@
+//! Lookup of phase and feature directories under `doplan/plan` by stable id.
+//!
+//! Directory names (`01-foundation/02-user-auth`) carry an ordering prefix and
+//! a slug of the display name, both of which change when things are reordered
+//! or renamed. The id each directory belongs to is recorded in its
+//! `phase-progress.json` / `progress.json`, which is what lookups key on.
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::state::deps::NodeRef;
+use crate::state::{tasks_md, Feature, Phase, ProjectState, TaskStatus};
+use super::{sanitize_name, strip_index_prefix};
+
+#[derive(Debug, Clone)]
+pub struct PlanDirs {
+    pub phases: Vec<PhaseDir>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PhaseDir {
+    pub id: Option<String>,
+    pub dir_name: String,
+    pub path: PathBuf,
+    pub features: Vec<FeatureDir>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FeatureDir {
+    pub id: Option<String>,
+    pub dir_name: String,
+    pub path: PathBuf,
+}
+
+impl PlanDirs {
+    /// Scan `plan_dir`, reading ids from the progress files. Directories from
+    /// before ids existed are matched to state by slug instead.
+    pub fn scan(plan_dir: &Path, state: &ProjectState) -> Result<Self> {
+        let mut phases = Vec::new();
+        if !plan_dir.exists() {
+            return Ok(Self { phases });
+        }
+
+        for phase_entry in sorted_subdirs(plan_dir)? {
+            let phase_dir_name = phase_entry.file_name().unwrap().to_string_lossy().to_string();
+            let phase_id = read_id(&phase_entry.join("phase-progress.json")).or_else(|| {
+                let slug = strip_index_prefix(&phase_dir_name);
+                state.phases.iter().flatten()
+                    .find(|p| sanitize_name(&p.name) == slug)
+                    .map(|p| p.id.clone())
+            });
+
+            let mut features = Vec::new();
+            for feature_entry in sorted_subdirs(&phase_entry)? {
+                if !feature_entry.join("tasks.md").exists() && !feature_entry.join("progress.json").exists() {
+                    continue;
+                }
+                let feature_dir_name = feature_entry.file_name().unwrap().to_string_lossy().to_string();
+                let feature_id = read_id(&feature_entry.join("progress.json")).or_else(|| {
+                    let slug = strip_index_prefix(&feature_dir_name);
+                    state.features.iter().flatten()
+                        .find(|f| sanitize_name(&f.name) == slug)
+                        .map(|f| f.id.clone())
+                });
+                features.push(FeatureDir {
+                    id: feature_id.filter(|id| !id.is_empty()),
+                    dir_name: feature_dir_name,
+                    path: feature_entry,
+                });
+            }
+
+            phases.push(PhaseDir {
+                id: phase_id.filter(|id| !id.is_empty()),
+                dir_name: phase_dir_name,
+                path: phase_entry,
+                features,
+            });
+        }
+
+        Ok(Self { phases })
+    }
+
+    pub fn phase(&self, id: &str) -> Option<&PhaseDir> {
+        self.phases.iter().find(|p| p.id.as_deref() == Some(id))
+    }
+
+    pub fn feature(&self, id: &str) -> Option<(&PhaseDir, &FeatureDir)> {
+        self.phases.iter().find_map(|p| {
+            p.features
+                .iter()
+                .find(|f| f.id.as_deref() == Some(id))
+                .map(|f| (p, f))
+        })
+    }
+
+    /// Find a feature directory by its path relative to the plan root
+    /// (`01-foundation/02-user-auth`).
+    pub fn feature_by_dir_names(&self, phase_dir: &str, feature_dir: &str) -> Option<(&PhaseDir, &FeatureDir)> {
+        let phase = self.phases.iter().find(|p| p.dir_name == phase_dir)?;
+        let feature = phase.features.iter().find(|f| f.dir_name == feature_dir)?;
+        Some((phase, feature))
+    }
+}
+
+/// Replace each feature's task list in `state` with the one in its `tasks.md`,
+/// which is the editable copy. Features without a plan directory keep the
+/// tasks stored in state. Returns the scanned layout.
+pub fn sync_tasks(plan_dir: &Path, state: &mut ProjectState) -> Result<PlanDirs> {
+    let layout = PlanDirs::scan(plan_dir, state)?;
+    for feature_dir in layout.phases.iter().flat_map(|p| &p.features) {
+        let Some(id) = feature_dir.id.as_deref() else {
+            continue;
+        };
+        let tasks_path = feature_dir.path.join("tasks.md");
+        if !tasks_path.exists() {
+            continue;
+        }
+        let tasks = tasks_md::read(&tasks_path)?;
+        if let Some(feature) = state.feature_by_id_mut(id) {
+            feature.tasks = tasks;
+        }
+    }
+    Ok(layout)
+}
+
+/// Set one task's status in its feature's `tasks.md`, leaving the rest of the
+/// file untouched, and mirror the change into `state` (which is not saved)
+pub fn set_task_status(
+    plan_dir: &Path,
+    state: &mut ProjectState,
+    feature_id: &str,
+    task_id: &str,
+    status: TaskStatus,
+) -> Result<()> {
+    let layout = PlanDirs::scan(plan_dir, state)?;
+    let (_, feature_dir) = layout
+        .feature(feature_id)
+        .ok_or_else(|| anyhow::anyhow!("No plan directory for {}. Run /plan first.", feature_id))?;
+    let tasks_path = feature_dir.path.join("tasks.md");
+    let content = fs::read_to_string(&tasks_path)
+        .with_context(|| format!("Failed to read {}", tasks_path.display()))?;
+    let updated = tasks_md::set_status(&content, task_id, status)
+        .ok_or_else(|| anyhow::anyhow!("Task {} not found in {}", task_id, tasks_path.display()))?;
+    fs::write(&tasks_path, updated)
+        .with_context(|| format!("Failed to write {}", tasks_path.display()))?;
+
+    if let Some(task) = state
+        .feature_by_id_mut(feature_id)
+        .and_then(|f| f.tasks.iter_mut().find(|t| t.id == task_id))
+    {
+        task.status = status;
+    }
+    Ok(())
+}
+
+/// Drop every task reference to `feature_id` (`F-0003` or `F-0003/T2`) from
+/// the other features' Depends On lines, in `tasks.md` where there is one and
+/// in `state` (which is not saved). Returns the `F-0001/T2` refs changed.
+pub fn remove_task_references(plan_dir: &Path, state: &mut ProjectState, feature_id: &str) -> Result<Vec<String>> {
+    let layout = sync_tasks(plan_dir, state)?;
+    let mut changed = Vec::new();
+    for feature in state.features.iter_mut().flatten().filter(|f| f.id != feature_id) {
+        let tasks_path = layout.feature(&feature.id).map(|(_, dir)| dir.path.join("tasks.md"));
+        for task in &mut feature.tasks {
+            let kept: Vec<String> = task
+                .depends_on
+                .iter()
+                .filter(|r| match NodeRef::parse(r, &feature.id) {
+                    Some(NodeRef::Feature(id)) | Some(NodeRef::Task { feature: id, .. }) => id != feature_id,
+                    None => true,
+                })
+                .cloned()
+                .collect();
+            if kept.len() == task.depends_on.len() {
+                continue;
+            }
+            if let Some(tasks_path) = tasks_path.as_ref().filter(|p| p.exists()) {
+                let content = fs::read_to_string(tasks_path)
+                    .with_context(|| format!("Failed to read {}", tasks_path.display()))?;
+                if let Some(updated) = tasks_md::set_depends_on(&content, &task.id, &kept) {
+                    fs::write(tasks_path, updated)
+                        .with_context(|| format!("Failed to write {}", tasks_path.display()))?;
+                }
+            }
+            task.depends_on = kept;
+            changed.push(format!("{}/{}", feature.id, task.id));
+        }
+    }
+    Ok(changed)
+}
+
+/// Directory name for the phase at `index` (0-based) in plan order
+pub fn phase_dir_name(index: usize, phase: &Phase) -> String {
+    format!("{:02}-{}", index + 1, sanitize_name(&phase.name))
+}
+
+/// Directory name for the feature at `index` (0-based) within its phase
+pub fn feature_dir_name(index: usize, feature: &Feature) -> String {
+    format!("{:02}-{}", index + 1, sanitize_name(&feature.name))
+}
+
+/// Keep the ordering prefix of `dir_name` but replace its slug with one for `new_name`
+pub fn renamed_dir_name(dir_name: &str, new_name: &str) -> String {
+    let slug = strip_index_prefix(dir_name);
+    let prefix = &dir_name[..dir_name.len() - slug.len()];
+    format!("{}{}", prefix, sanitize_name(new_name))
+}
+
+/// Apply a set of directory moves. Moves are staged through temporary names
+/// so that swaps (`01-a` <-> `01-b`) and chains do not collide. When a move
+/// fails, every directory is put back where it was.
+pub fn apply_moves(moves: &[(PathBuf, PathBuf)]) -> Result<()> {
+    let moves: Vec<_> = moves.iter().filter(|(from, to)| from != to).collect();
+    // Where each directory is now
+    let mut current: Vec<PathBuf> = moves.iter().map(|(from, _)| from.clone()).collect();
+
+    let result = move_all(&moves, &mut current);
+    if result.is_err() {
+        restore(&moves, &mut current);
+    }
+    result
+}
+
+fn move_all(moves: &[&(PathBuf, PathBuf)], current: &mut [PathBuf]) -> Result<()> {
+    for (index, (from, _)) in moves.iter().enumerate() {
+        let temp = staging_path(from, index)?;
+        fs::rename(from, &temp)
+            .with_context(|| format!("Failed to move {}", from.display()))?;
+        current[index] = temp;
+    }
+
+    for (index, (_, to)) in moves.iter().enumerate() {
+        if to.exists() {
+            anyhow::bail!("Cannot move plan directory, target already exists: {}", to.display());
+        }
+        if let Some(parent) = to.parent() {
+            super::ensure_dir(parent)?;
+        }
+        fs::rename(&current[index], to)
+            .with_context(|| format!("Failed to move plan directory to: {}", to.display()))?;
+        current[index] = to.clone();
+    }
+
+    Ok(())
+}
+
+/// Undo a partly applied [`apply_moves`], staging again first so that moved
+/// directories do not block each other's way back. Best effort: the error
+/// that started the rollback is the one reported.
+fn restore(moves: &[&(PathBuf, PathBuf)], current: &mut [PathBuf]) {
+    for (index, (from, _)) in moves.iter().enumerate() {
+        let Ok(temp) = staging_path(from, index) else {
+            continue;
+        };
+        if current[index] != *from && current[index] != temp && fs::rename(&current[index], &temp).is_ok() {
+            current[index] = temp;
+        }
+    }
+    for (index, (from, _)) in moves.iter().enumerate() {
+        if current[index] != *from && fs::rename(&current[index], from).is_ok() {
+            current[index] = from.clone();
+        }
+    }
+}
+
+fn staging_path(from: &Path, index: usize) -> Result<PathBuf> {
+    let parent = from.parent().context("Plan directory has no parent")?;
+    Ok(parent.join(format!(".doplan-move-{}", index)))
+}
+
+/// Move the directory of a feature to match a new display name, keeping its
+/// ordering prefix. Call with the state as it was before the rename so that
+/// legacy directories without ids can still be matched by slug.
+pub fn rename_feature_dir(plan_dir: &Path, state: &ProjectState, feature_id: &str, new_name: &str) -> Result<Option<(PathBuf, PathBuf)>> {
+    let layout = PlanDirs::scan(plan_dir, state)?;
+    let Some((_, feature_dir)) = layout.feature(feature_id) else {
+        return Ok(None);
+    };
+    rename_in_place(&feature_dir.path, &feature_dir.dir_name, new_name)
+}
+
+/// Phase counterpart of [`rename_feature_dir`].
+pub fn rename_phase_dir(plan_dir: &Path, state: &ProjectState, phase_id: &str, new_name: &str) -> Result<Option<(PathBuf, PathBuf)>> {
+    let layout = PlanDirs::scan(plan_dir, state)?;
+    let Some(phase_dir) = layout.phase(phase_id) else {
+        return Ok(None);
+    };
+    rename_in_place(&phase_dir.path, &phase_dir.dir_name, new_name)
+}
+
+/// Move a plan directory into `archive_root`, keeping its path relative to
+/// `plan_dir` (`01-foundation/02-billing`). Returns the archived location.
+pub fn archive_dir(plan_dir: &Path, archive_root: &Path, path: &Path) -> Result<PathBuf> {
+    let relative = path.strip_prefix(plan_dir).unwrap_or(path);
+    let target = archive_root.join(relative);
+    if target.exists() {
+        anyhow::bail!("Cannot archive plan directory, target already exists: {}", target.display());
+    }
+    if let Some(parent) = target.parent() {
+        super::ensure_dir(parent)?;
+    }
+    fs::rename(path, &target)
+        .with_context(|| format!("Failed to archive {} to {}", path.display(), target.display()))?;
+    Ok(target)
+}
+
+fn rename_in_place(path: &Path, dir_name: &str, new_name: &str) -> Result<Option<(PathBuf, PathBuf)>> {
+    let target = path.with_file_name(renamed_dir_name(dir_name, new_name));
+    if target == path {
+        return Ok(None);
+    }
+    if target.exists() {
+        anyhow::bail!("Cannot rename plan directory, target already exists: {}", target.display());
+    }
+    fs::rename(path, &target)
+        .with_context(|| format!("Failed to move {} to {}", path.display(), target.display()))?;
+    Ok(Some((path.to_path_buf(), target)))
+}
+
+fn sorted_subdirs(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut dirs: Vec<PathBuf> = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read directory: {}", dir.display()))?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .filter(|p| {
+            p.file_name()
+                .map(|n| !n.to_string_lossy().starts_with('.'))
+                .unwrap_or(false)
+        })
+        .collect();
+    dirs.sort();
+    Ok(dirs)
+}
+
+fn read_id(progress_path: &Path) -> Option<String> {
+    let content = fs::read_to_string(progress_path).ok()?;
+    let value: Value = serde_json::from_str(&content).ok()?;
+    value.get("id").and_then(|v| v.as_str()).map(|s| s.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_renamed_dir_name_keeps_prefix() {
+        assert_eq!(renamed_dir_name("03-user-auth", "Sign In"), "03-sign-in");
+        assert_eq!(renamed_dir_name("user-auth", "Sign In"), "sign-in");
+    }
+
+    #[test]
+    fn test_apply_moves_rolls_back_on_error() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let plan = temp_dir.path();
+        for dir in ["01-a", "02-b", "03-c", "04-taken"] {
+            fs::create_dir(plan.join(dir)).unwrap();
+            fs::write(plan.join(dir).join("id"), dir).unwrap();
+        }
+
+        // The swap succeeds before the last move finds its target taken
+        let moves = vec![
+            (plan.join("01-a"), plan.join("02-b")),
+            (plan.join("02-b"), plan.join("01-a")),
+            (plan.join("03-c"), plan.join("04-taken")),
+        ];
+        let err = apply_moves(&moves).unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+
+        let mut names: Vec<String> = fs::read_dir(plan)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        assert_eq!(names, ["01-a", "02-b", "03-c", "04-taken"]);
+        for dir in ["01-a", "02-b", "03-c"] {
+            assert_eq!(fs::read_to_string(plan.join(dir).join("id")).unwrap(), dir);
+        }
+
+        apply_moves(&moves[..2]).unwrap();
+        assert_eq!(fs::read_to_string(plan.join("01-a").join("id")).unwrap(), "02-b");
+    }
+}
@@ -0,0 +1,151 @@
+//! Record of the plan files `/plan` generated, used to tell generated files
+//! apart from ones the user has edited since.
+//!
+//! Entries are keyed on `<id>/<file name>` (`F-0003/design.md`) rather than on
+//! paths, so they stay valid when plan directories are moved or renamed. The
+//! stored value is a hash of the content as it was written; a file on disk
+//! whose hash no longer matches has been edited and is left alone.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PlanManifest {
+    #[serde(default)]
+    pub files: BTreeMap<String, String>,
+}
+
+/// What happened to a plan file during a reconcile
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteOutcome {
+    Created,
+    Updated,
+    Unchanged,
+    /// The file was edited by hand and was kept as is
+    Kept,
+}
+
+impl PlanManifest {
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read plan manifest: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse plan manifest: {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            super::ensure_dir(parent)?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .context("Failed to serialize plan manifest")?;
+        fs::write(path, content)
+            .with_context(|| format!("Failed to write plan manifest: {}", path.display()))
+    }
+
+    pub fn key(owner_id: &str, file_name: &str) -> String {
+        format!("{}/{}", owner_id, file_name)
+    }
+
+    /// Whether the file at `path` is still exactly what DoPlan last wrote.
+    /// Files with no record (written by older versions or by hand) count as edited.
+    pub fn is_pristine(&self, key: &str, path: &Path) -> bool {
+        let Some(recorded) = self.files.get(key) else {
+            return false;
+        };
+        match fs::read(path) {
+            Ok(bytes) => content_hash(&bytes) == *recorded,
+            Err(_) => false,
+        }
+    }
+
+    /// Write `content` to `path` unless the user has edited the existing file.
+    /// With `force`, edited files are overwritten as well.
+    pub fn write(&mut self, key: &str, path: &Path, content: &str, force: bool) -> Result<WriteOutcome> {
+        let outcome = if !path.exists() {
+            WriteOutcome::Created
+        } else if fs::read(path).map(|b| b == content.as_bytes()).unwrap_or(false) {
+            WriteOutcome::Unchanged
+        } else if force || self.is_pristine(key, path) {
+            WriteOutcome::Updated
+        } else {
+            return Ok(WriteOutcome::Kept);
+        };
+
+        if outcome != WriteOutcome::Unchanged {
+            fs::write(path, content)
+                .with_context(|| format!("Failed to write plan file: {}", path.display()))?;
+        }
+        self.files.insert(key.to_string(), content_hash(content.as_bytes()));
+        Ok(outcome)
+    }
+
+    /// Drop every entry belonging to `owner_id`
+    pub fn forget(&mut self, owner_id: &str) {
+        let prefix = format!("{}/", owner_id);
+        self.files.retain(|key, _| !key.starts_with(&prefix));
+    }
+}
+
+/// Location of the manifest inside `.doplan/`
+pub fn manifest_path(dot_doplan: &Path) -> PathBuf {
+    dot_doplan.join("plan-manifest.json")
+}
+
+/// FNV-1a over the file bytes. Only used to detect edits, so a small stable
+/// hash is enough.
+pub fn content_hash(bytes: &[u8]) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_keeps_edited_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("design.md");
+        let mut manifest = PlanManifest::default();
+
+        assert_eq!(manifest.write("F-0001/design.md", &path, "v1", false).unwrap(), WriteOutcome::Created);
+        assert_eq!(manifest.write("F-0001/design.md", &path, "v2", false).unwrap(), WriteOutcome::Updated);
+
+        fs::write(&path, "my notes").unwrap();
+        assert_eq!(manifest.write("F-0001/design.md", &path, "v3", false).unwrap(), WriteOutcome::Kept);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "my notes");
+
+        assert_eq!(manifest.write("F-0001/design.md", &path, "v3", true).unwrap(), WriteOutcome::Updated);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "v3");
+    }
+
+    #[test]
+    fn test_unrecorded_files_count_as_edited() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("plan.md");
+        fs::write(&path, "old").unwrap();
+        let mut manifest = PlanManifest::default();
+        assert_eq!(manifest.write("F-0001/plan.md", &path, "new", false).unwrap(), WriteOutcome::Kept);
+    }
+
+    #[test]
+    fn test_forget_drops_owner_entries() {
+        let mut manifest = PlanManifest::default();
+        manifest.files.insert("F-0001/plan.md".to_string(), "a".to_string());
+        manifest.files.insert("F-00012/plan.md".to_string(), "b".to_string());
+        manifest.forget("F-0001");
+        assert_eq!(manifest.files.len(), 1);
+    }
+}
@@ -0,0 +1,265 @@
+//! Velocity and completion-date forecasts from the progress history.
+//!
+//! Velocity is the number of tasks completed per calendar day over the last
+//! [`ROLLING_WINDOW_DAYS`] of `.doplan/history.jsonl`. Projected finish dates
+//! divide the remaining tasks by that velocity; the confidence range uses the
+//! day-to-day spread of the rate (at least ±[`MIN_SPREAD`] of it). Phases are
+//! assumed to be worked in plan order, so each phase finishes once the work in
+//! it and every phase before it is done.
+
+use chrono::{Duration, NaiveDate};
+use serde_json::{json, Value};
+use super::history::{self, DailyPoint, Snapshot};
+
+pub const ROLLING_WINDOW_DAYS: i64 = 14;
+pub const MIN_SPREAD: f64 = 0.2;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Velocity {
+    /// Completed tasks per calendar day over the window
+    pub tasks_per_day: f64,
+    /// Lower and upper bounds on `tasks_per_day`
+    pub slowest_per_day: f64,
+    pub fastest_per_day: f64,
+    /// Days of history the velocity is based on
+    pub window_days: i64,
+    /// Tasks completed on each recorded day of the window
+    pub daily_completed: Vec<(NaiveDate, usize)>,
+}
+
+impl Velocity {
+    pub fn tasks_per_week(&self) -> f64 {
+        self.tasks_per_day * 7.0
+    }
+
+    /// Rolling velocity over the trailing window of `points`. `None` until
+    /// there are at least two days of history to compare.
+    pub fn from_points(points: &[DailyPoint]) -> Option<Velocity> {
+        let last = points.last()?;
+        let window_start = last.date - Duration::days(ROLLING_WINDOW_DAYS);
+        let window: Vec<&DailyPoint> = points.iter().filter(|p| p.date >= window_start).collect();
+        if window.len() < 2 {
+            return None;
+        }
+
+        let intervals: Vec<(i64, f64)> = window
+            .windows(2)
+            .map(|pair| {
+                let days = (pair[1].date - pair[0].date).num_days().max(1);
+                let done = pair[1].tasks.completed as f64 - pair[0].tasks.completed as f64;
+                (days, done.max(0.0))
+            })
+            .collect();
+        let window_days: i64 = intervals.iter().map(|(days, _)| days).sum();
+        let completed: f64 = intervals.iter().map(|(_, done)| done).sum();
+        let tasks_per_day = completed / window_days as f64;
+
+        // Day-weighted spread of the per-interval rates
+        let variance = intervals
+            .iter()
+            .map(|(days, done)| *days as f64 * (done / *days as f64 - tasks_per_day).powi(2))
+            .sum::<f64>()
+            / window_days as f64;
+        let spread = variance.sqrt().max(tasks_per_day * MIN_SPREAD);
+
+        Some(Velocity {
+            tasks_per_day,
+            slowest_per_day: (tasks_per_day - spread).max(0.0),
+            fastest_per_day: tasks_per_day + spread,
+            window_days,
+            daily_completed: window
+                .windows(2)
+                .zip(&intervals)
+                .map(|(pair, (_, done))| (pair[1].date, *done as usize))
+                .collect(),
+        })
+    }
+}
+
+/// When `remaining` tasks are done at a given velocity
+#[derive(Debug, Clone, PartialEq)]
+pub struct Projection {
+    pub remaining_tasks: usize,
+    /// `None` when the velocity is zero and no date can be projected
+    pub projected_finish: Option<NaiveDate>,
+    pub earliest_finish: Option<NaiveDate>,
+    pub latest_finish: Option<NaiveDate>,
+}
+
+impl Projection {
+    fn new(remaining_tasks: usize, work_before: usize, velocity: Option<&Velocity>, today: NaiveDate) -> Self {
+        let work = (remaining_tasks + work_before) as f64;
+        let finish = |rate: f64| {
+            if remaining_tasks == 0 {
+                Some(today)
+            } else if rate > 0.0 {
+                Some(today + Duration::days((work / rate).ceil() as i64))
+            } else {
+                None
+            }
+        };
+        Projection {
+            remaining_tasks,
+            projected_finish: velocity.and_then(|v| finish(v.tasks_per_day)),
+            earliest_finish: velocity.and_then(|v| finish(v.fastest_per_day)),
+            latest_finish: velocity.and_then(|v| finish(v.slowest_per_day)),
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        let date = |d: Option<NaiveDate>| d.map(|d| d.to_string());
+        json!({
+            "remaining_tasks": self.remaining_tasks,
+            "projected_finish": date(self.projected_finish),
+            "earliest_finish": date(self.earliest_finish),
+            "latest_finish": date(self.latest_finish)
+        })
+    }
+
+    /// `2026-11-04 (2026-10-30 – 2026-11-12)`, or why there is no date
+    pub fn describe(&self) -> String {
+        if self.remaining_tasks == 0 {
+            return "done".to_string();
+        }
+        match (self.projected_finish, self.earliest_finish, self.latest_finish) {
+            (Some(finish), Some(earliest), Some(latest)) => format!("{} ({} – {})", finish, earliest, latest),
+            (Some(finish), Some(earliest), None) => format!("{} ({} – open-ended)", finish, earliest),
+            _ => "no velocity yet".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhaseProjection {
+    pub id: String,
+    pub name: String,
+    pub projection: Projection,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Forecast {
+    pub as_of: NaiveDate,
+    pub velocity: Option<Velocity>,
+    pub project: Projection,
+    pub phases: Vec<PhaseProjection>,
+}
+
+impl Forecast {
+    /// Forecast from the recorded history, using the latest snapshot for the
+    /// remaining work. `None` when there is no history at all.
+    pub fn from_history(snapshots: &[Snapshot], today: NaiveDate) -> Option<Forecast> {
+        let latest = snapshots.last()?;
+        let velocity = Velocity::from_points(&history::daily_points(snapshots, None));
+
+        let mut work_before = 0;
+        let phases = latest
+            .phases
+            .iter()
+            .map(|phase| {
+                let remaining = phase.tasks.remaining();
+                let projection = Projection::new(remaining, work_before, velocity.as_ref(), today);
+                work_before += remaining;
+                PhaseProjection { id: phase.id.clone(), name: phase.name.clone(), projection }
+            })
+            .collect();
+
+        Some(Forecast {
+            as_of: today,
+            project: Projection::new(latest.tasks.remaining(), 0, velocity.as_ref(), today),
+            velocity,
+            phases,
+        })
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "as_of": self.as_of.to_string(),
+            "velocity": self.velocity.as_ref().map(|v| json!({
+                "tasks_per_day": v.tasks_per_day,
+                "tasks_per_week": v.tasks_per_week(),
+                "slowest_per_day": v.slowest_per_day,
+                "fastest_per_day": v.fastest_per_day,
+                "window_days": v.window_days,
+                "daily_completed": v.daily_completed.iter()
+                    .map(|(date, completed)| json!({ "date": date.to_string(), "completed": completed }))
+                    .collect::<Vec<_>>()
+            })),
+            "project": self.project.to_json(),
+            "phases": self.phases.iter().map(|p| {
+                let mut phase = p.projection.to_json();
+                phase["id"] = json!(p.id);
+                phase["name"] = json!(p.name);
+                phase
+            }).collect::<Vec<_>>()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::history::{PhaseSnapshot, TaskCounts};
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    fn snapshot(day: u32, phase_completed: [usize; 2]) -> Snapshot {
+        let phases: Vec<PhaseSnapshot> = phase_completed
+            .iter()
+            .enumerate()
+            .map(|(i, completed)| PhaseSnapshot {
+                id: format!("P-0{}", i + 1),
+                name: format!("Phase {}", i + 1),
+                progress: 0.0,
+                tasks: TaskCounts { total: 10, completed: *completed, ..Default::default() },
+            })
+            .collect();
+        Snapshot {
+            timestamp: format!("2026-10-{:02}T12:00:00+00:00", day),
+            overall_progress: 0.0,
+            tasks: TaskCounts { total: 20, completed: phase_completed.iter().sum(), ..Default::default() },
+            phases,
+            features: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_velocity_needs_two_days() {
+        assert_eq!(Velocity::from_points(&history::daily_points(&[snapshot(1, [0, 0])], None)), None);
+
+        let forecast = Forecast::from_history(&[snapshot(1, [0, 0])], date(1)).unwrap();
+        assert_eq!(forecast.project.projected_finish, None);
+        assert_eq!(forecast.project.describe(), "no velocity yet");
+    }
+
+    #[test]
+    fn test_steady_velocity_projects_each_phase_in_order() {
+        let snapshots = vec![snapshot(1, [0, 0]), snapshot(3, [4, 0]), snapshot(5, [8, 0])];
+        let forecast = Forecast::from_history(&snapshots, date(5)).unwrap();
+
+        let velocity = forecast.velocity.as_ref().unwrap();
+        assert_eq!(velocity.tasks_per_day, 2.0);
+        assert_eq!(velocity.tasks_per_week(), 14.0);
+        assert_eq!(velocity.window_days, 4);
+        assert_eq!(velocity.daily_completed, vec![(date(3), 4), (date(5), 4)]);
+        // A perfectly steady rate still gets the minimum spread
+        assert!((velocity.slowest_per_day - 1.6).abs() < 1e-9);
+        assert!((velocity.fastest_per_day - 2.4).abs() < 1e-9);
+
+        // 2 tasks left in phase 1, then 10 more in phase 2
+        assert_eq!(forecast.phases[0].projection.projected_finish, Some(date(6)));
+        assert_eq!(forecast.phases[1].projection.projected_finish, Some(date(11)));
+        assert_eq!(forecast.project.projected_finish, Some(date(11)));
+        assert_eq!(forecast.project.earliest_finish, Some(date(10)));
+        assert_eq!(forecast.project.latest_finish, Some(date(13)));
+    }
+
+    #[test]
+    fn test_window_ignores_old_history() {
+        let snapshots = vec![snapshot(1, [0, 0]), snapshot(2, [10, 0]), snapshot(20, [10, 2]), snapshot(22, [10, 4])];
+        let velocity = Forecast::from_history(&snapshots, date(22)).unwrap().velocity.unwrap();
+        assert_eq!(velocity.window_days, 2);
+        assert_eq!(velocity.tasks_per_day, 1.0);
+    }
+}
@@ -0,0 +1,94 @@
+use anyhow::Result;
+use doplan::commands;
+use doplan::state::{Feature, Phase, ProjectState};
+use doplan::utils::history;
+use std::fs;
+
+mod common;
+use common::TestEnv;
+
+fn two_phase_state() -> ProjectState {
+    let feature = |id: &str, name: &str| Feature {
+        id: id.to_string(),
+        name: name.to_string(),
+        description: format!("{} feature", name),
+        priority: "high".to_string(),
+        ..Default::default()
+    };
+    ProjectState {
+        project_name: Some("Test Project".to_string()),
+        features: Some(vec![feature("F-0001", "User Auth"), feature("F-0002", "Billing")]),
+        phases: Some(vec![
+            Phase {
+                id: "P-01".to_string(),
+                name: "Foundation".to_string(),
+                description: "First phase".to_string(),
+                features: vec!["F-0001".to_string()],
+            },
+            Phase {
+                id: "P-02".to_string(),
+                name: "Launch".to_string(),
+                description: "Second phase".to_string(),
+                features: vec!["F-0002".to_string()],
+            },
+        ]),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_progress_appends_snapshots_to_history() -> Result<()> {
+    let env = TestEnv::new(&two_phase_state())?;
+    commands::plan::execute(vec![]).await?;
+    commands::progress::execute(vec![]).await?;
+
+    let tasks_path = env.path("doplan/plan/01-foundation/01-user-auth/tasks.md");
+    let tasks = fs::read_to_string(&tasks_path)?;
+    fs::write(
+        &tasks_path,
+        tasks.replacen("[x] Not Started | [ ] In Progress | [ ] Completed", "[ ] Not Started | [ ] In Progress | [x] Completed", 1),
+    )?;
+    commands::progress::execute(vec![]).await?;
+
+    let snapshots = history::load(&env.path(".doplan/history.jsonl"))?;
+    assert_eq!(snapshots.len(), 2);
+    assert_eq!(snapshots[0].tasks.total, 6);
+    assert_eq!(snapshots[0].tasks.completed, 0);
+    assert_eq!(snapshots[1].tasks.completed, 1);
+    assert_eq!(snapshots[1].phases[0].id, "P-01");
+    assert_eq!(snapshots[1].phases[0].tasks.completed, 1);
+    assert_eq!(snapshots[1].phases[1].tasks.completed, 0);
+    assert_eq!(snapshots[1].features[0].id, "F-0001");
+    assert_eq!(snapshots[1].features[0].phase_id, "P-01");
+
+    // The dashboard still only holds the latest numbers
+    assert!(env.path(".doplan/dashboard.json").exists());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_burndown_writes_svg_charts() -> Result<()> {
+    let env = TestEnv::new(&two_phase_state())?;
+
+    // No history yet is not an error
+    commands::burndown::execute(vec![]).await?;
+    assert!(!env.path("doplan/burndown.svg").exists());
+
+    commands::plan::execute(vec![]).await?;
+    commands::progress::execute(vec![]).await?;
+    commands::burndown::execute(vec![]).await?;
+
+    let svg = fs::read_to_string(env.path("doplan/burndown.svg"))?;
+    assert!(svg.contains("Project Burndown"));
+    assert_eq!(svg.matches("<polyline").count(), 3);
+
+    commands::burndown::execute(vec!["--phase".to_string(), "P-02".to_string()]).await?;
+    let svg = fs::read_to_string(env.path("doplan/burndown-p-02.svg"))?;
+    assert!(svg.contains("Launch Burndown"));
+
+    let err = commands::burndown::execute(vec!["--phase".to_string(), "P-09".to_string()]).await.unwrap_err();
+    assert!(err.to_string().contains("No progress history recorded for phase P-09"));
+
+    Ok(())
+}
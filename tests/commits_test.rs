@@ -0,0 +1,166 @@
+use anyhow::Result;
+use doplan::commands;
+use doplan::integration::commits;
+use doplan::state::{tasks_md, Feature, Phase, ProjectState, TaskStatus};
+use git2::{Repository, RepositoryInitOptions, Signature};
+use std::fs;
+use std::path::Path;
+
+mod common;
+use common::TestEnv;
+
+const TASKS: &str = "doplan/plan/01-foundation/01-user-auth/tasks.md";
+
+/// A planned project with one commit on `main`
+async fn setup() -> Result<(TestEnv, Repository)> {
+    let env = TestEnv::project(Some(&test_state()))?;
+    let repo = Repository::init_opts(env.root(), RepositoryInitOptions::new().initial_head("main"))?;
+    let mut git_config = repo.config()?;
+    git_config.set_str("user.name", "Ada Lovelace")?;
+    git_config.set_str("user.email", "ada@example.com")?;
+
+    commands::plan::execute(vec![]).await?;
+    commit(&repo, "initial")?;
+    Ok((env, repo))
+}
+
+impl TestEnv {
+    fn status(&self, task_id: &str) -> Result<TaskStatus> {
+        let tasks = tasks_md::read(&self.path(TASKS))?;
+        Ok(tasks.iter().find(|t| t.id == task_id).expect("task exists").status)
+    }
+
+    fn log(&self) -> Result<Vec<commits::StatusChange>> {
+        commits::load_log(&commits::log_path(&self.path(".doplan")))
+    }
+}
+
+fn test_state() -> ProjectState {
+    ProjectState {
+        project_name: Some("Test Project".to_string()),
+        idea: Some("Share family recipes".to_string()),
+        features: Some(vec![Feature {
+            id: "F-0001".to_string(),
+            name: "User Auth".to_string(),
+            description: "Login and signup".to_string(),
+            priority: "high".to_string(),
+            ..Default::default()
+        }]),
+        phases: Some(vec![Phase {
+            id: "P-01".to_string(),
+            name: "Foundation".to_string(),
+            description: "First phase".to_string(),
+            features: vec!["F-0001".to_string()],
+        }]),
+        ..Default::default()
+    }
+}
+
+/// Commit a change to `app.txt` on HEAD with `message`
+fn commit(repo: &Repository, message: &str) -> Result<git2::Oid> {
+    let path = repo.workdir().unwrap().join("app.txt");
+    let mut content = fs::read_to_string(&path).unwrap_or_default();
+    content.push_str(message);
+    content.push('\n');
+    fs::write(&path, content)?;
+    let mut index = repo.index()?;
+    index.add_path(Path::new("app.txt"))?;
+    index.write()?;
+    let tree = repo.find_tree(index.write_tree()?)?;
+    let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+    let signature = Signature::now("Ada Lovelace", "ada@example.com")?;
+    Ok(repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &parents)?)
+}
+
+#[tokio::test]
+async fn test_feature_branch_commits_update_tasks() -> Result<()> {
+    let (env, repo) = setup().await?;
+    commands::implement::execute(vec!["F-0001".to_string()]).await?;
+    let started = commit(&repo, "Add login form\n\nRefs T1")?;
+    let finished = commit(&repo, "Validate passwords\n\nTask: T2 done")?;
+
+    commands::commits::execute(vec![]).await?;
+    assert_eq!(env.status("T1")?, TaskStatus::InProgress);
+    assert_eq!(env.status("T2")?, TaskStatus::Completed);
+    assert_eq!(env.status("T3")?, TaskStatus::NotStarted);
+
+    let log = env.log()?;
+    assert_eq!(log.len(), 2);
+    assert_eq!((log[0].task_id.as_str(), log[0].to, log[0].commit.clone()), ("T1", TaskStatus::InProgress, started.to_string()));
+    assert_eq!((log[1].task_id.as_str(), log[1].from, log[1].commit.clone()), ("T2", TaskStatus::NotStarted, finished.to_string()));
+    assert_eq!(log[1].summary, "Validate passwords");
+
+    // The state mirrors tasks.md
+    let state = ProjectState::load_from(&env.root().join(".doplan").join("state.json"))?;
+    let feature = state.feature_by_id("F-0001").unwrap();
+    assert_eq!(feature.tasks.iter().find(|t| t.id == "T2").unwrap().status, TaskStatus::Completed);
+
+    // A commit is applied once: a status changed by hand afterwards stays
+    let mut state = ProjectState::load()?;
+    doplan::utils::plan_dirs::set_task_status(&env.root().join("doplan/plan"), &mut state, "F-0001", "T2", TaskStatus::InProgress)?;
+    commands::commits::execute(vec!["--quiet".to_string()]).await?;
+    assert_eq!(env.status("T2")?, TaskStatus::InProgress);
+    assert_eq!(env.log()?.len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bare_ids_need_a_feature_branch() -> Result<()> {
+    let (env, repo) = setup().await?;
+    commit(&repo, "Tidy up\n\nRefs T1")?;
+    commit(&repo, "Wire the login page\n\nTask: F-0001/T3 done")?;
+
+    commands::commits::execute(vec![]).await?;
+    assert_eq!(env.status("T1")?, TaskStatus::NotStarted);
+    assert_eq!(env.status("T3")?, TaskStatus::Completed);
+    assert_eq!(env.log()?.len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_progress_applies_commits() -> Result<()> {
+    let (env, repo) = setup().await?;
+    commands::implement::execute(vec!["F-0001".to_string()]).await?;
+    commit(&repo, "Finish signup\n\nCloses T1")?;
+
+    commands::progress::execute(vec!["--no-commits".to_string()]).await?;
+    assert_eq!(env.status("T1")?, TaskStatus::NotStarted);
+
+    commands::progress::execute(vec![]).await?;
+    assert_eq!(env.status("T1")?, TaskStatus::Completed);
+    let progress = fs::read_to_string(env.root().join("doplan/plan/01-foundation/01-user-auth/progress.json"))?;
+    let progress: serde_json::Value = serde_json::from_str(&progress)?;
+    assert!(progress["progress"].as_f64().unwrap() > 0.0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_install_and_uninstall_hook() -> Result<()> {
+    let (env, repo) = setup().await?;
+    let hook = repo.path().join("hooks").join("post-commit");
+
+    commands::commits::execute(vec!["install-hook".to_string()]).await?;
+    let script = fs::read_to_string(&hook)?;
+    assert!(script.starts_with("#!/bin/sh\n"));
+    assert!(script.contains("doplan execute commits --quiet"));
+    assert!(script.contains(&env.root().to_string_lossy().to_string()));
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        assert_eq!(fs::metadata(&hook)?.permissions().mode() & 0o111, 0o111);
+    }
+    // Reinstalling our own hook is fine
+    commands::commits::execute(vec!["install-hook".to_string()]).await?;
+    commands::commits::execute(vec!["uninstall-hook".to_string()]).await?;
+    assert!(!hook.exists());
+
+    // Someone else's hook is left alone unless --force
+    fs::write(&hook, "#!/bin/sh\nmake lint\n")?;
+    let error = commands::commits::execute(vec!["install-hook".to_string()]).await.unwrap_err();
+    assert!(error.to_string().contains("--force"));
+    assert!(commands::commits::execute(vec!["uninstall-hook".to_string()]).await.is_err());
+    commands::commits::execute(vec!["install-hook".to_string(), "--force".to_string()]).await?;
+    assert!(fs::read_to_string(&hook)?.contains("doplan execute commits"));
+    Ok(())
+}
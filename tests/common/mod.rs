@@ -0,0 +1,75 @@
+//! Fixture shared by the integration tests: a temporary directory that is the
+//! current directory for as long as the [`TestEnv`] lives.
+//!
+//! Commands resolve the project from the current directory, so tests in one
+//! binary take turns through a mutex; the previous directory is restored on drop.
+
+#![allow(dead_code)]
+
+use anyhow::Result;
+use doplan::state::ProjectState;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use tempfile::TempDir;
+
+static TEST_MUTEX: Mutex<()> = Mutex::new(());
+
+/// A PRD long enough for `/plan` to accept
+pub const PRD: &str = "# Product Requirements Document\n\n**Project:** Test Project\n\nThis is a test PRD with sufficient content to pass validation.";
+
+pub struct TestEnv {
+    temp_dir: TempDir,
+    original_cwd: PathBuf,
+    _guard: MutexGuard<'static, ()>,
+}
+
+impl TestEnv {
+    /// An empty directory
+    pub fn empty() -> Result<Self> {
+        let guard = TEST_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+        let temp_dir = TempDir::new()?;
+        let original_cwd = std::env::current_dir()?;
+        std::env::set_current_dir(temp_dir.path())?;
+
+        Ok(TestEnv {
+            temp_dir,
+            original_cwd,
+            _guard: guard,
+        })
+    }
+
+    /// A project with `doplan/PRD.md` and `state` in `.doplan/state.json`
+    pub fn new(state: &ProjectState) -> Result<Self> {
+        Self::project(Some(state))
+    }
+
+    /// A project with `doplan/PRD.md`, and `state` when given
+    pub fn project(state: Option<&ProjectState>) -> Result<Self> {
+        let env = Self::empty()?;
+        fs::create_dir_all(env.path("doplan"))?;
+        fs::write(env.path("doplan/PRD.md"), PRD)?;
+        if let Some(state) = state {
+            state.save_to(&env.state_path())?;
+        }
+        Ok(env)
+    }
+
+    pub fn root(&self) -> &Path {
+        self.temp_dir.path()
+    }
+
+    pub fn path(&self, relative: &str) -> PathBuf {
+        self.temp_dir.path().join(relative)
+    }
+
+    pub fn state_path(&self) -> PathBuf {
+        self.path(".doplan/state.json")
+    }
+}
+
+impl Drop for TestEnv {
+    fn drop(&mut self) {
+        let _ = std::env::set_current_dir(&self.original_cwd);
+    }
+}
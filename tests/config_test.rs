@@ -0,0 +1,139 @@
+use anyhow::Result;
+use doplan::commands;
+use doplan::config::{self, Config, Source};
+use doplan::error::DoPlanError;
+use doplan::utils;
+use std::fs;
+use std::path::PathBuf;
+
+mod common;
+use common::TestEnv;
+
+/// A project in `project/` with the user config under `home-config/` and
+/// none of the `DOPLAN_*` variables an earlier test may have set
+fn setup() -> Result<TestEnv> {
+    let env = TestEnv::empty()?;
+    for key in config::KEYS {
+        std::env::remove_var(config::env_var(key.name));
+    }
+    fs::create_dir_all(env.project_dir())?;
+    std::env::set_var("XDG_CONFIG_HOME", env.path("home-config"));
+    std::env::set_current_dir(env.project_dir())?;
+    Ok(env)
+}
+
+impl TestEnv {
+    fn project_dir(&self) -> PathBuf {
+        self.path("project")
+    }
+
+    fn user_file(&self) -> PathBuf {
+        self.path("home-config/doplan/config.toml")
+    }
+
+    fn write_user(&self, content: &str) -> Result<()> {
+        fs::create_dir_all(self.user_file().parent().unwrap())?;
+        fs::write(self.user_file(), content)?;
+        Ok(())
+    }
+}
+
+#[test]
+fn test_layers_override_in_order() -> Result<()> {
+    let env = setup()?;
+    env.write_user("[git]\nbranch_suffix = \"-user\"\n\n[display]\nprogress_bar_width = 50\n\n[llm]\nmodel = \"llama3.1\"\n")?;
+    fs::write(env.project_dir().join("doplan.toml"), "[git]\nbranch_suffix = \"-project\"\n")?;
+    std::env::set_var("DOPLAN_LLM_MODEL", "qwen2.5");
+
+    let resolved = Config::resolve(&env.project_dir())?;
+    assert_eq!(resolved.config.git.branch_suffix, "-project");
+    assert_eq!(resolved.config.display.progress_bar_width, 50);
+    assert_eq!(resolved.config.llm.model, "qwen2.5");
+    assert_eq!(resolved.config.plan.priorities, ["high", "medium", "low"]);
+
+    assert_eq!(resolved.sources["git.branch_suffix"], Source::Project(env.project_dir().join("doplan.toml")));
+    assert_eq!(resolved.sources["display.progress_bar_width"], Source::User(env.user_file()));
+    assert_eq!(resolved.sources["llm.model"], Source::Env("DOPLAN_LLM_MODEL".to_string()));
+    assert_eq!(resolved.sources["paths.docs_dir"], Source::Default);
+    Ok(())
+}
+
+#[test]
+fn test_invalid_config_is_a_config_error() -> Result<()> {
+    let env = setup()?;
+    fs::write(env.project_dir().join("doplan.toml"), "[display]\nprogress_bar_width = 1000\n")?;
+    let error = Config::load(&env.project_dir()).unwrap_err();
+    assert!(matches!(error.downcast_ref::<DoPlanError>(), Some(DoPlanError::Config(_))));
+    assert!(error.to_string().contains("display.progress_bar_width: 1000 is outside 5..=200"));
+
+    fs::write(env.project_dir().join("doplan.toml"), "[paths]\ndocs = \"documents\"\n")?;
+    let error = Config::load(&env.project_dir()).unwrap_err();
+    assert!(error.to_string().contains("unknown field `docs`"));
+
+    fs::remove_file(env.project_dir().join("doplan.toml"))?;
+    std::env::set_var("DOPLAN_DISPLAY_PROGRESS_BAR_WIDTH", "wide");
+    let error = Config::load(&env.project_dir()).unwrap_err();
+    assert!(error.to_string().contains("$DOPLAN_DISPLAY_PROGRESS_BAR_WIDTH"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_keeps_comments_and_validates() -> Result<()> {
+    let env = setup()?;
+    let path = env.project_dir().join("doplan.toml");
+    fs::write(&path, "# Team settings\n[git]\n# appended to feature branches\nbranch_suffix = \"-old\"\n")?;
+
+    commands::config::execute(vec!["set".into(), "git.branch_suffix".into(), "-team".into()]).await?;
+    commands::config::execute(vec!["set".into(), "plan.priorities".into(), "critical, high, low".into()]).await?;
+    let content = fs::read_to_string(&path)?;
+    assert!(content.starts_with("# Team settings\n[git]\n# appended to feature branches\nbranch_suffix = \"-team\"\n"));
+
+    let config = Config::load(&env.project_dir())?;
+    assert_eq!(config.plan.priorities, ["critical", "high", "low"]);
+    assert_eq!(config.middle_priority(), "high");
+    assert_eq!(config.get("plan.priorities")?.as_deref(), Some("critical, high, low"));
+
+    // Invalid values are rejected and the file is left alone
+    let error = commands::config::execute(vec!["set".into(), "display.progress_bar_width".into(), "2".into()])
+        .await
+        .unwrap_err();
+    assert!(matches!(error.downcast_ref::<DoPlanError>(), Some(DoPlanError::Config(_))));
+    assert!(commands::config::execute(vec!["set".into(), "git.prefix".into(), "x".into()]).await.is_err());
+    assert_eq!(fs::read_to_string(&path)?, content);
+
+    // An empty value removes the key
+    commands::config::execute(vec!["set".into(), "git.branch_suffix".into(), "".into()]).await?;
+    assert_eq!(Config::load(&env.project_dir())?.git.branch_suffix, "");
+    assert!(fs::read_to_string(&path)?.contains("# Team settings"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_global_writes_user_file() -> Result<()> {
+    let env = setup()?;
+    commands::config::execute(vec!["set".into(), "llm.model".into(), "llama3.1".into(), "--global".into()]).await?;
+    assert_eq!(fs::read_to_string(env.user_file())?, "[llm]\nmodel = \"llama3.1\"\n");
+    assert!(!env.project_dir().join("doplan.toml").exists());
+    assert_eq!(Config::load(&env.project_dir())?.llm.model, "llama3.1");
+
+    // Secrets stay out of the committed project file
+    let error = commands::config::execute(vec!["set".into(), "llm.api_key".into(), "sk-test".into()]).await.unwrap_err();
+    assert!(error.to_string().contains("--global"));
+    assert!(error.to_string().contains("DOPLAN_LLM_API_KEY"));
+    assert!(!env.project_dir().join("doplan.toml").exists());
+    commands::config::execute(vec!["set".into(), "llm.api_key".into(), "sk-test".into(), "--global".into()]).await?;
+    assert!(fs::read_to_string(env.user_file())?.contains("api_key = \"sk-test\""));
+    Ok(())
+}
+
+#[test]
+fn test_paths_follow_config() -> Result<()> {
+    let env = setup()?;
+    fs::write(env.project_dir().join("doplan.toml"), "[paths]\ndocs_dir = \"docs/plan\"\nstate_dir = \".plan-state\"\n")?;
+    assert!(utils::doplan_dir()?.ends_with("docs/plan"));
+    assert!(utils::dot_doplan_dir()?.ends_with(".plan-state"));
+
+    fs::write(env.project_dir().join("doplan.toml"), "[paths]\ndocs_dir = \"../elsewhere\"\n")?;
+    assert!(utils::doplan_dir().is_err());
+    Ok(())
+}
@@ -0,0 +1,173 @@
+use anyhow::Result;
+use doplan::commands;
+use doplan::state::deps::{DependencyGraph, NodeRef};
+use doplan::state::{tasks_md, Feature, Phase, ProjectState, Task, TaskStatus};
+use doplan::utils::plan_dirs;
+use std::fs;
+
+mod common;
+use common::TestEnv;
+
+fn dependent_features_state() -> ProjectState {
+    ProjectState {
+        project_name: Some("Test Project".to_string()),
+        features: Some(vec![
+            Feature {
+                id: "F-0001".to_string(),
+                name: "User Auth".to_string(),
+                description: "Sign in".to_string(),
+                priority: "high".to_string(),
+                ..Default::default()
+            },
+            Feature {
+                id: "F-0002".to_string(),
+                name: "Billing".to_string(),
+                description: "Payments".to_string(),
+                priority: "high".to_string(),
+                depends_on: vec!["F-0001".to_string()],
+                ..Default::default()
+            },
+        ]),
+        phases: Some(vec![Phase {
+            id: "P-01".to_string(),
+            name: "Foundation".to_string(),
+            description: "First phase".to_string(),
+            features: vec!["F-0001".to_string(), "F-0002".to_string()],
+        }]),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_depends_on_round_trips_through_tasks_md() {
+    let feature = Feature {
+        id: "F-0002".to_string(),
+        name: "Billing".to_string(),
+        depends_on: vec!["F-0001".to_string()],
+        tasks: vec![
+            Task::new("T1", "Setup"),
+            Task {
+                depends_on: vec!["T1".to_string(), "F-0001/T3".to_string()],
+                ..Task::new("T2", "Checkout")
+            },
+        ],
+        ..Default::default()
+    };
+
+    let content = tasks_md::render(&feature);
+    assert!(content.contains("- **Depends On**: T1, F-0001/T3"));
+    assert!(content.contains("## Dependencies\n\n- F-0001\n"));
+
+    let parsed = tasks_md::parse(&content);
+    assert_eq!(parsed, feature.tasks);
+}
+
+#[tokio::test]
+async fn test_plan_writes_dependencies_and_next_respects_them() -> Result<()> {
+    let env = TestEnv::new(&dependent_features_state())?;
+    commands::plan::execute(vec![]).await?;
+
+    let tasks = fs::read_to_string(env.path("doplan/plan/01-foundation/01-user-auth/tasks.md"))?;
+    assert!(tasks.contains("- **Depends On**: T1"));
+    let plan = fs::read_to_string(env.path("doplan/plan/01-foundation/02-billing/plan.md"))?;
+    assert!(plan.contains("- User Auth (F-0001)"));
+
+    // Every Billing task waits on the whole of User Auth
+    let state = ProjectState::load_from(&env.path(".doplan/state.json"))?;
+    let graph = DependencyGraph::build(&state);
+    assert!(graph.issues().is_empty());
+    let billing_setup = NodeRef::parse("F-0002/T1", "F-0002").unwrap();
+    assert_eq!(graph.blockers(&billing_setup).len(), 3);
+    assert!(graph.is_ready(&NodeRef::parse("F-0001/T1", "F-0001").unwrap()));
+
+    commands::next::execute(vec![]).await?;
+    commands::next::execute(vec!["explain".to_string(), "F-0002/T1".to_string()]).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cycle_in_tasks_md_is_rejected() -> Result<()> {
+    let env = TestEnv::new(&dependent_features_state())?;
+    commands::plan::execute(vec![]).await?;
+
+    // Make User Auth's setup wait on Billing, which already waits on User Auth
+    let tasks_path = env.path("doplan/plan/01-foundation/01-user-auth/tasks.md");
+    let content = fs::read_to_string(&tasks_path)?;
+    fs::write(&tasks_path, content.replacen("- **Depends On**: None", "- **Depends On**: F-0002/T1", 1))?;
+
+    let err = commands::next::execute(vec![]).await.unwrap_err();
+    assert!(format!("{:#}", err).contains("dependency cycle"));
+
+    let state_before = fs::read_to_string(env.state_path())?;
+    let err = commands::plan::execute(vec![]).await.unwrap_err();
+    assert!(format!("{:#}", err).contains("dependency cycle"));
+    // The cyclic task lists are not saved into the state
+    assert_eq!(fs::read_to_string(env.state_path())?, state_before);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_unknown_reference_is_rejected() -> Result<()> {
+    let env = TestEnv::new(&dependent_features_state())?;
+    commands::plan::execute(vec![]).await?;
+
+    let tasks_path = env.path("doplan/plan/01-foundation/01-user-auth/tasks.md");
+    let content = fs::read_to_string(&tasks_path)?;
+    fs::write(&tasks_path, content.replacen("- **Depends On**: None", "- **Depends On**: T42", 1))?;
+
+    let err = commands::next::execute(vec![]).await.unwrap_err();
+    assert!(format!("{:#}", err).contains("F-0001/T1 depends on unknown reference 'T42'"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_deleting_a_feature_drops_task_references_to_it() -> Result<()> {
+    let env = TestEnv::new(&dependent_features_state())?;
+    commands::plan::execute(vec![]).await?;
+
+    // User Auth's T3 waits on a Billing task as well as on its own T2
+    let tasks_path = env.path("doplan/plan/01-foundation/01-user-auth/tasks.md");
+    let content = fs::read_to_string(&tasks_path)?;
+    fs::write(&tasks_path, content.replacen("- **Depends On**: T2\n", "- **Depends On**: F-0002/T1, T2\n", 1))?;
+    assert!(fs::read_to_string(&tasks_path)?.contains("F-0002/T1"));
+
+    commands::feature::execute(vec!["delete".to_string(), "F-0002".to_string()]).await?;
+
+    let content = fs::read_to_string(&tasks_path)?;
+    assert!(content.contains("- **Depends On**: T2\n"));
+    assert!(!content.contains("F-0002"));
+    let state = ProjectState::load()?;
+    assert_eq!(state.feature_by_id("F-0001").unwrap().tasks[2].depends_on, vec!["T2".to_string()]);
+    assert!(DependencyGraph::build(&state).issues().is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_next_skips_feature_directories_without_an_id() -> Result<()> {
+    let env = TestEnv::new(&dependent_features_state())?;
+    commands::plan::execute(vec![]).await?;
+
+    // A hand-made directory the state knows nothing about: its prerequisites
+    // cannot be checked, so it is never recommended
+    let stray = env.path("doplan/plan/01-foundation/03-stray");
+    fs::create_dir_all(&stray)?;
+    fs::write(stray.join("tasks.md"), "#### Task 1: Wander\n- **Status**: [x] Not Started | [ ] In Progress\n- **Depends On**: None\n")?;
+
+    let plan_dir = env.path("doplan/plan");
+    let mut state = ProjectState::load()?;
+    for feature_id in ["F-0001", "F-0002"] {
+        let task_ids: Vec<String> = state.feature_by_id(feature_id).unwrap().tasks.iter().map(|t| t.id.clone()).collect();
+        for task_id in task_ids {
+            plan_dirs::set_task_status(&plan_dir, &mut state, feature_id, &task_id, TaskStatus::Completed)?;
+        }
+    }
+
+    let next = commands::next::recommend(&mut ProjectState::load()?)?;
+    assert!(matches!(next, commands::next::NextAction::AllComplete));
+
+    Ok(())
+}
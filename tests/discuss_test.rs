@@ -0,0 +1,201 @@
+use anyhow::Result;
+use assert_cmd::Command;
+use doplan::commands::discuss::{self, DiscussSession};
+use doplan::state::{Feature, ProjectState};
+use predicates::prelude::*;
+use std::fs;
+
+mod common;
+use common::TestEnv;
+
+impl TestEnv {
+    /// `doplan` run in the test directory with no terminal on stdin
+    fn doplan(&self) -> Command {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_doplan"));
+        command.current_dir(self.root()).write_stdin("");
+        command
+    }
+}
+
+const ANSWERS: &str = r#"
+project_name: Recipe Box
+idea: Share and plan family recipes with the whole household
+questions:
+  target_audience: Home cooks
+  problem: Recipes are scattered across notebooks and apps
+  unique_value: Shared meal plans
+  core_features: Recipes, search, planning
+  constraints: Works offline
+  timeline: Three months
+  design_preferences: Warm and simple
+  platforms: web, mobile
+features:
+  - name: User Auth
+    priority: high
+    description: Accounts and households
+  - Recipe Search
+  - name: Meal Planner
+    priority: LOW
+tech_stack: ["Backend: Rust/Axum", "Database: PostgreSQL"]
+phases:
+  - name: Foundation
+    description: Accounts and storage
+    features: [User Auth, recipe search]
+  - name: Planning
+    features: [Meal Planner]
+"#;
+
+#[tokio::test]
+async fn test_answers_file_fills_state_and_notes() -> Result<()> {
+    let env = TestEnv::empty()?;
+    // A re-discussed feature keeps its id
+    ProjectState {
+        features: Some(vec![Feature {
+            id: "F-0007".to_string(),
+            name: "Meal Planner".to_string(),
+            ..Default::default()
+        }]),
+        id_counters: doplan::state::IdCounters { feature: 7, phase: 0 },
+        ..Default::default()
+    }
+    .save()?;
+    fs::write(env.path("answers.yaml"), ANSWERS)?;
+
+    discuss::execute(vec!["--answers".to_string(), "answers.yaml".to_string()]).await?;
+
+    let state = ProjectState::load()?;
+    assert_eq!(state.project_name.as_deref(), Some("Recipe Box"));
+    assert_eq!(state.tech_stack.as_ref().unwrap().len(), 2);
+    let features = state.features.as_ref().unwrap();
+    let summary: Vec<(&str, &str, &str)> = features.iter().map(|f| (f.id.as_str(), f.name.as_str(), f.priority.as_str())).collect();
+    assert_eq!(summary, vec![
+        ("F-0008", "User Auth", "high"),
+        ("F-0009", "Recipe Search", "high"),
+        ("F-0007", "Meal Planner", "low"),
+    ]);
+    assert_eq!(features[0].description, "Accounts and households");
+    let phases = state.phases.as_ref().unwrap();
+    assert_eq!(phases[0].features, vec!["F-0008".to_string(), "F-0009".to_string()]);
+    assert_eq!(phases[1].features, vec!["F-0007".to_string()]);
+    assert_eq!(phases[1].description, "");
+
+    assert_eq!(state.discussion.len(), 8);
+    assert_eq!(state.discussion[0].question, "Who is your target audience?");
+    assert_eq!(state.answer("timeline"), Some("Three months"));
+
+    let notes = fs::read_to_string(env.path("doplan/idea-notes.md"))?;
+    assert!(notes.contains("### Who is your target audience?\n\nHome cooks"));
+    assert!(notes.contains("### Phase 2: Planning"));
+
+    Ok(())
+}
+
+#[test]
+fn test_json_answers_without_terminal() -> Result<()> {
+    let env = TestEnv::empty()?;
+    fs::write(
+        env.path("answers.json"),
+        r#"{ "project_name": "CLI Tool", "idea": "A small tool", "features": ["Parser"], "phases": [{ "name": "Core", "features": ["Parser"] }] }"#,
+    )?;
+
+    env.doplan()
+        .args(["execute", "discuss", "--answers", "answers.json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Discussion Complete!"));
+
+    let state = ProjectState::load_from(&env.path(".doplan/state.json"))?;
+    assert_eq!(state.project_name.as_deref(), Some("CLI Tool"));
+    assert_eq!(state.tech_stack, Some(vec![]));
+    assert_eq!(state.phases.unwrap()[0].features, vec!["F-0001".to_string()]);
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_or_incomplete_answers_fail_without_terminal() -> Result<()> {
+    let env = TestEnv::empty()?;
+
+    fs::write(env.path("bad.yaml"), "project_name: X\nidea: Y\nfeatures: [Auth]\nphases:\n  - name: One\n    features: [Auth, Billing]\n")?;
+    env.doplan()
+        .args(["execute", "discuss", "--answers", "bad.yaml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("phases[0].features[1] (One): unknown feature 'Billing'"));
+
+    fs::write(env.path("partial.yaml"), "idea: Y\n")?;
+    env.doplan()
+        .args(["execute", "discuss", "--answers", "partial.yaml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("`project_name` is missing from the answers file and there is no terminal to ask for it"));
+
+    env.doplan()
+        .args(["execute", "discuss"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Pass --answers <file>"));
+    assert!(!env.path(".doplan/state.json").exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_unfinished_session_is_resumed_and_removed() -> Result<()> {
+    let env = TestEnv::empty()?;
+    let session_path = env.path(".doplan/discuss-session.json");
+    // Aborted after the idea and the first two questions
+    let mut session = DiscussSession::default();
+    session.answers.project_name = Some("Recipe Box".to_string());
+    session.answers.idea = Some("Family recipes".to_string());
+    session.answers.questions = Some([
+        ("target_audience".to_string(), "Home cooks".to_string()),
+        ("problem".to_string(), String::new()),
+    ].into());
+    session.save(&session_path)?;
+    assert_eq!(session.next_step(), "question 3 of 8");
+
+    // The answers file wins over the draft where both have a value
+    fs::write(env.path("rest.yaml"), "idea: Shared family recipes\nquestions:\n  timeline: Spring\nfeatures: [Search]\ntech_stack: []\n")?;
+    env.doplan()
+        .args(["execute", "discuss", "--answers", "rest.yaml", "--resume"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Resuming the discussion at question 3 of 8"));
+
+    assert!(!session_path.exists());
+    let state = ProjectState::load_from(&env.path(".doplan/state.json"))?;
+    assert_eq!(state.project_name.as_deref(), Some("Recipe Box"));
+    assert_eq!(state.idea.as_deref(), Some("Shared family recipes"));
+    assert_eq!(state.answer("target_audience"), Some("Home cooks"));
+    assert_eq!(state.answer("timeline"), Some("Spring"));
+    assert_eq!(state.answer("problem"), None);
+    assert_eq!(state.discussion.len(), 8);
+
+    Ok(())
+}
+
+#[test]
+fn test_unfinished_session_is_ignored_without_resume() -> Result<()> {
+    let env = TestEnv::empty()?;
+    let session_path = env.path(".doplan/discuss-session.json");
+    let mut session = DiscussSession::default();
+    session.answers.project_name = Some("Old Draft".to_string());
+    session.answers.questions = Some([("target_audience".to_string(), "Stale answer".to_string())].into());
+    session.save(&session_path)?;
+
+    // Only the answers file counts; the draft's answers are not merged in
+    fs::write(env.path("answers.yaml"), "project_name: Recipe Box\nidea: Family recipes\nfeatures: [Search]\ntech_stack: []\n")?;
+    env.doplan()
+        .args(["execute", "discuss", "--answers", "answers.yaml"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Ignoring the unfinished discussion at"));
+
+    assert!(!session_path.exists());
+    let state = ProjectState::load_from(&env.path(".doplan/state.json"))?;
+    assert_eq!(state.project_name.as_deref(), Some("Recipe Box"));
+    assert_eq!(state.answer("target_audience"), None);
+
+    Ok(())
+}
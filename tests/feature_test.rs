@@ -15,11 +15,13 @@ fn create_test_state() -> ProjectState {
                 name: "Feature 1".to_string(),
                 description: "First feature".to_string(),
                 priority: "high".to_string(),
+                ..Default::default()
             },
         ]),
         phases: Some(vec![]),
         improvements: None,
         notes: None,
+        ..Default::default()
     }
 }
 
@@ -77,6 +79,7 @@ async fn test_feature_list_empty() -> Result<()> {
         phases: None,
         improvements: None,
         notes: None,
+        ..Default::default()
     };
     let state_path = temp_dir.path().join(".doplan").join("state.json");
     let state_json = serde_json::to_string_pretty(&empty_state)?;
@@ -147,6 +150,7 @@ async fn test_feature_incomplete_state() -> Result<()> {
         phases: None,
         improvements: None,
         notes: None,
+        ..Default::default()
     };
     let state_path = temp_dir.path().join(".doplan").join("state.json");
     let state_json = serde_json::to_string_pretty(&incomplete_state)?;
@@ -0,0 +1,101 @@
+use anyhow::Result;
+use doplan::commands;
+use doplan::state::{Feature, Phase, ProjectState};
+use chrono::{Duration, Utc};
+use doplan::utils::history::{self, Snapshot};
+use serde_json::Value;
+use std::fs;
+
+mod common;
+use common::TestEnv;
+
+fn two_phase_state() -> ProjectState {
+    let feature = |id: &str, name: &str| Feature {
+        id: id.to_string(),
+        name: name.to_string(),
+        description: format!("{} feature", name),
+        priority: "high".to_string(),
+        ..Default::default()
+    };
+    ProjectState {
+        project_name: Some("Test Project".to_string()),
+        features: Some(vec![feature("F-0001", "User Auth"), feature("F-0002", "Billing")]),
+        phases: Some(vec![
+            Phase {
+                id: "P-01".to_string(),
+                name: "Foundation".to_string(),
+                description: "First phase".to_string(),
+                features: vec!["F-0001".to_string()],
+            },
+            Phase {
+                id: "P-02".to_string(),
+                name: "Launch".to_string(),
+                description: "Second phase".to_string(),
+                features: vec!["F-0002".to_string()],
+            },
+        ]),
+        ..Default::default()
+    }
+}
+
+/// A snapshot of the freshly planned project, `days_ago` days back
+fn backdated_snapshot(env: &TestEnv, days_ago: i64) -> Result<Snapshot> {
+    let snapshots = history::load(&env.path(".doplan/history.jsonl"))?;
+    let mut snapshot = snapshots.last().cloned().expect("a recorded snapshot");
+    snapshot.timestamp = (Utc::now() - Duration::days(days_ago)).to_rfc3339();
+    Ok(snapshot)
+}
+
+#[tokio::test]
+async fn test_progress_writes_forecast_to_dashboard() -> Result<()> {
+    let env = TestEnv::new(&two_phase_state())?;
+    commands::plan::execute(vec![]).await?;
+
+    // A single day of history gives no velocity yet
+    commands::progress::execute(vec![]).await?;
+    let dashboard: Value = serde_json::from_str(&fs::read_to_string(env.path(".doplan/dashboard.json"))?)?;
+    assert!(dashboard["forecast"]["velocity"].is_null());
+    assert!(dashboard["forecast"]["project"]["projected_finish"].is_null());
+    assert_eq!(dashboard["forecast"]["project"]["remaining_tasks"], 6);
+
+    // Pretend the plan was recorded two days ago, then complete two tasks today
+    let history_path = env.path(".doplan/history.jsonl");
+    let earlier = backdated_snapshot(&env, 2)?;
+    fs::write(&history_path, "")?;
+    history::append(&history_path, &earlier)?;
+
+    let tasks_path = env.path("doplan/plan/01-foundation/01-user-auth/tasks.md");
+    let tasks = fs::read_to_string(&tasks_path)?;
+    fs::write(
+        &tasks_path,
+        tasks.replace("[x] Not Started | [ ] In Progress | [ ] Completed", "[ ] Not Started | [ ] In Progress | [x] Completed")
+            .replacen("[ ] Not Started | [ ] In Progress | [x] Completed", "[x] Not Started | [ ] In Progress | [ ] Completed", 1),
+    )?;
+    commands::progress::execute(vec![]).await?;
+
+    let dashboard: Value = serde_json::from_str(&fs::read_to_string(env.path(".doplan/dashboard.json"))?)?;
+    let forecast = &dashboard["forecast"];
+    assert_eq!(forecast["velocity"]["tasks_per_day"], 1.0);
+    assert_eq!(forecast["velocity"]["tasks_per_week"], 7.0);
+    assert_eq!(forecast["velocity"]["window_days"], 2);
+
+    // Four tasks left at one a day; Foundation's last task is done first
+    let today = Utc::now().date_naive();
+    assert_eq!(forecast["project"]["remaining_tasks"], 4);
+    assert_eq!(forecast["project"]["projected_finish"], (today + Duration::days(4)).to_string());
+    assert_eq!(forecast["phases"][0]["id"], "P-01");
+    assert_eq!(forecast["phases"][0]["projected_finish"], (today + Duration::days(1)).to_string());
+    assert_eq!(forecast["phases"][1]["projected_finish"], (today + Duration::days(4)).to_string());
+    assert!(forecast["project"]["earliest_finish"].as_str() < forecast["project"]["latest_finish"].as_str());
+
+    let md = fs::read_to_string(env.path("doplan/dashboard.md"))?;
+    let task_summary = md.find("## Task Summary").unwrap();
+    let forecast_section = md.find("## Forecast").unwrap();
+    assert!(forecast_section > task_summary);
+    assert!(md.contains("- **Velocity:** 1.0 tasks/day (7.0 tasks/week, last 2 days)"));
+    assert!(md.contains(&format!("- **Projected Completion:** {} (", today + Duration::days(4))));
+
+    commands::dashboard::execute(vec![]).await?;
+
+    Ok(())
+}
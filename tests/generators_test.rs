@@ -1,5 +1,5 @@
 use anyhow::Result;
-use doplan::state::{ProjectState, Feature, Phase};
+use doplan::state::{DiscussionAnswer, ProjectState, Feature, Phase};
 use doplan::generators;
 use tempfile::TempDir;
 use std::fs;
@@ -16,11 +16,13 @@ fn create_test_state() -> ProjectState {
                 name: "Feature 1".to_string(),
                 description: "First feature".to_string(),
                 priority: "high".to_string(),
+                ..Default::default()
             },
             Feature {
                 name: "Feature 2".to_string(),
                 description: "Second feature".to_string(),
                 priority: "medium".to_string(),
+                ..Default::default()
             },
         ]),
         phases: Some(vec![
@@ -28,10 +30,12 @@ fn create_test_state() -> ProjectState {
                 name: "Phase 1".to_string(),
                 description: "First phase".to_string(),
                 features: vec!["Feature 1".to_string()],
+                ..Default::default()
             },
         ]),
         improvements: None,
         notes: None,
+        ..Default::default()
     }
 }
 
@@ -107,6 +111,32 @@ fn test_prd_generation() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_prd_uses_discussion_answers() -> Result<()> {
+    let env = TestEnv::new()?;
+    let mut state = env.state.clone();
+    let answer = |key: &str, question: &str, answer: &str| DiscussionAnswer {
+        key: key.to_string(),
+        question: question.to_string(),
+        answer: answer.to_string(),
+    };
+    state.discussion = vec![
+        answer("target_audience", "Who is your target audience?", "Busy parents"),
+        answer("problem", "What problem does this solve?", "Meal planning takes too long"),
+        answer("timeline", "What's your timeline or deadline?", "Beta by March"),
+    ];
+    // Answers in state take precedence over idea-notes.md
+    let idea_notes = Some("### What problem does this solve?\n\nOld problem\n\n### Next".to_string());
+
+    let content = fs::read_to_string(generators::prd::generate(&state, &idea_notes)?)?;
+    assert!(content.contains("## Problem Statement\n\nMeal planning takes too long\n"));
+    assert!(content.contains("## Target Audience\n\nBusy parents\n"));
+    assert!(content.contains("**Target:** Beta by March"));
+    assert!(!content.contains("Old problem"));
+
+    Ok(())
+}
+
 #[test]
 fn test_prd_generation_missing_state() -> Result<()> {
     let _env = TestEnv::new()?;
@@ -235,16 +265,14 @@ fn test_dpr_generation() -> Result<()> {
         .map_err(|e| anyhow::anyhow!("Failed to write plan.md to {}: {}", plan_path, e))?;
     
     // Verify file was written
-    if !fs::metadata(&plan_path).is_ok() {
+    if fs::metadata(&plan_path).is_err() {
         eprintln!("Error: plan.md file not found after write");
         eprintln!("Current directory: {:?}", std::env::current_dir());
         eprintln!("Expected path: {}", plan_path);
         if let Ok(entries) = fs::read_dir(plan_dir) {
             eprintln!("Files in directory:");
-            for entry in entries {
-                if let Ok(entry) = entry {
-                    eprintln!("  Found: {}", entry.path().display());
-                }
+            for entry in entries.flatten() {
+                eprintln!("  Found: {}", entry.path().display());
             }
         } else {
             eprintln!("Could not read directory: {}", plan_dir);
@@ -267,10 +295,8 @@ fn test_dpr_generation() -> Result<()> {
     if plan_dir_path.exists() {
         eprintln!("Files in plan directory:");
         if let Ok(entries) = fs::read_dir(&plan_dir_path) {
-            for entry in entries {
-                if let Ok(entry) = entry {
-                    eprintln!("  Found: {}", entry.path().display());
-                }
+            for entry in entries.flatten() {
+                eprintln!("  Found: {}", entry.path().display());
             }
         }
     }
@@ -347,7 +373,7 @@ fn test_sops_generation() -> Result<()> {
     let generated = result?;
     
     // Should generate SOPS for PostgreSQL (from tech_stack)
-    assert!(generated.len() > 0);
+    assert!(!generated.is_empty());
     
     // Check for PostgreSQL SOPS
     let postgres_sops = generated.iter().find(|p| p.to_string_lossy().contains("postgresql"));
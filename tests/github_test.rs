@@ -0,0 +1,209 @@
+use anyhow::Result;
+use doplan::commands;
+use doplan::integration::github::{self, GitHubClient, SyncMap};
+use doplan::state::{Feature, Phase, ProjectState, TaskStatus};
+use mockito::Matcher;
+use serde_json::json;
+use std::fs;
+
+mod common;
+use common::TestEnv;
+
+fn test_state() -> ProjectState {
+    ProjectState {
+        project_name: Some("Test Project".to_string()),
+        features: Some(vec![Feature {
+            id: "F-0001".to_string(),
+            name: "User Auth".to_string(),
+            description: "Login and signup".to_string(),
+            priority: "high".to_string(),
+            ..Default::default()
+        }]),
+        phases: Some(vec![Phase {
+            id: "P-01".to_string(),
+            name: "Foundation".to_string(),
+            description: "First phase".to_string(),
+            features: vec!["F-0001".to_string()],
+        }]),
+        ..Default::default()
+    }
+}
+
+const TASKS_MD: &str = "doplan/plan/01-foundation/01-user-auth/tasks.md";
+
+#[tokio::test]
+async fn test_sync_creates_then_pulls_checked_items() -> Result<()> {
+    let env = TestEnv::new(&test_state())?;
+    commands::plan::execute(vec![]).await?;
+    let plan_dir = env.path("doplan/plan");
+    let mut server = mockito::Server::new_async().await;
+    let client = GitHubClient::new(&server.url(), "acme/app", "secret");
+
+    // First sync: everything is created
+    let milestone = server.mock("POST", "/repos/acme/app/milestones")
+        .match_header("authorization", "Bearer secret")
+        .match_body(Matcher::PartialJson(json!({ "title": "P-01: Foundation", "state": "open" })))
+        .with_status(201)
+        .with_body(r#"{"number": 4}"#)
+        .create_async()
+        .await;
+    let label = server.mock("POST", "/repos/acme/app/labels")
+        .match_body(Matcher::PartialJson(json!({ "name": "priority:high" })))
+        .with_status(422)
+        .with_body(r#"{"message": "Validation Failed"}"#)
+        .create_async()
+        .await;
+    let issue = server.mock("POST", "/repos/acme/app/issues")
+        .match_body(Matcher::PartialJson(json!({ "title": "User Auth", "milestone": 4, "labels": ["priority:high"] })))
+        .with_status(201)
+        .with_body(r#"{"number": 17}"#)
+        .create_async()
+        .await;
+
+    let mut state = ProjectState::load()?;
+    let mut map = SyncMap::default();
+    let report = github::sync(&client, &plan_dir, &mut state, &mut map).await?;
+    milestone.assert_async().await;
+    label.assert_async().await;
+    issue.assert_async().await;
+    assert_eq!((report.milestones_created, report.issues_created, report.issues_updated), (1, 1, 0));
+    assert_eq!(map.milestones["P-01"], 4);
+    assert_eq!(map.issues["F-0001"], 17);
+    assert_eq!(map.repo.as_deref(), Some("acme/app"));
+
+    // Second sync: T2 was checked and a label added on GitHub
+    let feature = state.feature_by_id("F-0001").unwrap().clone();
+    let remote_body = github::issue_body(&feature).replace("- [ ] T2", "- [x] T2");
+    let get = server.mock("GET", "/repos/acme/app/issues/17")
+        .with_body(json!({
+            "number": 17,
+            "title": "User Auth",
+            "state": "open",
+            "body": remote_body,
+            "milestone": { "number": 4 },
+            "labels": [{ "name": "priority:high" }, { "name": "backend" }]
+        }).to_string())
+        .create_async()
+        .await;
+    let patch_milestone = server.mock("PATCH", "/repos/acme/app/milestones/4")
+        .with_body(r#"{"number": 4}"#)
+        .create_async()
+        .await;
+    let patch_issue = server.mock("PATCH", "/repos/acme/app/issues/17")
+        .expect(0)
+        .create_async()
+        .await;
+
+    let report = github::sync(&client, &plan_dir, &mut state, &mut map).await?;
+    get.assert_async().await;
+    patch_milestone.assert_async().await;
+    // The remote issue already matches the plan once T2 is pulled
+    patch_issue.assert_async().await;
+    assert_eq!(report.tasks_completed, vec!["F-0001/T2".to_string()]);
+    assert_eq!(report.issues_updated, 0);
+    let tasks = fs::read_to_string(env.path(TASKS_MD))?;
+    assert_eq!(tasks.matches("[x] Completed").count(), 1);
+    assert_eq!(state.feature_by_id("F-0001").unwrap().tasks[1].status, TaskStatus::Completed);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_command_pushes_local_changes_and_closed_issues() -> Result<()> {
+    let env = TestEnv::new(&test_state())?;
+    commands::plan::execute(vec![]).await?;
+    let mut server = mockito::Server::new_async().await;
+
+    let map_path = env.path(".doplan/github.json");
+    let map = SyncMap {
+        repo: Some("acme/app".to_string()),
+        milestones: [("P-01".to_string(), 4)].into(),
+        issues: [("F-0001".to_string(), 17)].into(),
+        labels: ["priority:high".to_string()].into(),
+        synced_at: None,
+    };
+    map.save(&map_path)?;
+
+    // Closed on GitHub with an outdated body: every task completes locally
+    let get = server.mock("GET", "/repos/acme/app/issues/17")
+        .with_body(json!({ "number": 17, "title": "Old title", "state": "closed", "state_reason": "completed", "body": "", "milestone": null, "labels": [] }).to_string())
+        .create_async()
+        .await;
+    let patch_milestone = server.mock("PATCH", "/repos/acme/app/milestones/4")
+        .match_body(Matcher::PartialJson(json!({ "state": "closed" })))
+        .with_body(r#"{"number": 4}"#)
+        .create_async()
+        .await;
+    let patch_issue = server.mock("PATCH", "/repos/acme/app/issues/17")
+        .match_body(Matcher::AllOf(vec![
+            Matcher::PartialJson(json!({ "title": "User Auth", "state": "closed", "milestone": 4 })),
+            Matcher::Regex(r"- \[x\] T1: .*- \[x\] T2: .*- \[x\] T3: ".to_string()),
+        ]))
+        .with_body(r#"{"number": 17}"#)
+        .create_async()
+        .await;
+
+    std::env::set_var("GITHUB_TOKEN", "secret");
+    let result = commands::github::execute(vec!["--api-url".to_string(), server.url()]).await;
+    std::env::remove_var("GITHUB_TOKEN");
+    result?;
+
+    get.assert_async().await;
+    patch_milestone.assert_async().await;
+    patch_issue.assert_async().await;
+    let tasks = fs::read_to_string(env.path(TASKS_MD))?;
+    assert_eq!(tasks.matches("[x] Completed").count(), 3);
+    let state = ProjectState::load()?;
+    assert!(state.features.unwrap()[0].tasks.iter().all(|t| t.status == TaskStatus::Completed));
+    let saved = SyncMap::load(&map_path)?;
+    assert!(saved.synced_at.is_some());
+    assert_eq!(saved.issues, map.issues);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_failed_sync_keeps_created_numbers_and_not_planned_issues_stay_open() -> Result<()> {
+    let env = TestEnv::new(&test_state())?;
+    commands::plan::execute(vec![]).await?;
+    let mut server = mockito::Server::new_async().await;
+
+    let map_path = env.path(".doplan/github.json");
+    SyncMap {
+        repo: Some("acme/app".to_string()),
+        issues: [("F-0001".to_string(), 17)].into(),
+        labels: ["priority:high".to_string()].into(),
+        ..Default::default()
+    }
+    .save(&map_path)?;
+
+    // Closed as not planned: no task completes
+    server.mock("GET", "/repos/acme/app/issues/17")
+        .with_body(json!({ "number": 17, "title": "User Auth", "state": "closed", "state_reason": "not_planned", "body": "", "milestone": null, "labels": [] }).to_string())
+        .create_async()
+        .await;
+    server.mock("POST", "/repos/acme/app/milestones")
+        .with_status(201)
+        .with_body(r#"{"number": 4}"#)
+        .create_async()
+        .await;
+    server.mock("PATCH", "/repos/acme/app/issues/17")
+        .with_status(500)
+        .with_body(r#"{"message": "Server Error"}"#)
+        .create_async()
+        .await;
+
+    std::env::set_var("GITHUB_TOKEN", "secret");
+    let result = commands::github::execute(vec!["--api-url".to_string(), server.url()]).await;
+    std::env::remove_var("GITHUB_TOKEN");
+    assert!(result.is_err());
+
+    let tasks = fs::read_to_string(env.path(TASKS_MD))?;
+    assert_eq!(tasks.matches("[x] Completed").count(), 0);
+    // The milestone created before the error is not created again next time
+    let saved = SyncMap::load(&map_path)?;
+    assert_eq!(saved.milestones.get("P-01"), Some(&4));
+    assert!(saved.synced_at.is_none());
+
+    Ok(())
+}
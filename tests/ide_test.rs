@@ -0,0 +1,120 @@
+use anyhow::Result;
+use doplan::integration::ide::{self, Ide};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Expected output lives in `tests/golden/ide/<ide>/`. Regenerate it with
+/// `UPDATE_GOLDEN=1 cargo test --test ide_test` and review the diff.
+fn golden_dir(ide: Ide) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden/ide").join(ide.as_str())
+}
+
+fn assert_golden(ide: Ide) -> Result<()> {
+    let root = TempDir::new()?;
+    let files = ide.render(root.path())?;
+    assert!(!files.is_empty());
+
+    let dir = golden_dir(ide);
+    for file in &files {
+        let golden = dir.join(&file.path);
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            fs::create_dir_all(golden.parent().unwrap())?;
+            fs::write(&golden, &file.content)?;
+            continue;
+        }
+        let expected = fs::read_to_string(&golden)
+            .unwrap_or_else(|_| panic!("missing golden file {}", golden.display()));
+        assert_eq!(file.content, expected, "{} differs from {}", file.path.display(), golden.display());
+    }
+
+    // No stale golden files for commands that are no longer generated
+    let mut golden_files = Vec::new();
+    for entry in walkdir::WalkDir::new(&dir) {
+        let entry = entry?;
+        if entry.file_type().is_file() {
+            golden_files.push(entry.path().strip_prefix(&dir)?.to_path_buf());
+        }
+    }
+    golden_files.sort();
+    let mut rendered: Vec<PathBuf> = files.iter().map(|f| f.path.clone()).collect();
+    rendered.sort();
+    assert_eq!(golden_files, rendered);
+    Ok(())
+}
+
+#[test]
+fn test_cursor_commands_and_rules() -> Result<()> {
+    assert_golden(Ide::Cursor)
+}
+
+#[test]
+fn test_claude_commands() -> Result<()> {
+    assert_golden(Ide::Claude)
+}
+
+#[test]
+fn test_gemini_commands() -> Result<()> {
+    assert_golden(Ide::Gemini)
+}
+
+#[test]
+fn test_windsurf_workflows() -> Result<()> {
+    assert_golden(Ide::Windsurf)
+}
+
+#[test]
+fn test_vscode_tasks() -> Result<()> {
+    assert_golden(Ide::Vscode)
+}
+
+#[test]
+fn test_vscode_task_arguments_are_split_by_the_shell() -> Result<()> {
+    let root = TempDir::new()?;
+    let files = Ide::Vscode.render(root.path())?;
+    let doc: serde_json::Value = serde_json::from_str(&files[0].content)?;
+    let next = doc["tasks"].as_array().unwrap().iter().find(|t| t["label"] == "DoPlan: Next").unwrap();
+    assert_eq!(next["type"], "shell");
+    assert!(next.get("args").is_none());
+
+    // What the shell runs once VS Code fills in `explain F-0001/T2`
+    let command = next["command"].as_str().unwrap().replace("${input:doplan-next-args}", "explain F-0001/T2");
+    let argv: Vec<&str> = command.split_whitespace().collect();
+    assert_eq!(argv, ["doplan", "execute", "next", "explain", "F-0001/T2"]);
+    Ok(())
+}
+
+#[test]
+fn test_vscode_tasks_merge_into_existing_file() -> Result<()> {
+    let root = TempDir::new()?;
+    fs::create_dir(root.path().join(".vscode"))?;
+    fs::write(
+        root.path().join(".vscode/tasks.json"),
+        r#"{ "version": "2.0.0", "tasks": [{ "label": "test", "type": "shell", "command": "cargo test" }] }"#,
+    )?;
+
+    let paths = ide::generate_commands(root.path(), Ide::Vscode)?;
+    assert_eq!(paths, vec![root.path().join(".vscode/tasks.json")]);
+    let doc: serde_json::Value = serde_json::from_str(&fs::read_to_string(&paths[0])?)?;
+    assert_eq!(doc["tasks"][0]["label"], "test");
+    assert_eq!(doc["tasks"][1]["label"], "DoPlan: Discuss");
+
+    // Rendering again gives the same file
+    let again = Ide::Vscode.render(root.path())?;
+    assert_eq!(again[0].content, fs::read_to_string(&paths[0])?);
+
+    fs::write(root.path().join(".vscode/tasks.json"), "// comment\n{}")?;
+    let err = Ide::Vscode.render(root.path()).unwrap_err();
+    assert!(format!("{:#}", err).contains("Failed to parse"));
+    Ok(())
+}
+
+#[test]
+fn test_detect_ides() -> Result<()> {
+    let root = TempDir::new()?;
+    assert!(ide::detect_ides(root.path()).is_empty());
+    fs::create_dir(root.path().join(".claude"))?;
+    fs::create_dir(root.path().join(".vscode"))?;
+    assert_eq!(ide::detect_ides(root.path()), vec![Ide::Claude, Ide::Vscode]);
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use anyhow::Result;
+use doplan::commands;
+use doplan::state::{Feature, Phase, ProjectState};
+use doplan::utils::plan_dirs;
+use std::fs;
+use std::path::Path;
+
+mod common;
+use common::TestEnv;
+
+fn two_feature_state() -> ProjectState {
+    ProjectState {
+        project_name: Some("Test Project".to_string()),
+        features: Some(vec![
+            Feature {
+                id: "F-0001".to_string(),
+                name: "User Auth".to_string(),
+                description: "Sign in".to_string(),
+                priority: "high".to_string(),
+                ..Default::default()
+            },
+            Feature {
+                id: "F-0002".to_string(),
+                name: "Billing".to_string(),
+                description: "Payments".to_string(),
+                priority: "medium".to_string(),
+                ..Default::default()
+            },
+        ]),
+        phases: Some(vec![Phase {
+            id: "P-01".to_string(),
+            name: "Foundation".to_string(),
+            description: "First phase".to_string(),
+            features: vec!["F-0001".to_string(), "F-0002".to_string()],
+        }]),
+        ..Default::default()
+    }
+}
+
+fn mark_first_task_done(tasks_path: &Path) -> Result<()> {
+    let content = fs::read_to_string(tasks_path)?;
+    let updated = content.replacen(
+        "[x] Not Started | [ ] In Progress | [ ] Completed",
+        "[ ] Not Started | [ ] In Progress | [x] Completed",
+        1,
+    );
+    fs::write(tasks_path, updated)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_plan_records_ids_in_progress_files() -> Result<()> {
+    let env = TestEnv::new(&two_feature_state())?;
+    commands::plan::execute(vec![]).await?;
+
+    let phase_progress = fs::read_to_string(env.path("doplan/plan/01-foundation/phase-progress.json"))?;
+    assert!(phase_progress.contains("\"id\": \"P-01\""));
+    let feature_progress = fs::read_to_string(env.path("doplan/plan/01-foundation/02-billing/progress.json"))?;
+    assert!(feature_progress.contains("\"id\": \"F-0002\""));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_plan_moves_renamed_and_reordered_features() -> Result<()> {
+    let env = TestEnv::new(&two_feature_state())?;
+    commands::plan::execute(vec![]).await?;
+    mark_first_task_done(&env.path("doplan/plan/01-foundation/01-user-auth/tasks.md"))?;
+
+    // Rename the first feature and swap the order within the phase
+    let mut state = ProjectState::load_from(&env.state_path())?;
+    state.features.as_mut().unwrap()[0].name = "Sign In".to_string();
+    state.phases.as_mut().unwrap()[0].features.reverse();
+    state.save_to(&env.state_path())?;
+
+    commands::plan::execute(vec![]).await?;
+
+    assert!(!env.path("doplan/plan/01-foundation/01-user-auth").exists());
+    assert!(env.path("doplan/plan/01-foundation/01-billing").exists());
+    let moved = env.path("doplan/plan/01-foundation/02-sign-in/tasks.md");
+    let content = fs::read_to_string(moved)?;
+    assert!(content.contains("[x] Completed"), "task progress must survive the move");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_renamed_feature_directory_keeps_progress() -> Result<()> {
+    let env = TestEnv::new(&two_feature_state())?;
+    commands::plan::execute(vec![]).await?;
+
+    // `feature update` moves the directory with the pre-rename state, then saves
+    let mut state = ProjectState::load_from(&env.state_path())?;
+    let plan_dir = env.path("doplan/plan");
+    let moved = plan_dirs::rename_feature_dir(&plan_dir, &state, "F-0002", "Payments")?;
+    assert!(moved.is_some());
+    state.feature_by_id_mut("F-0002").unwrap().name = "Payments".to_string();
+    state.save_to(&env.state_path())?;
+
+    assert!(!env.path("doplan/plan/01-foundation/02-billing").exists());
+    assert!(env.path("doplan/plan/01-foundation/02-payments/tasks.md").exists());
+
+    let state = ProjectState::load_from(&env.state_path())?;
+    let feature = state.feature_by_id("F-0002").unwrap();
+    assert_eq!(feature.name, "Payments");
+
+    // Progress still finds the feature under its new directory
+    commands::progress::execute(vec![]).await?;
+    let dashboard = fs::read_to_string(env.path(".doplan/dashboard.json"))?;
+    assert!(dashboard.contains("\"id\": \"F-0002\""));
+    assert!(dashboard.contains("\"name\": \"Payments\""));
+
+    Ok(())
+}
@@ -0,0 +1,290 @@
+use anyhow::Result;
+use doplan::commands;
+use doplan::state::{Feature, Phase, ProjectState};
+use git2::build::CheckoutBuilder;
+use git2::{Repository, RepositoryInitOptions, Signature};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+mod common;
+use common::TestEnv;
+
+/// A planned project in a git repository whose HEAD is the unborn `main`
+async fn setup() -> Result<(TestEnv, Repository)> {
+    let env = TestEnv::project(Some(&test_state()))?;
+    let repo = Repository::init_opts(env.root(), RepositoryInitOptions::new().initial_head("main"))?;
+    let mut git_config = repo.config()?;
+    git_config.set_str("user.name", "Ada Lovelace")?;
+    git_config.set_str("user.email", "ada@example.com")?;
+
+    fs::write(env.path("README.md"), "# Test Project\n")?;
+    commands::plan::execute(vec![]).await?;
+    Ok((env, repo))
+}
+
+fn test_state() -> ProjectState {
+    ProjectState {
+        project_name: Some("Test Project".to_string()),
+        idea: Some("Share family recipes".to_string()),
+        features: Some(vec![Feature {
+            id: "F-0001".to_string(),
+            name: "User Auth".to_string(),
+            description: "Login and signup".to_string(),
+            priority: "high".to_string(),
+            ..Default::default()
+        }]),
+        phases: Some(vec![Phase {
+            id: "P-01".to_string(),
+            name: "Foundation".to_string(),
+            description: "First phase".to_string(),
+            features: vec!["F-0001".to_string()],
+        }]),
+        ..Default::default()
+    }
+}
+
+/// Commit `file` on `branch` without touching HEAD
+fn commit_file(repo: &Repository, branch: &str, file: &str) -> Result<git2::Oid> {
+    let signature = Signature::now("Someone Else", "else@example.com")?;
+    let blob = repo.blob(file.as_bytes())?;
+    let mut builder = repo.treebuilder(None)?;
+    builder.insert(file, blob, 0o100644)?;
+    let tree = repo.find_tree(builder.write()?)?;
+    let parent = repo.find_reference(branch).ok().and_then(|r| r.peel_to_commit().ok());
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+    Ok(repo.commit(Some(branch), &signature, &signature, file, &tree, &parents)?)
+}
+
+#[tokio::test]
+async fn test_implement_in_empty_repository() -> Result<()> {
+    let (_env, repo) = setup().await?;
+    commands::implement::execute(vec!["F-0001".to_string()]).await?;
+
+    // The bootstrap commit lands on the repository's default branch, not master
+    assert!(repo.find_branch("master", git2::BranchType::Local).is_err());
+    let main = repo.find_branch("main", git2::BranchType::Local)?.get().peel_to_commit()?;
+    assert_eq!(main.message(), Some("chore: initial commit"));
+
+    let head = repo.head()?;
+    assert_eq!(head.shorthand(), Some("feature/01-phase-01-user-auth"));
+    let commit = head.peel_to_commit()?;
+    assert_eq!(commit.message(), Some("docs: add planning docs for user auth"));
+    assert_eq!(commit.parent_id(0)?, main.id());
+    assert_eq!(commit.author().name(), Some("Ada Lovelace"));
+    assert_eq!(commit.committer().email(), Some("ada@example.com"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_implement_uses_template_and_origin_head() -> Result<()> {
+    let (env, repo) = setup().await?;
+    fs::write(
+        env.root().join("doplan.toml"),
+        "[git]\nbranch_template = \"{type}/{feature_key}-{feature_slug}\"\nbranch_type = \"feat\"\n",
+    )?;
+    commit_file(&repo, "refs/heads/main", "main.txt")?;
+    repo.checkout_head(Some(CheckoutBuilder::new().force()))?;
+    let develop = commit_file(&repo, "refs/remotes/origin/develop", "develop.txt")?;
+    repo.reference_symbolic("refs/remotes/origin/HEAD", "refs/remotes/origin/develop", true, "test")?;
+
+    commands::implement::execute(vec!["F-0001".to_string()]).await?;
+
+    let head = repo.head()?;
+    assert_eq!(head.shorthand(), Some("feat/F-0001-user-auth"));
+    assert_eq!(head.peel_to_commit()?.parent_id(0)?, develop);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_configured_base_branch_wins() -> Result<()> {
+    let (env, repo) = setup().await?;
+    fs::write(env.root().join("doplan.toml"), "[git]\nbase_branch = \"release\"\n")?;
+    commit_file(&repo, "refs/heads/main", "main.txt")?;
+    repo.checkout_head(Some(CheckoutBuilder::new().force()))?;
+    let release = commit_file(&repo, "refs/heads/release", "release.txt")?;
+    commit_file(&repo, "refs/remotes/origin/develop", "develop.txt")?;
+    repo.reference_symbolic("refs/remotes/origin/HEAD", "refs/remotes/origin/develop", true, "test")?;
+
+    commands::implement::execute(vec!["F-0001".to_string()]).await?;
+    assert_eq!(repo.head()?.peel_to_commit()?.parent_id(0)?, release);
+
+    fs::write(env.root().join("doplan.toml"), "[git]\nbase_branch = \"missing\"\nbranch_suffix = \"-2\"\n")?;
+    let error = commands::implement::execute(vec!["F-0001".to_string()]).await.unwrap_err();
+    assert!(format!("{:#}", error).contains("Base branch 'missing' not found"));
+    Ok(())
+}
+
+/// A repository with `README.md` and `app.txt` committed on `main`
+async fn committed_env() -> Result<(TestEnv, Repository)> {
+    let (env, repo) = setup().await?;
+    fs::write(env.root().join("app.txt"), "version 1\n")?;
+    let mut index = repo.index()?;
+    index.add_path(Path::new("README.md"))?;
+    index.add_path(Path::new("app.txt"))?;
+    index.write()?;
+    let tree = repo.find_tree(index.write_tree()?)?;
+    let signature = Signature::now("Someone Else", "else@example.com")?;
+    repo.commit(Some("HEAD"), &signature, &signature, "initial", &tree, &[])?;
+    drop(tree);
+    Ok((env, repo))
+}
+
+fn status_of(repo: &Repository, path: &str) -> Result<git2::Status> {
+    Ok(repo.status_file(Path::new(path))?)
+}
+
+#[tokio::test]
+async fn test_dirty_tree_continue_commits_only_planning_docs() -> Result<()> {
+    let (env, repo) = committed_env().await?;
+    fs::write(env.root().join("app.txt"), "version 2\n")?;
+    fs::write(env.root().join("notes.txt"), "unrelated work\n")?;
+    let mut index = repo.index()?;
+    index.add_path(Path::new("notes.txt"))?;
+    index.write()?;
+
+    commands::implement::execute(vec!["F-0001".into(), "--dirty".into(), "continue".into()]).await?;
+
+    let head = repo.head()?;
+    assert_eq!(head.shorthand(), Some("feature/01-phase-01-user-auth"));
+    let tree = head.peel_to_tree()?;
+    assert!(tree.get_path(Path::new("doplan/plan/01-foundation/01-user-auth/tasks.md")).is_ok());
+    assert!(tree.get_path(Path::new("notes.txt")).is_err());
+    let app = tree.get_path(Path::new("app.txt"))?.to_object(&repo)?.peel_to_blob()?;
+    assert_eq!(app.content(), b"version 1\n");
+
+    // The unrelated work is still there, staged and unstaged as before
+    assert_eq!(status_of(&repo, "notes.txt")?, git2::Status::INDEX_NEW);
+    assert_eq!(status_of(&repo, "app.txt")?, git2::Status::WT_MODIFIED);
+    assert_eq!(fs::read_to_string(env.root().join("app.txt"))?, "version 2\n");
+    assert!(status_of(&repo, "doplan/plan/01-foundation/01-user-auth/plan.md")?.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_dirty_tree_abort_and_stash() -> Result<()> {
+    let (env, mut repo) = committed_env().await?;
+    fs::write(env.root().join("app.txt"), "version 2\n")?;
+
+    commands::implement::execute(vec!["F-0001".into(), "--dirty".into(), "abort".into()]).await?;
+    assert_eq!(repo.head()?.shorthand(), Some("main"));
+    assert!(repo.find_branch("feature/01-phase-01-user-auth", git2::BranchType::Local).is_err());
+    assert_eq!(fs::read_to_string(env.root().join("app.txt"))?, "version 2\n");
+
+    let error = commands::implement::execute(vec!["F-0001".into(), "--dirty".into(), "ignore".into()]).await.unwrap_err();
+    assert!(error.to_string().contains("Unknown --dirty value 'ignore'"));
+
+    commands::implement::execute(vec!["--dirty".into(), "stash".into(), "F-0001".into()]).await?;
+    assert_eq!(repo.head()?.shorthand(), Some("feature/01-phase-01-user-auth"));
+    assert_eq!(fs::read_to_string(env.root().join("app.txt"))?, "version 1\n");
+    let mut stashes = Vec::new();
+    repo.stash_foreach(|_, message, _| {
+        stashes.push(message.to_string());
+        true
+    })?;
+    assert_eq!(stashes.len(), 1);
+    assert!(stashes[0].contains("doplan: before implementing feature/01-phase-01-user-auth"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_conflicting_changes_block_checkout() -> Result<()> {
+    let (env, repo) = committed_env().await?;
+    fs::write(env.root().join("doplan.toml"), "[git]\nbase_branch = \"release\"\n")?;
+    let main = repo.head()?.peel_to_commit()?;
+    let signature = Signature::now("Someone Else", "else@example.com")?;
+    let mut builder = repo.treebuilder(Some(&main.tree()?))?;
+    builder.insert("app.txt", repo.blob(b"release version\n")?, 0o100644)?;
+    let tree = repo.find_tree(builder.write()?)?;
+    repo.commit(Some("refs/heads/release"), &signature, &signature, "release", &tree, &[&main])?;
+    fs::write(env.root().join("app.txt"), "local edit\n")?;
+
+    let error = commands::implement::execute(vec!["F-0001".into(), "--dirty".into(), "continue".into()])
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("uncommitted changes conflict"));
+    assert_eq!(repo.head()?.shorthand(), Some("main"));
+    assert!(repo.find_branch("feature/01-phase-01-user-auth", git2::BranchType::Local).is_err());
+    assert_eq!(fs::read_to_string(env.root().join("app.txt"))?, "local edit\n");
+    Ok(())
+}
+
+/// Commit `file` with `content` on the branch checked out in `repo`'s working tree
+fn commit_in_worktree(repo: &Repository, file: &str, content: &str) -> Result<git2::Oid> {
+    fs::write(repo.workdir().unwrap().join(file), content)?;
+    let mut index = repo.index()?;
+    index.add_path(Path::new(file))?;
+    index.write()?;
+    let tree = repo.find_tree(index.write_tree()?)?;
+    let parent = repo.head()?.peel_to_commit()?;
+    let signature = Signature::now("Ada Lovelace", "ada@example.com")?;
+    Ok(repo.commit(Some("HEAD"), &signature, &signature, file, &tree, &[&parent])?)
+}
+
+const WORKTREE: &str = ".doplan/worktrees/feature-01-phase-01-user-auth";
+
+#[tokio::test]
+async fn test_worktree_lifecycle() -> Result<()> {
+    let (env, repo) = committed_env().await?;
+    fs::write(env.root().join("app.txt"), "local edit\n")?;
+
+    // The main working tree keeps its branch and its uncommitted changes
+    commands::implement::execute(vec!["F-0001".into(), "--worktree".into()]).await?;
+    assert_eq!(repo.head()?.shorthand(), Some("main"));
+    assert_eq!(fs::read_to_string(env.root().join("app.txt"))?, "local edit\n");
+    assert_eq!(status_of(&repo, "doplan/plan/01-foundation/01-user-auth/plan.md")?, git2::Status::WT_NEW);
+
+    let worktree_path = env.root().join(WORKTREE);
+    assert!(worktree_path.join("doplan/plan/01-foundation/01-user-auth/tasks.md").exists());
+    let worktree = Repository::open(&worktree_path)?;
+    assert_eq!(worktree.head()?.shorthand(), Some("feature/01-phase-01-user-auth"));
+
+    let state = ProjectState::load()?;
+    assert_eq!(state.worktrees.len(), 1);
+    assert_eq!(state.worktrees[0].feature_id, "F-0001");
+    assert_eq!(state.worktrees[0].branch, "feature/01-phase-01-user-auth");
+
+    // Running it again reuses the worktree
+    commands::implement::execute(vec!["F-0001".into(), "--worktree".into()]).await?;
+    assert_eq!(ProjectState::load()?.worktrees.len(), 1);
+    commands::implement::execute(vec!["list".into()]).await?;
+
+    // Uncommitted work in the worktree blocks finish
+    commit_in_worktree(&worktree, "login.txt", "login form\n")?;
+    fs::write(worktree_path.join("login.txt"), "unsaved\n")?;
+    let error = commands::implement::execute(vec!["finish".into(), "F-0001".into(), "--merge".into()])
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("has uncommitted changes"));
+    fs::write(worktree_path.join("login.txt"), "login form\n")?;
+
+    fs::write(env.root().join("app.txt"), "version 1\n")?;
+    commands::implement::execute(vec!["finish".into(), "F-0001".into(), "--merge".into()]).await?;
+    let main = repo.head()?.peel_to_commit()?;
+    assert_eq!(main.id(), repo.find_branch("feature/01-phase-01-user-auth", git2::BranchType::Local)?.get().peel_to_commit()?.id());
+    assert_eq!(fs::read_to_string(env.root().join("login.txt"))?, "login form\n");
+    assert!(!worktree_path.exists());
+    assert!(repo.find_worktree("feature-01-phase-01-user-auth").is_err());
+    assert!(ProjectState::load()?.worktrees.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_finish_merges_diverged_branch() -> Result<()> {
+    let (env, repo) = committed_env().await?;
+    let trees = TempDir::new()?;
+    fs::write(env.root().join("doplan.toml"), format!("[git]\nworktree_dir = {:?}\n", trees.path()))?;
+    commands::implement::execute(vec!["F-0001".into(), "--worktree".into()]).await?;
+
+    let worktree_path = trees.path().join("feature-01-phase-01-user-auth");
+    let feature_tip = commit_in_worktree(&Repository::open(&worktree_path)?, "login.txt", "login form\n")?;
+    let main_tip = commit_in_worktree(&repo, "other.txt", "meanwhile on main\n")?;
+
+    commands::implement::execute(vec!["finish".into(), "feature/01-phase-01-user-auth".into(), "--merge".into()]).await?;
+    let merge = repo.head()?.peel_to_commit()?;
+    assert_eq!(merge.parent_ids().collect::<Vec<_>>(), vec![main_tip, feature_tip]);
+    assert_eq!(merge.message(), Some("Merge branch 'feature/01-phase-01-user-auth' into main"));
+    assert!(env.root().join("login.txt").exists());
+    assert!(!worktree_path.exists());
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use anyhow::Result;
+use doplan::commands;
+use doplan::commands::install::{self, Change};
+use doplan::integration::ide::Ide;
+use doplan::state::ProjectState;
+use std::fs;
+
+mod common;
+use common::TestEnv;
+
+#[tokio::test]
+async fn test_install_bootstraps_project() -> Result<()> {
+    let env = TestEnv::empty()?;
+    commands::install::execute(vec!["--ide".to_string(), "claude".to_string()]).await?;
+
+    assert!(env.path("doplan").is_dir());
+    assert!(env.path(".claude/commands/discuss.md").exists());
+    assert!(env.path(".claude/commands/progress.md").exists());
+    assert!(!env.path(".cursor").exists());
+    assert_eq!(fs::read_to_string(env.path(".gitignore"))?, "# DoPlan\n.doplan/\n");
+
+    let state = ProjectState::load()?;
+    let info = state.install.expect("install is recorded in state");
+    assert_eq!(info.ides, vec!["claude".to_string()]);
+    assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+    assert!(state.project_name.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_reinstall_is_idempotent() -> Result<()> {
+    let env = TestEnv::empty()?;
+    fs::write(env.path(".gitignore"), "target/")?;
+    let ides = vec![Ide::Cursor, Ide::Gemini];
+
+    let mut state = ProjectState::load()?;
+    let first = install::install(&env.path(""), &mut state, &ides)?;
+    assert!(first.iter().all(|c| c.change != Change::Unchanged));
+    let gitignore = first.iter().find(|c| c.path.ends_with(".gitignore")).unwrap();
+    assert_eq!(gitignore.change, Change::Updated);
+    assert_eq!(fs::read_to_string(env.path(".gitignore"))?, "target/\n\n# DoPlan\n.doplan/\n");
+    let state_json = fs::read_to_string(env.path(".doplan/state.json"))?;
+
+    let mut state = ProjectState::load()?;
+    let second = install::install(&env.path(""), &mut state, &ides)?;
+    assert_eq!(second.len(), first.len());
+    assert!(second.iter().all(|c| c.change == Change::Unchanged), "{:?}", second);
+    assert_eq!(fs::read_to_string(env.path(".gitignore"))?, "target/\n\n# DoPlan\n.doplan/\n");
+    assert_eq!(fs::read_to_string(env.path(".doplan/state.json"))?, state_json);
+
+    // Re-running without --ide reuses the recorded IDEs
+    commands::install::execute(vec![]).await?;
+    assert_eq!(ProjectState::load()?.install.unwrap().ides, vec!["cursor".to_string(), "gemini".to_string()]);
+
+    // Edited command files are restored and reported
+    fs::write(env.path(".gemini/commands/next.toml"), "edited")?;
+    let mut state = ProjectState::load()?;
+    let third = install::install(&env.path(""), &mut state, &ides)?;
+    let updated: Vec<_> = third.iter().filter(|c| c.change == Change::Updated).collect();
+    assert_eq!(updated.len(), 1);
+    assert!(updated[0].path.ends_with("next.toml"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_install_from_subdirectory_uses_repository_root() -> Result<()> {
+    let env = TestEnv::empty()?;
+    fs::create_dir(env.path(".git"))?;
+    fs::create_dir_all(env.path("src/bin"))?;
+    std::env::set_current_dir(env.path("src/bin"))?;
+
+    commands::install::execute(vec!["--ide=cursor".to_string()]).await?;
+
+    assert!(env.path(".doplan/state.json").exists());
+    assert!(env.path("doplan").is_dir());
+    assert!(!env.path("src/bin/.doplan").exists());
+
+    let err = commands::install::execute(vec!["--ide".to_string(), "emacs".to_string()]).await.unwrap_err();
+    assert!(err.to_string().contains("Unsupported IDE 'emacs'"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_install_follows_configured_state_dir() -> Result<()> {
+    let env = TestEnv::empty()?;
+    fs::create_dir(env.path(".git"))?;
+    fs::create_dir_all(env.path("src/bin"))?;
+    std::env::set_var("DOPLAN_PATHS_STATE_DIR", ".plan-state");
+    std::env::set_current_dir(env.path("src/bin"))?;
+
+    let result = commands::install::execute(vec!["--ide=cursor".to_string()]).await;
+    let gitignore = fs::read_to_string(env.path(".gitignore"));
+    // An existing state directory marks the root, even inside another checkout
+    fs::create_dir_all(env.path("src/.plan-state"))?;
+    let root = doplan::utils::find_project_root(&env.path("src/bin"));
+    std::env::remove_var("DOPLAN_PATHS_STATE_DIR");
+    result?;
+
+    assert_eq!(gitignore?, "# DoPlan\n.plan-state/\n");
+    assert!(env.path(".plan-state/state.json").exists());
+    assert!(!env.path(".doplan").exists());
+    assert_eq!(root, env.path("src"));
+
+    Ok(())
+}
@@ -0,0 +1,181 @@
+use anyhow::Result;
+use doplan::commands;
+use doplan::config;
+use doplan::integration::llm::{self, OpenAiProvider};
+use doplan::state::{Feature, Phase, ProjectState};
+use mockito::Matcher;
+use serde_json::{json, Value};
+use std::fs;
+
+mod common;
+use common::TestEnv;
+
+fn test_state() -> ProjectState {
+    ProjectState {
+        project_name: Some("Test Project".to_string()),
+        idea: Some("Share family recipes".to_string()),
+        features: Some(vec![Feature {
+            id: "F-0001".to_string(),
+            name: "User Auth".to_string(),
+            description: "Login and signup".to_string(),
+            priority: "high".to_string(),
+            ..Default::default()
+        }]),
+        phases: Some(vec![Phase {
+            id: "P-01".to_string(),
+            name: "Foundation".to_string(),
+            description: "First phase".to_string(),
+            features: vec!["F-0001".to_string()],
+        }]),
+        ..Default::default()
+    }
+}
+
+/// A chat completions response whose message is `content`
+fn completion(content: &str) -> String {
+    json!({
+        "choices": [{ "index": 0, "message": { "role": "assistant", "content": content } }]
+    })
+    .to_string()
+}
+
+fn tasks_reply() -> Value {
+    json!({
+        "tasks": [
+            {
+                "title": "Create user table",
+                "description": "Migration for users",
+                "priority": "High",
+                "estimate": "2 hours",
+                "acceptance_criteria": ["Migration runs"]
+            },
+            {
+                "title": "Login endpoint",
+                "description": "POST /login",
+                "priority": "Medium",
+                "estimate": "1 day",
+                "depends_on": ["T1"]
+            }
+        ]
+    })
+}
+
+#[tokio::test]
+async fn test_break_down_tasks() -> Result<()> {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server.mock("POST", "/v1/chat/completions")
+        .match_header("authorization", "Bearer secret")
+        .match_body(Matcher::PartialJson(json!({ "model": "llama3" })))
+        .with_body(completion(&format!("```json\n{}\n```", tasks_reply())))
+        .create_async()
+        .await;
+
+    let provider = OpenAiProvider::new(&format!("{}/v1/", server.url()), "llama3", Some("secret".to_string()));
+    let state = test_state();
+    let feature = state.feature_by_id("F-0001").unwrap();
+    let tasks = llm::break_down_tasks(&provider, &state, feature).await?;
+    mock.assert_async().await;
+
+    assert_eq!(tasks.len(), 2);
+    assert_eq!((tasks[0].id.as_str(), tasks[0].title.as_str()), ("T1", "Create user table"));
+    assert_eq!(tasks[0].acceptance_criteria[0].text, "Migration runs");
+    assert_eq!((tasks[1].id.as_str(), tasks[1].estimate.as_deref()), ("T2", Some("1 day")));
+    assert_eq!(tasks[1].depends_on, vec!["T1".to_string()]);
+    Ok(())
+}
+
+/// The error for a task breakdown the model answered with `reply`
+async fn rejection(reply: Value) -> String {
+    let mut server = mockito::Server::new_async().await;
+    server.mock("POST", "/chat/completions")
+        .with_body(completion(&reply.to_string()))
+        .create_async()
+        .await;
+    let provider = OpenAiProvider::new(&server.url(), "llama3", None);
+    let state = test_state();
+    let feature = state.feature_by_id("F-0001").unwrap();
+    let result = llm::break_down_tasks(&provider, &state, feature).await;
+    format!("{:#}", result.unwrap_err())
+}
+
+#[tokio::test]
+async fn test_invalid_replies_are_rejected() {
+    let mut reply = tasks_reply();
+    reply["tasks"][1].as_object_mut().unwrap().remove("estimate");
+    assert_eq!(
+        rejection(reply).await,
+        "The model's task_breakdown reply failed validation: $.tasks[1]: missing required property 'estimate'"
+    );
+
+    let mut reply = tasks_reply();
+    reply["tasks"][0]["priority"] = json!("Urgent");
+    assert!(rejection(reply).await.contains("$.tasks[0].priority: \"Urgent\" is not one of"));
+
+    let mut reply = tasks_reply();
+    reply["tasks"][1]["depends_on"] = json!(["T2"]);
+    assert!(rejection(reply).await.ends_with("$.tasks[1].depends_on: 'T2' is not an earlier task"));
+
+    let mut reply = tasks_reply();
+    reply["tasks"][0]["estimate"] = json!("soon");
+    assert!(rejection(reply).await.ends_with("$.tasks[0].estimate: 'soon' is not an estimate"));
+
+    assert!(rejection(json!("not an object")).await.ends_with("$: expected an object"));
+}
+
+#[tokio::test]
+async fn test_plan_drafts_stories_and_tasks_with_provider() -> Result<()> {
+    let env = TestEnv::new(&test_state())?;
+    let mut server = mockito::Server::new_async().await;
+    let stories = server.mock("POST", "/chat/completions")
+        .match_body(Matcher::Regex("Write the user stories".to_string()))
+        .with_body(completion(&json!({
+            "user_stories": [{ "role": "a new visitor", "goal": "to sign up with my email", "benefit": "I can save recipes" }]
+        }).to_string()))
+        .create_async()
+        .await;
+    let tasks = server.mock("POST", "/chat/completions")
+        .match_body(Matcher::Regex("Break the feature".to_string()))
+        .with_body(completion(&tasks_reply().to_string()))
+        .create_async()
+        .await;
+
+    std::env::set_var(config::env_var("llm.base_url"), server.url());
+    commands::plan::execute(vec![]).await?;
+    stories.assert_async().await;
+    tasks.assert_async().await;
+
+    let feature_dir = env.path("doplan/plan/01-foundation/01-user-auth");
+    let plan = fs::read_to_string(feature_dir.join("plan.md"))?;
+    assert!(plan.contains("- As a new visitor, I want to sign up with my email so that I can save recipes"));
+    let tasks_md = fs::read_to_string(feature_dir.join("tasks.md"))?;
+    assert!(tasks_md.contains("Create user table"));
+    assert!(tasks_md.contains("Login endpoint"));
+
+    let state = ProjectState::load()?;
+    let feature = state.feature_by_id("F-0001").unwrap();
+    assert_eq!(feature.user_stories.len(), 1);
+    assert_eq!(feature.tasks.len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_plan_falls_back_when_provider_fails() -> Result<()> {
+    let env = TestEnv::new(&test_state())?;
+    let mut server = mockito::Server::new_async().await;
+    let failing = server.mock("POST", "/chat/completions")
+        .with_status(500)
+        .with_body(r#"{"error": {"message": "model not loaded"}}"#)
+        .expect(2)
+        .create_async()
+        .await;
+
+    std::env::set_var(config::env_var("llm.base_url"), server.url());
+    commands::plan::execute(vec![]).await?;
+    failing.assert_async().await;
+
+    // The built-in starter tasks are used instead
+    let tasks_md = fs::read_to_string(env.path("doplan/plan/01-foundation/01-user-auth/tasks.md"))?;
+    assert!(tasks_md.contains("Setup"));
+    assert!(ProjectState::load()?.feature_by_id("F-0001").unwrap().user_stories.is_empty());
+    Ok(())
+}
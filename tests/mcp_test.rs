@@ -0,0 +1,181 @@
+use anyhow::Result;
+use doplan::commands;
+use doplan::integration::mcp;
+use doplan::state::{Feature, Phase, ProjectState, TaskStatus};
+use serde_json::{json, Value};
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+mod common;
+use common::TestEnv;
+
+fn test_state() -> ProjectState {
+    ProjectState {
+        project_name: Some("Test Project".to_string()),
+        features: Some(vec![Feature {
+            id: "F-0001".to_string(),
+            name: "User Auth".to_string(),
+            description: "User Auth feature".to_string(),
+            priority: "high".to_string(),
+            ..Default::default()
+        }]),
+        phases: Some(vec![Phase {
+            id: "P-01".to_string(),
+            name: "Foundation".to_string(),
+            description: "First phase".to_string(),
+            features: vec!["F-0001".to_string()],
+        }]),
+        ..Default::default()
+    }
+}
+
+/// Scripted client talking to `doplan mcp` over its stdio
+struct McpClient {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    next_id: u64,
+}
+
+impl McpClient {
+    fn spawn(env: &TestEnv) -> Result<Self> {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_doplan"))
+            .arg("mcp")
+            .current_dir(env.path(""))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let stdin = child.stdin.take().unwrap();
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+        Ok(McpClient { child, stdin, stdout, next_id: 1 })
+    }
+
+    fn send(&mut self, message: &Value) -> Result<()> {
+        writeln!(self.stdin, "{}", message)?;
+        self.stdin.flush()?;
+        Ok(())
+    }
+
+    fn request(&mut self, method: &str, params: Value) -> Result<Value> {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.send(&json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }))?;
+        let mut line = String::new();
+        self.stdout.read_line(&mut line)?;
+        let response: Value = serde_json::from_str(&line)?;
+        assert_eq!(response["id"], id);
+        Ok(response)
+    }
+
+    /// Call a tool and parse the JSON text it returns
+    fn call(&mut self, tool: &str, arguments: Value) -> Result<Value> {
+        let response = self.request("tools/call", json!({ "name": tool, "arguments": arguments }))?;
+        let result = &response["result"];
+        assert_eq!(result["isError"], false, "{} failed: {}", tool, result);
+        Ok(serde_json::from_str(result["content"][0]["text"].as_str().unwrap())?)
+    }
+
+    fn finish(mut self) -> Result<()> {
+        drop(self.stdin);
+        assert!(self.child.wait()?.success());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_scripted_session_over_stdio() -> Result<()> {
+    let env = TestEnv::new(&test_state())?;
+    commands::plan::execute(vec![]).await?;
+
+    let mut client = McpClient::spawn(&env)?;
+    let init = client.request("initialize", json!({
+        "protocolVersion": "2024-11-05",
+        "capabilities": {},
+        "clientInfo": { "name": "test", "version": "0" }
+    }))?;
+    assert_eq!(init["result"]["protocolVersion"], "2024-11-05");
+    assert_eq!(init["result"]["serverInfo"]["name"], "doplan");
+    client.send(&json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }))?;
+
+    let tools = client.request("tools/list", json!({}))?;
+    let names: Vec<&str> = tools["result"]["tools"].as_array().unwrap().iter().map(|t| t["name"].as_str().unwrap()).collect();
+    assert_eq!(names, vec!["next_task", "set_task_status", "add_feature", "update_progress", "read_plan"]);
+
+    let next = client.call("next_task", json!({}))?;
+    assert_eq!(next["status"], "task");
+    assert_eq!(next["task"], "F-0001/T1");
+
+    let updated = client.call("set_task_status", json!({ "task": "F-0001/T1", "status": "completed" }))?;
+    assert_eq!(updated["status"], "completed");
+    let tasks = fs::read_to_string(env.path("doplan/plan/01-foundation/01-user-auth/tasks.md"))?;
+    assert_eq!(tasks.matches("[x] Completed").count(), 1);
+    assert_eq!(client.call("next_task", json!({}))?["task"], "F-0001/T2");
+
+    let progress = client.call("update_progress", json!({}))?;
+    assert!((progress["overall_progress"].as_f64().unwrap() - 100.0 / 3.0).abs() < 1e-9);
+    assert!(env.path(".doplan/dashboard.json").exists());
+
+    let resources = client.request("resources/list", json!({}))?;
+    let uris: Vec<&str> = resources["result"]["resources"].as_array().unwrap().iter().map(|r| r["uri"].as_str().unwrap()).collect();
+    assert!(uris.contains(&"doplan://prd"));
+    assert!(uris.contains(&"doplan://dashboard"));
+    assert!(uris.contains(&"doplan://plan/01-foundation/01-user-auth/tasks.md"));
+
+    let read = client.request("resources/read", json!({ "uri": "doplan://plan/01-foundation/01-user-auth/tasks.md" }))?;
+    assert_eq!(read["result"]["contents"][0]["text"], tasks);
+    let escape = client.request("resources/read", json!({ "uri": "doplan://plan/../PRD.md" }))?;
+    assert_eq!(escape["error"]["code"], -32602);
+
+    let urgent = client.request("tools/call", json!({ "name": "add_feature", "arguments": { "name": "Billing", "priority": "urgent" } }))?;
+    assert_eq!(urgent["result"]["isError"], true);
+    assert!(urgent["result"]["content"][0]["text"].as_str().unwrap().contains("Unknown priority 'urgent' (expected high, medium, low)"));
+    let added = client.call("add_feature", json!({ "name": "Billing", "priority": "low", "depends_on": ["User Auth"], "phase": "P-01" }))?;
+    assert_eq!(added["id"], "F-0002");
+    let plan = client.call("read_plan", json!({}))?;
+    let features = plan["phases"][0]["features"].as_array().unwrap();
+    assert_eq!(features.len(), 2);
+    assert_eq!(features[0]["tasks"][0]["status"], "completed");
+    assert_eq!(features[1]["depends_on"], json!(["F-0001"]));
+
+    let failed = client.request("tools/call", json!({ "name": "set_task_status", "arguments": { "task": "T1", "status": "done" } }))?;
+    assert_eq!(failed["result"]["isError"], true);
+    let unknown = client.request("tasks/list", json!({}))?;
+    assert_eq!(unknown["error"]["code"], -32601);
+
+    client.finish()?;
+
+    let state = ProjectState::load()?;
+    assert_eq!(state.features.as_ref().unwrap()[0].tasks[0].status, TaskStatus::Completed);
+    assert_eq!(state.phases.unwrap()[0].features, vec!["F-0001".to_string(), "F-0002".to_string()]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_serve_skips_notifications_and_reports_parse_errors() -> Result<()> {
+    let env = TestEnv::new(&test_state())?;
+    commands::plan::execute(vec![]).await?;
+
+    let script = [
+        r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#,
+        "",
+        "not json",
+        r#"{"jsonrpc":"2.0","id":"a","method":"tools/call","params":{"name":"read_plan","arguments":{"feature":"User Auth"}}}"#,
+    ]
+    .join("\n");
+    let mut output = Vec::new();
+    mcp::serve(script.as_bytes(), &mut output)?;
+
+    let responses: Vec<Value> = String::from_utf8(output)?
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()?;
+    assert_eq!(responses.len(), 2);
+    assert_eq!(responses[0]["error"]["code"], -32700);
+    assert_eq!(responses[1]["id"], "a");
+    let feature: Value = serde_json::from_str(responses[1]["result"]["content"][0]["text"].as_str().unwrap())?;
+    assert_eq!(feature["tasks"], fs::read_to_string(env.path("doplan/plan/01-foundation/01-user-auth/tasks.md"))?);
+
+    Ok(())
+}
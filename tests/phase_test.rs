@@ -16,10 +16,12 @@ fn create_test_state() -> ProjectState {
                 name: "Phase 1".to_string(),
                 description: "First phase".to_string(),
                 features: vec![],
+                ..Default::default()
             },
         ]),
         improvements: None,
         notes: None,
+        ..Default::default()
     }
 }
 
@@ -77,6 +79,7 @@ async fn test_phase_list_empty() -> Result<()> {
         phases: None,
         improvements: None,
         notes: None,
+        ..Default::default()
     };
     let state_path = temp_dir.path().join(".doplan").join("state.json");
     let state_json = serde_json::to_string_pretty(&empty_state)?;
@@ -147,6 +150,7 @@ async fn test_phase_incomplete_state() -> Result<()> {
         phases: None,
         improvements: None,
         notes: None,
+        ..Default::default()
     };
     let state_path = temp_dir.path().join(".doplan").join("state.json");
     let state_json = serde_json::to_string_pretty(&incomplete_state)?;
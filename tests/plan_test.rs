@@ -15,11 +15,13 @@ fn create_test_state() -> ProjectState {
                 name: "Feature 1".to_string(),
                 description: "First feature".to_string(),
                 priority: "high".to_string(),
+                ..Default::default()
             },
             Feature {
                 name: "Feature 2".to_string(),
                 description: "Second feature".to_string(),
                 priority: "medium".to_string(),
+                ..Default::default()
             },
         ]),
         phases: Some(vec![
@@ -27,10 +29,12 @@ fn create_test_state() -> ProjectState {
                 name: "Phase 1".to_string(),
                 description: "First phase".to_string(),
                 features: vec!["Feature 1".to_string(), "Feature 2".to_string()],
+                ..Default::default()
             },
         ]),
         improvements: None,
         notes: None,
+        ..Default::default()
     }
 }
 
@@ -223,11 +227,13 @@ async fn test_plan_command_multiple_phases() -> Result<()> {
             name: "Phase 1".to_string(),
             description: "First phase".to_string(),
             features: vec!["Feature 1".to_string()],
+            ..Default::default()
         },
         Phase {
             name: "Phase 2".to_string(),
             description: "Second phase".to_string(),
             features: vec!["Feature 2".to_string()],
+            ..Default::default()
         },
     ]);
     let state_path = temp_dir.path().join(".doplan").join("state.json");
@@ -253,3 +259,40 @@ async fn test_plan_command_multiple_phases() -> Result<()> {
     Ok(())
 }
 
+
+#[tokio::test]
+async fn test_plan_seeds_tasks_from_task_rules() -> Result<()> {
+    let (temp_dir, _) = setup_test_env()?;
+    let mut state = ProjectState::load()?;
+    state.features.as_mut().unwrap()[0].description = "Sign up and login with email".to_string();
+    state.save()?;
+
+    // The project's own catalog replaces the built-in rules
+    let templates_dir = temp_dir.path().join("doplan").join("templates");
+    fs::create_dir_all(&templates_dir)?;
+    fs::write(
+        templates_dir.join("task-rules.yaml"),
+        "rules:\n  - name: auth\n    keywords: [login]\n    tasks:\n      - title: Session handling\n        estimate: 4h\n      - title: Lockout\n        priority: low\nfallback:\n  - title: Build it\n    estimate: 1 day\nclosing:\n  - title: Ship it\n",
+    )?;
+
+    commands::plan::execute(vec![]).await?;
+
+    let phase_dir = temp_dir.path().join("doplan").join("plan").join("01-phase-1");
+    let tasks = fs::read_to_string(phase_dir.join("01-feature-1").join("tasks.md"))?;
+    assert!(tasks.contains("Session handling"));
+    assert!(tasks.contains("Lockout"));
+    assert!(!tasks.contains("Build it"));
+    let tasks = fs::read_to_string(phase_dir.join("02-feature-2").join("tasks.md"))?;
+    assert!(tasks.contains("Build it"));
+
+    // Progress totals follow the tasks actually planned
+    let progress: serde_json::Value = serde_json::from_str(&fs::read_to_string(phase_dir.join("01-feature-1").join("progress.json"))?)?;
+    assert_eq!(progress["tasks"]["total"], 3);
+    let state = ProjectState::load()?;
+    let feature = &state.features.as_ref().unwrap()[0];
+    assert_eq!(feature.tasks[1].depends_on, vec!["T1".to_string()]);
+    assert_eq!(feature.tasks[2].depends_on, vec!["T2".to_string()]);
+
+    cleanup_test_env(temp_dir);
+    Ok(())
+}
@@ -0,0 +1,149 @@
+use anyhow::Result;
+use doplan::commands;
+use doplan::integration::github::SyncMap;
+use doplan::state::{Feature, Phase, ProjectState, PullRequest, TaskStatus};
+use doplan::utils::plan_dirs;
+use git2::{Repository, RepositoryInitOptions, Signature};
+use mockito::Matcher;
+use serde_json::json;
+use std::fs;
+use std::path::Path;
+
+mod common;
+use common::TestEnv;
+
+const BRANCH: &str = "feature/01-phase-01-user-auth";
+
+/// A planned project on the feature branch `/implement` created for F-0001
+async fn setup() -> Result<TestEnv> {
+    let env = TestEnv::project(Some(&test_state()))?;
+    let repo = Repository::init_opts(env.root(), RepositoryInitOptions::new().initial_head("main"))?;
+    let mut git_config = repo.config()?;
+    git_config.set_str("user.name", "Ada Lovelace")?;
+    git_config.set_str("user.email", "ada@example.com")?;
+
+    fs::write(env.path("README.md"), "# Test Project\n")?;
+    fs::write(env.path("doplan.toml"), "[git]\nbase_branch = \"main\"\n")?;
+    commands::plan::execute(vec![]).await?;
+    commit_readme(&repo)?;
+    commands::implement::execute(vec!["F-0001".to_string()]).await?;
+    Ok(env)
+}
+
+fn test_state() -> ProjectState {
+    ProjectState {
+        project_name: Some("Test Project".to_string()),
+        features: Some(vec![Feature {
+            id: "F-0001".to_string(),
+            name: "User Auth".to_string(),
+            description: "Login and signup".to_string(),
+            priority: "high".to_string(),
+            ..Default::default()
+        }]),
+        phases: Some(vec![Phase {
+            id: "P-01".to_string(),
+            name: "Foundation".to_string(),
+            description: "First phase".to_string(),
+            features: vec!["F-0001".to_string()],
+        }]),
+        ..Default::default()
+    }
+}
+
+fn commit_readme(repo: &Repository) -> Result<()> {
+    let mut index = repo.index()?;
+    index.add_path(Path::new("README.md"))?;
+    index.write()?;
+    let tree = repo.find_tree(index.write_tree()?)?;
+    let signature = Signature::now("Ada Lovelace", "ada@example.com")?;
+    repo.commit(Some("HEAD"), &signature, &signature, "initial", &tree, &[])?;
+    Ok(())
+}
+
+fn args(server: &mockito::Server, extra: &[&str]) -> Vec<String> {
+    let mut args = vec!["F-0001".to_string(), "--repo".into(), "acme/app".into(), "--api-url".into(), server.url()];
+    args.extend(extra.iter().map(|a| a.to_string()));
+    args
+}
+
+#[tokio::test]
+async fn test_pr_opens_then_updates() -> Result<()> {
+    let env = setup().await?;
+    std::env::set_var("GITHUB_TOKEN", "secret");
+    let mut state = ProjectState::load()?;
+    plan_dirs::set_task_status(&env.root().join("doplan/plan"), &mut state, "F-0001", "T1", TaskStatus::Completed)?;
+    let map = SyncMap { issues: [("F-0001".to_string(), 17)].into(), ..Default::default() };
+    map.save(&SyncMap::path(&env.root().join(".doplan")))?;
+
+    let mut server = mockito::Server::new_async().await;
+    let head_query = Matcher::AllOf(vec![
+        Matcher::UrlEncoded("state".into(), "open".into()),
+        Matcher::UrlEncoded("head".into(), format!("acme:{}", BRANCH)),
+    ]);
+    let none_open = server.mock("GET", "/repos/acme/app/pulls")
+        .match_query(head_query.clone())
+        .with_body("[]")
+        .create_async()
+        .await;
+    let created = server.mock("POST", "/repos/acme/app/pulls")
+        .match_header("authorization", "Bearer secret")
+        .match_body(Matcher::AllOf(vec![
+            Matcher::PartialJson(json!({ "title": "F-0001: User Auth", "head": BRANCH, "base": "main", "draft": true })),
+            Matcher::Regex(r"## Overview\\n\\nLogin and signup".to_string()),
+            Matcher::Regex(r"- \[ \] Feature implemented".to_string()),
+            Matcher::Regex(r"- \[x\] T1: ".to_string()),
+            Matcher::Regex("Closes #17".to_string()),
+        ]))
+        .with_status(201)
+        .with_body(r#"{"number": 5, "html_url": "https://github.com/acme/app/pull/5"}"#)
+        .create_async()
+        .await;
+
+    commands::pr::execute(args(&server, &["--draft"])).await?;
+    none_open.assert_async().await;
+    created.assert_async().await;
+    let state = ProjectState::load()?;
+    assert_eq!(
+        state.feature_by_id("F-0001").unwrap().pull_request,
+        Some(PullRequest { number: 5, url: "https://github.com/acme/app/pull/5".to_string() })
+    );
+
+    // Running again updates the open pull request
+    none_open.remove_async().await;
+    let one_open = server.mock("GET", "/repos/acme/app/pulls")
+        .match_query(head_query)
+        .with_body(r#"[{"number": 5, "html_url": "https://github.com/acme/app/pull/5"}]"#)
+        .create_async()
+        .await;
+    let updated = server.mock("PATCH", "/repos/acme/app/pulls/5")
+        .match_body(Matcher::PartialJson(json!({ "title": "F-0001: User Auth" })))
+        .with_body(r#"{"number": 5, "html_url": "https://github.com/acme/app/pull/5"}"#)
+        .create_async()
+        .await;
+    commands::pr::execute(args(&server, &[])).await?;
+    one_open.assert_async().await;
+    updated.assert_async().await;
+    std::env::remove_var("GITHUB_TOKEN");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_pr_needs_a_branch_and_a_base() -> Result<()> {
+    let env = setup().await?;
+    std::env::set_var("GITHUB_TOKEN", "secret");
+    let server = mockito::Server::new_async().await;
+
+    // HEAD is the feature branch itself, so there is nothing to merge into
+    fs::remove_file(env.root().join("doplan.toml"))?;
+    let error = commands::pr::execute(args(&server, &[])).await.unwrap_err();
+    assert!(error.to_string().contains("--base"));
+
+    fs::write(env.root().join("doplan.toml"), "[git]\nbranch_suffix = \"-2\"\n")?;
+    let error = commands::pr::execute(args(&server, &["--base", "main"])).await.unwrap_err();
+    assert!(error.to_string().contains(&format!("Branch '{}-2' does not exist", BRANCH)));
+
+    let error = commands::pr::execute(vec!["F-0099".to_string()]).await.unwrap_err();
+    assert!(error.to_string().contains("No feature 'F-0099'"));
+    std::env::remove_var("GITHUB_TOKEN");
+    Ok(())
+}
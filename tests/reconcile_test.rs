@@ -0,0 +1,141 @@
+use anyhow::Result;
+use doplan::commands;
+use doplan::state::{Feature, Phase, ProjectState};
+use std::fs;
+
+mod common;
+use common::TestEnv;
+
+impl TestEnv {
+    fn update_state(&self, update: impl FnOnce(&mut ProjectState)) -> Result<()> {
+        let mut state = ProjectState::load_from(&self.state_path())?;
+        update(&mut state);
+        state.save_to(&self.state_path())?;
+        Ok(())
+    }
+}
+
+fn feature(id: &str, name: &str) -> Feature {
+    Feature {
+        id: id.to_string(),
+        name: name.to_string(),
+        description: format!("{} feature", name),
+        priority: "high".to_string(),
+        ..Default::default()
+    }
+}
+
+fn one_feature_state() -> ProjectState {
+    ProjectState {
+        project_name: Some("Test Project".to_string()),
+        features: Some(vec![feature("F-0001", "User Auth")]),
+        phases: Some(vec![Phase {
+            id: "P-01".to_string(),
+            name: "Foundation".to_string(),
+            description: "First phase".to_string(),
+            features: vec!["F-0001".to_string()],
+        }]),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_replan_keeps_edited_files_and_checked_tasks() -> Result<()> {
+    let env = TestEnv::new(&one_feature_state())?;
+    commands::plan::execute(vec![]).await?;
+
+    let feature_dir = env.path("doplan/plan/01-foundation/01-user-auth");
+    fs::write(feature_dir.join("design.md"), "# Design\n\nHand-written notes\n")?;
+    let tasks = fs::read_to_string(feature_dir.join("tasks.md"))?;
+    fs::write(
+        feature_dir.join("tasks.md"),
+        tasks.replacen("[x] Not Started | [ ] In Progress | [ ] Completed", "[ ] Not Started | [ ] In Progress | [x] Completed", 1),
+    )?;
+
+    // Adding a feature and re-planning must not touch the edited files
+    env.update_state(|state| {
+        state.features.as_mut().unwrap().push(feature("F-0002", "Billing"));
+        state.phases.as_mut().unwrap()[0].features.push("F-0002".to_string());
+    })?;
+    commands::plan::execute(vec![]).await?;
+
+    assert_eq!(fs::read_to_string(feature_dir.join("design.md"))?, "# Design\n\nHand-written notes\n");
+    assert!(fs::read_to_string(feature_dir.join("tasks.md"))?.contains("[x] Completed"));
+    assert!(env.path("doplan/plan/01-foundation/02-billing/tasks.md").exists());
+
+    // The untouched phase plan picks up the new feature
+    let phase_plan = fs::read_to_string(env.path("doplan/plan/01-foundation/phase-plan.md"))?;
+    assert!(phase_plan.contains("- Billing"));
+
+    // Progress reflects the checked task instead of being reset
+    let phase_progress = fs::read_to_string(env.path("doplan/plan/01-foundation/phase-progress.json"))?;
+    assert!(phase_progress.contains("\"in_progress\": 0"));
+    assert!(!phase_progress.contains("\"progress\": 0.0"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_replan_force_overwrites_edited_files() -> Result<()> {
+    let env = TestEnv::new(&one_feature_state())?;
+    commands::plan::execute(vec![]).await?;
+
+    let design_path = env.path("doplan/plan/01-foundation/01-user-auth/design.md");
+    fs::write(&design_path, "# Design\n\nHand-written notes\n")?;
+
+    commands::plan::execute(vec!["--force".to_string()]).await?;
+
+    assert!(fs::read_to_string(&design_path)?.contains("Design Specification"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_replan_archives_removed_features() -> Result<()> {
+    let mut state = one_feature_state();
+    state.features.as_mut().unwrap().push(feature("F-0002", "Billing"));
+    state.phases.as_mut().unwrap()[0].features.push("F-0002".to_string());
+    let env = TestEnv::new(&state)?;
+    commands::plan::execute(vec![]).await?;
+
+    env.update_state(|state| {
+        state.features.as_mut().unwrap().retain(|f| f.id != "F-0002");
+        state.phases.as_mut().unwrap()[0].features.retain(|f| f != "F-0002");
+    })?;
+    commands::plan::execute(vec![]).await?;
+
+    assert!(!env.path("doplan/plan/01-foundation/02-billing").exists());
+    let archives: Vec<_> = fs::read_dir(env.path("doplan/archive"))?.filter_map(|e| e.ok()).collect();
+    assert_eq!(archives.len(), 1);
+    assert!(archives[0].path().join("01-foundation/02-billing/tasks.md").exists());
+    assert!(env.path("doplan/plan/01-foundation/01-user-auth/tasks.md").exists());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_replan_archives_removed_phase_after_moving_its_features() -> Result<()> {
+    let mut state = one_feature_state();
+    state.phases.as_mut().unwrap().push(Phase {
+        id: "P-02".to_string(),
+        name: "Launch".to_string(),
+        description: "Second phase".to_string(),
+        features: Vec::new(),
+    });
+    let env = TestEnv::new(&state)?;
+    commands::plan::execute(vec![]).await?;
+
+    // Drop the first phase but keep its feature by moving it to the second
+    env.update_state(|state| {
+        let phases = state.phases.as_mut().unwrap();
+        phases.remove(0);
+        phases[0].features.push("F-0001".to_string());
+    })?;
+    commands::plan::execute(vec![]).await?;
+
+    assert!(env.path("doplan/plan/01-launch/01-user-auth/tasks.md").exists());
+    assert!(!env.path("doplan/plan/01-foundation").exists());
+    assert!(env.path("doplan/archive").exists());
+
+    Ok(())
+}
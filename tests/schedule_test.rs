@@ -0,0 +1,143 @@
+use anyhow::Result;
+use doplan::commands;
+use doplan::commands::schedule::Schedule;
+use doplan::state::deps::NodeRef;
+use doplan::state::{Feature, Phase, ProjectState, WorkingCalendar};
+use chrono::NaiveDate;
+use std::fs;
+
+mod common;
+use common::TestEnv;
+
+fn dependent_features_state() -> ProjectState {
+    ProjectState {
+        project_name: Some("Test Project".to_string()),
+        features: Some(vec![
+            Feature {
+                id: "F-0001".to_string(),
+                name: "User Auth".to_string(),
+                description: "Sign in".to_string(),
+                priority: "high".to_string(),
+                ..Default::default()
+            },
+            Feature {
+                id: "F-0002".to_string(),
+                name: "Billing".to_string(),
+                description: "Payments".to_string(),
+                priority: "high".to_string(),
+                depends_on: vec!["F-0001".to_string()],
+                ..Default::default()
+            },
+            Feature {
+                id: "F-0003".to_string(),
+                name: "Docs".to_string(),
+                description: "User guide".to_string(),
+                priority: "low".to_string(),
+                ..Default::default()
+            },
+        ]),
+        phases: Some(vec![Phase {
+            id: "P-01".to_string(),
+            name: "Foundation".to_string(),
+            description: "First phase".to_string(),
+            features: vec!["F-0001".to_string(), "F-0002".to_string(), "F-0003".to_string()],
+        }]),
+        ..Default::default()
+    }
+}
+
+fn node(reference: &str) -> NodeRef {
+    NodeRef::parse(reference, "").unwrap()
+}
+
+#[tokio::test]
+async fn test_schedule_follows_the_longest_dependency_chain() -> Result<()> {
+    let env = TestEnv::new(&dependent_features_state())?;
+    commands::plan::execute(vec![]).await?;
+    commands::schedule::execute(vec!["--start".to_string(), "2026-10-19".to_string()]).await?;
+
+    let json: serde_json::Value = serde_json::from_str(&fs::read_to_string(env.path(".doplan/schedule.json"))?)?;
+    // Each generated feature is a 1h -> 4h -> 2h chain; Billing waits on all of User Auth
+    assert_eq!(json["critical_path_hours"], 14.0);
+    assert_eq!(json["remaining_hours"], 21.0);
+    assert_eq!(json["projected_completion"], "2026-10-20");
+    assert_eq!(
+        json["critical_path"],
+        serde_json::json!(["F-0001/T1", "F-0001/T2", "F-0001/T3", "F-0002/T1", "F-0002/T2", "F-0002/T3"])
+    );
+
+    let docs = json["tasks"].as_array().unwrap().iter().find(|t| t["id"] == "F-0003/T3").unwrap();
+    assert_eq!(docs["slack_hours"], 7.0);
+    assert_eq!(docs["critical"], false);
+
+    let report = fs::read_to_string(env.path("doplan/schedule.md"))?;
+    assert!(report.contains("**Projected Completion:** 2026-10-20"));
+    assert!(report.contains("| Foundation | 21.0h | 14.0h | 2026-10-20 |"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_schedule_uses_edited_estimates_and_skips_completed_work() -> Result<()> {
+    let env = TestEnv::new(&dependent_features_state())?;
+    commands::plan::execute(vec![]).await?;
+
+    // Docs becomes a three-day job and User Auth's setup is already done
+    let docs_path = env.path("doplan/plan/01-foundation/03-docs/tasks.md");
+    let docs = fs::read_to_string(&docs_path)?;
+    fs::write(&docs_path, docs.replacen("- **Estimated Time**: 4 hours", "- **Estimated Time**: 3d", 1))?;
+    let auth_path = env.path("doplan/plan/01-foundation/01-user-auth/tasks.md");
+    let auth = fs::read_to_string(&auth_path)?;
+    fs::write(
+        &auth_path,
+        auth.replacen("[x] Not Started | [ ] In Progress | [ ] Completed", "[ ] Not Started | [ ] In Progress | [x] Completed", 1),
+    )?;
+
+    let mut state = ProjectState::load_from(&env.path(".doplan/state.json"))?;
+    doplan::utils::plan_dirs::sync_tasks(&env.path("doplan/plan"), &mut state)?;
+    let start = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+    let schedule = Schedule::compute(&state, &WorkingCalendar::default(), start)?;
+
+    // 1h + 24h + 2h of Docs now outweighs the 13h left across User Auth and Billing
+    assert_eq!(schedule.critical_path_hours, 27.0);
+    assert_eq!(schedule.critical_path, vec![node("F-0003/T1"), node("F-0003/T2"), node("F-0003/T3")]);
+    // Friday start, four working days, over the weekend
+    assert_eq!(schedule.projected_completion, NaiveDate::from_ymd_opt(2026, 10, 21).unwrap());
+    assert!(!schedule.is_critical(&node("F-0001/T1")));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_schedule_hours_per_day_override() -> Result<()> {
+    let env = TestEnv::new(&dependent_features_state())?;
+    commands::plan::execute(vec![]).await?;
+
+    // The calendar comes from doplan.toml unless the run overrides it
+    fs::write(env.path("doplan.toml"), "[calendar]\nhours_per_day = 6\n")?;
+    commands::schedule::execute(vec!["--json".to_string()]).await?;
+    let json: serde_json::Value = serde_json::from_str(&fs::read_to_string(env.path(".doplan/schedule.json"))?)?;
+    assert_eq!(json["calendar"]["hours_per_day"], 6.0);
+
+    commands::schedule::execute(vec![
+        "--start".to_string(),
+        "2026-10-19".to_string(),
+        "--hours-per-day".to_string(),
+        "4".to_string(),
+        "--json".to_string(),
+    ])
+    .await?;
+
+    let json: serde_json::Value = serde_json::from_str(&fs::read_to_string(env.path(".doplan/schedule.json"))?)?;
+    assert_eq!(json["calendar"]["hours_per_day"], 4.0);
+    assert_eq!(json["projected_completion"], "2026-10-22");
+
+    let err = commands::schedule::execute(vec!["--start".to_string(), "next week".to_string()]).await.unwrap_err();
+    assert!(format!("{:#}", err).contains("Invalid --start date"));
+    for hours in ["0", "25", "NaN", "inf", "-4"] {
+        let err = commands::schedule::execute(vec!["--hours-per-day".to_string(), hours.to_string()]).await.unwrap_err();
+        assert!(format!("{:#}", err).contains("expected more than 0 and at most 24"), "{}", hours);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,206 @@
+use anyhow::Result;
+use doplan::commands;
+use doplan::server;
+use doplan::state::{Feature, Phase, ProjectState, TaskStatus};
+use serde_json::{json, Value};
+use std::fs;
+use std::time::Duration;
+use tokio::net::TcpListener;
+
+mod common;
+use common::TestEnv;
+
+fn test_state() -> ProjectState {
+    ProjectState {
+        project_name: Some("Test Project".to_string()),
+        features: Some(vec![Feature {
+            id: "F-0001".to_string(),
+            name: "User Auth".to_string(),
+            description: "User Auth feature".to_string(),
+            priority: "high".to_string(),
+            ..Default::default()
+        }]),
+        phases: Some(vec![Phase {
+            id: "P-01".to_string(),
+            name: "Foundation".to_string(),
+            description: "First phase".to_string(),
+            features: vec!["F-0001".to_string()],
+        }]),
+        ..Default::default()
+    }
+}
+
+/// Start the server on a free port and return its base URL
+async fn start_server() -> Result<String> {
+    start_server_allowing(&[]).await
+}
+
+async fn start_server_allowing(hosts: &[&str]) -> Result<String> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("http://{}", listener.local_addr()?);
+    let hosts = hosts.iter().map(|h| h.to_string()).collect();
+    tokio::spawn(server::serve(listener, Duration::from_millis(50), hosts));
+    Ok(url)
+}
+
+#[tokio::test]
+async fn test_rest_api() -> Result<()> {
+    let env = TestEnv::new(&test_state())?;
+    commands::plan::execute(vec![]).await?;
+    let url = start_server().await?;
+    let client = reqwest::Client::new();
+
+    let page = client.get(format!("{}/", url)).send().await?.text().await?;
+    assert!(page.contains("EventSource(\"/api/events\")"));
+
+    let state: Value = client.get(format!("{}/api/state", url)).send().await?.json().await?;
+    assert_eq!(state["project_name"], "Test Project");
+
+    let plan: Value = client.get(format!("{}/api/plan", url)).send().await?.json().await?;
+    assert_eq!(plan["phases"][0]["features"][0]["tasks"].as_array().unwrap().len(), 3);
+
+    let tasks: Value = client.get(format!("{}/api/tasks", url)).send().await?.json().await?;
+    assert_eq!(tasks[1]["ref"], "F-0001/T2");
+    assert_eq!(tasks[1]["status"], "not_started");
+
+    let response = client
+        .put(format!("{}/api/tasks/F-0001/T1", url))
+        .json(&json!({ "status": "completed" }))
+        .send()
+        .await?;
+    assert_eq!(response.status(), 200);
+    let tasks_md = fs::read_to_string(env.path("doplan/plan/01-foundation/01-user-auth/tasks.md"))?;
+    assert_eq!(tasks_md.matches("[x] Completed").count(), 1);
+
+    let feature: Value = client.get(format!("{}/api/features/User%20Auth", url)).send().await?.json().await?;
+    assert_eq!(feature["id"], "F-0001");
+    assert_eq!(feature["phase"], "P-01");
+    assert_eq!(feature["files"]["tasks"], tasks_md);
+
+    let dashboard: Value = client.get(format!("{}/api/dashboard", url)).send().await?.json().await?;
+    assert!((dashboard["overall_progress"].as_f64().unwrap() - 100.0 / 3.0).abs() < 1e-9);
+    assert_eq!(dashboard["phases"][0]["features"][0]["tasks"]["completed"], 1);
+    assert!(!env.path(".doplan/dashboard.json").exists());
+
+    // Writes from another site, or through another host name, are refused
+    let port = url.rsplit(':').next().unwrap();
+    let cross_site = client
+        .post(format!("{}/api/progress", url))
+        .header("origin", "http://evil.example")
+        .send()
+        .await?;
+    assert_eq!(cross_site.status(), 403);
+    let rebound = client
+        .post(format!("{}/api/progress", url))
+        .header("host", format!("evil.example:{}", port))
+        .send()
+        .await?;
+    assert_eq!(rebound.status(), 403);
+    assert!(!env.path(".doplan/dashboard.json").exists());
+
+    let progress: Value = client
+        .post(format!("{}/api/progress", url))
+        .header("origin", format!("http://localhost:{}", port))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(progress["overall_progress"], dashboard["overall_progress"]);
+    assert!(env.path(".doplan/dashboard.json").exists());
+
+    let bad_status = client
+        .put(format!("{}/api/tasks/F-0001/T2", url))
+        .json(&json!({ "status": "done" }))
+        .send()
+        .await?;
+    assert_eq!(bad_status.status(), 400);
+    assert!(bad_status.json::<Value>().await?["error"].as_str().unwrap().contains("Unknown status"));
+    let missing = client.get(format!("{}/api/features/F-0009", url)).send().await?;
+    assert_eq!(missing.status(), 404);
+
+    let mut state: Value = client.get(format!("{}/api/state", url)).send().await?.json().await?;
+    state["features"][0]["depends_on"] = json!(["F-0001"]);
+    let cyclic = client.put(format!("{}/api/state", url)).json(&state).send().await?;
+    assert_eq!(cyclic.status(), 400);
+    state["features"][0]["depends_on"] = json!([]);
+
+    // A document this build cannot read back, or with broken ids, is refused
+    let mut newer = state.clone();
+    newer["schema_version"] = json!(99);
+    let refused = client.put(format!("{}/api/state", url)).json(&newer).send().await?;
+    assert_eq!(refused.status(), 400);
+    assert!(refused.json::<Value>().await?["error"].as_str().unwrap().contains("schema_version"));
+    let mut unknown = state.clone();
+    unknown["phases"][0]["features"] = json!(["F-0042"]);
+    let refused = client.put(format!("{}/api/state", url)).json(&unknown).send().await?;
+    assert_eq!(refused.status(), 400);
+    assert!(refused.json::<Value>().await?["error"].as_str().unwrap().contains("unknown feature 'F-0042'"));
+    assert_eq!(ProjectState::load()?.project_name.as_deref(), Some("Test Project"));
+
+    state["project_name"] = json!("Renamed");
+    let saved = client.put(format!("{}/api/state", url)).json(&state).send().await?;
+    assert_eq!(saved.status(), 200);
+
+    let state = ProjectState::load()?;
+    assert_eq!(state.project_name.as_deref(), Some("Renamed"));
+    assert_eq!(state.features.unwrap()[0].tasks[0].status, TaskStatus::Completed);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_events_on_plan_change() -> Result<()> {
+    let env = TestEnv::new(&test_state())?;
+    commands::plan::execute(vec![]).await?;
+    let url = start_server().await?;
+    let client = reqwest::Client::new();
+
+    let mut events = client.get(format!("{}/api/events", url)).send().await?;
+    assert_eq!(events.headers()["content-type"], "text/event-stream");
+
+    let tasks_path = env.path("doplan/plan/01-foundation/01-user-auth/tasks.md");
+    let tasks = fs::read_to_string(&tasks_path)?;
+    fs::write(&tasks_path, format!("{}\nNotes from review.\n", tasks))?;
+
+    let mut received = String::new();
+    while !received.contains("\n\n") {
+        let chunk = tokio::time::timeout(Duration::from_secs(5), events.chunk())
+            .await??
+            .expect("event stream ended");
+        received.push_str(std::str::from_utf8(&chunk)?);
+    }
+    assert!(received.contains("event: plan-changed"), "{}", received);
+    assert!(received.contains("01-foundation/01-user-auth/tasks.md"), "{}", received);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_reads_need_the_servers_own_host() -> Result<()> {
+    let _env = TestEnv::new(&test_state())?;
+    commands::plan::execute(vec![]).await?;
+    let url = start_server_allowing(&["dev.box"]).await?;
+    let port = url.rsplit(':').next().unwrap();
+    let client = reqwest::Client::new();
+
+    // A page on a rebound DNS name cannot read the project
+    for path in ["/api/state", "/api/features/F-0001", "/api/events"] {
+        let rebound = client
+            .get(format!("{}{}", url, path))
+            .header("host", format!("evil.example:{}", port))
+            .send()
+            .await?;
+        assert_eq!(rebound.status(), 403, "{}", path);
+    }
+
+    let allowed = client
+        .get(format!("{}/api/state", url))
+        .header("host", format!("dev.box:{}", port))
+        .header("origin", format!("http://dev.box:{}", port))
+        .send()
+        .await?;
+    assert_eq!(allowed.status(), 200);
+    assert_eq!(allowed.json::<Value>().await?["project_name"], "Test Project");
+
+    Ok(())
+}
@@ -0,0 +1,154 @@
+use anyhow::Result;
+use doplan::error::DoPlanError;
+use doplan::state::{ProjectState, CURRENT_SCHEMA_VERSION};
+use serde_json::{json, Value};
+use std::fs;
+use tempfile::TempDir;
+
+fn write_state(temp_dir: &TempDir, value: &Value) -> Result<std::path::PathBuf> {
+    let dot_doplan_dir = temp_dir.path().join(".doplan");
+    fs::create_dir_all(&dot_doplan_dir)?;
+    let state_path = dot_doplan_dir.join("state.json");
+    fs::write(&state_path, serde_json::to_string_pretty(value)?)?;
+    Ok(state_path)
+}
+
+#[test]
+fn test_load_missing_file_returns_current_version() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let state = ProjectState::load_from(&temp_dir.path().join("state.json"))?;
+
+    assert_eq!(state.schema_version, CURRENT_SCHEMA_VERSION);
+    assert!(state.project_name.is_none());
+    Ok(())
+}
+
+#[test]
+fn test_load_unversioned_state_migrates_and_backs_up() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let legacy = json!({
+        "project_name": "Legacy Project",
+        "idea": "An old idea",
+        "tech_stack": ["Rust"],
+        "features": [
+            { "name": "Feature 1", "description": "First feature", "priority": "high" }
+        ],
+        "phases": [
+            { "name": "Phase 1", "description": "First phase", "features": ["Feature 1"] }
+        ],
+        "improvements": null,
+        "notes": null
+    });
+    let state_path = write_state(&temp_dir, &legacy)?;
+
+    let state = ProjectState::load_from(&state_path)?;
+    assert_eq!(state.schema_version, CURRENT_SCHEMA_VERSION);
+    assert_eq!(state.project_name.as_deref(), Some("Legacy Project"));
+    assert_eq!(state.features.as_ref().map(|f| f.len()), Some(1));
+
+    // Original file is preserved untouched next to the rewritten one
+    let backup_path = temp_dir.path().join(".doplan").join("state.json.v0.bak");
+    assert!(backup_path.exists());
+    let backup: Value = serde_json::from_str(&fs::read_to_string(&backup_path)?)?;
+    assert_eq!(backup, legacy);
+
+    let rewritten: Value = serde_json::from_str(&fs::read_to_string(&state_path)?)?;
+    assert_eq!(rewritten["schema_version"], json!(CURRENT_SCHEMA_VERSION));
+
+    Ok(())
+}
+
+#[test]
+fn test_load_assigns_ids_and_rewrites_phase_membership() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let legacy = json!({
+        "schema_version": 2,
+        "project_name": "Legacy Project",
+        "features": [
+            { "name": "Feature 1", "description": "First", "priority": "high", "tasks": [] },
+            { "name": "Feature 2", "description": "Second", "priority": "low", "tasks": [] }
+        ],
+        "phases": [
+            { "name": "Phase 1", "description": "First phase", "features": ["Feature 2", "Feature 1"] }
+        ]
+    });
+    let state_path = write_state(&temp_dir, &legacy)?;
+
+    let mut state = ProjectState::load_from(&state_path)?;
+    let features = state.features.as_ref().unwrap();
+    assert_eq!(features[0].id, "F-0001");
+    assert_eq!(features[1].id, "F-0002");
+
+    let phase = &state.phases.as_ref().unwrap()[0];
+    assert_eq!(phase.id, "P-01");
+    assert_eq!(phase.features, vec!["F-0002".to_string(), "F-0001".to_string()]);
+
+    // Ids are never reused, even after the newest feature is removed
+    state.features.as_mut().unwrap().pop();
+    assert_eq!(state.new_feature_id(), "F-0003");
+
+    Ok(())
+}
+
+#[test]
+fn test_load_current_version_does_not_back_up() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let state_path = temp_dir.path().join(".doplan").join("state.json");
+    let mut state = ProjectState::new();
+    state.project_name = Some("Current".to_string());
+    state.save_to(&state_path)?;
+
+    let loaded = ProjectState::load_from(&state_path)?;
+    assert_eq!(loaded.project_name.as_deref(), Some("Current"));
+
+    let backups: Vec<_> = fs::read_dir(temp_dir.path().join(".doplan"))?
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name().to_string_lossy().ends_with(".bak"))
+        .collect();
+    assert!(backups.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_load_newer_version_is_rejected() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let newer = json!({
+        "schema_version": CURRENT_SCHEMA_VERSION + 1,
+        "project_name": "From the future"
+    });
+    let state_path = write_state(&temp_dir, &newer)?;
+
+    let err = ProjectState::load_from(&state_path).unwrap_err();
+    let state_err = err.downcast_ref::<DoPlanError>().expect("expected a DoPlanError");
+    assert!(matches!(state_err, DoPlanError::State(_)));
+    assert!(err.to_string().contains("newer DoPlan"));
+
+    // The file must be left alone
+    let on_disk: Value = serde_json::from_str(&fs::read_to_string(&state_path)?)?;
+    assert_eq!(on_disk, newer);
+    Ok(())
+}
+
+#[test]
+fn test_saved_state_is_stamped_past_fields_older_builds_would_drop() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let written_by_v3_build = json!({
+        "schema_version": 3,
+        "project_name": "Mid Upgrade",
+        "progress_weighting": "estimate",
+        "discussion": [{ "key": "target_audience", "question": "Who is it for?", "answer": "Teams" }],
+        "worktrees": []
+    });
+    let state_path = write_state(&temp_dir, &written_by_v3_build)?;
+
+    let state = ProjectState::load_from(&state_path)?;
+    assert_eq!(state.discussion[0].answer, "Teams");
+    assert!(temp_dir.path().join(".doplan").join("state.json.v3.bak").exists());
+
+    // A v3 build reading this file back refuses it instead of losing the
+    // discussion answers on its next save
+    let rewritten: Value = serde_json::from_str(&fs::read_to_string(&state_path)?)?;
+    assert!(rewritten["schema_version"].as_u64().unwrap() > 3);
+    assert!(rewritten.get("progress_weighting").is_none());
+    Ok(())
+}
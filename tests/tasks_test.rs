@@ -0,0 +1,177 @@
+use anyhow::Result;
+use doplan::commands;
+use doplan::state::{tasks_md, AcceptanceCriterion, Feature, Phase, ProjectState, Task, TaskStatus};
+use std::fs;
+
+mod common;
+use common::TestEnv;
+
+const LEGACY_TASKS_MD: &str = r#"# Tasks
+
+**Feature:** Feature 1
+
+**Date:** 2025-01-01
+
+---
+
+## Tasks
+
+#### Task 1: Setup
+- **Status**: [ ] Not Started | [ ] In Progress | [x] Completed | [ ] Blocked
+- **Priority**: High
+- **Description**: Setup feature structure and initial files
+- **Acceptance Criteria**:
+  - [x] Feature directory created
+  - [ ] Initial files generated
+- **Estimated Time**: 1 hour
+- **Notes**: _Additional notes_
+
+#### Task 2: Implementation
+- **Status**: [ ] Not Started | [x] In Progress | [ ] Completed | [ ] Blocked
+- **Priority**: High
+- **Description**: Implement core feature functionality
+- **Acceptance Criteria**:
+  - [ ] Core functionality implemented
+- **Estimated Time**: 4 hours
+- **Notes**: Waiting on API review
+
+#### Task 3: Testing
+- **Status**: [ ] Not Started | [ ] In Progress | [ ] Completed | [ ] Blocked
+- **Priority**: Medium
+- **Description**: Write and run tests
+- **Acceptance Criteria**:
+  - [ ] All tests passing
+- **Estimated Time**: 2 hours
+- **Notes**: _Additional notes_
+
+## Progress Tracking
+
+**Overall Progress**: 33%
+
+- Completed: 1
+- In Progress: 1
+- Not Started: 1
+- Blocked: 0
+"#;
+
+fn sample_feature() -> Feature {
+    Feature {
+        id: "F-0001".to_string(),
+        name: "Feature 1".to_string(),
+        description: "First feature".to_string(),
+        priority: "high".to_string(),
+        tasks: vec![
+            Task {
+                status: TaskStatus::Completed,
+                priority: "High".to_string(),
+                description: "Setup feature structure".to_string(),
+                estimate: Some("1 hour".to_string()),
+                acceptance_criteria: vec![AcceptanceCriterion {
+                    text: "Directory created".to_string(),
+                    done: true,
+                }],
+                ..Task::new("T1", "Setup")
+            },
+            Task {
+                status: TaskStatus::Blocked,
+                priority: "Low".to_string(),
+                notes: Some("Needs design sign-off".to_string()),
+                ..Task::new("T4", "Polish")
+            },
+        ],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_parse_legacy_tasks_md() {
+    let tasks = tasks_md::parse(LEGACY_TASKS_MD);
+
+    assert_eq!(tasks.len(), 3);
+    assert_eq!(tasks[0].id, "T1");
+    assert_eq!(tasks[0].title, "Setup");
+    assert_eq!(tasks[0].status, TaskStatus::Completed);
+    assert_eq!(tasks[0].estimate.as_deref(), Some("1 hour"));
+    assert_eq!(tasks[0].acceptance_criteria.len(), 2);
+    assert!(tasks[0].acceptance_criteria[0].done);
+    assert!(!tasks[0].acceptance_criteria[1].done);
+    assert_eq!(tasks[0].notes, None);
+
+    assert_eq!(tasks[1].status, TaskStatus::InProgress);
+    assert_eq!(tasks[1].notes.as_deref(), Some("Waiting on API review"));
+
+    assert_eq!(tasks[2].status, TaskStatus::NotStarted);
+    assert_eq!(tasks[2].priority, "Medium");
+}
+
+#[test]
+fn test_render_parse_round_trip() {
+    let feature = sample_feature();
+    let rendered = tasks_md::render(&feature);
+
+    assert!(rendered.contains("#### Task 1: Setup"));
+    assert!(rendered.contains("#### Task 4: Polish"));
+    assert!(rendered.contains("- Completed: 1"));
+    assert_eq!(tasks_md::parse(&rendered), feature.tasks);
+}
+
+#[test]
+fn test_next_task_id_never_reuses() {
+    let feature = sample_feature();
+    assert_eq!(feature.next_task_id(), "T5");
+}
+
+fn plan_state() -> ProjectState {
+    ProjectState {
+        project_name: Some("Test Project".to_string()),
+        features: Some(vec![Feature {
+            name: "Feature 1".to_string(),
+            description: "First feature".to_string(),
+            priority: "high".to_string(),
+            ..Default::default()
+        }]),
+        phases: Some(vec![Phase {
+            name: "Phase 1".to_string(),
+            description: "First phase".to_string(),
+            features: vec!["Feature 1".to_string()],
+            ..Default::default()
+        }]),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_plan_seeds_tasks_into_state() -> Result<()> {
+    let env = TestEnv::new(&plan_state())?;
+
+    commands::plan::execute(vec![]).await?;
+
+    let state = ProjectState::load_from(&env.state_path())?;
+    let feature = &state.features.unwrap()[0];
+    assert_eq!(feature.tasks.len(), 3);
+    assert_eq!(feature.tasks[0].id, "T1");
+    assert_eq!(feature.tasks[0].title, "Setup");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_progress_syncs_edited_tasks_into_state() -> Result<()> {
+    let env = TestEnv::new(&plan_state())?;
+    commands::plan::execute(vec![]).await?;
+
+    let tasks_path = env.path("doplan/plan/01-phase-1/01-feature-1/tasks.md");
+    fs::write(&tasks_path, LEGACY_TASKS_MD)?;
+
+    commands::progress::execute(vec![]).await?;
+
+    let state = ProjectState::load_from(&env.state_path())?;
+    let feature = &state.features.unwrap()[0];
+    assert_eq!(feature.tasks[0].status, TaskStatus::Completed);
+    assert_eq!(feature.tasks[1].status, TaskStatus::InProgress);
+
+    let dashboard = fs::read_to_string(env.path(".doplan/dashboard.json"))?;
+    assert!(dashboard.contains("\"completed\": 1"));
+
+    Ok(())
+}
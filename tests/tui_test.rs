@@ -0,0 +1,100 @@
+use anyhow::Result;
+use crossterm::event::KeyCode;
+use doplan::commands;
+use doplan::state::{Feature, Phase, ProjectState, TaskStatus};
+use doplan::tui::{ui, App, Focus, Screen, TreeNode};
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+use std::fs;
+
+mod common;
+use common::TestEnv;
+
+fn test_state() -> ProjectState {
+    ProjectState {
+        project_name: Some("Test Project".to_string()),
+        features: Some(vec![Feature {
+            id: "F-0001".to_string(),
+            name: "User Auth".to_string(),
+            description: "User Auth feature".to_string(),
+            priority: "high".to_string(),
+            ..Default::default()
+        }]),
+        phases: Some(vec![Phase {
+            id: "P-01".to_string(),
+            name: "Foundation".to_string(),
+            description: "First phase".to_string(),
+            features: vec!["F-0001".to_string()],
+        }]),
+        ..Default::default()
+    }
+}
+
+fn screen_text(app: &App) -> Result<String> {
+    let mut terminal = Terminal::new(TestBackend::new(120, 30))?;
+    terminal.draw(|frame| ui::draw(frame, app))?;
+    Ok(terminal.backend().buffer().content().iter().map(|cell| cell.symbol()).collect())
+}
+
+#[tokio::test]
+async fn test_toggling_a_task_updates_tasks_md() -> Result<()> {
+    let env = TestEnv::project(Some(&test_state()))?;
+    commands::plan::execute(vec![]).await?;
+
+    let mut app = App::load()?;
+    assert!(matches!(app.screen, Screen::Main));
+    assert_eq!(app.tree[0].node, TreeNode::Phase("P-01".to_string()));
+    assert_eq!(app.tree[1].node, TreeNode::Feature("F-0001".to_string()));
+
+    app.handle_key(KeyCode::Down)?;
+    app.handle_key(KeyCode::Tab)?;
+    assert_eq!(app.focus, Focus::Tasks);
+    app.handle_key(KeyCode::Char(' '))?;
+    assert_eq!(app.selected_task().unwrap().status, TaskStatus::InProgress);
+
+    let tasks = fs::read_to_string(env.path("doplan/plan/01-foundation/01-user-auth/tasks.md"))?;
+    assert_eq!(tasks.matches("[x] In Progress").count(), 1);
+
+    let state = ProjectState::load()?;
+    assert_eq!(state.features.unwrap()[0].tasks[0].status, TaskStatus::InProgress);
+
+    let screen = screen_text(&app)?;
+    assert!(screen.contains("Next Action"));
+    assert!(screen.contains("Progress"));
+    assert!(screen.contains("User Auth"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_wizard_creates_project_state() -> Result<()> {
+    let env = TestEnv::project(None)?;
+
+    let mut app = App::load()?;
+    assert!(matches!(app.screen, Screen::Wizard(_)));
+    assert!(screen_text(&app)?.contains("Project name"));
+
+    let answers = ["Shop", "An online shop", "Cart, Checkout, Reviews", "MVP, Growth", "Rust"];
+    for (i, answer) in answers.iter().enumerate() {
+        for c in answer.chars() {
+            app.handle_key(KeyCode::Char(c))?;
+        }
+        if i + 1 < answers.len() {
+            app.handle_key(KeyCode::Enter)?;
+        }
+    }
+    app.handle_key(KeyCode::Enter)?;
+
+    assert!(matches!(app.screen, Screen::Main));
+    let state = ProjectState::load()?;
+    assert_eq!(state.project_name.as_deref(), Some("Shop"));
+    let features = state.features.unwrap();
+    assert_eq!(features.len(), 3);
+    let phases = state.phases.unwrap();
+    assert_eq!(phases.len(), 2);
+    assert_eq!(phases[0].features.len(), 2);
+    assert_eq!(phases[1].features, vec![features[2].id.clone()]);
+    assert!(env.path("doplan/idea-notes.md").exists());
+
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use anyhow::Result;
+use doplan::commands;
+use doplan::state::{Feature, Phase, ProjectState};
+use serde_json::Value;
+use std::fs;
+
+mod common;
+use common::TestEnv;
+
+fn two_phase_state() -> ProjectState {
+    let feature = |id: &str, name: &str| Feature {
+        id: id.to_string(),
+        name: name.to_string(),
+        description: format!("{} feature", name),
+        priority: "high".to_string(),
+        ..Default::default()
+    };
+    ProjectState {
+        project_name: Some("Test Project".to_string()),
+        features: Some(vec![feature("F-0001", "User Auth"), feature("F-0002", "Billing")]),
+        phases: Some(vec![
+            Phase {
+                id: "P-01".to_string(),
+                name: "Foundation".to_string(),
+                description: "First phase".to_string(),
+                features: vec!["F-0001".to_string()],
+            },
+            Phase {
+                id: "P-02".to_string(),
+                name: "Launch".to_string(),
+                description: "Second phase".to_string(),
+                features: vec!["F-0002".to_string()],
+            },
+        ]),
+        ..Default::default()
+    }
+}
+
+/// Mark the first task in User Auth (1 hour of its 7) as completed
+fn complete_setup_task(env: &TestEnv) -> Result<()> {
+    let tasks_path = env.path("doplan/plan/01-foundation/01-user-auth/tasks.md");
+    let tasks = fs::read_to_string(&tasks_path)?;
+    fs::write(
+        &tasks_path,
+        tasks.replacen("[x] Not Started | [ ] In Progress | [ ] Completed", "[ ] Not Started | [ ] In Progress | [x] Completed", 1),
+    )?;
+    Ok(())
+}
+
+fn dashboard(env: &TestEnv) -> Result<Value> {
+    Ok(serde_json::from_str(&fs::read_to_string(env.path(".doplan/dashboard.json"))?)?)
+}
+
+#[tokio::test]
+async fn test_count_weighting_is_the_default() -> Result<()> {
+    let env = TestEnv::new(&two_phase_state())?;
+    commands::plan::execute(vec![]).await?;
+    complete_setup_task(&env)?;
+    commands::progress::execute(vec![]).await?;
+
+    let dashboard = dashboard(&env)?;
+    assert_eq!(dashboard["weighting"]["mode"], "count");
+    let auth = &dashboard["phases"][0]["features"][0];
+    assert!((auth["progress"].as_f64().unwrap() - 100.0 / 3.0).abs() < 1e-9);
+    assert!((dashboard["overall_progress"].as_f64().unwrap() - 50.0 / 3.0).abs() < 1e-9);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_estimate_weighting_uses_task_hours() -> Result<()> {
+    let env = TestEnv::new(&two_phase_state())?;
+    fs::write(env.path("doplan.toml"), "[progress]\nweighting = \"estimate\"\n")?;
+    commands::plan::execute(vec![]).await?;
+    complete_setup_task(&env)?;
+    commands::progress::execute(vec![]).await?;
+
+    // Setup is 1 of the feature's 7 hours, and 1 of the project's 14
+    let dashboard = dashboard(&env)?;
+    assert_eq!(dashboard["weighting"]["mode"], "estimate");
+    let auth = &dashboard["phases"][0]["features"][0];
+    assert!((auth["progress"].as_f64().unwrap() - 100.0 / 7.0).abs() < 1e-9);
+    assert_eq!(auth["weight"], 7.0);
+    assert!((dashboard["overall_progress"].as_f64().unwrap() - 100.0 / 14.0).abs() < 1e-9);
+
+    let md = fs::read_to_string(env.path("doplan/dashboard.md"))?;
+    assert!(md.contains("_Weighting: estimate ("));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_priority_weighting_from_command_line() -> Result<()> {
+    let mut state = two_phase_state();
+    state.features.as_mut().unwrap()[1].priority = "low".to_string();
+    let env = TestEnv::new(&state)?;
+    commands::plan::execute(vec![]).await?;
+    complete_setup_task(&env)?;
+    commands::progress::execute(vec!["--weighting".to_string(), "priority".to_string()]).await?;
+
+    // The high-priority phase counts three times as much as the low one
+    let dashboard = dashboard(&env)?;
+    assert_eq!(dashboard["weighting"]["mode"], "priority");
+    assert_eq!(dashboard["phases"][0]["weight"], 3.0);
+    assert_eq!(dashboard["phases"][1]["weight"], 1.0);
+    let expected = (100.0 / 7.0) * 3.0 / 4.0;
+    assert!((dashboard["overall_progress"].as_f64().unwrap() - expected).abs() < 1e-9);
+
+    let err = commands::progress::execute(vec!["--weighting".to_string(), "hours".to_string()]).await.unwrap_err();
+    assert!(err.to_string().contains("Unknown weighting mode 'hours'"));
+
+    Ok(())
+}